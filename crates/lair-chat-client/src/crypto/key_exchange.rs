@@ -0,0 +1,517 @@
+//! X25519 key exchange, authenticated with the server's long-term ed25519
+//! identity key.
+//!
+//! A bare X25519 exchange is vulnerable to an active MITM: whoever sits on
+//! the wire can swap in their own ephemeral key and neither side is any the
+//! wiser. To close that, the server signs a transcript of the
+//! `ServerHello`, `ClientHello`, and both ephemeral public keys with a
+//! long-term ed25519 identity key, and the client verifies that signature
+//! (and the identity key itself, per [`ServerKeyPolicy`]) before trusting
+//! the derived shared secret. Covering the hellos binds feature
+//! negotiation — `ClientHello.features` in particular — to the signature,
+//! so a MITM can't silently strip a feature like length-hiding and have
+//! the signature still check out.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use base64::prelude::*;
+use directories::ProjectDirs;
+use ed25519_dalek::{Signature, Verifier, VerifyingKey};
+use hkdf::Hkdf;
+use once_cell::sync::Lazy;
+use rand::rngs::OsRng;
+use sha2::{Digest, Sha256};
+use tracing::warn;
+use x25519_dalek::{EphemeralSecret, PublicKey, SharedSecret};
+
+/// Errors that can occur during key exchange.
+#[derive(Debug, thiserror::Error)]
+pub enum KeyExchangeError {
+    #[error("Invalid public key length: expected 32 bytes, got {0}")]
+    InvalidKeyLength(usize),
+
+    #[error("Invalid signature length: expected 64 bytes, got {0}")]
+    InvalidSignatureLength(usize),
+
+    #[error("Base64 decode error: {0}")]
+    Base64DecodeError(#[from] base64::DecodeError),
+
+    #[error("invalid server identity key: {0}")]
+    InvalidIdentityKey(ed25519_dalek::SignatureError),
+
+    #[error("handshake signature verification failed")]
+    SignatureInvalid,
+
+    #[error("server identity key for {0} changed since the last connection")]
+    IdentityKeyChanged(String),
+
+    #[error("server identity key does not match the pinned key")]
+    IdentityKeyMismatch,
+}
+
+/// An X25519 keypair for ephemeral key exchange.
+pub struct KeyPair {
+    secret: EphemeralSecret,
+    public: PublicKey,
+}
+
+impl KeyPair {
+    /// Generate a new random keypair.
+    pub fn generate() -> Self {
+        let secret = EphemeralSecret::random_from_rng(OsRng);
+        let public = PublicKey::from(&secret);
+        Self { secret, public }
+    }
+
+    /// Get the public key as base64-encoded string.
+    pub fn public_key_base64(&self) -> String {
+        BASE64_STANDARD.encode(self.public.as_bytes())
+    }
+
+    /// Get the raw public key bytes.
+    pub fn public_key_bytes(&self) -> [u8; 32] {
+        *self.public.as_bytes()
+    }
+
+    /// Perform Diffie-Hellman key exchange with the peer's public key.
+    /// Returns a 32-byte shared secret suitable for use as an AES-256 key.
+    pub fn diffie_hellman(self, peer_public: PublicKey) -> [u8; 32] {
+        let shared: SharedSecret = self.secret.diffie_hellman(&peer_public);
+        *shared.as_bytes()
+    }
+}
+
+/// Parse a base64-encoded public key.
+pub fn parse_public_key(base64_key: &str) -> Result<PublicKey, KeyExchangeError> {
+    let bytes = BASE64_STANDARD.decode(base64_key)?;
+    if bytes.len() != 32 {
+        return Err(KeyExchangeError::InvalidKeyLength(bytes.len()));
+    }
+    let mut array = [0u8; 32];
+    array.copy_from_slice(&bytes);
+    Ok(PublicKey::from(array))
+}
+
+/// How the client verifies the server's ed25519 identity key during the
+/// authenticated key exchange (see [`verify_server_identity`]).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ServerKeyPolicy {
+    /// Trust whichever identity key the server presents the first time we
+    /// connect to a given address, then pin it for subsequent connections.
+    /// If the server's key ever changes, the handshake is rejected instead
+    /// of silently trusting the new key, so a MITM can't just swap in once
+    /// we've already learned the real server.
+    TrustOnFirstUse,
+    /// Only accept this exact server identity key; reject everything else,
+    /// including on the very first connection. For operators who have
+    /// already distributed the server's public key out-of-band.
+    Pinned([u8; 32]),
+}
+
+/// Server identity keys learned under [`ServerKeyPolicy::TrustOnFirstUse`],
+/// keyed by server address. Loaded from [`known_server_keys_path`] on first
+/// use and rewritten to disk on every new pin, so a pinned server is still
+/// recognized (and a changed one still rejected) across client restarts.
+static KNOWN_SERVER_KEYS: Lazy<Mutex<HashMap<String, [u8; 32]>>> =
+    Lazy::new(|| Mutex::new(load_known_server_keys()));
+
+/// Path to the file the client pins [`ServerKeyPolicy::TrustOnFirstUse`]
+/// server identity keys in, mirroring how `SavedAccount`s are kept under the
+/// platform data directory. `None` if the platform data directory can't be
+/// determined.
+fn known_server_keys_path() -> Option<PathBuf> {
+    let project_dirs = ProjectDirs::from("com", "lair-chat", "lair-chat")?;
+    let data_dir = project_dirs.data_dir();
+    std::fs::create_dir_all(data_dir).ok()?;
+    Some(data_dir.join("known_server_keys.json"))
+}
+
+/// Load previously pinned server identity keys from disk. Missing or
+/// unreadable state is treated as "nothing pinned yet" rather than an error,
+/// since the only consequence is the next connection to each server re-runs
+/// trust-on-first-use.
+fn load_known_server_keys() -> HashMap<String, [u8; 32]> {
+    let Some(path) = known_server_keys_path() else {
+        return HashMap::new();
+    };
+    let Ok(content) = std::fs::read_to_string(&path) else {
+        return HashMap::new();
+    };
+    let encoded: HashMap<String, String> = match serde_json::from_str(&content) {
+        Ok(encoded) => encoded,
+        Err(e) => {
+            warn!("Failed to parse known server keys at {path:?}: {e}");
+            return HashMap::new();
+        }
+    };
+    encoded
+        .into_iter()
+        .filter_map(|(addr, key_base64)| {
+            let bytes = BASE64_STANDARD.decode(&key_base64).ok()?;
+            let key: [u8; 32] = bytes.try_into().ok()?;
+            Some((addr, key))
+        })
+        .collect()
+}
+
+/// Persist the current set of pinned server identity keys to disk. Failure
+/// is logged rather than propagated: an unpersisted pin still protects the
+/// rest of this session, it just won't survive a restart.
+fn persist_known_server_keys(known: &HashMap<String, [u8; 32]>) {
+    let Some(path) = known_server_keys_path() else {
+        return;
+    };
+    let encoded: HashMap<String, String> = known
+        .iter()
+        .map(|(addr, key)| (addr.clone(), BASE64_STANDARD.encode(key)))
+        .collect();
+    match serde_json::to_string_pretty(&encoded) {
+        Ok(json) => {
+            if let Err(e) = std::fs::write(&path, json) {
+                warn!("Failed to persist known server keys to {path:?}: {e}");
+            }
+        }
+        Err(e) => warn!("Failed to serialize known server keys: {e}"),
+    }
+}
+
+/// Canonical, field-order-independent encoding of a `ClientHello`'s
+/// handshake-relevant fields, for mixing into [`handshake_transcript`].
+/// Hand-built rather than serialized via `serde_json` from the protocol
+/// type directly, so the encoding doesn't depend on the client's and
+/// server's independently declared `ClientHello` structs happening to
+/// serialize their fields in the same order.
+pub fn client_hello_bytes(
+    version: &str,
+    client_name: Option<&str>,
+    features: &[String],
+    signing_public_key: Option<&str>,
+) -> Vec<u8> {
+    format!(
+        r#"{{"client_name":{},"features":{},"signing_public_key":{},"version":{}}}"#,
+        serde_json::to_string(&client_name).expect("Option<&str> always serializes"),
+        serde_json::to_string(features).expect("string slice always serializes"),
+        serde_json::to_string(&signing_public_key).expect("Option<&str> always serializes"),
+        serde_json::to_string(version).expect("str always serializes"),
+    )
+    .into_bytes()
+}
+
+/// Canonical encoding of a `ServerHello`'s handshake-relevant fields, for
+/// mixing into [`handshake_transcript`]. See [`client_hello_bytes`] for
+/// why this is hand-built rather than derived.
+pub fn server_hello_bytes(
+    version: &str,
+    server_name: &str,
+    features: &[String],
+    encryption_required: bool,
+) -> Vec<u8> {
+    format!(
+        r#"{{"encryption_required":{},"features":{},"server_name":{},"version":{}}}"#,
+        encryption_required,
+        serde_json::to_string(features).expect("string slice always serializes"),
+        serde_json::to_string(server_name).expect("str always serializes"),
+        serde_json::to_string(version).expect("str always serializes"),
+    )
+    .into_bytes()
+}
+
+/// Compute the transcript the server signs: the SHA-256 of the
+/// `ServerHello`, the `ClientHello`, and the client's and server's
+/// ephemeral X25519 public keys, in that order. Covering the hellos closes
+/// the gap a transcript of only the two ephemeral keys would leave:
+/// without it, a MITM could alter `ClientHello.features` (e.g. to strip
+/// `length_hiding`) and the signature would still verify. Must match the
+/// server's own `handshake_transcript` exactly, or every signature
+/// verification will (correctly) fail.
+pub fn handshake_transcript(
+    client_public_key: &PublicKey,
+    server_public_key: &PublicKey,
+    server_hello: &[u8],
+    client_hello: &[u8],
+) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(server_hello);
+    hasher.update(client_hello);
+    hasher.update(client_public_key.as_bytes());
+    hasher.update(server_public_key.as_bytes());
+    hasher.finalize().into()
+}
+
+/// Check the server's identity key against `policy`, pinning it on first
+/// use. Returns an error if the policy rejects the key.
+pub fn verify_server_identity(
+    addr: &str,
+    server_identity_key: &[u8; 32],
+    policy: &ServerKeyPolicy,
+) -> Result<(), KeyExchangeError> {
+    match policy {
+        ServerKeyPolicy::Pinned(expected) => {
+            if server_identity_key != expected {
+                return Err(KeyExchangeError::IdentityKeyMismatch);
+            }
+        }
+        ServerKeyPolicy::TrustOnFirstUse => {
+            let mut known = KNOWN_SERVER_KEYS.lock().unwrap();
+            match known.get(addr) {
+                Some(expected) if expected != server_identity_key => {
+                    return Err(KeyExchangeError::IdentityKeyChanged(addr.to_string()));
+                }
+                Some(_) => {}
+                None => {
+                    known.insert(addr.to_string(), *server_identity_key);
+                    persist_known_server_keys(&known);
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Verify that `signature` is the server identity key's ed25519 signature
+/// over `transcript`.
+pub fn verify_handshake_signature(
+    transcript: &[u8; 32],
+    server_identity_key: &[u8; 32],
+    signature: &[u8; 64],
+) -> Result<(), KeyExchangeError> {
+    let verifying_key = VerifyingKey::from_bytes(server_identity_key)
+        .map_err(KeyExchangeError::InvalidIdentityKey)?;
+    let signature = Signature::from_bytes(signature);
+    verifying_key
+        .verify(transcript, &signature)
+        .map_err(|_| KeyExchangeError::SignatureInvalid)
+}
+
+/// HKDF info label for the client->server directional key.
+const HKDF_INFO_C2S: &[u8] = b"lair c2s v1";
+/// HKDF info label for the server->client directional key.
+const HKDF_INFO_S2C: &[u8] = b"lair s2c v1";
+
+/// Derive independent client->server and server->client AEAD keys from the
+/// raw X25519 shared secret via HKDF-SHA256, using the handshake transcript
+/// as salt and distinct info labels per direction. A single shared cipher
+/// would reuse the same keystream for both directions; splitting it means
+/// messages the client sends and messages it receives are never encrypted
+/// under the same key. Returns `(c2s_key, s2c_key)` — the client sends with
+/// `c2s_key` and receives with `s2c_key`; the server does the opposite.
+pub fn derive_directional_keys(
+    shared_secret: &[u8],
+    transcript: &[u8; 32],
+) -> ([u8; 32], [u8; 32]) {
+    let hk = Hkdf::<Sha256>::new(Some(transcript), shared_secret);
+
+    let mut c2s_key = [0u8; 32];
+    hk.expand(HKDF_INFO_C2S, &mut c2s_key)
+        .expect("32 is a valid HKDF-SHA256 output length");
+    let mut s2c_key = [0u8; 32];
+    hk.expand(HKDF_INFO_S2C, &mut s2c_key)
+        .expect("32 is a valid HKDF-SHA256 output length");
+
+    (c2s_key, s2c_key)
+}
+
+/// HKDF info label for the client->server frame-length-header key.
+const HKDF_INFO_C2S_HEADER: &[u8] = b"lair hdr c2s v1";
+/// HKDF info label for the server->client frame-length-header key.
+const HKDF_INFO_S2C_HEADER: &[u8] = b"lair hdr s2c v1";
+
+/// Derive the directional keys used to seal a frame's length header in
+/// length-hiding framing mode, from the same shared secret and transcript
+/// as [`derive_directional_keys`] but under their own info labels, so a
+/// header key never doubles as a payload key. Returns
+/// `(c2s_header_key, s2c_header_key)` — the client seals outbound headers
+/// with `c2s_header_key` and opens inbound headers with `s2c_header_key`;
+/// the server does the opposite.
+pub fn derive_header_keys(shared_secret: &[u8], transcript: &[u8; 32]) -> ([u8; 32], [u8; 32]) {
+    let hk = Hkdf::<Sha256>::new(Some(transcript), shared_secret);
+
+    let mut c2s_header_key = [0u8; 32];
+    hk.expand(HKDF_INFO_C2S_HEADER, &mut c2s_header_key)
+        .expect("32 is a valid HKDF-SHA256 output length");
+    let mut s2c_header_key = [0u8; 32];
+    hk.expand(HKDF_INFO_S2C_HEADER, &mut s2c_header_key)
+        .expect("32 is a valid HKDF-SHA256 output length");
+
+    (c2s_header_key, s2c_header_key)
+}
+
+/// Decode a base64-encoded 32-byte field (identity key, ephemeral public
+/// key, etc).
+pub fn decode_32_bytes(b64: &str) -> Result<[u8; 32], KeyExchangeError> {
+    let bytes = BASE64_STANDARD.decode(b64)?;
+    bytes
+        .as_slice()
+        .try_into()
+        .map_err(|_| KeyExchangeError::InvalidKeyLength(bytes.len()))
+}
+
+/// Decode a base64 field into a 64-byte array (an ed25519 signature).
+pub fn decode_64_bytes(b64: &str) -> Result<[u8; 64], KeyExchangeError> {
+    let bytes = BASE64_STANDARD.decode(b64)?;
+    bytes
+        .as_slice()
+        .try_into()
+        .map_err(|_| KeyExchangeError::InvalidSignatureLength(bytes.len()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_keypair_generation() {
+        let kp = KeyPair::generate();
+        let base64_key = kp.public_key_base64();
+        assert_eq!(base64_key.len(), 44);
+    }
+
+    #[test]
+    fn test_parse_public_key() {
+        let kp = KeyPair::generate();
+        let parsed = parse_public_key(&kp.public_key_base64()).unwrap();
+        assert_eq!(parsed.as_bytes(), kp.public_key_bytes().as_slice());
+    }
+
+    #[test]
+    fn test_key_exchange() {
+        let alice = KeyPair::generate();
+        let alice_public = alice.public_key_base64();
+
+        let bob = KeyPair::generate();
+        let bob_public = bob.public_key_base64();
+
+        let alice_peer = parse_public_key(&bob_public).unwrap();
+        let bob_peer = parse_public_key(&alice_public).unwrap();
+
+        assert_eq!(
+            alice.diffie_hellman(alice_peer),
+            bob.diffie_hellman(bob_peer)
+        );
+    }
+
+    #[test]
+    fn test_invalid_key_length() {
+        let result = parse_public_key("aGVsbG8=");
+        assert!(matches!(result, Err(KeyExchangeError::InvalidKeyLength(5))));
+    }
+
+    #[test]
+    fn test_verify_handshake_signature_rejects_tampered_transcript() {
+        use ed25519_dalek::{Signer, SigningKey};
+
+        let identity = SigningKey::generate(&mut rand::rngs::OsRng);
+        let transcript = [1u8; 32];
+        let signature = identity.sign(&transcript).to_bytes();
+        let identity_key = identity.verifying_key().to_bytes();
+
+        // Correct transcript verifies.
+        verify_handshake_signature(&transcript, &identity_key, &signature).unwrap();
+
+        // Tampered transcript does not.
+        let tampered = [2u8; 32];
+        let err = verify_handshake_signature(&tampered, &identity_key, &signature).unwrap_err();
+        assert!(matches!(err, KeyExchangeError::SignatureInvalid));
+    }
+
+    #[test]
+    fn test_verify_server_identity_pinned() {
+        let key = [5u8; 32];
+        assert!(verify_server_identity("addr", &key, &ServerKeyPolicy::Pinned(key)).is_ok());
+        assert!(matches!(
+            verify_server_identity("addr", &[6u8; 32], &ServerKeyPolicy::Pinned(key)),
+            Err(KeyExchangeError::IdentityKeyMismatch)
+        ));
+    }
+
+    #[test]
+    fn test_derive_directional_keys_are_independent_and_deterministic() {
+        let shared_secret = [3u8; 32];
+        let transcript = [9u8; 32];
+
+        let (c2s_key, s2c_key) = derive_directional_keys(&shared_secret, &transcript);
+        let (c2s_key_again, s2c_key_again) = derive_directional_keys(&shared_secret, &transcript);
+
+        assert_ne!(c2s_key, s2c_key);
+        assert_eq!(c2s_key, c2s_key_again);
+        assert_eq!(s2c_key, s2c_key_again);
+    }
+
+    #[test]
+    fn test_derive_header_keys_are_independent_of_payload_keys() {
+        let shared_secret = [3u8; 32];
+        let transcript = [9u8; 32];
+
+        let (c2s_key, s2c_key) = derive_directional_keys(&shared_secret, &transcript);
+        let (c2s_header_key, s2c_header_key) = derive_header_keys(&shared_secret, &transcript);
+        let (c2s_header_key_again, s2c_header_key_again) =
+            derive_header_keys(&shared_secret, &transcript);
+
+        assert_ne!(c2s_header_key, s2c_header_key);
+        assert_ne!(c2s_header_key, c2s_key);
+        assert_ne!(s2c_header_key, s2c_key);
+        assert_eq!(c2s_header_key, c2s_header_key_again);
+        assert_eq!(s2c_header_key, s2c_header_key_again);
+    }
+
+    #[test]
+    fn test_handshake_transcript_is_sensitive_to_client_hello_features() {
+        let client = KeyPair::generate();
+        let server = KeyPair::generate();
+        let client_public = parse_public_key(&client.public_key_base64()).unwrap();
+        let server_public = parse_public_key(&server.public_key_base64()).unwrap();
+        let server_hello = server_hello_bytes(
+            "1.0",
+            "Lair Chat Server",
+            &["encryption".to_string()],
+            false,
+        );
+
+        let with_length_hiding = client_hello_bytes(
+            "1.0",
+            Some("test client"),
+            &["encryption".to_string(), "length_hiding".to_string()],
+            None,
+        );
+        let without_length_hiding = client_hello_bytes(
+            "1.0",
+            Some("test client"),
+            &["encryption".to_string()],
+            None,
+        );
+
+        let transcript = handshake_transcript(
+            &client_public,
+            &server_public,
+            &server_hello,
+            &with_length_hiding,
+        );
+        let downgraded = handshake_transcript(
+            &client_public,
+            &server_public,
+            &server_hello,
+            &without_length_hiding,
+        );
+        assert_ne!(
+            transcript, downgraded,
+            "stripping a feature from ClientHello must change the transcript"
+        );
+    }
+
+    #[test]
+    fn test_verify_server_identity_trust_on_first_use_pins_after_first_connection() {
+        let addr = format!("tofu-test-{}", uuid::Uuid::new_v4());
+        assert!(
+            verify_server_identity(&addr, &[1u8; 32], &ServerKeyPolicy::TrustOnFirstUse).is_ok()
+        );
+        assert!(
+            verify_server_identity(&addr, &[1u8; 32], &ServerKeyPolicy::TrustOnFirstUse).is_ok()
+        );
+        assert!(matches!(
+            verify_server_identity(&addr, &[2u8; 32], &ServerKeyPolicy::TrustOnFirstUse),
+            Err(KeyExchangeError::IdentityKeyChanged(_))
+        ));
+    }
+}
@@ -0,0 +1,10 @@
+//! Encryption utilities - AES-256-GCM, X25519
+pub mod aes_gcm;
+pub mod key_exchange;
+
+pub use aes_gcm::{Cipher, CryptoError, NONCE_SIZE, TAG_SIZE};
+pub use key_exchange::{
+    client_hello_bytes, decode_32_bytes, decode_64_bytes, derive_directional_keys,
+    derive_header_keys, handshake_transcript, parse_public_key, server_hello_bytes,
+    verify_handshake_signature, verify_server_identity, KeyExchangeError, KeyPair, ServerKeyPolicy,
+};
@@ -0,0 +1,386 @@
+//! Chunked attachment (file/image) reassembly.
+//!
+//! Frames are capped at `MAX_MESSAGE_SIZE` (see [`super::tcp`]), so an
+//! attachment larger than one frame is split client-side into an
+//! `AttachmentBegin` header, a run of `AttachmentChunk`s, and a trailing
+//! `AttachmentEnd` carrying the whole-file checksum. [`AttachmentAssembler`]
+//! reconstructs one transfer's bytes as chunks arrive and verifies the
+//! final SHA-256 before handing the buffer back.
+
+use std::collections::HashMap;
+
+use base64::prelude::*;
+use sha2::{Digest, Sha256};
+
+use super::messages::TransferId;
+use super::tcp::MAX_MESSAGE_SIZE;
+
+/// Maximum total size of a chunked attachment transfer (100 MB). Checked
+/// against `AttachmentBegin.total_size` before reserving any reassembly
+/// buffer, since that field comes straight from the wire and is otherwise
+/// unbounded.
+pub const MAX_ATTACHMENT_SIZE: u64 = 100 * 1024 * 1024;
+
+/// Maximum number of chunks a single attachment transfer may declare.
+/// Bounds memory independently of [`MAX_ATTACHMENT_SIZE`], since
+/// `AttachmentBegin.total_chunks` is also wire-supplied and a small
+/// `total_size` with a huge `total_chunks` would otherwise still be
+/// accepted.
+pub const MAX_ATTACHMENT_CHUNKS: u32 = 10_000;
+
+/// Errors produced while assembling a chunked attachment transfer.
+#[derive(Debug, thiserror::Error)]
+pub enum AttachmentError {
+    #[error("transfer {0} is already in progress")]
+    AlreadyStarted(TransferId),
+
+    #[error("unknown transfer {0}")]
+    UnknownTransfer(TransferId),
+
+    #[error(
+        "transfer {transfer_id} declares {total_size} bytes / {total_chunks} chunks, over the {MAX_ATTACHMENT_SIZE} byte / {MAX_ATTACHMENT_CHUNKS} chunk limit"
+    )]
+    TransferTooLarge {
+        transfer_id: TransferId,
+        total_size: u64,
+        total_chunks: u32,
+    },
+
+    #[error(
+        "chunk index {index} out of range for transfer {transfer_id} (total_chunks={total_chunks})"
+    )]
+    ChunkIndexOutOfRange {
+        transfer_id: TransferId,
+        index: u32,
+        total_chunks: u32,
+    },
+
+    #[error("chunk {index} of transfer {transfer_id} is {size} bytes, over the {MAX_MESSAGE_SIZE}-byte frame limit")]
+    ChunkTooLarge {
+        transfer_id: TransferId,
+        index: u32,
+        size: usize,
+    },
+
+    #[error("chunk {index} of transfer {transfer_id} is not valid base64: {source}")]
+    InvalidChunkEncoding {
+        transfer_id: TransferId,
+        index: u32,
+        source: base64::DecodeError,
+    },
+
+    #[error("transfer {transfer_id} finished with {missing} missing chunk(s)")]
+    IncompleteTransfer {
+        transfer_id: TransferId,
+        missing: usize,
+    },
+
+    #[error("transfer {transfer_id} reassembled to {actual} bytes, expected {expected}")]
+    SizeMismatch {
+        transfer_id: TransferId,
+        expected: u64,
+        actual: u64,
+    },
+
+    #[error("transfer {transfer_id} failed SHA-256 verification")]
+    ChecksumMismatch { transfer_id: TransferId },
+}
+
+struct PendingTransfer {
+    file_name: String,
+    mime_type: String,
+    total_size: u64,
+    total_chunks: u32,
+    chunks: HashMap<u32, Vec<u8>>,
+}
+
+/// A fully reassembled, checksum-verified attachment.
+#[derive(Debug, Clone)]
+pub struct CompletedAttachment {
+    pub file_name: String,
+    pub mime_type: String,
+    pub data: Vec<u8>,
+}
+
+/// Tracks in-flight chunked attachment transfers and reassembles them.
+///
+/// One instance should be kept per connection; transfer state is removed
+/// as soon as a transfer completes (successfully or not), so it doesn't
+/// grow unbounded.
+#[derive(Debug, Default)]
+pub struct AttachmentAssembler {
+    pending: HashMap<TransferId, PendingTransfer>,
+}
+
+impl AttachmentAssembler {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record the start of a new transfer.
+    pub fn begin(
+        &mut self,
+        transfer_id: TransferId,
+        file_name: impl Into<String>,
+        mime_type: impl Into<String>,
+        total_size: u64,
+        total_chunks: u32,
+    ) -> Result<(), AttachmentError> {
+        if self.pending.contains_key(&transfer_id) {
+            return Err(AttachmentError::AlreadyStarted(transfer_id));
+        }
+        if total_size > MAX_ATTACHMENT_SIZE || total_chunks > MAX_ATTACHMENT_CHUNKS {
+            return Err(AttachmentError::TransferTooLarge {
+                transfer_id,
+                total_size,
+                total_chunks,
+            });
+        }
+        self.pending.insert(
+            transfer_id,
+            PendingTransfer {
+                file_name: file_name.into(),
+                mime_type: mime_type.into(),
+                total_size,
+                total_chunks,
+                chunks: HashMap::new(),
+            },
+        );
+        Ok(())
+    }
+
+    /// Record one base64-encoded chunk of a transfer started with [`begin`](Self::begin).
+    pub fn chunk(
+        &mut self,
+        transfer_id: TransferId,
+        index: u32,
+        data: &str,
+    ) -> Result<(), AttachmentError> {
+        let transfer = self
+            .pending
+            .get_mut(&transfer_id)
+            .ok_or(AttachmentError::UnknownTransfer(transfer_id))?;
+        if index >= transfer.total_chunks {
+            return Err(AttachmentError::ChunkIndexOutOfRange {
+                transfer_id,
+                index,
+                total_chunks: transfer.total_chunks,
+            });
+        }
+        let bytes = BASE64_STANDARD.decode(data).map_err(|source| {
+            AttachmentError::InvalidChunkEncoding {
+                transfer_id,
+                index,
+                source,
+            }
+        })?;
+        if bytes.len() > MAX_MESSAGE_SIZE as usize {
+            return Err(AttachmentError::ChunkTooLarge {
+                transfer_id,
+                index,
+                size: bytes.len(),
+            });
+        }
+        transfer.chunks.insert(index, bytes);
+        Ok(())
+    }
+
+    /// Finish a transfer: verify every chunk arrived, reassemble them in
+    /// order, and check `sha256` (hex-encoded) against the result.
+    pub fn end(
+        &mut self,
+        transfer_id: TransferId,
+        sha256: &str,
+    ) -> Result<CompletedAttachment, AttachmentError> {
+        let transfer = self
+            .pending
+            .remove(&transfer_id)
+            .ok_or(AttachmentError::UnknownTransfer(transfer_id))?;
+
+        let missing = transfer.total_chunks as usize - transfer.chunks.len();
+        if missing > 0 {
+            return Err(AttachmentError::IncompleteTransfer {
+                transfer_id,
+                missing,
+            });
+        }
+
+        let mut data = Vec::with_capacity(transfer.total_size as usize);
+        for index in 0..transfer.total_chunks {
+            let chunk = transfer
+                .chunks
+                .get(&index)
+                .expect("missing-chunk count checked above");
+            data.extend_from_slice(chunk);
+        }
+
+        if data.len() as u64 != transfer.total_size {
+            return Err(AttachmentError::SizeMismatch {
+                transfer_id,
+                expected: transfer.total_size,
+                actual: data.len() as u64,
+            });
+        }
+
+        let mut hasher = Sha256::new();
+        hasher.update(&data);
+        if to_hex(&hasher.finalize()) != sha256.to_lowercase() {
+            return Err(AttachmentError::ChecksumMismatch { transfer_id });
+        }
+
+        Ok(CompletedAttachment {
+            file_name: transfer.file_name,
+            mime_type: transfer.mime_type,
+            data,
+        })
+    }
+}
+
+/// Hex-encode bytes without pulling in a dedicated hex dependency.
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    fn chunked(data: &[u8], chunk_size: usize) -> Vec<String> {
+        data.chunks(chunk_size)
+            .map(|c| BASE64_STANDARD.encode(c))
+            .collect()
+    }
+
+    #[test]
+    fn test_reassemble_success() {
+        let data = b"the quick brown fox jumps over the lazy dog".to_vec();
+        let chunks = chunked(&data, 10);
+        let transfer_id = Uuid::new_v4();
+
+        let mut hasher = Sha256::new();
+        hasher.update(&data);
+        let sha256 = to_hex(&hasher.finalize());
+
+        let mut assembler = AttachmentAssembler::new();
+        assembler
+            .begin(
+                transfer_id,
+                "fox.txt",
+                "text/plain",
+                data.len() as u64,
+                chunks.len() as u32,
+            )
+            .unwrap();
+        for (index, chunk) in chunks.iter().enumerate() {
+            assembler.chunk(transfer_id, index as u32, chunk).unwrap();
+        }
+
+        let completed = assembler.end(transfer_id, &sha256).unwrap();
+        assert_eq!(completed.data, data);
+        assert_eq!(completed.file_name, "fox.txt");
+        assert_eq!(completed.mime_type, "text/plain");
+    }
+
+    #[test]
+    fn test_missing_chunk_rejected() {
+        let data = b"0123456789".to_vec();
+        let transfer_id = Uuid::new_v4();
+        let mut assembler = AttachmentAssembler::new();
+        assembler
+            .begin(transfer_id, "f.bin", "application/octet-stream", 10, 2)
+            .unwrap();
+        assembler
+            .chunk(transfer_id, 0, &BASE64_STANDARD.encode(&data[..5]))
+            .unwrap();
+
+        let err = assembler.end(transfer_id, "deadbeef").unwrap_err();
+        assert!(matches!(err, AttachmentError::IncompleteTransfer { .. }));
+    }
+
+    #[test]
+    fn test_checksum_mismatch_rejected() {
+        let data = b"0123456789".to_vec();
+        let transfer_id = Uuid::new_v4();
+        let mut assembler = AttachmentAssembler::new();
+        assembler
+            .begin(transfer_id, "f.bin", "application/octet-stream", 10, 1)
+            .unwrap();
+        assembler
+            .chunk(transfer_id, 0, &BASE64_STANDARD.encode(&data))
+            .unwrap();
+
+        let err = assembler.end(transfer_id, "not-the-real-hash").unwrap_err();
+        assert!(matches!(err, AttachmentError::ChecksumMismatch { .. }));
+    }
+
+    #[test]
+    fn test_chunk_too_large_rejected() {
+        let transfer_id = Uuid::new_v4();
+        let mut assembler = AttachmentAssembler::new();
+        assembler
+            .begin(transfer_id, "big.bin", "application/octet-stream", 0, 1)
+            .unwrap();
+        let oversized = vec![0u8; MAX_MESSAGE_SIZE as usize + 1];
+
+        let err = assembler
+            .chunk(transfer_id, 0, &BASE64_STANDARD.encode(&oversized))
+            .unwrap_err();
+        assert!(matches!(err, AttachmentError::ChunkTooLarge { .. }));
+    }
+
+    #[test]
+    fn test_chunk_index_out_of_range_rejected() {
+        let transfer_id = Uuid::new_v4();
+        let mut assembler = AttachmentAssembler::new();
+        assembler
+            .begin(transfer_id, "f.bin", "application/octet-stream", 1, 1)
+            .unwrap();
+
+        let err = assembler
+            .chunk(transfer_id, 5, &BASE64_STANDARD.encode(b"x"))
+            .unwrap_err();
+        assert!(matches!(err, AttachmentError::ChunkIndexOutOfRange { .. }));
+    }
+
+    #[test]
+    fn test_oversized_transfer_rejected() {
+        let transfer_id = Uuid::new_v4();
+        let mut assembler = AttachmentAssembler::new();
+        let err = assembler
+            .begin(
+                transfer_id,
+                "huge.bin",
+                "application/octet-stream",
+                MAX_ATTACHMENT_SIZE + 1,
+                1,
+            )
+            .unwrap_err();
+        assert!(matches!(err, AttachmentError::TransferTooLarge { .. }));
+    }
+
+    #[test]
+    fn test_too_many_chunks_rejected() {
+        let transfer_id = Uuid::new_v4();
+        let mut assembler = AttachmentAssembler::new();
+        let err = assembler
+            .begin(
+                transfer_id,
+                "huge.bin",
+                "application/octet-stream",
+                0,
+                MAX_ATTACHMENT_CHUNKS + 1,
+            )
+            .unwrap_err();
+        assert!(matches!(err, AttachmentError::TransferTooLarge { .. }));
+    }
+
+    #[test]
+    fn test_unknown_transfer_rejected() {
+        let mut assembler = AttachmentAssembler::new();
+        let err = assembler
+            .chunk(Uuid::new_v4(), 0, &BASE64_STANDARD.encode(b"x"))
+            .unwrap_err();
+        assert!(matches!(err, AttachmentError::UnknownTransfer(_)));
+    }
+}
@@ -0,0 +1,255 @@
+//! Event-handler dispatch for decoded [`ServerMessage`]s.
+//!
+//! Application code can implement [`EventHandler`] and register it on an
+//! [`EventDispatcher`] to react to specific server events instead of
+//! pattern-matching the wire enum directly. Every method has a no-op
+//! default, so implementors only override the events they care about.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use super::messages::{Message, MessageId, MessageTarget, RoomId, ServerMessage, UserId};
+
+/// Observer for decoded [`ServerMessage`]s.
+#[async_trait]
+pub trait EventHandler: Send + Sync {
+    /// A new message arrived in a room or DM the client is part of.
+    async fn on_message_received(&self, _message: &Message, _author_username: &str) {}
+
+    /// An existing message's content was changed.
+    async fn on_message_edited(
+        &self,
+        _message: &Message,
+        _editor_username: &str,
+        _previous_content: Option<&str>,
+    ) {
+    }
+
+    /// A message was removed outright.
+    async fn on_message_deleted(
+        &self,
+        _message_id: MessageId,
+        _target: &MessageTarget,
+        _deleted_by: UserId,
+    ) {
+    }
+
+    /// A message's content was blanked while keeping its envelope.
+    async fn on_message_redacted(
+        &self,
+        _message_id: MessageId,
+        _target: &MessageTarget,
+        _redacted_by: UserId,
+    ) {
+    }
+
+    /// A user came online.
+    async fn on_user_online(&self, _user_id: UserId, _username: &str) {}
+
+    /// A user went offline.
+    async fn on_user_offline(&self, _user_id: UserId, _username: &str) {}
+
+    /// A room was superseded by `replacement_room_id` (see
+    /// [`super::messages::ClientMessage::UpgradeRoom`]).
+    async fn on_room_tombstone(
+        &self,
+        _room_id: RoomId,
+        _replacement_room_id: RoomId,
+        _reason: Option<&str>,
+    ) {
+    }
+
+    /// The server reported an error. Every `Error` message is forwarded,
+    /// whether or not it carries a `request_id`.
+    async fn on_error(&self, _code: &str, _message: &str) {}
+
+    /// Any event without a dedicated handler method above.
+    async fn on_other(&self, _message: &ServerMessage) {}
+}
+
+/// Holds a set of registered [`EventHandler`]s and fans decoded
+/// [`ServerMessage`]s out to them.
+#[derive(Clone, Default)]
+pub struct EventDispatcher {
+    handlers: Vec<Arc<dyn EventHandler>>,
+}
+
+impl EventDispatcher {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a handler. Handlers are invoked in registration order.
+    pub fn register(&mut self, handler: Arc<dyn EventHandler>) {
+        self.handlers.push(handler);
+    }
+
+    /// Decode `message`'s variant and invoke the matching handler method on
+    /// every registered handler.
+    pub async fn dispatch(&self, message: &ServerMessage) {
+        match message {
+            ServerMessage::MessageReceived {
+                message,
+                author_username,
+            } => {
+                for handler in &self.handlers {
+                    handler.on_message_received(message, author_username).await;
+                }
+            }
+            ServerMessage::MessageEdited {
+                message,
+                editor_username,
+                previous_content,
+            } => {
+                for handler in &self.handlers {
+                    handler
+                        .on_message_edited(message, editor_username, previous_content.as_deref())
+                        .await;
+                }
+            }
+            ServerMessage::MessageDeleted {
+                message_id,
+                target,
+                deleted_by,
+            } => {
+                for handler in &self.handlers {
+                    handler
+                        .on_message_deleted(*message_id, target, *deleted_by)
+                        .await;
+                }
+            }
+            ServerMessage::MessageRedacted {
+                message_id,
+                target,
+                redacted_by,
+            } => {
+                for handler in &self.handlers {
+                    handler
+                        .on_message_redacted(*message_id, target, *redacted_by)
+                        .await;
+                }
+            }
+            ServerMessage::UserOnline { user_id, username } => {
+                for handler in &self.handlers {
+                    handler.on_user_online(*user_id, username).await;
+                }
+            }
+            ServerMessage::UserOffline { user_id, username } => {
+                for handler in &self.handlers {
+                    handler.on_user_offline(*user_id, username).await;
+                }
+            }
+            ServerMessage::RoomTombstone {
+                room_id,
+                replacement_room_id,
+                reason,
+            } => {
+                for handler in &self.handlers {
+                    handler
+                        .on_room_tombstone(*room_id, *replacement_room_id, reason.as_deref())
+                        .await;
+                }
+            }
+            ServerMessage::Error { code, message, .. } => {
+                for handler in &self.handlers {
+                    handler.on_error(code, message).await;
+                }
+            }
+            other => {
+                for handler in &self.handlers {
+                    handler.on_other(other).await;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protocol::messages::MessageTarget;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use uuid::Uuid;
+
+    #[derive(Default)]
+    struct RecordingHandler {
+        message_received: AtomicUsize,
+        user_online: AtomicUsize,
+        other: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl EventHandler for RecordingHandler {
+        async fn on_message_received(&self, _message: &Message, _author_username: &str) {
+            self.message_received.fetch_add(1, Ordering::SeqCst);
+        }
+
+        async fn on_user_online(&self, _user_id: UserId, _username: &str) {
+            self.user_online.fetch_add(1, Ordering::SeqCst);
+        }
+
+        async fn on_other(&self, _message: &ServerMessage) {
+            self.other.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_dispatch_invokes_matching_handler() {
+        let handler = Arc::new(RecordingHandler::default());
+        let mut dispatcher = EventDispatcher::new();
+        dispatcher.register(handler.clone());
+
+        let message = Message {
+            id: Uuid::new_v4(),
+            author: Uuid::new_v4(),
+            target: MessageTarget::Room {
+                room_id: Uuid::new_v4(),
+            },
+            content: "hi".to_string(),
+            edited: false,
+            redacted: false,
+            created_at: chrono::Utc::now(),
+        };
+        dispatcher
+            .dispatch(&ServerMessage::MessageReceived {
+                message,
+                author_username: "alice".to_string(),
+            })
+            .await;
+        dispatcher
+            .dispatch(&ServerMessage::UserOnline {
+                user_id: Uuid::new_v4(),
+                username: "bob".to_string(),
+            })
+            .await;
+        dispatcher
+            .dispatch(&ServerMessage::Pong { server_time: None })
+            .await;
+
+        assert_eq!(handler.message_received.load(Ordering::SeqCst), 1);
+        assert_eq!(handler.user_online.load(Ordering::SeqCst), 1);
+        assert_eq!(handler.other.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_unhandled_event_defaults_are_no_ops() {
+        let handler = Arc::new(RecordingHandler::default());
+        let mut dispatcher = EventDispatcher::new();
+        dispatcher.register(handler.clone());
+
+        dispatcher
+            .dispatch(&ServerMessage::MessageDeleted {
+                message_id: Uuid::new_v4(),
+                target: MessageTarget::Room {
+                    room_id: Uuid::new_v4(),
+                },
+                deleted_by: Uuid::new_v4(),
+            })
+            .await;
+
+        assert_eq!(handler.message_received.load(Ordering::SeqCst), 0);
+        assert_eq!(handler.user_online.load(Ordering::SeqCst), 0);
+        assert_eq!(handler.other.load(Ordering::SeqCst), 0);
+    }
+}
@@ -25,6 +25,9 @@ pub type SessionId = Uuid;
 /// Invitation ID.
 pub type InvitationId = Uuid;
 
+/// Chunked attachment transfer ID.
+pub type TransferId = Uuid;
+
 // ============================================================================
 // Client -> Server Messages
 // ============================================================================
@@ -39,6 +42,10 @@ pub enum ClientMessage {
         client_name: String,
         #[serde(default)]
         features: Vec<String>,
+        /// Base64-encoded long-term ed25519 public key, advertised so the
+        /// server can verify `SignedMessage`s from this connection.
+        #[serde(skip_serializing_if = "Option::is_none", default)]
+        signing_public_key: Option<String>,
     },
 
     // Authentication
@@ -81,13 +88,23 @@ pub enum ClientMessage {
         #[serde(skip_serializing_if = "Option::is_none")]
         request_id: Option<String>,
         message_id: MessageId,
-        content: String,
+        new_content: String,
     },
     DeleteMessage {
         #[serde(skip_serializing_if = "Option::is_none")]
         request_id: Option<String>,
         message_id: MessageId,
     },
+    /// Blank a message's content server-side while preserving its envelope
+    /// (id, author, created_at), unlike [`ClientMessage::DeleteMessage`]
+    /// which removes it outright.
+    RedactMessage {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        request_id: Option<String>,
+        message_id: MessageId,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        reason: Option<String>,
+    },
     GetMessages {
         #[serde(skip_serializing_if = "Option::is_none")]
         request_id: Option<String>,
@@ -98,6 +115,54 @@ pub enum ClientMessage {
         before: Option<DateTime<Utc>>,
     },
 
+    // Chunked attachment transfer. A file/image larger than one frame
+    // (see `MAX_MESSAGE_SIZE`) is split client-side into `AttachmentBegin`
+    // + a run of `AttachmentChunk`s + a trailing `AttachmentEnd`. The
+    // client picks `transfer_id` up front so chunks can be correlated
+    // without waiting on a response to `AttachmentBegin`.
+    AttachmentBegin {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        request_id: Option<String>,
+        target: MessageTarget,
+        transfer_id: TransferId,
+        file_name: String,
+        mime_type: String,
+        total_size: u64,
+        total_chunks: u32,
+        /// Small base64-encoded preview image, so receivers can show
+        /// something before all chunks have arrived.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        thumbnail: Option<String>,
+    },
+    AttachmentChunk {
+        transfer_id: TransferId,
+        index: u32,
+        /// Base64-encoded chunk bytes, kept under `MAX_MESSAGE_SIZE`.
+        data: String,
+    },
+    AttachmentEnd {
+        transfer_id: TransferId,
+        /// Hex-encoded SHA-256 of the full reassembled file.
+        sha256: String,
+    },
+
+    // Delivery / read markers. Both are "up to" cumulative: marking
+    // `up_to_message_id` implies every earlier message in `target` carries
+    // the same marker, so the server only needs to persist the latest one
+    // per (user, target) pair rather than one per message.
+    MarkDelivered {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        request_id: Option<String>,
+        target: MessageTarget,
+        up_to_message_id: MessageId,
+    },
+    MarkRead {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        request_id: Option<String>,
+        target: MessageTarget,
+        up_to_message_id: MessageId,
+    },
+
     // Rooms
     CreateRoom {
         #[serde(skip_serializing_if = "Option::is_none")]
@@ -133,6 +198,17 @@ pub enum ClientMessage {
         request_id: Option<String>,
         room_id: RoomId,
     },
+    /// Supersede `room_id` with a freshly created successor room, for
+    /// settings changes that can't be applied in place. The server
+    /// auto-migrates membership to the successor and broadcasts
+    /// [`ServerMessage::RoomTombstone`] to the old room.
+    UpgradeRoom {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        request_id: Option<String>,
+        room_id: RoomId,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        reason: Option<String>,
+    },
 
     // Invitations
     InviteToRoom {
@@ -350,6 +426,15 @@ pub enum ServerMessage {
         #[serde(skip_serializing_if = "Option::is_none")]
         error: Option<ErrorInfo>,
     },
+    UpgradeRoomResponse {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        request_id: Option<String>,
+        success: bool,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        room: Option<Room>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        error: Option<ErrorInfo>,
+    },
 
     // Invitation responses
     InviteToRoomResponse {
@@ -431,6 +516,18 @@ pub enum ServerMessage {
     // Key Exchange Response
     KeyExchangeResponse {
         public_key: String,
+        /// Base64-encoded ed25519 public key identifying this server.
+        identity_key: String,
+        /// Base64-encoded ed25519 signature over the handshake transcript
+        /// (SHA-256 of the `ServerHello`, the `ClientHello`, and the client's
+        /// and server's ephemeral public keys),
+        /// proving `identity_key` actually generated `public_key`.
+        signature: String,
+        /// Whether the server also enabled length-hiding framing for this
+        /// connection, i.e. we requested the `length_hiding` feature and
+        /// the server honored it.
+        #[serde(default)]
+        length_hiding: bool,
     },
 
     // Error
@@ -450,6 +547,7 @@ pub enum ServerMessage {
     },
     MessageEdited {
         message: Message,
+        editor_username: String,
         #[serde(skip_serializing_if = "Option::is_none")]
         previous_content: Option<String>,
     },
@@ -458,6 +556,25 @@ pub enum ServerMessage {
         target: MessageTarget,
         deleted_by: UserId,
     },
+    /// Broadcast once [`ClientMessage::RedactMessage`] has taken effect;
+    /// `target` lets clients find the message without a full history
+    /// reload.
+    MessageRedacted {
+        message_id: MessageId,
+        target: MessageTarget,
+        redacted_by: UserId,
+    },
+    /// Sent once every chunk of a transfer has arrived and passed checksum
+    /// verification; `message_id` is the now-visible message carrying the
+    /// attachment.
+    AttachmentReceived {
+        transfer_id: TransferId,
+        message_id: MessageId,
+        target: MessageTarget,
+        file_name: String,
+        mime_type: String,
+        size: u64,
+    },
     UserJoinedRoom {
         room_id: RoomId,
         user: User,
@@ -479,6 +596,14 @@ pub enum ServerMessage {
         room_name: String,
         deleted_by: UserId,
     },
+    /// `room_id` has been superseded by `replacement_room_id` and is now
+    /// frozen (read-only); clients should offer to join the replacement.
+    RoomTombstone {
+        room_id: RoomId,
+        replacement_room_id: RoomId,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        reason: Option<String>,
+    },
     UserOnline {
         user_id: UserId,
         username: String,
@@ -491,6 +616,15 @@ pub enum ServerMessage {
         user_id: UserId,
         target: MessageTarget,
     },
+    /// Fan-out of a `MarkDelivered`/`MarkRead` marker to other members of
+    /// `target`, so their clients can render "seen by" state.
+    MarkerUpdate {
+        target: MessageTarget,
+        user_id: UserId,
+        username: String,
+        marker_kind: MarkerKind,
+        message_id: MessageId,
+    },
     InvitationReceived {
         invitation: Invitation,
     },
@@ -521,6 +655,14 @@ pub enum MessageTarget {
     },
 }
 
+/// Kind of delivery/read marker reported for a message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MarkerKind {
+    Delivered,
+    Read,
+}
+
 /// User information.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct User {
@@ -546,6 +688,11 @@ pub struct Message {
     pub target: MessageTarget,
     pub content: String,
     pub edited: bool,
+    /// Set once [`ClientMessage::RedactMessage`] has blanked `content`
+    /// server-side. The envelope (`id`, `author`, `created_at`) is kept so
+    /// history stays consistent; only `content` is cleared.
+    #[serde(default)]
+    pub redacted: bool,
     pub created_at: DateTime<Utc>,
 }
 
@@ -683,12 +830,80 @@ impl ClientMessage {
         }
     }
 
+    /// Create a mark-delivered request for every message in `target` up to
+    /// and including `up_to_message_id`.
+    pub fn mark_delivered(target: MessageTarget, up_to_message_id: MessageId) -> Self {
+        Self::MarkDelivered {
+            request_id: Some(Uuid::new_v4().to_string()),
+            target,
+            up_to_message_id,
+        }
+    }
+
+    /// Create a mark-read request for every message in `target` up to and
+    /// including `up_to_message_id`.
+    pub fn mark_read(target: MessageTarget, up_to_message_id: MessageId) -> Self {
+        Self::MarkRead {
+            request_id: Some(Uuid::new_v4().to_string()),
+            target,
+            up_to_message_id,
+        }
+    }
+
+    /// Create an attachment-begin request, generating a fresh `transfer_id`
+    /// for the caller to reuse on the matching `attachment_chunk`/
+    /// `attachment_end` calls.
+    #[allow(clippy::too_many_arguments)]
+    pub fn attachment_begin(
+        target: MessageTarget,
+        file_name: impl Into<String>,
+        mime_type: impl Into<String>,
+        total_size: u64,
+        total_chunks: u32,
+        thumbnail: Option<String>,
+    ) -> (Self, TransferId) {
+        let transfer_id = Uuid::new_v4();
+        (
+            Self::AttachmentBegin {
+                request_id: Some(Uuid::new_v4().to_string()),
+                target,
+                transfer_id,
+                file_name: file_name.into(),
+                mime_type: mime_type.into(),
+                total_size,
+                total_chunks,
+                thumbnail,
+            },
+            transfer_id,
+        )
+    }
+
+    /// Create an attachment-chunk message; `data` is the already
+    /// base64-encoded chunk payload.
+    pub fn attachment_chunk(transfer_id: TransferId, index: u32, data: impl Into<String>) -> Self {
+        Self::AttachmentChunk {
+            transfer_id,
+            index,
+            data: data.into(),
+        }
+    }
+
+    /// Create an attachment-end message; `sha256` is the hex-encoded
+    /// checksum of the full reassembled file.
+    pub fn attachment_end(transfer_id: TransferId, sha256: impl Into<String>) -> Self {
+        Self::AttachmentEnd {
+            transfer_id,
+            sha256: sha256.into(),
+        }
+    }
+
     /// Create a client hello message.
     pub fn client_hello() -> Self {
         Self::ClientHello {
             version: "1.0".to_string(),
             client_name: "Lair Chat TUI".to_string(),
             features: vec![],
+            signing_public_key: None,
         }
     }
 
@@ -697,7 +912,34 @@ impl ClientMessage {
         Self::ClientHello {
             version: "1.0".to_string(),
             client_name: "Lair Chat TUI".to_string(),
-            features: vec!["encryption".to_string()],
+            features: vec!["encryption".to_string(), "length_hiding".to_string()],
+            signing_public_key: None,
+        }
+    }
+
+    /// Create a client hello message that advertises a long-term ed25519
+    /// public key for verifying this connection's `SignedMessage`s.
+    pub fn client_hello_with_signing_key(public_key: impl Into<String>) -> Self {
+        Self::ClientHello {
+            version: "1.0".to_string(),
+            client_name: "Lair Chat TUI".to_string(),
+            features: vec!["message_signing".to_string()],
+            signing_public_key: Some(public_key.into()),
+        }
+    }
+
+    /// Create a client hello message requesting both transport encryption
+    /// and message signing.
+    pub fn client_hello_with_encryption_and_signing_key(public_key: impl Into<String>) -> Self {
+        Self::ClientHello {
+            version: "1.0".to_string(),
+            client_name: "Lair Chat TUI".to_string(),
+            features: vec![
+                "encryption".to_string(),
+                "length_hiding".to_string(),
+                "message_signing".to_string(),
+            ],
+            signing_public_key: Some(public_key.into()),
         }
     }
 
@@ -730,6 +972,7 @@ impl ServerMessage {
             | ServerMessage::LeaveRoomResponse { request_id, .. }
             | ServerMessage::ListRoomsResponse { request_id, .. }
             | ServerMessage::GetRoomResponse { request_id, .. }
+            | ServerMessage::UpgradeRoomResponse { request_id, .. }
             | ServerMessage::InviteToRoomResponse { request_id, .. }
             | ServerMessage::AcceptInvitationResponse { request_id, .. }
             | ServerMessage::DeclineInvitationResponse { request_id, .. }
@@ -13,15 +13,23 @@
 //! - **TCP**: Lower latency, supports end-to-end encryption, requires direct connection
 //! - **WebSocket**: HTTP-compatible, passes through firewalls/proxies, uses TLS for encryption
 
+pub mod attachments;
+pub mod events;
 pub mod http;
 pub mod messages;
+pub mod signing;
+pub mod stream;
 pub mod tcp;
 pub mod ws;
 
+pub use attachments::{AttachmentAssembler, AttachmentError, CompletedAttachment};
+pub use events::{EventDispatcher, EventHandler};
 pub use http::{HttpClient, HttpClientConfig};
 pub use messages::{
     ClientMessage, Invitation, MessageTarget, Room, RoomListItem, RoomMember, ServerMessage,
     Session, User,
 };
-pub use tcp::{Connection, TcpError};
+pub use signing::{canonical_json, sign_message, verify_message, SignedMessage, SigningError};
+pub use stream::{EncryptedReadHalf, EncryptedStream, EncryptedWriteHalf};
+pub use tcp::{Connection, SendQueueConfig, SendQueuePolicy, TcpError};
 pub use ws::{WsConnection, WsError};
@@ -0,0 +1,189 @@
+//! Detached ed25519 signatures over a canonical JSON encoding of
+//! [`ClientMessage`].
+//!
+//! Transport encryption (see [`super::tcp`]) only protects the link to
+//! whatever terminates it, which may be a relay rather than the server
+//! itself. A signature over the message content lets the server detect
+//! tampering regardless of where encryption ends.
+//!
+//! Canonicalization is deterministic: object keys are sorted by UTF-8 byte
+//! order at every level and no insignificant whitespace is emitted, so the
+//! same logical message always signs to the same bytes no matter what order
+//! its fields were inserted in.
+
+use base64::prelude::*;
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::messages::ClientMessage;
+
+/// Errors from signing or verifying a [`SignedMessage`].
+#[derive(Debug, thiserror::Error)]
+pub enum SigningError {
+    #[error("failed to canonicalize message: {0}")]
+    Canonicalization(#[from] serde_json::Error),
+
+    #[error("invalid base64 in {0}: {1}")]
+    InvalidBase64(&'static str, base64::DecodeError),
+
+    #[error("public key must be 32 bytes, got {0}")]
+    InvalidPublicKeyLength(usize),
+
+    #[error("signature must be 64 bytes, got {0}")]
+    InvalidSignatureLength(usize),
+
+    #[error("invalid public key: {0}")]
+    InvalidPublicKey(ed25519_dalek::SignatureError),
+
+    #[error("signature verification failed")]
+    VerificationFailed,
+}
+
+/// A [`ClientMessage`] paired with a detached signature over its canonical
+/// JSON encoding, plus the base64-encoded public key to verify it with.
+///
+/// Wrapping the message rather than adding a `signature` field to every
+/// `ClientMessage` variant keeps the canonicalization boundary unambiguous:
+/// the signed bytes are exactly the canonical encoding of `message`, and
+/// `signature`/`public_key` never appear inside them.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SignedMessage {
+    pub message: ClientMessage,
+    /// Base64-encoded ed25519 public key, as advertised in `ClientHello`.
+    pub public_key: String,
+    /// Base64-encoded ed25519 signature over `canonical_json(&message)`.
+    pub signature: String,
+}
+
+/// Serialize `message` to a canonical JSON byte string: object keys sorted
+/// by UTF-8 byte order at every nesting level, no insignificant whitespace.
+pub fn canonical_json(message: &ClientMessage) -> Result<Vec<u8>, SigningError> {
+    let value = serde_json::to_value(message)?;
+    Ok(canonicalize(&value).into_bytes())
+}
+
+fn canonicalize(value: &Value) -> String {
+    match value {
+        Value::Object(map) => {
+            let mut entries: Vec<(&String, &Value)> = map.iter().collect();
+            entries.sort_by(|(a, _), (b, _)| a.as_bytes().cmp(b.as_bytes()));
+            let body: Vec<String> = entries
+                .into_iter()
+                .map(|(k, v)| format!("{}:{}", serde_json::to_string(k).unwrap(), canonicalize(v)))
+                .collect();
+            format!("{{{}}}", body.join(","))
+        }
+        Value::Array(items) => {
+            let body: Vec<String> = items.iter().map(canonicalize).collect();
+            format!("[{}]", body.join(","))
+        }
+        leaf => serde_json::to_string(leaf).unwrap(),
+    }
+}
+
+/// Sign `message` with `signing_key`, producing a [`SignedMessage`] that
+/// advertises `signing_key`'s public half for verification.
+pub fn sign_message(
+    message: ClientMessage,
+    signing_key: &SigningKey,
+) -> Result<SignedMessage, SigningError> {
+    let bytes = canonical_json(&message)?;
+    let signature = signing_key.sign(&bytes);
+    Ok(SignedMessage {
+        message,
+        public_key: BASE64_STANDARD.encode(signing_key.verifying_key().to_bytes()),
+        signature: BASE64_STANDARD.encode(signature.to_bytes()),
+    })
+}
+
+/// Verify `signed`'s signature against its own advertised `public_key`.
+pub fn verify_message(signed: &SignedMessage) -> Result<(), SigningError> {
+    let key_bytes = BASE64_STANDARD
+        .decode(&signed.public_key)
+        .map_err(|e| SigningError::InvalidBase64("public_key", e))?;
+    let key_bytes: [u8; 32] = key_bytes
+        .try_into()
+        .map_err(|v: Vec<u8>| SigningError::InvalidPublicKeyLength(v.len()))?;
+    let verifying_key =
+        VerifyingKey::from_bytes(&key_bytes).map_err(SigningError::InvalidPublicKey)?;
+
+    let sig_bytes = BASE64_STANDARD
+        .decode(&signed.signature)
+        .map_err(|e| SigningError::InvalidBase64("signature", e))?;
+    let sig_bytes: [u8; 64] = sig_bytes
+        .try_into()
+        .map_err(|v: Vec<u8>| SigningError::InvalidSignatureLength(v.len()))?;
+    let signature = Signature::from_bytes(&sig_bytes);
+
+    let bytes = canonical_json(&signed.message)?;
+    verifying_key
+        .verify(&bytes, &signature)
+        .map_err(|_| SigningError::VerificationFailed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protocol::messages::MessageTarget;
+    use rand::rngs::OsRng;
+    use uuid::Uuid;
+
+    fn sample_message() -> ClientMessage {
+        ClientMessage::send_message(
+            MessageTarget::Room {
+                room_id: Uuid::new_v4(),
+            },
+            "hello",
+        )
+    }
+
+    #[test]
+    fn test_canonical_form_is_stable_regardless_of_field_order() {
+        // Two structurally-equal Values built with keys inserted in a
+        // different order must canonicalize identically.
+        let a: Value = serde_json::from_str(r#"{"b":1,"a":2,"c":{"y":1,"x":2}}"#).unwrap();
+        let b: Value = serde_json::from_str(r#"{"a":2,"c":{"x":2,"y":1},"b":1}"#).unwrap();
+        assert_eq!(canonicalize(&a), canonicalize(&b));
+        assert_eq!(canonicalize(&a), r#"{"a":2,"b":1,"c":{"x":2,"y":1}}"#);
+    }
+
+    #[test]
+    fn test_sign_and_verify_round_trip() {
+        let signing_key = SigningKey::generate(&mut OsRng);
+        let signed = sign_message(sample_message(), &signing_key).unwrap();
+        verify_message(&signed).unwrap();
+    }
+
+    #[test]
+    fn test_verify_rejects_tampered_message() {
+        let signing_key = SigningKey::generate(&mut OsRng);
+        let mut signed = sign_message(sample_message(), &signing_key).unwrap();
+        signed.message = sample_message();
+
+        let err = verify_message(&signed).unwrap_err();
+        assert!(matches!(err, SigningError::VerificationFailed));
+    }
+
+    #[test]
+    fn test_verify_rejects_wrong_key() {
+        let signing_key = SigningKey::generate(&mut OsRng);
+        let signed = sign_message(sample_message(), &signing_key).unwrap();
+
+        let other_key = SigningKey::generate(&mut OsRng);
+        let mut wrong_signer = signed;
+        wrong_signer.public_key = BASE64_STANDARD.encode(other_key.verifying_key().to_bytes());
+
+        let err = verify_message(&wrong_signer).unwrap_err();
+        assert!(matches!(err, SigningError::VerificationFailed));
+    }
+
+    #[test]
+    fn test_canonical_json_excludes_nothing_but_is_deterministic() {
+        let message = sample_message();
+        let first = canonical_json(&message).unwrap();
+        let second = canonical_json(&message).unwrap();
+        assert_eq!(first, second);
+        assert!(!String::from_utf8(first).unwrap().contains(' '));
+    }
+}
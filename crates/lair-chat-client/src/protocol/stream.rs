@@ -0,0 +1,424 @@
+//! `AsyncRead`/`AsyncWrite` adapter over an encrypted TCP connection.
+//!
+//! [`Connection`](super::tcp::Connection) only exposes discrete
+//! `ClientMessage`/`ServerMessage` framing, which is fine for protocol
+//! messages but awkward for pushing an arbitrary byte stream (e.g. a file
+//! transfer) through the same encrypted channel. [`EncryptedStream`] wraps
+//! a connected [`TcpStream`] plus the session [`Cipher`](crate::crypto::Cipher)
+//! and presents it as a plain `AsyncRead`/`AsyncWrite` pair, chunking writes
+//! into AEAD frames on the way out and reassembling frames into a
+//! contiguous plaintext stream on the way in.
+//!
+//! Wire format is the same length-prefixed `nonce || ciphertext` frame
+//! [`super::tcp::Connection`] uses when length hiding is not negotiated: a
+//! 4-byte big-endian length header, followed by that many bytes of
+//! `nonce || ciphertext+tag`. Length-hiding (sealed header) framing is not
+//! supported here.
+
+use std::io;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
+use tokio::net::tcp::{OwnedReadHalf, OwnedWriteHalf};
+use tokio::net::TcpStream;
+
+use crate::crypto::{Cipher, NONCE_SIZE, TAG_SIZE};
+
+/// Maximum plaintext bytes sealed into a single AEAD frame. A `write` call
+/// larger than this is split across multiple frames; a `read` call is
+/// served from one frame's worth of plaintext at a time.
+const MAX_CHUNK_SIZE: usize = 1_048_576;
+
+/// State machine for reassembling frames out of however many bytes the
+/// underlying socket hands back per `poll_read`.
+enum ReadState {
+    /// Reading the 4-byte big-endian frame length header.
+    Header { buf: [u8; 4], filled: usize },
+    /// Reading `nonce || ciphertext` for a frame of the given total length.
+    Frame { buf: Vec<u8>, filled: usize },
+    /// Holding plaintext already decrypted but not yet drained by the
+    /// caller. This is the invariant rsh's `ESock` exists to protect: one
+    /// `read` call must never be assumed to map to one frame, since a
+    /// frame can easily contain more plaintext than the caller's buffer.
+    Ready { plaintext: Vec<u8>, pos: usize },
+}
+
+/// Owned read half of an [`EncryptedStream`], mirroring
+/// [`tokio::net::tcp::OwnedReadHalf`].
+pub struct EncryptedReadHalf {
+    inner: OwnedReadHalf,
+    cipher: Arc<Cipher>,
+    state: ReadState,
+}
+
+/// Owned write half of an [`EncryptedStream`], mirroring
+/// [`tokio::net::tcp::OwnedWriteHalf`].
+pub struct EncryptedWriteHalf {
+    inner: OwnedWriteHalf,
+    cipher: Arc<Cipher>,
+    /// A fully framed (length header + nonce + ciphertext) buffer still
+    /// being drained to the socket. `poll_write` must finish draining any
+    /// previous frame before it accepts and seals new plaintext.
+    write_buf: Vec<u8>,
+    write_pos: usize,
+}
+
+/// `AsyncRead`/`AsyncWrite` wrapper over an encrypted TCP connection. See
+/// the module docs for the wire format.
+pub struct EncryptedStream {
+    read_half: EncryptedReadHalf,
+    write_half: EncryptedWriteHalf,
+}
+
+impl EncryptedStream {
+    /// Wrap an already-connected `stream` using `read_cipher`/`write_cipher`
+    /// for decryption and encryption respectively. They're separate
+    /// parameters because [`TcpClient::handshake`](super::tcp::TcpClient::handshake)
+    /// derives an independent key per direction (server-to-client for
+    /// reading, client-to-server for writing); passing the same
+    /// `Arc<Cipher>` for both also works when only one key is shared.
+    pub fn new(stream: TcpStream, read_cipher: Arc<Cipher>, write_cipher: Arc<Cipher>) -> Self {
+        let (reader, writer) = stream.into_split();
+        Self {
+            read_half: EncryptedReadHalf {
+                inner: reader,
+                cipher: read_cipher,
+                state: ReadState::Header {
+                    buf: [0u8; 4],
+                    filled: 0,
+                },
+            },
+            write_half: EncryptedWriteHalf {
+                inner: writer,
+                cipher: write_cipher,
+                write_buf: Vec::new(),
+                write_pos: 0,
+            },
+        }
+    }
+
+    /// Split into independent owned read/write halves, mirroring
+    /// [`tokio::net::TcpStream::into_split`].
+    pub fn split(self) -> (EncryptedReadHalf, EncryptedWriteHalf) {
+        (self.read_half, self.write_half)
+    }
+
+    /// Reunite a previously [`split`](Self::split) pair back into a single
+    /// stream. Unlike `OwnedReadHalf::unsplit`, this doesn't verify the two
+    /// halves came from the same connection (we don't track a shared
+    /// connection identity) — passing mismatched halves just produces a
+    /// stream that reads from one connection and writes to another.
+    pub fn unsplit(read_half: EncryptedReadHalf, write_half: EncryptedWriteHalf) -> Self {
+        Self {
+            read_half,
+            write_half,
+        }
+    }
+}
+
+/// Poll-fill `buf[*filled..]` from `inner`. Returns `Ok(true)` once `buf` is
+/// completely filled, `Ok(false)` on a clean EOF before any bytes of this
+/// call were read, or an `UnexpectedEof` error on an EOF that lands in the
+/// middle of a header or frame.
+fn poll_fill(
+    inner: &mut OwnedReadHalf,
+    cx: &mut Context<'_>,
+    buf: &mut [u8],
+    filled: &mut usize,
+) -> Poll<io::Result<bool>> {
+    while *filled < buf.len() {
+        let mut read_buf = ReadBuf::new(&mut buf[*filled..]);
+        match Pin::new(&mut *inner).poll_read(cx, &mut read_buf) {
+            Poll::Ready(Ok(())) => {
+                let n = read_buf.filled().len();
+                if n == 0 {
+                    return Poll::Ready(if *filled == 0 {
+                        Ok(false)
+                    } else {
+                        Err(io::Error::new(
+                            io::ErrorKind::UnexpectedEof,
+                            "connection closed mid-frame",
+                        ))
+                    });
+                }
+                *filled += n;
+            }
+            Poll::Ready(Err(e)) => return Poll::Ready(Err(e)),
+            Poll::Pending => return Poll::Pending,
+        }
+    }
+    Poll::Ready(Ok(true))
+}
+
+impl AsyncRead for EncryptedReadHalf {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        out: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+        loop {
+            match &mut this.state {
+                ReadState::Ready { plaintext, pos } => {
+                    if *pos < plaintext.len() {
+                        let n = std::cmp::min(out.remaining(), plaintext.len() - *pos);
+                        out.put_slice(&plaintext[*pos..*pos + n]);
+                        *pos += n;
+                        return Poll::Ready(Ok(()));
+                    }
+                    // Fully drained; wait for the next frame.
+                    this.state = ReadState::Header {
+                        buf: [0u8; 4],
+                        filled: 0,
+                    };
+                }
+                ReadState::Header { buf, filled } => {
+                    match poll_fill(&mut this.inner, cx, buf, filled) {
+                        Poll::Ready(Ok(true)) => {
+                            let length = u32::from_be_bytes(*buf) as usize;
+                            if length < NONCE_SIZE + TAG_SIZE {
+                                return Poll::Ready(Err(io::Error::new(
+                                    io::ErrorKind::InvalidData,
+                                    "encrypted frame too small",
+                                )));
+                            }
+                            if length > NONCE_SIZE + TAG_SIZE + MAX_CHUNK_SIZE {
+                                return Poll::Ready(Err(io::Error::new(
+                                    io::ErrorKind::InvalidData,
+                                    format!("encrypted frame of {length} bytes exceeds max"),
+                                )));
+                            }
+                            this.state = ReadState::Frame {
+                                buf: vec![0u8; length],
+                                filled: 0,
+                            };
+                        }
+                        // Clean EOF between frames; signal end-of-stream.
+                        Poll::Ready(Ok(false)) => return Poll::Ready(Ok(())),
+                        Poll::Ready(Err(e)) => return Poll::Ready(Err(e)),
+                        Poll::Pending => return Poll::Pending,
+                    }
+                }
+                ReadState::Frame { buf, filled } => {
+                    match poll_fill(&mut this.inner, cx, buf, filled) {
+                        Poll::Ready(Ok(true)) => {
+                            let (nonce, ciphertext) = buf.split_at(NONCE_SIZE);
+                            let plaintext = match this.cipher.decrypt(nonce, ciphertext) {
+                                Ok(p) => p,
+                                Err(e) => {
+                                    return Poll::Ready(Err(io::Error::new(
+                                        io::ErrorKind::InvalidData,
+                                        format!("decryption failed: {e}"),
+                                    )));
+                                }
+                            };
+                            this.state = ReadState::Ready { plaintext, pos: 0 };
+                        }
+                        Poll::Ready(Ok(false)) => {
+                            return Poll::Ready(Err(io::Error::new(
+                                io::ErrorKind::UnexpectedEof,
+                                "connection closed mid-frame",
+                            )));
+                        }
+                        Poll::Ready(Err(e)) => return Poll::Ready(Err(e)),
+                        Poll::Pending => return Poll::Pending,
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl EncryptedWriteHalf {
+    /// Drain any previously framed bytes to the socket. Must complete
+    /// before a new plaintext chunk is sealed, since we only keep one
+    /// frame buffered at a time.
+    fn poll_drain(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        while self.write_pos < self.write_buf.len() {
+            match Pin::new(&mut self.inner).poll_write(cx, &self.write_buf[self.write_pos..]) {
+                Poll::Ready(Ok(0)) => {
+                    return Poll::Ready(Err(io::Error::new(
+                        io::ErrorKind::WriteZero,
+                        "failed to write encrypted frame",
+                    )));
+                }
+                Poll::Ready(Ok(n)) => self.write_pos += n,
+                Poll::Ready(Err(e)) => return Poll::Ready(Err(e)),
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl AsyncWrite for EncryptedWriteHalf {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        data: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        let this = self.get_mut();
+
+        match this.poll_drain(cx) {
+            Poll::Ready(Ok(())) => {}
+            Poll::Ready(Err(e)) => return Poll::Ready(Err(e)),
+            Poll::Pending => return Poll::Pending,
+        }
+
+        if data.is_empty() {
+            return Poll::Ready(Ok(0));
+        }
+
+        let chunk_len = std::cmp::min(data.len(), MAX_CHUNK_SIZE);
+        let chunk = &data[..chunk_len];
+
+        let (nonce, ciphertext) = match this.cipher.encrypt(chunk) {
+            Ok(pair) => pair,
+            Err(e) => {
+                return Poll::Ready(Err(io::Error::new(
+                    io::ErrorKind::Other,
+                    format!("encryption failed: {e}"),
+                )));
+            }
+        };
+
+        let frame_len = (NONCE_SIZE + ciphertext.len()) as u32;
+        let mut frame = Vec::with_capacity(4 + NONCE_SIZE + ciphertext.len());
+        frame.extend_from_slice(&frame_len.to_be_bytes());
+        frame.extend_from_slice(&nonce);
+        frame.extend_from_slice(&ciphertext);
+
+        this.write_buf = frame;
+        this.write_pos = 0;
+
+        // Best-effort immediate drain; if the socket isn't ready yet we've
+        // still accepted `chunk_len` plaintext bytes into `write_buf` and
+        // will finish draining on the next poll_write/poll_flush. A real
+        // write error surfaces now instead of being deferred.
+        if let Poll::Ready(Err(e)) = this.poll_drain(cx) {
+            return Poll::Ready(Err(e));
+        }
+
+        Poll::Ready(Ok(chunk_len))
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+        match this.poll_drain(cx) {
+            Poll::Ready(Ok(())) => Pin::new(&mut this.inner).poll_flush(cx),
+            other => other,
+        }
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+        match this.poll_drain(cx) {
+            Poll::Ready(Ok(())) => Pin::new(&mut this.inner).poll_shutdown(cx),
+            other => other,
+        }
+    }
+}
+
+impl AsyncRead for EncryptedStream {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.get_mut().read_half).poll_read(cx, buf)
+    }
+}
+
+impl AsyncWrite for EncryptedStream {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        Pin::new(&mut self.get_mut().write_half).poll_write(cx, buf)
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.get_mut().write_half).poll_flush(cx)
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.get_mut().write_half).poll_shutdown(cx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    fn test_cipher() -> Arc<Cipher> {
+        Arc::new(Cipher::new(&[7u8; 32]))
+    }
+
+    async fn connected_pair() -> (TcpStream, TcpStream) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let (server, client) = tokio::join!(async { listener.accept().await.unwrap().0 }, async {
+            TcpStream::connect(addr).await.unwrap()
+        });
+        (server, client)
+    }
+
+    #[tokio::test]
+    async fn test_round_trip_single_write_smaller_than_read_buffer() {
+        let (a, b) = connected_pair().await;
+        let cipher = test_cipher();
+        let mut client = EncryptedStream::new(a, cipher.clone(), cipher.clone());
+        let mut server = EncryptedStream::new(b, cipher.clone(), cipher);
+
+        client.write_all(b"hello, encrypted stream").await.unwrap();
+        client.flush().await.unwrap();
+
+        let mut received = vec![0u8; 23];
+        server.read_exact(&mut received).await.unwrap();
+        assert_eq!(&received, b"hello, encrypted stream");
+    }
+
+    #[tokio::test]
+    async fn test_small_reads_drain_one_frame_across_many_calls() {
+        let (a, b) = connected_pair().await;
+        let cipher = test_cipher();
+        let mut client = EncryptedStream::new(a, cipher.clone(), cipher.clone());
+        let mut server = EncryptedStream::new(b, cipher.clone(), cipher);
+
+        let payload = b"this single frame must be served across several small reads";
+        client.write_all(payload).await.unwrap();
+        client.flush().await.unwrap();
+
+        let mut received = Vec::new();
+        let mut chunk = [0u8; 4];
+        while received.len() < payload.len() {
+            let n = server.read(&mut chunk).await.unwrap();
+            assert!(n > 0);
+            received.extend_from_slice(&chunk[..n]);
+        }
+        assert_eq!(received, payload);
+    }
+
+    #[tokio::test]
+    async fn test_split_and_unsplit_round_trip() {
+        let (a, b) = connected_pair().await;
+        let cipher = test_cipher();
+        let stream_a = EncryptedStream::new(a, cipher.clone(), cipher.clone());
+        let mut server = EncryptedStream::new(b, cipher.clone(), cipher);
+
+        let (read_half, mut write_half) = stream_a.split();
+        write_half.write_all(b"split works").await.unwrap();
+        write_half.flush().await.unwrap();
+
+        let mut received = vec![0u8; 11];
+        server.read_exact(&mut received).await.unwrap();
+        assert_eq!(&received, b"split works");
+
+        let _client = EncryptedStream::unsplit(read_half, write_half);
+    }
+}
@@ -2,21 +2,32 @@
 //!
 //! Handles connection, message framing, and communication with the server.
 
+use std::collections::VecDeque;
 use std::net::SocketAddr;
+use std::sync::atomic::{AtomicU32, AtomicUsize, Ordering};
 use std::sync::{Arc, RwLock};
 use std::time::Duration;
 
 use tokio::io::{AsyncReadExt, AsyncWriteExt};
 use tokio::net::TcpStream;
 use tokio::sync::mpsc;
-use tokio::time::timeout;
+use tokio::time::{sleep_until, timeout, Instant};
 use tracing::{debug, error, info, warn};
 
+use base64::prelude::*;
+use ed25519_dalek::SigningKey;
+
+use super::events::EventDispatcher;
 use super::messages::{ClientMessage, ServerMessage};
-use crate::crypto::{parse_public_key, Cipher, KeyPair, NONCE_SIZE};
+use super::signing::sign_message;
+use crate::crypto::{
+    client_hello_bytes, derive_header_keys, handshake_transcript, parse_public_key,
+    server_hello_bytes, verify_handshake_signature, verify_server_identity, Cipher, KeyPair,
+    ServerKeyPolicy, NONCE_SIZE, TAG_SIZE,
+};
 
 /// Maximum message size (1 MB).
-const MAX_MESSAGE_SIZE: u32 = 1_048_576;
+pub(crate) const MAX_MESSAGE_SIZE: u32 = 1_048_576;
 
 /// Protocol version.
 pub const PROTOCOL_VERSION: &str = "1.0";
@@ -59,21 +70,270 @@ pub enum TcpError {
 
     #[error("Key exchange failed: {0}")]
     KeyExchangeFailed(String),
+
+    #[error("Send queue full (high-water mark {0} reached)")]
+    SendQueueFull(usize),
+
+    #[error("Signature verification failed: {0}")]
+    SignatureInvalid(#[from] super::signing::SigningError),
 }
 
 /// Minimum size for encrypted frame: nonce (12) + tag (16) = 28 bytes
 const MIN_ENCRYPTED_SIZE: usize = NONCE_SIZE + 16;
 
+/// Maximum time to wait for a frame's payload once its length prefix has
+/// already arrived. A peer is free to take as long as it likes between
+/// frames, but once it starts a frame it must finish it within this
+/// window, or the read is treated as a dead connection rather than a
+/// task pinned open forever.
+const RECEIVE_PAYLOAD_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Idle interval after which [`Connection`]'s writer task sends a
+/// keepalive [`ClientMessage::Ping`] if nothing else has gone out, so a
+/// half-open connection (one where the TCP peer has vanished without a
+/// clean close) is detected instead of silently pinning the reader and
+/// writer tasks open indefinitely.
+const PING_INTERVAL: Duration = Duration::from_secs(15);
+
+/// Read exactly `buf.len()` bytes, bounded by [`RECEIVE_PAYLOAD_TIMEOUT`].
+/// Used once a frame's length prefix has already been read, so a peer
+/// that stalls mid-payload doesn't pin the caller forever.
+async fn read_exact_timeout<R: tokio::io::AsyncRead + Unpin>(
+    reader: &mut R,
+    buf: &mut [u8],
+) -> Result<(), TcpError> {
+    match timeout(RECEIVE_PAYLOAD_TIMEOUT, reader.read_exact(buf)).await {
+        Ok(Ok(_)) => Ok(()),
+        Ok(Err(e)) if e.kind() == std::io::ErrorKind::UnexpectedEof => {
+            Err(TcpError::ConnectionClosed)
+        }
+        Ok(Err(e)) => Err(TcpError::Io(e)),
+        Err(_) => Err(TcpError::Timeout),
+    }
+}
+
+/// Backpressure policy applied once [`Connection`]'s outbound send queue
+/// reaches its high-water mark, matching OpenEthereum's connection model of
+/// an explicit, bounded send queue rather than letting buffered messages
+/// grow without limit when the server reads slower than the client sends.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SendQueuePolicy {
+    /// Await until the queue drains below the high-water mark.
+    Block,
+    /// Return [`TcpError::SendQueueFull`] immediately instead of waiting.
+    Reject,
+}
+
+/// Configuration for [`Connection`]'s outbound send queue.
+#[derive(Debug, Clone, Copy)]
+pub struct SendQueueConfig {
+    /// Maximum number of messages queued but not yet written before
+    /// `policy` kicks in.
+    pub high_water_mark: usize,
+    /// What to do once `high_water_mark` is reached.
+    pub policy: SendQueuePolicy,
+}
+
+impl Default for SendQueueConfig {
+    fn default() -> Self {
+        Self {
+            high_water_mark: 256,
+            policy: SendQueuePolicy::Block,
+        }
+    }
+}
+
+/// Write progress for the frame currently being drained to the socket,
+/// mirroring OpenEthereum's `WriteStatus`: a partially written frame is
+/// tracked explicitly rather than assumed away, so the writer can resume
+/// exactly where it left off instead of re-sending or corrupting the
+/// stream.
+enum WriteStatus {
+    /// No frame is in flight.
+    Complete,
+    /// `buf[pos..]` of this frame is still unwritten.
+    Ongoing { buf: Vec<u8>, pos: usize },
+}
+
+impl WriteStatus {
+    /// Resume (or start) writing `self`'s buffered frame, looping until the
+    /// whole frame is written. Does nothing if `self` is already
+    /// `Complete`.
+    async fn drain(&mut self, writer: &mut tokio::net::tcp::OwnedWriteHalf) -> std::io::Result<()> {
+        if let WriteStatus::Ongoing { buf, pos } = self {
+            while *pos < buf.len() {
+                let n = writer.write(&buf[*pos..]).await?;
+                if n == 0 {
+                    return Err(std::io::Error::new(
+                        std::io::ErrorKind::WriteZero,
+                        "failed to write frame",
+                    ));
+                }
+                *pos += n;
+            }
+            *self = WriteStatus::Complete;
+        }
+        Ok(())
+    }
+}
+
+/// Plaintext layout sealed inside a length-hiding frame's header: the
+/// true (unpadded) body length, followed by the wire (possibly padded)
+/// body length, both big-endian `u32`.
+const HEADER_PLAINTEXT_LEN: usize = 8;
+
+/// Size of a sealed header: a random nonce, the 8-byte plaintext above,
+/// and the AEAD tag. Fixed-size and indistinguishable from random bytes,
+/// so unlike the plain 4-byte cleartext length prefix it reveals nothing
+/// about the frame that follows.
+const SEALED_HEADER_LEN: usize = NONCE_SIZE + HEADER_PLAINTEXT_LEN + TAG_SIZE;
+
+/// Seal `(true_len, wire_len)` into a fixed-size header under
+/// `header_cipher`, so an observer on the wire can't read frame lengths
+/// in cleartext the way they can with the plain 4-byte length prefix.
+fn seal_header(header_cipher: &Cipher, true_len: u32, wire_len: u32) -> Result<Vec<u8>, TcpError> {
+    let mut plaintext = [0u8; HEADER_PLAINTEXT_LEN];
+    plaintext[0..4].copy_from_slice(&true_len.to_be_bytes());
+    plaintext[4..8].copy_from_slice(&wire_len.to_be_bytes());
+
+    let (nonce, ciphertext) = header_cipher
+        .encrypt(&plaintext)
+        .map_err(|e| TcpError::EncryptionFailed(e.to_string()))?;
+
+    let mut sealed = Vec::with_capacity(SEALED_HEADER_LEN);
+    sealed.extend_from_slice(&nonce);
+    sealed.extend_from_slice(&ciphertext);
+    Ok(sealed)
+}
+
+/// Open a sealed header, recovering `(true_len, wire_len)`. A failing AEAD
+/// tag means the header isn't trustworthy, not just malformed, so it's
+/// reported as [`TcpError::DecryptionFailed`] the same as a failing
+/// payload tag.
+fn open_header(header_cipher: &Cipher, sealed: &[u8]) -> Result<(u32, u32), TcpError> {
+    let (nonce, ciphertext) = sealed.split_at(NONCE_SIZE);
+    let plaintext = header_cipher
+        .decrypt(nonce, ciphertext)
+        .map_err(|e| TcpError::DecryptionFailed(e.to_string()))?;
+
+    let true_len = u32::from_be_bytes(plaintext[0..4].try_into().unwrap());
+    let wire_len = u32::from_be_bytes(plaintext[4..8].try_into().unwrap());
+    Ok((true_len, wire_len))
+}
+
+/// Serialize `message` to JSON, wrapping it in a signed envelope (see
+/// [`super::signing`]) when `signing_key` is set so the server can detect
+/// tampering regardless of where transport encryption ends.
+fn encode_message(
+    message: &ClientMessage,
+    signing_key: Option<&SigningKey>,
+) -> Result<String, TcpError> {
+    match signing_key {
+        Some(key) => {
+            let signed = sign_message(message.clone(), key)?;
+            Ok(serde_json::to_string(&signed)?)
+        }
+        None => Ok(serde_json::to_string(message)?),
+    }
+}
+
+/// Serialize `message` to JSON, signing it with `signing_key` if present,
+/// and frame it exactly like [`TcpClient::send`]: if `header_cipher` is
+/// set, a sealed length header followed by `nonce || ciphertext+tag`
+/// under `cipher`; otherwise a 4-byte big-endian length prefix followed
+/// by `nonce || ciphertext+tag` when `cipher` is set, or the raw JSON
+/// bytes otherwise.
+fn encode_frame(
+    message: &ClientMessage,
+    cipher: Option<&Cipher>,
+    header_cipher: Option<&Cipher>,
+    signing_key: Option<&SigningKey>,
+) -> Result<Vec<u8>, TcpError> {
+    let json = encode_message(message, signing_key)?;
+    debug!("Sent: {}", json);
+
+    if let (Some(cipher), Some(header_cipher)) = (cipher, header_cipher) {
+        let (nonce, ciphertext) = cipher
+            .encrypt(json.as_bytes())
+            .map_err(|e| TcpError::EncryptionFailed(e.to_string()))?;
+
+        let mut wire_body = Vec::with_capacity(NONCE_SIZE + ciphertext.len());
+        wire_body.extend_from_slice(&nonce);
+        wire_body.extend_from_slice(&ciphertext);
+
+        let true_len = wire_body.len();
+        if true_len > MAX_MESSAGE_SIZE as usize {
+            return Err(TcpError::MessageTooLarge {
+                size: true_len as u32,
+            });
+        }
+
+        let sealed_header = seal_header(header_cipher, true_len as u32, true_len as u32)?;
+        let mut frame = Vec::with_capacity(sealed_header.len() + wire_body.len());
+        frame.extend_from_slice(&sealed_header);
+        frame.extend_from_slice(&wire_body);
+        return Ok(frame);
+    }
+
+    let (length, body): (usize, Vec<u8>) = match cipher {
+        Some(cipher) => {
+            let (nonce, ciphertext) = cipher
+                .encrypt(json.as_bytes())
+                .map_err(|e| TcpError::EncryptionFailed(e.to_string()))?;
+            let frame_size = NONCE_SIZE + ciphertext.len();
+            let mut body = Vec::with_capacity(frame_size);
+            body.extend_from_slice(&nonce);
+            body.extend_from_slice(&ciphertext);
+            (frame_size, body)
+        }
+        None => (json.len(), json.into_bytes()),
+    };
+
+    if length > MAX_MESSAGE_SIZE as usize {
+        return Err(TcpError::MessageTooLarge {
+            size: length as u32,
+        });
+    }
+
+    let mut frame = Vec::with_capacity(4 + body.len());
+    frame.extend_from_slice(&(length as u32).to_be_bytes());
+    frame.extend_from_slice(&body);
+    Ok(frame)
+}
+
 /// TCP client for communicating with the server.
 pub struct TcpClient {
     stream: Option<TcpStream>,
     server_addr: SocketAddr,
     connect_timeout: Duration,
     read_timeout: Duration,
-    /// Cipher for encrypted communication.
-    cipher: Option<Cipher>,
+    /// Cipher for encrypting outbound messages (the client's c2s key).
+    send_cipher: Option<Cipher>,
+    /// Cipher for decrypting inbound messages (the client's s2c key).
+    recv_cipher: Option<Cipher>,
+    /// Cipher for sealing outbound frames' length headers (the client's
+    /// c2s header key), set alongside `send_cipher` only when the server
+    /// also honored the `length_hiding` feature.
+    send_header_cipher: Option<Cipher>,
+    /// Cipher for opening inbound frames' length headers (the client's
+    /// s2c header key), set alongside `recv_cipher` only when the server
+    /// also honored the `length_hiding` feature.
+    recv_header_cipher: Option<Cipher>,
     /// Whether encryption is enabled.
     encryption_enabled: bool,
+    /// How to verify the server's ed25519 identity key during key exchange.
+    server_key_policy: ServerKeyPolicy,
+    /// Long-term ed25519 key this connection signs outbound messages with,
+    /// if message signing is enabled (see [`super::signing`]).
+    signing_key: Option<SigningKey>,
+    /// Handlers fanned out to by [`TcpClient::recv_and_dispatch`].
+    events: EventDispatcher,
+    /// Canonical encoding of the `ServerHello` we received, mixed into
+    /// `handshake_transcript` during key exchange.
+    server_hello_bytes: Vec<u8>,
+    /// Canonical encoding of the `ClientHello` we sent, mixed into
+    /// `handshake_transcript` during key exchange.
+    client_hello_bytes: Vec<u8>,
 }
 
 #[allow(dead_code)]
@@ -85,11 +345,39 @@ impl TcpClient {
             server_addr,
             connect_timeout: Duration::from_secs(10),
             read_timeout: Duration::from_secs(60),
-            cipher: None,
+            send_cipher: None,
+            recv_cipher: None,
+            send_header_cipher: None,
+            recv_header_cipher: None,
             encryption_enabled: false,
+            server_key_policy: ServerKeyPolicy::TrustOnFirstUse,
+            signing_key: None,
+            events: EventDispatcher::new(),
+            server_hello_bytes: Vec::new(),
+            client_hello_bytes: Vec::new(),
         }
     }
 
+    /// Set the policy used to verify the server's ed25519 identity key
+    /// during key exchange. Defaults to [`ServerKeyPolicy::TrustOnFirstUse`].
+    pub fn with_server_key_policy(mut self, policy: ServerKeyPolicy) -> Self {
+        self.server_key_policy = policy;
+        self
+    }
+
+    /// Sign every outbound message with `signing_key`, and advertise its
+    /// public half to the server via `ClientHello` so it can verify them.
+    pub fn with_signing_key(mut self, signing_key: SigningKey) -> Self {
+        self.signing_key = Some(signing_key);
+        self
+    }
+
+    /// Register an [`EventHandler`](super::events::EventHandler) to be
+    /// invoked by [`recv_and_dispatch`](Self::recv_and_dispatch).
+    pub fn register_handler(&mut self, handler: std::sync::Arc<dyn super::events::EventHandler>) {
+        self.events.register(handler);
+    }
+
     /// Set connection timeout.
     pub fn with_connect_timeout(mut self, timeout: Duration) -> Self {
         self.connect_timeout = timeout;
@@ -133,60 +421,62 @@ impl TcpClient {
 
     /// Send a message to the server.
     pub async fn send(&mut self, message: &ClientMessage) -> Result<(), TcpError> {
+        let frame = encode_frame(
+            message,
+            self.send_cipher.as_ref(),
+            self.send_header_cipher.as_ref(),
+            self.signing_key.as_ref(),
+        )?;
+
         let stream = self.stream.as_mut().ok_or(TcpError::NotConnected)?;
+        stream.write_all(&frame).await?;
+        stream.flush().await?;
 
-        let json = serde_json::to_string(message)?;
-        debug!("Sending: {}", json);
+        Ok(())
+    }
 
-        if let Some(ref cipher) = self.cipher {
-            // Encrypted write
-            let plaintext = json.as_bytes();
-            let (nonce, ciphertext) = cipher
-                .encrypt(plaintext)
-                .map_err(|e| TcpError::EncryptionFailed(e.to_string()))?;
+    /// Receive a message from the server.
+    pub async fn recv(&mut self) -> Result<ServerMessage, TcpError> {
+        let stream = self.stream.as_mut().ok_or(TcpError::NotConnected)?;
 
-            let frame_size = NONCE_SIZE + ciphertext.len();
-            if frame_size > MAX_MESSAGE_SIZE as usize {
-                return Err(TcpError::MessageTooLarge {
-                    size: frame_size as u32,
-                });
+        if let (Some(cipher), Some(header_cipher)) =
+            (self.recv_cipher.as_ref(), self.recv_header_cipher.as_ref())
+        {
+            let mut sealed_header = [0u8; SEALED_HEADER_LEN];
+            match timeout(self.read_timeout, stream.read_exact(&mut sealed_header)).await {
+                Ok(Ok(_)) => {}
+                Ok(Err(e)) if e.kind() == std::io::ErrorKind::UnexpectedEof => {
+                    return Err(TcpError::ConnectionClosed);
+                }
+                Ok(Err(e)) => return Err(TcpError::Io(e)),
+                Err(_) => return Err(TcpError::Timeout),
             }
 
-            // Write length prefix
-            let length = frame_size as u32;
-            stream.write_all(&length.to_be_bytes()).await?;
-
-            // Write nonce
-            stream.write_all(&nonce).await?;
-
-            // Write ciphertext
-            stream.write_all(&ciphertext).await?;
-            stream.flush().await?;
-        } else {
-            // Unencrypted write
-            let payload = json.as_bytes();
-
-            if payload.len() > MAX_MESSAGE_SIZE as usize {
-                return Err(TcpError::MessageTooLarge {
-                    size: payload.len() as u32,
-                });
+            let (true_len, wire_len) = open_header(header_cipher, &sealed_header)?;
+            if wire_len > MAX_MESSAGE_SIZE || true_len > wire_len {
+                return Err(TcpError::Protocol(
+                    "length-hiding frame header is inconsistent".to_string(),
+                ));
+            }
+            if (true_len as usize) < MIN_ENCRYPTED_SIZE {
+                return Err(TcpError::EncryptedMessageTooSmall);
             }
 
-            // Write length prefix (big-endian u32)
-            let length = payload.len() as u32;
-            stream.write_all(&length.to_be_bytes()).await?;
+            let mut wire_body = vec![0u8; wire_len as usize];
+            read_exact_timeout(stream, &mut wire_body).await?;
+            wire_body.truncate(true_len as usize);
 
-            // Write payload
-            stream.write_all(payload).await?;
-            stream.flush().await?;
-        }
+            let (nonce, ciphertext) = wire_body.split_at(NONCE_SIZE);
+            let plaintext = cipher
+                .decrypt(nonce, ciphertext)
+                .map_err(|e| TcpError::DecryptionFailed(e.to_string()))?;
 
-        Ok(())
-    }
+            let json = String::from_utf8_lossy(&plaintext);
+            debug!("Received: {}", json);
 
-    /// Receive a message from the server.
-    pub async fn recv(&mut self) -> Result<ServerMessage, TcpError> {
-        let stream = self.stream.as_mut().ok_or(TcpError::NotConnected)?;
+            let message: ServerMessage = serde_json::from_slice(&plaintext)?;
+            return Ok(message);
+        }
 
         // Read length prefix
         let mut length_bytes = [0u8; 4];
@@ -207,7 +497,7 @@ impl TcpClient {
             });
         }
 
-        if let Some(ref cipher) = self.cipher {
+        if let Some(ref cipher) = self.recv_cipher {
             // Encrypted read
             if length < MIN_ENCRYPTED_SIZE {
                 return Err(TcpError::EncryptedMessageTooSmall);
@@ -215,12 +505,12 @@ impl TcpClient {
 
             // Read nonce
             let mut nonce = [0u8; NONCE_SIZE];
-            stream.read_exact(&mut nonce).await?;
+            read_exact_timeout(stream, &mut nonce).await?;
 
             // Read ciphertext
             let ciphertext_len = length - NONCE_SIZE;
             let mut ciphertext = vec![0u8; ciphertext_len];
-            stream.read_exact(&mut ciphertext).await?;
+            read_exact_timeout(stream, &mut ciphertext).await?;
 
             // Decrypt
             let plaintext = cipher
@@ -235,7 +525,7 @@ impl TcpClient {
         } else {
             // Unencrypted read
             let mut payload = vec![0u8; length];
-            stream.read_exact(&mut payload).await?;
+            read_exact_timeout(stream, &mut payload).await?;
 
             let json = String::from_utf8_lossy(&payload);
             debug!("Received: {}", json);
@@ -245,6 +535,14 @@ impl TcpClient {
         }
     }
 
+    /// Receive a message and fan it out to every handler registered via
+    /// [`register_handler`](Self::register_handler) before returning it.
+    pub async fn recv_and_dispatch(&mut self) -> Result<ServerMessage, TcpError> {
+        let message = self.recv().await?;
+        self.events.dispatch(&message).await;
+        Ok(message)
+    }
+
     /// Perform the initial handshake with the server.
     pub async fn handshake(&mut self) -> Result<(), TcpError> {
         self.handshake_with_encryption(true).await
@@ -277,6 +575,9 @@ impl TcpClient {
                     );
                 }
 
+                self.server_hello_bytes =
+                    server_hello_bytes(&version, &server_name, &features, encryption_required);
+
                 let supports_encryption = features.iter().any(|f| f == "encryption");
                 (supports_encryption, encryption_required)
             }
@@ -300,24 +601,75 @@ impl TcpClient {
             ));
         }
 
+        // Advertise our signing key (if any) so the server can verify the
+        // signed messages encode_message will wrap every send in from here
+        // on, including this very hello.
+        let signing_public_key = self
+            .signing_key
+            .as_ref()
+            .map(|key| BASE64_STANDARD.encode(key.verifying_key().to_bytes()));
+
         // Send ClientHello with encryption feature if we want encryption
         if use_encryption {
-            self.send(&ClientMessage::client_hello_with_encryption())
-                .await?;
+            let hello = match &signing_public_key {
+                Some(public_key) => {
+                    ClientMessage::client_hello_with_encryption_and_signing_key(public_key.clone())
+                }
+                None => ClientMessage::client_hello_with_encryption(),
+            };
+            self.remember_client_hello(&hello);
+            self.send(&hello).await?;
 
             // Perform key exchange
             self.perform_key_exchange().await?;
         } else {
-            self.send(&ClientMessage::client_hello()).await?;
+            let hello = match &signing_public_key {
+                Some(public_key) => {
+                    ClientMessage::client_hello_with_signing_key(public_key.clone())
+                }
+                None => ClientMessage::client_hello(),
+            };
+            self.remember_client_hello(&hello);
+            self.send(&hello).await?;
         }
 
         Ok(())
     }
 
-    /// Perform X25519 key exchange with the server.
+    /// Record a `ClientHello` we're about to send as `client_hello_bytes`,
+    /// for mixing into the key exchange's `handshake_transcript`. A no-op
+    /// for any other message variant.
+    fn remember_client_hello(&mut self, hello: &ClientMessage) {
+        if let ClientMessage::ClientHello {
+            version,
+            client_name,
+            features,
+            signing_public_key,
+        } = hello
+        {
+            self.client_hello_bytes = client_hello_bytes(
+                version,
+                Some(client_name),
+                features,
+                signing_public_key.as_deref(),
+            );
+        }
+    }
+
+    /// Perform X25519 key exchange with the server, authenticated by the
+    /// server's ed25519 identity key.
+    ///
+    /// A bare X25519 exchange can't tell a real server from an active MITM
+    /// swapping in its own ephemeral key, so the server signs a transcript
+    /// of both hellos and both ephemeral public keys with its long-term
+    /// identity key. We
+    /// verify that signature, and the identity key itself (per
+    /// [`ServerKeyPolicy`]), before trusting the derived shared secret.
     async fn perform_key_exchange(&mut self) -> Result<(), TcpError> {
         // Generate client keypair
         let keypair = KeyPair::generate();
+        let client_public_key = parse_public_key(&keypair.public_key_base64())
+            .expect("freshly-generated public key must parse");
         let client_public = keypair.public_key_base64();
 
         // Send our public key
@@ -328,20 +680,68 @@ impl TcpClient {
         let response = self.recv().await?;
 
         match response {
-            ServerMessage::KeyExchangeResponse { public_key } => {
+            ServerMessage::KeyExchangeResponse {
+                public_key,
+                identity_key,
+                signature,
+                length_hiding,
+            } => {
                 // Parse server's public key
                 let server_public = parse_public_key(&public_key).map_err(|e| {
                     TcpError::KeyExchangeFailed(format!("Invalid server public key: {}", e))
                 })?;
 
-                // Derive shared secret
-                let shared_secret = keypair.diffie_hellman(server_public);
+                let identity_key_bytes =
+                    crate::crypto::decode_32_bytes(&identity_key).map_err(|e| {
+                        TcpError::KeyExchangeFailed(format!("Invalid server identity key: {}", e))
+                    })?;
+                let signature_bytes = crate::crypto::decode_64_bytes(&signature).map_err(|e| {
+                    TcpError::KeyExchangeFailed(format!("Invalid handshake signature: {}", e))
+                })?;
+
+                let transcript = handshake_transcript(
+                    &client_public_key,
+                    &server_public,
+                    &self.server_hello_bytes,
+                    &self.client_hello_bytes,
+                );
+                verify_handshake_signature(&transcript, &identity_key_bytes, &signature_bytes)
+                    .map_err(|e| {
+                        TcpError::KeyExchangeFailed(format!(
+                            "Server handshake signature invalid: {}",
+                            e
+                        ))
+                    })?;
+                verify_server_identity(
+                    &self.server_addr.to_string(),
+                    &identity_key_bytes,
+                    &self.server_key_policy,
+                )
+                .map_err(|e| {
+                    TcpError::KeyExchangeFailed(format!("Server identity rejected: {}", e))
+                })?;
 
-                // Create cipher
-                self.cipher = Some(Cipher::new(&shared_secret));
+                // Derive shared secret, then split it into independent
+                // send/recv keys so the two directions never share a
+                // keystream.
+                let shared_secret = keypair.diffie_hellman(server_public);
+                let (c2s_key, s2c_key) =
+                    crate::crypto::derive_directional_keys(&shared_secret, &transcript);
+                self.send_cipher = Some(Cipher::new(&c2s_key));
+                self.recv_cipher = Some(Cipher::new(&s2c_key));
                 self.encryption_enabled = true;
 
-                info!("Encryption enabled");
+                if length_hiding {
+                    let (c2s_header_key, s2c_header_key) =
+                        derive_header_keys(&shared_secret, &transcript);
+                    self.send_header_cipher = Some(Cipher::new(&c2s_header_key));
+                    self.recv_header_cipher = Some(Cipher::new(&s2c_header_key));
+                }
+
+                info!(
+                    "Encryption enabled, server identity verified (length_hiding={})",
+                    length_hiding
+                );
                 Ok(())
             }
             ServerMessage::Error { code, message, .. } => Err(TcpError::KeyExchangeFailed(
@@ -366,6 +766,15 @@ pub struct Connection {
     /// Whether encryption is enabled.
     #[allow(dead_code)]
     encryption_enabled: bool,
+    /// Outbound send queue configuration (high-water mark and backpressure
+    /// policy).
+    send_queue: SendQueueConfig,
+    /// Number of messages queued but not yet written to the socket, tracked
+    /// separately from the `tx` channel's own capacity because a message
+    /// can sit in the writer task's internal batch (see
+    /// [`Connection::connect_with_options`]) after leaving the channel but
+    /// before it's actually on the wire.
+    queue_depth: Arc<AtomicUsize>,
 }
 
 impl Connection {
@@ -378,99 +787,105 @@ impl Connection {
     pub async fn connect_with_encryption(
         server_addr: SocketAddr,
         enable_encryption: bool,
+    ) -> Result<Self, TcpError> {
+        Self::connect_with_options(server_addr, enable_encryption, SendQueueConfig::default()).await
+    }
+
+    /// Create a new connection with optional encryption and full control
+    /// over the outbound send queue's high-water mark and backpressure
+    /// policy.
+    pub async fn connect_with_options(
+        server_addr: SocketAddr,
+        enable_encryption: bool,
+        send_queue: SendQueueConfig,
+    ) -> Result<Self, TcpError> {
+        Self::connect_full(server_addr, enable_encryption, None, send_queue).await
+    }
+
+    /// Create a new connection that signs every outbound message with
+    /// `signing_key` (see [`super::signing`]), in addition to whatever
+    /// transport encryption `enable_encryption` negotiates.
+    pub async fn connect_with_signing_key(
+        server_addr: SocketAddr,
+        enable_encryption: bool,
+        signing_key: SigningKey,
+    ) -> Result<Self, TcpError> {
+        Self::connect_full(
+            server_addr,
+            enable_encryption,
+            Some(signing_key),
+            SendQueueConfig::default(),
+        )
+        .await
+    }
+
+    /// Create a new connection with full control over encryption, message
+    /// signing, and the outbound send queue.
+    pub async fn connect_full(
+        server_addr: SocketAddr,
+        enable_encryption: bool,
+        signing_key: Option<SigningKey>,
+        send_queue: SendQueueConfig,
     ) -> Result<Self, TcpError> {
         let mut client = TcpClient::new(server_addr);
+        if let Some(signing_key) = signing_key {
+            client = client.with_signing_key(signing_key);
+        }
         client.connect().await?;
         client.handshake_with_encryption(enable_encryption).await?;
 
-        let (outgoing_tx, mut outgoing_rx) = mpsc::channel::<ClientMessage>(32);
+        let (outgoing_tx, mut outgoing_rx) =
+            mpsc::channel::<ClientMessage>(send_queue.high_water_mark.max(1));
         let (incoming_tx, incoming_rx) = mpsc::channel::<ServerMessage>(32);
         let (shutdown_tx, mut shutdown_rx) = mpsc::channel::<()>(1);
-
-        // Take cipher from client (if encryption enabled)
-        let cipher: Arc<RwLock<Option<Arc<Cipher>>>> =
-            Arc::new(RwLock::new(client.cipher.take().map(Arc::new)));
+        let queue_depth = Arc::new(AtomicUsize::new(0));
+        let pongs_acked = Arc::new(AtomicU32::new(0));
+
+        // Take the directional ciphers from client (if encryption enabled).
+        // The writer task encrypts with send_cipher, the reader task
+        // decrypts with recv_cipher — distinct Arcs so the two directions
+        // never share a keystream.
+        let writer_cipher: Arc<RwLock<Option<Arc<Cipher>>>> =
+            Arc::new(RwLock::new(client.send_cipher.take().map(Arc::new)));
+        let reader_cipher: Arc<RwLock<Option<Arc<Cipher>>>> =
+            Arc::new(RwLock::new(client.recv_cipher.take().map(Arc::new)));
+        // Set alongside the payload ciphers only when the server also
+        // honored the `length_hiding` feature.
+        let writer_header_cipher: Arc<RwLock<Option<Arc<Cipher>>>> =
+            Arc::new(RwLock::new(client.send_header_cipher.take().map(Arc::new)));
+        let reader_header_cipher: Arc<RwLock<Option<Arc<Cipher>>>> =
+            Arc::new(RwLock::new(client.recv_header_cipher.take().map(Arc::new)));
         let encryption_enabled = client.encryption_enabled;
 
+        // Take the signing key (if any). Unlike the ciphers it never
+        // changes mid-connection, so the writer task just owns it outright
+        // instead of sharing it behind a lock.
+        let signing_key = client.signing_key.take();
+
         // Split the stream for concurrent read/write
         let stream = client.stream.take().unwrap();
         let (mut reader, mut writer) = stream.into_split();
 
-        // Spawn writer task
-        let writer_cipher = cipher.clone();
+        // Spawn writer task. Messages are coalesced: once woken, the task
+        // drains everything currently queued into a local batch and issues
+        // a single `flush()` for the whole batch rather than one per
+        // message, mirroring OpenEthereum's connection writer.
         let writer_shutdown = shutdown_tx.clone();
+        let writer_queue_depth = queue_depth.clone();
+        let writer_pongs_acked = pongs_acked.clone();
         tokio::spawn(async move {
+            let mut batch: VecDeque<ClientMessage> = VecDeque::new();
+            let mut write_status = WriteStatus::Complete;
+            let mut pings_sent: u32 = 0;
+            let mut idle_deadline = Instant::now() + PING_INTERVAL;
+
             loop {
                 tokio::select! {
                     msg = outgoing_rx.recv() => {
                         match msg {
                             Some(message) => {
-                                let json = match serde_json::to_string(&message) {
-                                    Ok(j) => j,
-                                    Err(e) => {
-                                        error!("Failed to serialize message: {}", e);
-                                        continue;
-                                    }
-                                };
-
-                                debug!("Sent: {}", json);
-
-                                // Clone cipher Arc before releasing lock
-                                let cipher_opt = {
-                                    let guard = writer_cipher.read().unwrap();
-                                    guard.as_ref().cloned()
-                                };
-
-                                let result = match cipher_opt {
-                                    Some(cipher) => {
-                                        // Encrypted write
-                                        let plaintext = json.as_bytes();
-                                        match cipher.encrypt(plaintext) {
-                                            Ok((nonce, ciphertext)) => {
-                                                let frame_size = NONCE_SIZE + ciphertext.len();
-                                                let length = frame_size as u32;
-
-                                                let mut write_ok = true;
-                                                if writer.write_all(&length.to_be_bytes()).await.is_err() {
-                                                    write_ok = false;
-                                                }
-                                                if write_ok && writer.write_all(&nonce).await.is_err() {
-                                                    write_ok = false;
-                                                }
-                                                if write_ok && writer.write_all(&ciphertext).await.is_err() {
-                                                    write_ok = false;
-                                                }
-                                                if write_ok && writer.flush().await.is_err() {
-                                                    write_ok = false;
-                                                }
-                                                if write_ok { Ok(()) } else { Err("write failed") }
-                                            }
-                                            Err(_) => Err("encryption failed"),
-                                        }
-                                    }
-                                    None => {
-                                        // Unencrypted write
-                                        let payload = json.as_bytes();
-                                        let length = payload.len() as u32;
-
-                                        let mut write_ok = true;
-                                        if writer.write_all(&length.to_be_bytes()).await.is_err() {
-                                            write_ok = false;
-                                        }
-                                        if write_ok && writer.write_all(payload).await.is_err() {
-                                            write_ok = false;
-                                        }
-                                        if write_ok && writer.flush().await.is_err() {
-                                            write_ok = false;
-                                        }
-                                        if write_ok { Ok(()) } else { Err("write failed") }
-                                    }
-                                };
-
-                                if result.is_err() {
-                                    error!("Failed to write message");
-                                    break;
-                                }
+                                batch.push_back(message);
+                                idle_deadline = Instant::now() + PING_INTERVAL;
                             }
                             None => {
                                 debug!("Outgoing channel closed");
@@ -482,17 +897,85 @@ impl Connection {
                         debug!("Writer shutdown signal received");
                         break;
                     }
+                    _ = sleep_until(idle_deadline) => {
+                        if pings_sent > 0 && writer_pongs_acked.load(Ordering::SeqCst) < pings_sent {
+                            warn!("No pong received within heartbeat interval; closing connection");
+                            let _ = writer_shutdown.send(()).await;
+                            break;
+                        }
+                        pings_sent += 1;
+                        writer_queue_depth.fetch_add(1, Ordering::SeqCst);
+                        batch.push_back(ClientMessage::Ping);
+                        idle_deadline = Instant::now() + PING_INTERVAL;
+                    }
+                }
+                while let Ok(message) = outgoing_rx.try_recv() {
+                    batch.push_back(message);
+                }
+
+                // Clone cipher Arcs before releasing lock.
+                let (cipher_opt, header_cipher_opt) = {
+                    let guard = writer_cipher.read().unwrap();
+                    let header_guard = writer_header_cipher.read().unwrap();
+                    (guard.as_ref().cloned(), header_guard.as_ref().cloned())
+                };
+
+                let mut wrote_any = false;
+                let mut write_failed = false;
+                while let Some(message) = batch.pop_front() {
+                    let frame = match encode_frame(
+                        &message,
+                        cipher_opt.as_deref(),
+                        header_cipher_opt.as_deref(),
+                        signing_key.as_ref(),
+                    ) {
+                        Ok(frame) => frame,
+                        Err(e) => {
+                            error!("Failed to encode message: {}", e);
+                            writer_queue_depth.fetch_sub(1, Ordering::SeqCst);
+                            continue;
+                        }
+                    };
+
+                    write_status = WriteStatus::Ongoing { buf: frame, pos: 0 };
+                    match write_status.drain(&mut writer).await {
+                        Ok(()) => {
+                            writer_queue_depth.fetch_sub(1, Ordering::SeqCst);
+                            wrote_any = true;
+                        }
+                        Err(e) => {
+                            error!("Failed to write message: {}", e);
+                            write_failed = true;
+                            break;
+                        }
+                    }
+                }
+
+                if wrote_any && writer.flush().await.is_err() {
+                    error!("Failed to flush writer");
+                    write_failed = true;
+                }
+
+                if write_failed {
+                    break;
                 }
             }
         });
 
         // Spawn reader task
-        let reader_cipher = cipher;
         tokio::spawn(async move {
             loop {
                 tokio::select! {
-                    result = read_message_with_cipher(&mut reader, &reader_cipher) => {
+                    result = read_message_with_cipher(&mut reader, &reader_cipher, &reader_header_cipher) => {
                         match result {
+                            // Keepalive pongs are handled here rather than
+                            // forwarded to the application: they carry no
+                            // app-relevant data, only proof the peer is
+                            // still alive to answer the writer task's idle
+                            // pings.
+                            Ok(ServerMessage::Pong { .. }) => {
+                                pongs_acked.fetch_add(1, Ordering::SeqCst);
+                            }
                             Ok(message) => {
                                 if incoming_tx.send(message).await.is_err() {
                                     debug!("Incoming channel closed");
@@ -522,15 +1005,52 @@ impl Connection {
             rx: incoming_rx,
             shutdown_tx,
             encryption_enabled,
+            send_queue,
+            queue_depth,
         })
     }
 
-    /// Send a message to the server.
+    /// Send a message to the server, applying the configured
+    /// [`SendQueuePolicy`] once [`queue_depth`](Self::queue_depth) reaches
+    /// `high_water_mark`.
     pub async fn send(&self, message: ClientMessage) -> Result<(), TcpError> {
-        self.tx
-            .send(message)
-            .await
-            .map_err(|_| TcpError::ConnectionClosed)
+        match self.send_queue.policy {
+            SendQueuePolicy::Block => {
+                self.queue_depth.fetch_add(1, Ordering::SeqCst);
+                let result = self
+                    .tx
+                    .send(message)
+                    .await
+                    .map_err(|_| TcpError::ConnectionClosed);
+                if result.is_err() {
+                    self.queue_depth.fetch_sub(1, Ordering::SeqCst);
+                }
+                result
+            }
+            SendQueuePolicy::Reject => {
+                if self.queue_depth.load(Ordering::SeqCst) >= self.send_queue.high_water_mark {
+                    return Err(TcpError::SendQueueFull(self.send_queue.high_water_mark));
+                }
+                self.queue_depth.fetch_add(1, Ordering::SeqCst);
+                match self.tx.try_send(message) {
+                    Ok(()) => Ok(()),
+                    Err(e) => {
+                        self.queue_depth.fetch_sub(1, Ordering::SeqCst);
+                        match e {
+                            mpsc::error::TrySendError::Full(_) => {
+                                Err(TcpError::SendQueueFull(self.send_queue.high_water_mark))
+                            }
+                            mpsc::error::TrySendError::Closed(_) => Err(TcpError::ConnectionClosed),
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// Number of messages queued but not yet written to the socket.
+    pub fn queue_depth(&self) -> usize {
+        self.queue_depth.load(Ordering::SeqCst)
     }
 
     /// Receive the next message from the server.
@@ -568,7 +1088,7 @@ async fn read_message(
 
     // Read payload
     let mut payload = vec![0u8; length as usize];
-    reader.read_exact(&mut payload).await?;
+    read_exact_timeout(reader, &mut payload).await?;
 
     let json = String::from_utf8_lossy(&payload);
     debug!("Received: {}", json);
@@ -577,16 +1097,58 @@ async fn read_message(
     Ok(message)
 }
 
-/// Read a single message from the stream, handling encryption if cipher is set.
+/// Read a single message from the stream, handling encryption (and, if
+/// negotiated, length-hiding framing) if its cipher(s) are set.
 async fn read_message_with_cipher(
     reader: &mut tokio::net::tcp::OwnedReadHalf,
     cipher_holder: &Arc<RwLock<Option<Arc<Cipher>>>>,
+    header_cipher_holder: &Arc<RwLock<Option<Arc<Cipher>>>>,
 ) -> Result<ServerMessage, TcpError> {
-    // Clone cipher Arc before releasing lock
+    // Clone cipher Arcs before releasing lock
     let cipher_opt = {
         let guard = cipher_holder.read().unwrap();
         guard.as_ref().cloned()
     };
+    let header_cipher_opt = {
+        let guard = header_cipher_holder.read().unwrap();
+        guard.as_ref().cloned()
+    };
+
+    if let (Some(cipher), Some(header_cipher)) = (&cipher_opt, &header_cipher_opt) {
+        let mut sealed_header = [0u8; SEALED_HEADER_LEN];
+        reader.read_exact(&mut sealed_header).await.map_err(|e| {
+            if e.kind() == std::io::ErrorKind::UnexpectedEof {
+                TcpError::ConnectionClosed
+            } else {
+                TcpError::Io(e)
+            }
+        })?;
+
+        let (true_len, wire_len) = open_header(header_cipher, &sealed_header)?;
+        if wire_len > MAX_MESSAGE_SIZE || true_len > wire_len {
+            return Err(TcpError::Protocol(
+                "length-hiding frame header is inconsistent".to_string(),
+            ));
+        }
+        if (true_len as usize) < MIN_ENCRYPTED_SIZE {
+            return Err(TcpError::EncryptedMessageTooSmall);
+        }
+
+        let mut wire_body = vec![0u8; wire_len as usize];
+        read_exact_timeout(reader, &mut wire_body).await?;
+        wire_body.truncate(true_len as usize);
+
+        let (nonce, ciphertext) = wire_body.split_at(NONCE_SIZE);
+        let plaintext = cipher
+            .decrypt(nonce, ciphertext)
+            .map_err(|e| TcpError::DecryptionFailed(e.to_string()))?;
+
+        let json = String::from_utf8_lossy(&plaintext);
+        debug!("Received: {}", json);
+
+        let message: ServerMessage = serde_json::from_slice(&plaintext)?;
+        return Ok(message);
+    }
 
     // Read length prefix
     let mut length_bytes = [0u8; 4];
@@ -615,24 +1177,12 @@ async fn read_message_with_cipher(
 
             // Read nonce
             let mut nonce = [0u8; NONCE_SIZE];
-            reader.read_exact(&mut nonce).await.map_err(|e| {
-                if e.kind() == std::io::ErrorKind::UnexpectedEof {
-                    TcpError::ConnectionClosed
-                } else {
-                    TcpError::Io(e)
-                }
-            })?;
+            read_exact_timeout(reader, &mut nonce).await?;
 
             // Read ciphertext
             let ciphertext_len = length - NONCE_SIZE;
             let mut ciphertext = vec![0u8; ciphertext_len];
-            reader.read_exact(&mut ciphertext).await.map_err(|e| {
-                if e.kind() == std::io::ErrorKind::UnexpectedEof {
-                    TcpError::ConnectionClosed
-                } else {
-                    TcpError::Io(e)
-                }
-            })?;
+            read_exact_timeout(reader, &mut ciphertext).await?;
 
             // Decrypt
             let plaintext = cipher
@@ -648,13 +1198,7 @@ async fn read_message_with_cipher(
         None => {
             // Unencrypted read
             let mut payload = vec![0u8; length];
-            reader.read_exact(&mut payload).await.map_err(|e| {
-                if e.kind() == std::io::ErrorKind::UnexpectedEof {
-                    TcpError::ConnectionClosed
-                } else {
-                    TcpError::Io(e)
-                }
-            })?;
+            read_exact_timeout(reader, &mut payload).await?;
 
             let json = String::from_utf8_lossy(&payload);
             debug!("Received: {}", json);
@@ -668,8 +1212,8 @@ async fn read_message_with_cipher(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::protocol::messages::{MarkerKind, MessageTarget};
     use uuid::Uuid;
-    use crate::protocol::messages::MessageTarget;
 
     // ========================================================================
     // TcpClient Tests
@@ -863,6 +1407,164 @@ mod tests {
         assert!(json.contains("\"type\":\"get_messages\""));
     }
 
+    #[test]
+    fn test_client_message_mark_delivered() {
+        let room_id = Uuid::new_v4();
+        let message_id = Uuid::new_v4();
+        let msg = ClientMessage::mark_delivered(MessageTarget::Room { room_id }, message_id);
+        let json = serde_json::to_string(&msg).unwrap();
+        assert!(json.contains("\"type\":\"mark_delivered\""));
+        assert!(json.contains(&message_id.to_string()));
+
+        let round_tripped: ClientMessage = serde_json::from_str(&json).unwrap();
+        match round_tripped {
+            ClientMessage::MarkDelivered {
+                target,
+                up_to_message_id,
+                ..
+            } => {
+                assert_eq!(target, MessageTarget::Room { room_id });
+                assert_eq!(up_to_message_id, message_id);
+            }
+            _ => panic!("Expected MarkDelivered"),
+        }
+    }
+
+    #[test]
+    fn test_client_message_mark_read() {
+        let room_id = Uuid::new_v4();
+        let message_id = Uuid::new_v4();
+        let msg = ClientMessage::mark_read(MessageTarget::Room { room_id }, message_id);
+        let json = serde_json::to_string(&msg).unwrap();
+        assert!(json.contains("\"type\":\"mark_read\""));
+
+        let round_tripped: ClientMessage = serde_json::from_str(&json).unwrap();
+        match round_tripped {
+            ClientMessage::MarkRead {
+                target,
+                up_to_message_id,
+                ..
+            } => {
+                assert_eq!(target, MessageTarget::Room { room_id });
+                assert_eq!(up_to_message_id, message_id);
+            }
+            _ => panic!("Expected MarkRead"),
+        }
+    }
+
+    #[test]
+    fn test_client_message_edit_message() {
+        let message_id = Uuid::new_v4();
+        let msg = ClientMessage::EditMessage {
+            request_id: None,
+            message_id,
+            new_content: "edited text".to_string(),
+        };
+        let json = serde_json::to_string(&msg).unwrap();
+        assert!(json.contains("\"type\":\"edit_message\""));
+        assert!(json.contains("\"new_content\":\"edited text\""));
+    }
+
+    #[test]
+    fn test_client_message_redact_message() {
+        let message_id = Uuid::new_v4();
+        let msg = ClientMessage::RedactMessage {
+            request_id: None,
+            message_id,
+            reason: Some("policy violation".to_string()),
+        };
+        let json = serde_json::to_string(&msg).unwrap();
+        assert!(json.contains("\"type\":\"redact_message\""));
+
+        let round_tripped: ClientMessage = serde_json::from_str(&json).unwrap();
+        match round_tripped {
+            ClientMessage::RedactMessage {
+                message_id: id,
+                reason,
+                ..
+            } => {
+                assert_eq!(id, message_id);
+                assert_eq!(reason.as_deref(), Some("policy violation"));
+            }
+            _ => panic!("Expected RedactMessage"),
+        }
+    }
+
+    #[test]
+    fn test_client_message_attachment_begin() {
+        let room_id = Uuid::new_v4();
+        let (msg, transfer_id) = ClientMessage::attachment_begin(
+            MessageTarget::Room { room_id },
+            "photo.png",
+            "image/png",
+            2048,
+            2,
+            Some("dGh1bWI=".to_string()),
+        );
+        let json = serde_json::to_string(&msg).unwrap();
+        assert!(json.contains("\"type\":\"attachment_begin\""));
+        assert!(json.contains(&transfer_id.to_string()));
+
+        let round_tripped: ClientMessage = serde_json::from_str(&json).unwrap();
+        match round_tripped {
+            ClientMessage::AttachmentBegin {
+                transfer_id: id,
+                file_name,
+                total_chunks,
+                thumbnail,
+                ..
+            } => {
+                assert_eq!(id, transfer_id);
+                assert_eq!(file_name, "photo.png");
+                assert_eq!(total_chunks, 2);
+                assert_eq!(thumbnail.as_deref(), Some("dGh1bWI="));
+            }
+            _ => panic!("Expected AttachmentBegin"),
+        }
+    }
+
+    #[test]
+    fn test_client_message_attachment_chunk() {
+        let transfer_id = Uuid::new_v4();
+        let msg = ClientMessage::attachment_chunk(transfer_id, 0, "YWJjZA==");
+        let json = serde_json::to_string(&msg).unwrap();
+        assert!(json.contains("\"type\":\"attachment_chunk\""));
+
+        let round_tripped: ClientMessage = serde_json::from_str(&json).unwrap();
+        match round_tripped {
+            ClientMessage::AttachmentChunk {
+                transfer_id: id,
+                index,
+                data,
+            } => {
+                assert_eq!(id, transfer_id);
+                assert_eq!(index, 0);
+                assert_eq!(data, "YWJjZA==");
+            }
+            _ => panic!("Expected AttachmentChunk"),
+        }
+    }
+
+    #[test]
+    fn test_client_message_attachment_end() {
+        let transfer_id = Uuid::new_v4();
+        let msg = ClientMessage::attachment_end(transfer_id, "deadbeef");
+        let json = serde_json::to_string(&msg).unwrap();
+        assert!(json.contains("\"type\":\"attachment_end\""));
+
+        let round_tripped: ClientMessage = serde_json::from_str(&json).unwrap();
+        match round_tripped {
+            ClientMessage::AttachmentEnd {
+                transfer_id: id,
+                sha256,
+            } => {
+                assert_eq!(id, transfer_id);
+                assert_eq!(sha256, "deadbeef");
+            }
+            _ => panic!("Expected AttachmentEnd"),
+        }
+    }
+
     // ========================================================================
     // ServerMessage Deserialization Tests
     // ========================================================================
@@ -998,7 +1700,12 @@ mod tests {
         let json = r#"{"type":"list_rooms_response","request_id":null,"success":true,"rooms":[{"room":{"id":"123e4567-e89b-12d3-a456-426614174000","name":"general","owner":"223e4567-e89b-12d3-a456-426614174000","settings":{"public":true},"created_at":"2026-01-01T00:00:00Z"},"member_count":5,"is_member":true}],"total_count":1}"#;
         let msg: ServerMessage = serde_json::from_str(json).unwrap();
         match msg {
-            ServerMessage::ListRoomsResponse { success, rooms, total_count, .. } => {
+            ServerMessage::ListRoomsResponse {
+                success,
+                rooms,
+                total_count,
+                ..
+            } => {
                 assert!(success);
                 assert_eq!(rooms.len(), 1);
                 assert_eq!(total_count, Some(1));
@@ -1007,12 +1714,46 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_upgrade_room_response() {
+        let json = r#"{"type":"upgrade_room_response","request_id":"req-123","success":true,"room":{"id":"923e4567-e89b-12d3-a456-426614174000","name":"general-v2","owner":"223e4567-e89b-12d3-a456-426614174000","settings":{"public":true},"created_at":"2026-01-01T00:00:00Z"}}"#;
+        let msg: ServerMessage = serde_json::from_str(json).unwrap();
+        match msg {
+            ServerMessage::UpgradeRoomResponse { success, room, .. } => {
+                assert!(success);
+                assert_eq!(room.unwrap().name, "general-v2");
+            }
+            _ => panic!("Expected UpgradeRoomResponse"),
+        }
+    }
+
+    #[test]
+    fn test_room_tombstone_event() {
+        let json = r#"{"type":"room_tombstone","room_id":"123e4567-e89b-12d3-a456-426614174000","replacement_room_id":"923e4567-e89b-12d3-a456-426614174000","reason":"settings migration"}"#;
+        let msg: ServerMessage = serde_json::from_str(json).unwrap();
+        match msg {
+            ServerMessage::RoomTombstone {
+                room_id,
+                replacement_room_id,
+                reason,
+            } => {
+                assert!(!room_id.is_nil());
+                assert!(replacement_room_id.to_string().starts_with("923e4567"));
+                assert_eq!(reason.as_deref(), Some("settings migration"));
+            }
+            _ => panic!("Expected RoomTombstone"),
+        }
+    }
+
     #[test]
     fn test_message_received_event() {
         let json = r#"{"type":"message_received","message":{"id":"123e4567-e89b-12d3-a456-426614174000","content":"Hello!","author":"223e4567-e89b-12d3-a456-426614174000","target":{"type":"room","room_id":"323e4567-e89b-12d3-a456-426614174000"},"edited":false,"created_at":"2026-01-01T00:00:00Z"},"author_username":"testuser"}"#;
         let msg: ServerMessage = serde_json::from_str(json).unwrap();
         match msg {
-            ServerMessage::MessageReceived { message, author_username } => {
+            ServerMessage::MessageReceived {
+                message,
+                author_username,
+            } => {
                 assert_eq!(message.content, "Hello!");
                 assert_eq!(author_username, "testuser");
             }
@@ -1020,6 +1761,77 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_message_edited_event() {
+        let json = r#"{"type":"message_edited","message":{"id":"123e4567-e89b-12d3-a456-426614174000","content":"Hello again!","author":"223e4567-e89b-12d3-a456-426614174000","target":{"type":"room","room_id":"323e4567-e89b-12d3-a456-426614174000"},"edited":true,"created_at":"2026-01-01T00:00:00Z"},"editor_username":"testuser","previous_content":"Hello!"}"#;
+        let msg: ServerMessage = serde_json::from_str(json).unwrap();
+        match msg {
+            ServerMessage::MessageEdited {
+                message,
+                editor_username,
+                previous_content,
+            } => {
+                assert_eq!(message.content, "Hello again!");
+                assert_eq!(editor_username, "testuser");
+                assert_eq!(previous_content.as_deref(), Some("Hello!"));
+            }
+            _ => panic!("Expected MessageEdited"),
+        }
+    }
+
+    #[test]
+    fn test_message_redacted_event() {
+        let json = r#"{"type":"message_redacted","message_id":"123e4567-e89b-12d3-a456-426614174000","target":{"type":"room","room_id":"323e4567-e89b-12d3-a456-426614174000"},"redacted_by":"223e4567-e89b-12d3-a456-426614174000"}"#;
+        let msg: ServerMessage = serde_json::from_str(json).unwrap();
+        match msg {
+            ServerMessage::MessageRedacted {
+                message_id,
+                redacted_by,
+                ..
+            } => {
+                assert!(!message_id.is_nil());
+                assert!(!redacted_by.is_nil());
+            }
+            _ => panic!("Expected MessageRedacted"),
+        }
+    }
+
+    #[test]
+    fn test_attachment_received_event() {
+        let json = r#"{"type":"attachment_received","transfer_id":"123e4567-e89b-12d3-a456-426614174000","message_id":"923e4567-e89b-12d3-a456-426614174000","target":{"type":"room","room_id":"323e4567-e89b-12d3-a456-426614174000"},"file_name":"photo.png","mime_type":"image/png","size":2048}"#;
+        let msg: ServerMessage = serde_json::from_str(json).unwrap();
+        match msg {
+            ServerMessage::AttachmentReceived {
+                file_name,
+                mime_type,
+                size,
+                ..
+            } => {
+                assert_eq!(file_name, "photo.png");
+                assert_eq!(mime_type, "image/png");
+                assert_eq!(size, 2048);
+            }
+            _ => panic!("Expected AttachmentReceived"),
+        }
+    }
+
+    #[test]
+    fn test_marker_update_event() {
+        let json = r#"{"type":"marker_update","target":{"type":"room","room_id":"323e4567-e89b-12d3-a456-426614174000"},"user_id":"223e4567-e89b-12d3-a456-426614174000","username":"testuser","marker_kind":"read","message_id":"123e4567-e89b-12d3-a456-426614174000"}"#;
+        let msg: ServerMessage = serde_json::from_str(json).unwrap();
+        match msg {
+            ServerMessage::MarkerUpdate {
+                username,
+                marker_kind,
+                ..
+            } => {
+                assert_eq!(username, "testuser");
+                assert_eq!(marker_kind, MarkerKind::Read);
+            }
+            _ => panic!("Expected MarkerUpdate"),
+        }
+    }
+
     #[test]
     fn test_user_online_event() {
         let json = r#"{"type":"user_online","user_id":"123e4567-e89b-12d3-a456-426614174000","username":"testuser"}"#;
@@ -1048,11 +1860,19 @@ mod tests {
 
     #[test]
     fn test_key_exchange_response() {
-        let json = r#"{"type":"key_exchange_response","public_key":"SGVsbG8gV29ybGQh"}"#;
+        let json = r#"{"type":"key_exchange_response","public_key":"SGVsbG8gV29ybGQh","identity_key":"SGVsbG8gV29ybGQh","signature":"c2lnbmF0dXJl"}"#;
         let msg: ServerMessage = serde_json::from_str(json).unwrap();
         match msg {
-            ServerMessage::KeyExchangeResponse { public_key } => {
+            ServerMessage::KeyExchangeResponse {
+                public_key,
+                identity_key,
+                signature,
+                length_hiding,
+            } => {
                 assert!(!public_key.is_empty());
+                assert!(!identity_key.is_empty());
+                assert!(!signature.is_empty());
+                assert!(!length_hiding);
             }
             _ => panic!("Expected KeyExchangeResponse"),
         }
@@ -1,7 +1,9 @@
 //! Authentication handlers.
 
+use std::net::SocketAddr;
+
 use axum::{
-    extract::State,
+    extract::{ConnectInfo, State},
     http::{header, HeaderMap, StatusCode},
     Json,
 };
@@ -23,12 +25,18 @@ pub struct RegisterRequest {
     pub username: String,
     pub email: String,
     pub password: String,
+    /// Optional human-readable name for the device/client registering,
+    /// shown alongside the session in the user's session list.
+    pub device_name: Option<String>,
 }
 
 #[derive(Deserialize)]
 pub struct LoginRequest {
     pub identifier: String,
     pub password: String,
+    /// Optional human-readable name for the device/client logging in,
+    /// shown alongside the session in the user's session list.
+    pub device_name: Option<String>,
 }
 
 #[derive(Deserialize)]
@@ -72,11 +80,18 @@ pub struct TokenResponse {
 /// Register a new user.
 pub async fn register<S: Storage + Clone + 'static>(
     State(state): State<AppState<S>>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
     Json(req): Json<RegisterRequest>,
 ) -> Result<(StatusCode, Json<AuthResponse>), Error> {
     let (user, session, token) = state
         .engine
-        .register(&req.username, &req.email, &req.password)
+        .register_with_device(
+            &req.username,
+            &req.email,
+            &req.password,
+            Some(addr.ip().to_string()),
+            req.device_name,
+        )
         .await?;
 
     Ok((
@@ -92,9 +107,18 @@ pub async fn register<S: Storage + Clone + 'static>(
 /// Login with username/email and password.
 pub async fn login<S: Storage + Clone + 'static>(
     State(state): State<AppState<S>>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
     Json(req): Json<LoginRequest>,
 ) -> Result<Json<AuthResponse>, Error> {
-    let (user, session, token) = state.engine.login(&req.identifier, &req.password).await?;
+    let (user, session, token) = state
+        .engine
+        .login_with_device(
+            &req.identifier,
+            &req.password,
+            Some(addr.ip().to_string()),
+            req.device_name,
+        )
+        .await?;
 
     Ok(Json(AuthResponse {
         user,
@@ -6,6 +6,7 @@ pub mod health;
 pub mod invitations;
 pub mod messages;
 pub mod rooms;
+pub mod sessions;
 pub mod users;
 
 use axum::{
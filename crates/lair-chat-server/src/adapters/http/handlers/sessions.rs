@@ -0,0 +1,82 @@
+//! Session handlers - view and revoke a user's active devices/logins.
+
+use axum::{
+    extract::{Path, State},
+    http::HeaderMap,
+    Json,
+};
+use serde::Serialize;
+
+use crate::adapters::http::routes::AppState;
+use crate::domain::SessionId;
+use crate::storage::Storage;
+use crate::Error;
+
+use super::auth::extract_session_id;
+use super::SuccessResponse;
+
+// ============================================================================
+// Request/Response Types
+// ============================================================================
+
+#[derive(Serialize)]
+pub struct SessionsListResponse {
+    pub sessions: Vec<SessionSummary>,
+}
+
+/// A single active session (device), as shown to the owning user.
+#[derive(Serialize)]
+pub struct SessionSummary {
+    pub id: String,
+    pub ip_address: Option<String>,
+    pub device_name: Option<String>,
+    pub created_at: String,
+    pub last_active_at: String,
+}
+
+impl From<crate::domain::Session> for SessionSummary {
+    fn from(session: crate::domain::Session) -> Self {
+        Self {
+            id: session.id.to_string(),
+            ip_address: session.ip_address,
+            device_name: session.user_agent,
+            created_at: session.created_at.to_rfc3339(),
+            last_active_at: session.last_active_at.to_rfc3339(),
+        }
+    }
+}
+
+// ============================================================================
+// Handlers
+// ============================================================================
+
+/// List all active sessions (devices) for the current user.
+pub async fn list_sessions<S: Storage + Clone + 'static>(
+    State(state): State<AppState<S>>,
+    headers: HeaderMap,
+) -> Result<Json<SessionsListResponse>, Error> {
+    let session_id = extract_session_id(&headers)?;
+    let sessions = state.engine.list_sessions(session_id).await?;
+
+    Ok(Json(SessionsListResponse {
+        sessions: sessions.into_iter().map(SessionSummary::from).collect(),
+    }))
+}
+
+/// Revoke one of the current user's sessions (log out a device remotely).
+pub async fn revoke_session<S: Storage + Clone + 'static>(
+    State(state): State<AppState<S>>,
+    headers: HeaderMap,
+    Path(target_session_id): Path<String>,
+) -> Result<Json<SuccessResponse>, Error> {
+    let session_id = extract_session_id(&headers)?;
+    let target_session_id =
+        SessionId::parse(&target_session_id).map_err(|_| Error::SessionNotFound)?;
+
+    state
+        .engine
+        .revoke_session(session_id, target_session_id)
+        .await?;
+
+    Ok(Json(SuccessResponse::ok()))
+}
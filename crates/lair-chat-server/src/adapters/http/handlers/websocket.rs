@@ -205,7 +205,7 @@ impl<S: Storage + 'static> WsConnection<S> {
                 self.commands
                     .user_connected(user.id, user.username.to_string())
                     .await;
-                self.spawn_event_listener(user.id);
+                self.spawn_event_listener(user.id, session_id);
 
                 // Send auth response to confirm authentication
                 self.send(response).await?;
@@ -389,7 +389,7 @@ impl<S: Storage + 'static> WsConnection<S> {
                         self.commands
                             .user_connected(user.id, user.username.to_string())
                             .await;
-                        self.spawn_event_listener(user.id);
+                        self.spawn_event_listener(user.id, session_id);
                     }
                 }
 
@@ -428,7 +428,7 @@ impl<S: Storage + 'static> WsConnection<S> {
                         self.commands
                             .user_connected(user.id, user.username.to_string())
                             .await;
-                        self.spawn_event_listener(user.id);
+                        self.spawn_event_listener(user.id, session_id);
                     }
                 }
 
@@ -470,7 +470,7 @@ impl<S: Storage + 'static> WsConnection<S> {
                         self.commands
                             .user_connected(user.id, user.username.to_string())
                             .await;
-                        self.spawn_event_listener(user.id);
+                        self.spawn_event_listener(user.id, session_id);
                     }
                 }
 
@@ -519,10 +519,10 @@ impl<S: Storage + 'static> WsConnection<S> {
             ClientMessage::EditMessage {
                 request_id: _,
                 message_id,
-                content,
+                new_content,
             } => {
                 self.commands
-                    .handle_edit_message(session_id, &message_id, &content)
+                    .handle_edit_message(session_id, &message_id, &new_content)
                     .await
             }
             ClientMessage::DeleteMessage {
@@ -561,6 +561,15 @@ impl<S: Storage + 'static> WsConnection<S> {
                 request_id: _,
                 room_id,
             } => self.commands.handle_leave_room(session_id, &room_id).await,
+            ClientMessage::UpgradeRoom {
+                request_id: _,
+                room_id,
+                reason,
+            } => {
+                self.commands
+                    .handle_upgrade_room(session_id, &room_id, reason)
+                    .await
+            }
             ClientMessage::ListRooms {
                 request_id: _,
                 filter,
@@ -616,6 +625,74 @@ impl<S: Storage + 'static> WsConnection<S> {
                 }
                 return Ok(()); // No response for typing
             }
+            ClientMessage::MarkDelivered {
+                target,
+                up_to_message_id,
+            } => {
+                if let Some(sid) = session_id {
+                    self.commands
+                        .handle_mark_delivered(sid, &target, &up_to_message_id)
+                        .await;
+                }
+                return Ok(()); // No response for marker updates
+            }
+            ClientMessage::MarkRead {
+                target,
+                up_to_message_id,
+            } => {
+                if let Some(sid) = session_id {
+                    self.commands
+                        .handle_mark_read(sid, &target, &up_to_message_id)
+                        .await;
+                }
+                return Ok(()); // No response for marker updates
+            }
+            ClientMessage::AttachmentBegin {
+                target,
+                transfer_id,
+                file_name,
+                mime_type,
+                total_size,
+                total_chunks,
+                ..
+            } => {
+                if let Some(error) = self
+                    .commands
+                    .handle_attachment_begin(
+                        &transfer_id,
+                        target,
+                        file_name,
+                        mime_type,
+                        total_size,
+                        total_chunks,
+                    )
+                    .await
+                {
+                    return self.send(error).await;
+                }
+                return Ok(()); // No response; chunks follow without waiting
+            }
+            ClientMessage::AttachmentChunk {
+                transfer_id,
+                index,
+                data,
+            } => {
+                self.commands
+                    .handle_attachment_chunk(&transfer_id, index, &data)
+                    .await;
+                return Ok(()); // No response for individual chunks
+            }
+            ClientMessage::AttachmentEnd {
+                transfer_id,
+                sha256,
+            } => {
+                if let Some(sid) = session_id {
+                    self.commands
+                        .handle_attachment_end(sid, &transfer_id, &sha256)
+                        .await;
+                }
+                return Ok(()); // Completion is announced via AttachmentReceived
+            }
             // Messages that shouldn't be sent when authenticated
             ClientMessage::ClientHello { .. }
             | ClientMessage::Authenticate { .. }
@@ -676,6 +753,14 @@ impl<S: Storage + 'static> WsConnection<S> {
                 target: e.target.clone(),
                 deleted_by: e.deleted_by.to_string(),
             }),
+            EventPayload::AttachmentReceived(e) => Some(ServerMessage::AttachmentReceived {
+                transfer_id: e.transfer_id.to_string(),
+                message_id: e.message_id.to_string(),
+                target: e.target.clone(),
+                file_name: e.file_name.clone(),
+                mime_type: e.mime_type.clone(),
+                size: e.size,
+            }),
             EventPayload::UserJoinedRoom(e) => Some(ServerMessage::UserJoinedRoom {
                 room_id: e.room_id.to_string(),
                 user: e.user.clone(),
@@ -703,6 +788,11 @@ impl<S: Storage + 'static> WsConnection<S> {
                 room_name: e.room_name.clone(),
                 deleted_by: e.deleted_by.to_string(),
             }),
+            EventPayload::RoomTombstone(e) => Some(ServerMessage::RoomTombstone {
+                room_id: e.room_id.to_string(),
+                replacement_room_id: e.replacement_room_id.to_string(),
+                reason: e.reason.clone(),
+            }),
             EventPayload::UserOnline(e) => Some(ServerMessage::UserOnline {
                 user_id: e.user_id.to_string(),
                 username: e.username.clone(),
@@ -715,6 +805,13 @@ impl<S: Storage + 'static> WsConnection<S> {
                 user_id: e.user_id.to_string(),
                 target: e.target.clone(),
             }),
+            EventPayload::MarkerUpdate(e) => Some(ServerMessage::MarkerUpdate {
+                user_id: e.user_id.to_string(),
+                username: e.username.clone(),
+                target: e.target.clone(),
+                marker_kind: e.kind.as_str().to_string(),
+                up_to_message_id: e.up_to_message_id.to_string(),
+            }),
             EventPayload::InvitationReceived(e) => Some(ServerMessage::InvitationReceived {
                 invitation: e.invitation.clone(),
             }),
@@ -722,6 +819,9 @@ impl<S: Storage + 'static> WsConnection<S> {
                 message: e.message.clone(),
                 severity: e.severity.to_string(),
             }),
+            EventPayload::SessionRevoked(e) => Some(ServerMessage::SessionRevoked {
+                session_id: e.session_id.to_string(),
+            }),
             // Session-specific events are not broadcast to connections
             EventPayload::InvitationCancelled(_) | EventPayload::SessionExpiring(_) => None,
         }
@@ -732,6 +832,7 @@ impl<S: Storage + 'static> WsConnection<S> {
         mut event_rx: tokio::sync::broadcast::Receiver<Event>,
         outgoing_tx: mpsc::Sender<ServerMessage>,
         user_id: UserId,
+        session_id: SessionId,
         storage: Arc<S>,
     ) {
         loop {
@@ -762,7 +863,7 @@ impl<S: Storage + 'static> WsConnection<S> {
                     };
 
                     // Check if this user should receive this event
-                    if !should_receive_event(&event, user_id, &user_rooms) {
+                    if !should_receive_event(&event, user_id, &user_rooms, Some(session_id)) {
                         debug!(
                             "Event filtered out for WebSocket user {}: {:?}",
                             user_id,
@@ -804,7 +905,7 @@ impl<S: Storage + 'static> WsConnection<S> {
     }
 
     /// Spawn the event listener task after successful authentication.
-    fn spawn_event_listener(&mut self, user_id: UserId) {
+    fn spawn_event_listener(&mut self, user_id: UserId, session_id: SessionId) {
         info!("Spawning WebSocket event listener for user {}", user_id);
         let event_rx = self.events.subscribe();
         let outgoing_tx = self.outgoing_tx.clone();
@@ -814,6 +915,7 @@ impl<S: Storage + 'static> WsConnection<S> {
             event_rx,
             outgoing_tx,
             user_id,
+            session_id,
             storage,
         ));
 
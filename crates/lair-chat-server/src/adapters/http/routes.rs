@@ -67,6 +67,7 @@ pub fn create_router_with_metrics<S: Storage + Clone + 'static>(
         )
         // Protected endpoints (general rate limit, JWT required)
         .nest("/api/v1/users", user_routes())
+        .nest("/api/v1/sessions", session_routes())
         .nest("/api/v1/rooms", room_routes())
         .nest(
             "/api/v1/messages",
@@ -110,6 +111,13 @@ fn user_routes<S: Storage + Clone + 'static>() -> Router<AppState<S>> {
         .route("/{user_id}", get(handlers::users::get_user))
 }
 
+/// Session routes (device list / remote revocation).
+fn session_routes<S: Storage + Clone + 'static>() -> Router<AppState<S>> {
+    Router::new()
+        .route("/", get(handlers::sessions::list_sessions))
+        .route("/{session_id}", delete(handlers::sessions::revoke_session))
+}
+
 /// Room routes.
 fn room_routes<S: Storage + Clone + 'static>() -> Router<AppState<S>> {
     Router::new()
@@ -1,27 +1,48 @@
 //! TCP command handler - maps protocol messages to core engine operations.
 
+use std::collections::HashMap;
 use std::sync::Arc;
 
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use sha2::{Digest, Sha256};
+use tokio::sync::Mutex;
+
 use crate::core::engine::ChatEngine;
 use crate::domain::{
-    InvitationId, MessageId, MessageTarget, Pagination, RoomId, SessionId, UserId,
+    InvitationId, MessageId, MessageTarget, Pagination, RoomId, SessionId, TransferId, UserId,
 };
 use crate::storage::{MembershipRepository, Storage};
 
 use super::protocol::{
     ErrorInfo, RoomFilter, RoomListItem, RoomSettingsRequest, ServerMessage, SessionInfo,
-    UserFilter,
+    UserFilter, MAX_ATTACHMENT_CHUNKS, MAX_ATTACHMENT_SIZE,
 };
 
+/// An attachment upload in progress, keyed by `transfer_id`.
+///
+/// Scoped to one connection — a begin/chunk/end run never spans multiple
+/// connections — and never persisted: once the transfer completes (or the
+/// connection drops) its buffer is simply dropped.
+struct PendingAttachment {
+    target: MessageTarget,
+    file_name: String,
+    mime_type: String,
+    chunks: Vec<Option<Vec<u8>>>,
+}
+
 /// Handles mapping protocol commands to engine operations.
 pub struct CommandHandler<S: Storage> {
     engine: Arc<ChatEngine<S>>,
+    attachments: Mutex<HashMap<TransferId, PendingAttachment>>,
 }
 
 impl<S: Storage + 'static> CommandHandler<S> {
     /// Create a new command handler.
     pub fn new(engine: Arc<ChatEngine<S>>) -> Self {
-        Self { engine }
+        Self {
+            engine,
+            attachments: Mutex::new(HashMap::new()),
+        }
     }
 
     // ========================================================================
@@ -416,6 +437,50 @@ impl<S: Storage + 'static> CommandHandler<S> {
         }
     }
 
+    /// Handle room upgrade request.
+    pub async fn handle_upgrade_room(
+        &self,
+        session_id: Option<SessionId>,
+        room_id: &str,
+        reason: Option<String>,
+    ) -> ServerMessage {
+        let Some(session_id) = session_id else {
+            return ServerMessage::UpgradeRoomResponse {
+                request_id: None,
+                success: false,
+                room: None,
+                error: Some(ErrorInfo::new("unauthorized", "Not authenticated")),
+            };
+        };
+
+        let room_id = match RoomId::parse(room_id) {
+            Ok(id) => id,
+            Err(_) => {
+                return ServerMessage::UpgradeRoomResponse {
+                    request_id: None,
+                    success: false,
+                    room: None,
+                    error: Some(ErrorInfo::new("validation_failed", "Invalid room ID")),
+                };
+            }
+        };
+
+        match self.engine.upgrade_room(session_id, room_id, reason).await {
+            Ok(successor) => ServerMessage::UpgradeRoomResponse {
+                request_id: None,
+                success: true,
+                room: Some(successor),
+                error: None,
+            },
+            Err(e) => ServerMessage::UpgradeRoomResponse {
+                request_id: None,
+                success: false,
+                room: None,
+                error: Some(error_to_info(&e)),
+            },
+        }
+    }
+
     /// Handle list rooms request.
     pub async fn handle_list_rooms(
         &self,
@@ -839,6 +904,160 @@ impl<S: Storage + 'static> CommandHandler<S> {
         let _ = self.engine.send_typing(session_id, target.clone()).await;
     }
 
+    /// Handle a delivered-marker update.
+    ///
+    /// Fire-and-forget like [`handle_typing`](Self::handle_typing) — an
+    /// unparseable `up_to_message_id` is silently ignored rather than
+    /// erroring, since there's no response channel for this message.
+    pub async fn handle_mark_delivered(
+        &self,
+        session_id: SessionId,
+        target: &MessageTarget,
+        up_to_message_id: &str,
+    ) {
+        if let Ok(message_id) = MessageId::parse(up_to_message_id) {
+            let _ = self
+                .engine
+                .mark_delivered(session_id, target.clone(), message_id)
+                .await;
+        }
+    }
+
+    /// Handle a read-marker update.
+    ///
+    /// See [`handle_mark_delivered`](Self::handle_mark_delivered) for the
+    /// fire-and-forget semantics.
+    pub async fn handle_mark_read(
+        &self,
+        session_id: SessionId,
+        target: &MessageTarget,
+        up_to_message_id: &str,
+    ) {
+        if let Ok(message_id) = MessageId::parse(up_to_message_id) {
+            let _ = self
+                .engine
+                .mark_read(session_id, target.clone(), message_id)
+                .await;
+        }
+    }
+
+    // ========================================================================
+    // Attachments
+    // ========================================================================
+
+    /// Begin a chunked attachment upload, allocating a reassembly buffer
+    /// for `transfer_id`.
+    ///
+    /// Fire-and-forget like [`handle_typing`](Self::handle_typing) on
+    /// success — chunks arrive without waiting on a response to this
+    /// message. An unparseable `transfer_id` is silently ignored, same as
+    /// [`handle_mark_delivered`](Self::handle_mark_delivered). A
+    /// `total_size`/`total_chunks` over [`MAX_ATTACHMENT_SIZE`]/
+    /// [`MAX_ATTACHMENT_CHUNKS`] is rejected with an error response instead
+    /// of being trusted: both are client-declared, and allocating a
+    /// reassembly buffer from them before the upload is verified would let
+    /// a single message trigger an allocation large enough to abort the
+    /// whole process.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn handle_attachment_begin(
+        &self,
+        transfer_id: &str,
+        target: MessageTarget,
+        file_name: String,
+        mime_type: String,
+        total_size: u64,
+        total_chunks: u32,
+    ) -> Option<ServerMessage> {
+        let transfer_id = TransferId::parse(transfer_id).ok()?;
+
+        if total_size > MAX_ATTACHMENT_SIZE || total_chunks > MAX_ATTACHMENT_CHUNKS {
+            return Some(ServerMessage::error(
+                None,
+                "attachment_too_large",
+                &format!(
+                    "attachment exceeds the {} byte / {} chunk limit",
+                    MAX_ATTACHMENT_SIZE, MAX_ATTACHMENT_CHUNKS
+                ),
+            ));
+        }
+
+        let mut attachments = self.attachments.lock().await;
+        attachments.insert(
+            transfer_id,
+            PendingAttachment {
+                target,
+                file_name,
+                mime_type,
+                chunks: vec![None; total_chunks as usize],
+            },
+        );
+        None
+    }
+
+    /// Buffer one chunk of an in-flight attachment upload.
+    ///
+    /// Silently ignored if `transfer_id`/`index`/`data` don't match a
+    /// pending transfer — there's no response channel to report the error
+    /// on, same as [`handle_mark_delivered`](Self::handle_mark_delivered).
+    pub async fn handle_attachment_chunk(&self, transfer_id: &str, index: u32, data: &str) {
+        let Ok(transfer_id) = TransferId::parse(transfer_id) else {
+            return;
+        };
+        let Ok(bytes) = BASE64.decode(data) else {
+            return;
+        };
+
+        let mut attachments = self.attachments.lock().await;
+        if let Some(pending) = attachments.get_mut(&transfer_id) {
+            if let Some(slot) = pending.chunks.get_mut(index as usize) {
+                *slot = Some(bytes);
+            }
+        }
+    }
+
+    /// Finish a chunked attachment upload: reassemble, verify its
+    /// checksum, and turn it into a visible message.
+    ///
+    /// Silently dropped if the transfer is unknown, incomplete, or fails
+    /// checksum verification — see
+    /// [`handle_attachment_chunk`](Self::handle_attachment_chunk).
+    pub async fn handle_attachment_end(
+        &self,
+        session_id: SessionId,
+        transfer_id: &str,
+        sha256: &str,
+    ) {
+        let Ok(transfer_id) = TransferId::parse(transfer_id) else {
+            return;
+        };
+
+        let pending = self.attachments.lock().await.remove(&transfer_id);
+        let Some(pending) = pending else {
+            return;
+        };
+        let Some(chunks) = pending.chunks.into_iter().collect::<Option<Vec<_>>>() else {
+            return;
+        };
+        let bytes: Vec<u8> = chunks.into_iter().flatten().collect();
+
+        let digest = hex_encode(&Sha256::digest(&bytes));
+        if !digest.eq_ignore_ascii_case(sha256) {
+            return;
+        }
+
+        let _ = self
+            .engine
+            .send_attachment(
+                session_id,
+                pending.target,
+                transfer_id,
+                &pending.file_name,
+                &pending.mime_type,
+                bytes.len() as u64,
+            )
+            .await;
+    }
+
     /// Notify that a user connected.
     pub async fn user_connected(&self, user_id: UserId, username: String) {
         self.engine.user_connected(user_id, username).await;
@@ -859,6 +1078,11 @@ fn error_to_info(error: &crate::Error) -> ErrorInfo {
     ErrorInfo::new(error.code(), error.to_string())
 }
 
+/// Hex-encode a digest for comparison against a client-supplied checksum.
+fn hex_encode(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
 // ============================================================================
 // Tests
 // ============================================================================
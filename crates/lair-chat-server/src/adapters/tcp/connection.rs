@@ -10,6 +10,7 @@
 use std::net::SocketAddr;
 use std::sync::{Arc, RwLock};
 
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
 use tokio::io::{BufReader, BufWriter};
 use tokio::net::tcp::{OwnedReadHalf, OwnedWriteHalf};
 use tokio::net::TcpStream;
@@ -19,16 +20,22 @@ use tracing::{debug, error, info, warn};
 
 use crate::core::engine::ChatEngine;
 use crate::core::events::{should_receive_event, EventDispatcher};
-use crate::crypto::{parse_public_key, Cipher, KeyPair};
+use crate::crypto::{
+    client_hello_bytes, derive_directional_keys, derive_header_keys, handshake_transcript,
+    parse_public_key, server_hello_bytes, server_identity_public_key_base64, sign_handshake,
+    Cipher, KeyPair,
+};
 use crate::domain::events::{Event, EventPayload};
 use crate::domain::{Pagination, Protocol, RoomId, Session, SessionId, User};
 use crate::storage::{RoomRepository, Storage, UserRepository};
 
 use super::commands::CommandHandler;
 use super::protocol::{
-    read_encrypted_message, read_message, write_encrypted_message, write_message, ClientMessage,
-    ProtocolError, ServerMessage, PROTOCOL_VERSION,
+    read_encrypted_message, read_length_hidden_message, read_message, write_encrypted_message,
+    write_length_hidden_message, write_message, ClientMessage, ProtocolError, ServerMessage,
+    PROTOCOL_VERSION,
 };
+use super::signing::{verify_message, SignedMessage};
 
 /// Timeout for handshake completion.
 const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(30);
@@ -56,14 +63,36 @@ enum ConnectionState {
 
 /// Encryption state shared between connection handler and writer task.
 ///
+/// `send_cipher` and `recv_cipher` are HKDF-derived from the same shared
+/// secret but with distinct info labels, so client->server and
+/// server->client frames never share a keystream (see
+/// `crypto::derive_directional_keys`).
+///
 /// The `pending` flag handles the race condition during key exchange:
-/// - When cipher is first set, `pending` is true (not yet active for writes)
-/// - After the writer sends a message with pending cipher, it sets pending=false
+/// - When the ciphers are first set, `pending` is true (send side not yet
+///   active for writes)
+/// - After the writer sends a message with a pending cipher, it sets
+///   pending=false
 /// - This ensures KeyExchangeResponse is sent unencrypted
+///
+/// `send_header_cipher`/`recv_header_cipher` are set alongside the payload
+/// ciphers only when the client also requested the `length_hiding`
+/// feature; when set, frames after `KeyExchangeResponse` use
+/// [`write_length_hidden_message`]/[`read_length_hidden_message`] instead
+/// of [`write_encrypted_message`]/[`read_encrypted_message`].
 #[derive(Default)]
 struct EncryptionState {
-    cipher: Option<Arc<Cipher>>,
-    /// True when cipher is set but not yet active for writing.
+    /// Used to encrypt outbound frames (the client's s2c key).
+    send_cipher: Option<Arc<Cipher>>,
+    /// Used to decrypt inbound frames (the client's c2s key).
+    recv_cipher: Option<Arc<Cipher>>,
+    /// Used to seal outbound frames' length headers (the client's s2c
+    /// header key), when length-hiding framing is enabled.
+    send_header_cipher: Option<Arc<Cipher>>,
+    /// Used to open inbound frames' length headers (the client's c2s
+    /// header key), when length-hiding framing is enabled.
+    recv_header_cipher: Option<Arc<Cipher>>,
+    /// True when `send_cipher` is set but not yet active for writing.
     /// The writer task will activate it after sending the next message.
     pending: bool,
 }
@@ -84,8 +113,15 @@ pub struct Connection<S: Storage> {
     outgoing_tx: mpsc::Sender<ServerMessage>,
     /// Whether encryption is enabled for this connection.
     encryption_enabled: bool,
+    /// Whether the client also requested (and the server honored)
+    /// length-hiding framing for this connection.
+    length_hiding_enabled: bool,
     /// Server's keypair for key exchange (consumed during exchange).
     keypair: Option<KeyPair>,
+    /// Whether the client requested length-hiding framing in
+    /// `ClientHello`, decided at handshake time and consumed once key
+    /// exchange derives the header keys.
+    wants_length_hiding: bool,
     /// Encryption state (shared with writer task).
     encryption_state: Arc<RwLock<EncryptionState>>,
     /// Event listener task handle (spawned after authentication).
@@ -94,6 +130,18 @@ pub struct Connection<S: Storage> {
     storage: Arc<S>,
     /// Event dispatcher for subscribing to events.
     events: EventDispatcher,
+    /// This connection's expected message-signing public key, pinned from
+    /// the first `SignedMessage` (or `ClientHello.signing_public_key`) we
+    /// see. `None` if the client never advertised one, in which case
+    /// messages are accepted unsigned.
+    signing_key: Option<[u8; 32]>,
+    /// Canonical encoding of the `ServerHello` we sent, mixed into
+    /// [`handshake_transcript`] during key exchange.
+    server_hello_bytes: Vec<u8>,
+    /// Canonical encoding of the `ClientHello` we received, mixed into
+    /// [`handshake_transcript`] during key exchange. Set once the
+    /// handshake message arrives; only ever read after that.
+    client_hello_bytes: Option<Vec<u8>>,
 }
 
 impl<S: Storage + 'static> Connection<S> {
@@ -121,11 +169,16 @@ impl<S: Storage + 'static> Connection<S> {
             commands: CommandHandler::new(engine.clone()),
             outgoing_tx: outgoing_tx.clone(),
             encryption_enabled: false,
+            length_hiding_enabled: false,
             keypair: None,
+            wants_length_hiding: false,
             encryption_state: encryption_state.clone(),
             event_task: None,
             storage: engine.storage_clone(),
             events: engine.events_clone(),
+            signing_key: None,
+            server_hello_bytes: Vec::new(),
+            client_hello_bytes: None,
         };
 
         // Send server hello
@@ -172,22 +225,28 @@ impl<S: Storage + 'static> Connection<S> {
         while let Some(msg) = rx.recv().await {
             match msg.to_json() {
                 Ok(json) => {
-                    // Get cipher and pending state
-                    let (cipher_opt, was_pending) = {
+                    // Get ciphers and pending state
+                    let (cipher_opt, header_cipher_opt, was_pending) = {
                         let state = encryption_state.read().unwrap();
-                        // Only use cipher for encryption if it's not pending
+                        // Only use ciphers for encryption if not pending
                         // (pending means KeyExchangeResponse hasn't been sent yet)
-                        let cipher = if state.pending {
-                            None // Don't encrypt while pending
+                        let (cipher, header_cipher) = if state.pending {
+                            (None, None) // Don't encrypt while pending
                         } else {
-                            state.cipher.as_ref().cloned()
+                            (
+                                state.send_cipher.as_ref().cloned(),
+                                state.send_header_cipher.as_ref().cloned(),
+                            )
                         };
-                        (cipher, state.pending)
+                        (cipher, header_cipher, state.pending)
                     };
 
-                    let result = match cipher_opt {
-                        Some(c) => write_encrypted_message(&mut writer, &json, &c).await,
-                        None => write_message(&mut writer, &json).await,
+                    let result = match (cipher_opt, header_cipher_opt) {
+                        (Some(c), Some(h)) => {
+                            write_length_hidden_message(&mut writer, &json, &h, &c, None).await
+                        }
+                        (Some(c), None) => write_encrypted_message(&mut writer, &json, &c).await,
+                        (None, _) => write_message(&mut writer, &json).await,
                     };
 
                     // If cipher was pending, activate it now (after writing unencrypted)
@@ -213,10 +272,20 @@ impl<S: Storage + 'static> Connection<S> {
 
     /// Send server hello message.
     async fn send_server_hello(
-        &self,
+        &mut self,
         writer: &mut BufWriter<OwnedWriteHalf>,
     ) -> Result<(), ProtocolError> {
         let hello = ServerMessage::server_hello();
+        if let ServerMessage::ServerHello {
+            ref version,
+            ref server_name,
+            ref features,
+            encryption_required,
+        } = hello
+        {
+            self.server_hello_bytes =
+                server_hello_bytes(version, server_name, features, encryption_required);
+        }
         let json = hello.to_json()?;
         write_message(writer, &json).await
     }
@@ -229,6 +298,40 @@ impl<S: Storage + 'static> Connection<S> {
             .map_err(|_| ProtocolError::ConnectionClosed)
     }
 
+    /// Parse an incoming frame, verifying and unwrapping a `SignedMessage`
+    /// envelope if present, or falling back to a plain [`ClientMessage`]
+    /// otherwise. The first signed message we ever see from a connection
+    /// pins its public key; every signed message after that must carry
+    /// the same key, so a mid-connection key swap is rejected rather than
+    /// silently trusted. Once a key is pinned, an unsigned message is no
+    /// longer a valid fallback either — accepting one would let a relay
+    /// strip the `SignedMessage` envelope and forward the bare
+    /// `ClientMessage` unverified.
+    fn parse_message(&mut self, json: &str) -> Result<ClientMessage, ProtocolError> {
+        if let Ok(signed) = serde_json::from_str::<SignedMessage>(json) {
+            let key_bytes = signed
+                .public_key_bytes()
+                .map_err(|e| ProtocolError::SignatureInvalid(e.to_string()))?;
+            if let Some(pinned) = self.signing_key {
+                if pinned != key_bytes {
+                    return Err(ProtocolError::SignatureInvalid(
+                        "signing key changed mid-connection".to_string(),
+                    ));
+                }
+            }
+            let message = verify_message(&signed)
+                .map_err(|e| ProtocolError::SignatureInvalid(e.to_string()))?;
+            self.signing_key.get_or_insert(key_bytes);
+            Ok(message)
+        } else if self.signing_key.is_some() {
+            Err(ProtocolError::SignatureInvalid(
+                "expected a signed message once a signing key is pinned".to_string(),
+            ))
+        } else {
+            ClientMessage::parse(json)
+        }
+    }
+
     /// Process incoming messages.
     async fn process_messages(
         &mut self,
@@ -245,20 +348,48 @@ impl<S: Storage + 'static> Connection<S> {
                 ConnectionState::Closing => return Ok(()),
             };
 
-            // Read with timeout - use encrypted read if encryption is enabled
-            // Clone the cipher Arc before releasing lock (if encryption enabled and not pending)
-            let cipher_opt = if self.encryption_enabled {
+            // Read with timeout - use encrypted (and, if negotiated,
+            // length-hidden) read once encryption is enabled.
+            // Clone the cipher Arcs before releasing lock (if encryption enabled and not pending)
+            let (cipher_opt, header_cipher_opt) = if self.encryption_enabled {
                 let state = self.encryption_state.read().unwrap();
-                // Only use cipher for reading if it's active (not pending)
                 // Note: for reading, we should always decrypt after key exchange,
                 // because the client will start encrypting after receiving KeyExchangeResponse
-                state.cipher.as_ref().cloned()
+                (
+                    state.recv_cipher.as_ref().cloned(),
+                    state.recv_header_cipher.as_ref().cloned(),
+                )
             } else {
-                None
+                (None, None)
             };
 
-            let json = match cipher_opt {
-                Some(cipher) => {
+            let json = match (cipher_opt, header_cipher_opt) {
+                (Some(cipher), Some(header_cipher)) => {
+                    match timeout(
+                        read_timeout,
+                        read_length_hidden_message(reader, &header_cipher, &cipher),
+                    )
+                    .await
+                    {
+                        Ok(Ok(json)) => json,
+                        Ok(Err(e)) => return Err(e),
+                        Err(_) => {
+                            warn!(
+                                "Connection {} timed out in state {:?}",
+                                self.addr, self.state
+                            );
+                            let _ = self
+                                .send(ServerMessage::error(
+                                    None,
+                                    "timeout",
+                                    "Connection timed out",
+                                ))
+                                .await;
+                            return Ok(());
+                        }
+                    }
+                }
+                (Some(cipher), None) => {
                     match timeout(read_timeout, read_encrypted_message(reader, &cipher)).await {
                         Ok(Ok(json)) => json,
                         Ok(Err(e)) => return Err(e),
@@ -278,7 +409,7 @@ impl<S: Storage + 'static> Connection<S> {
                         }
                     }
                 }
-                None => match timeout(read_timeout, read_message(reader)).await {
+                (None, _) => match timeout(read_timeout, read_message(reader)).await {
                     Ok(Ok(json)) => json,
                     Ok(Err(e)) => return Err(e),
                     Err(_) => {
@@ -299,7 +430,7 @@ impl<S: Storage + 'static> Connection<S> {
             };
 
             // Parse message
-            let msg = match ClientMessage::parse(&json) {
+            let msg = match self.parse_message(&json) {
                 Ok(msg) => msg,
                 Err(e) => {
                     warn!("Invalid message from {}: {}", self.addr, e);
@@ -341,8 +472,44 @@ impl<S: Storage + 'static> Connection<S> {
     async fn handle_handshake(&mut self, msg: ClientMessage) -> Result<(), ProtocolError> {
         match msg {
             ClientMessage::ClientHello {
-                version, features, ..
+                version,
+                client_name,
+                features,
+                signing_public_key,
             } => {
+                self.client_hello_bytes = Some(client_hello_bytes(
+                    &version,
+                    client_name.as_deref(),
+                    &features,
+                    signing_public_key.as_deref(),
+                ));
+
+                // Remember the key this connection will sign messages
+                // with, in case the hello itself wasn't signed (e.g. the
+                // client signs everything after the hello, not the hello
+                // itself).
+                if let Some(signing_public_key) = signing_public_key {
+                    let key_bytes: Option<[u8; 32]> = BASE64
+                        .decode(&signing_public_key)
+                        .ok()
+                        .and_then(|bytes| bytes.try_into().ok());
+                    match key_bytes {
+                        Some(key_bytes) => {
+                            self.signing_key.get_or_insert(key_bytes);
+                        }
+                        None => {
+                            self.send(ServerMessage::error(
+                                None,
+                                "invalid_message",
+                                "signing_public_key must be a base64-encoded 32-byte key",
+                            ))
+                            .await?;
+                            self.state = ConnectionState::Closing;
+                            return Ok(());
+                        }
+                    }
+                }
+
                 // Check version compatibility
                 if !version.starts_with("1.") {
                     self.send(ServerMessage::error(
@@ -360,6 +527,11 @@ impl<S: Storage + 'static> Connection<S> {
 
                 // Check if client wants encryption
                 let wants_encryption = features.iter().any(|f| f == "encryption");
+                // Length-hiding framing seals the frame length itself, so
+                // it only makes sense layered on top of an encrypted
+                // connection.
+                self.wants_length_hiding =
+                    wants_encryption && features.iter().any(|f| f == "length_hiding");
 
                 if wants_encryption {
                     debug!(
@@ -410,27 +582,62 @@ impl<S: Storage + 'static> Connection<S> {
                     ProtocolError::KeyExchangeFailed("Server keypair not available".to_string())
                 })?;
 
-                // Send our public key to client
-                let server_public = keypair.public_key_base64();
+                // Send our public key to client, signed so it can't be
+                // swapped in transit by an active MITM.
+                let server_public_b64 = keypair.public_key_base64();
+                let server_public = parse_public_key(&server_public_b64).map_err(|e| {
+                    ProtocolError::KeyExchangeFailed(format!(
+                        "Failed to re-parse our own public key: {}",
+                        e
+                    ))
+                })?;
+                let client_hello = self.client_hello_bytes.take().ok_or_else(|| {
+                    ProtocolError::KeyExchangeFailed("ClientHello not yet received".to_string())
+                })?;
+                let transcript = handshake_transcript(
+                    &client_public,
+                    &server_public,
+                    &self.server_hello_bytes,
+                    &client_hello,
+                );
+                let signature = sign_handshake(&transcript);
+                let length_hiding = self.wants_length_hiding;
                 self.send(ServerMessage::KeyExchangeResponse {
-                    public_key: server_public,
+                    public_key: server_public_b64,
+                    identity_key: server_identity_public_key_base64(),
+                    signature: BASE64.encode(signature),
+                    length_hiding,
                 })
                 .await?;
 
-                // Derive shared secret
+                // Derive shared secret, then split it into independent
+                // send/recv keys so the two directions never share a
+                // keystream.
                 let shared_secret = keypair.diffie_hellman(client_public);
+                let (c2s_key, s2c_key) = derive_directional_keys(&shared_secret, &transcript);
 
-                // Create cipher from shared secret and store in shared holder.
+                // Store the directional ciphers in the shared holder.
                 // Set pending=true so the KeyExchangeResponse is sent unencrypted.
                 // The writer task will clear pending after sending that message.
                 {
                     let mut state = self.encryption_state.write().unwrap();
-                    state.cipher = Some(Arc::new(Cipher::new(&shared_secret)));
+                    state.recv_cipher = Some(Arc::new(Cipher::new(&c2s_key)));
+                    state.send_cipher = Some(Arc::new(Cipher::new(&s2c_key)));
+                    if length_hiding {
+                        let (c2s_header_key, s2c_header_key) =
+                            derive_header_keys(&shared_secret, &transcript);
+                        state.recv_header_cipher = Some(Arc::new(Cipher::new(&c2s_header_key)));
+                        state.send_header_cipher = Some(Arc::new(Cipher::new(&s2c_header_key)));
+                    }
                     state.pending = true;
                 }
                 self.encryption_enabled = true;
+                self.length_hiding_enabled = length_hiding;
 
-                info!("Encryption enabled for connection from {}", self.addr);
+                info!(
+                    "Encryption enabled for connection from {} (length_hiding={})",
+                    self.addr, length_hiding
+                );
                 self.state = ConnectionState::AwaitingAuth;
                 Ok(())
             }
@@ -477,7 +684,7 @@ impl<S: Storage + 'static> Connection<S> {
                         self.commands
                             .user_connected(user.id, user.username.to_string())
                             .await;
-                        self.spawn_event_listener(user.id);
+                        self.spawn_event_listener(user.id, session_id);
                     }
                 }
 
@@ -518,7 +725,7 @@ impl<S: Storage + 'static> Connection<S> {
                             .await;
 
                         // Spawn event listener to receive real-time updates
-                        self.spawn_event_listener(user.id);
+                        self.spawn_event_listener(user.id, session_id);
                     }
                 }
 
@@ -561,7 +768,7 @@ impl<S: Storage + 'static> Connection<S> {
                             .await;
 
                         // Spawn event listener to receive real-time updates
-                        self.spawn_event_listener(user.id);
+                        self.spawn_event_listener(user.id, session_id);
                     }
                 }
 
@@ -610,10 +817,10 @@ impl<S: Storage + 'static> Connection<S> {
             ClientMessage::EditMessage {
                 request_id: _,
                 message_id,
-                content,
+                new_content,
             } => {
                 self.commands
-                    .handle_edit_message(session_id, &message_id, &content)
+                    .handle_edit_message(session_id, &message_id, &new_content)
                     .await
             }
             ClientMessage::DeleteMessage {
@@ -656,6 +863,15 @@ impl<S: Storage + 'static> Connection<S> {
                 request_id: _,
                 room_id,
             } => self.commands.handle_leave_room(session_id, &room_id).await,
+            ClientMessage::UpgradeRoom {
+                request_id: _,
+                room_id,
+                reason,
+            } => {
+                self.commands
+                    .handle_upgrade_room(session_id, &room_id, reason)
+                    .await
+            }
             // DEPRECATED: Use HTTP GET /rooms instead
             ClientMessage::ListRooms {
                 request_id: _,
@@ -733,6 +949,74 @@ impl<S: Storage + 'static> Connection<S> {
                 }
                 return Ok(()); // No response for typing
             }
+            ClientMessage::MarkDelivered {
+                target,
+                up_to_message_id,
+            } => {
+                if let Some(sid) = session_id {
+                    self.commands
+                        .handle_mark_delivered(sid, &target, &up_to_message_id)
+                        .await;
+                }
+                return Ok(()); // No response for marker updates
+            }
+            ClientMessage::MarkRead {
+                target,
+                up_to_message_id,
+            } => {
+                if let Some(sid) = session_id {
+                    self.commands
+                        .handle_mark_read(sid, &target, &up_to_message_id)
+                        .await;
+                }
+                return Ok(()); // No response for marker updates
+            }
+            ClientMessage::AttachmentBegin {
+                target,
+                transfer_id,
+                file_name,
+                mime_type,
+                total_size,
+                total_chunks,
+                ..
+            } => {
+                if let Some(error) = self
+                    .commands
+                    .handle_attachment_begin(
+                        &transfer_id,
+                        target,
+                        file_name,
+                        mime_type,
+                        total_size,
+                        total_chunks,
+                    )
+                    .await
+                {
+                    return self.send(error).await;
+                }
+                return Ok(()); // No response; chunks follow without waiting
+            }
+            ClientMessage::AttachmentChunk {
+                transfer_id,
+                index,
+                data,
+            } => {
+                self.commands
+                    .handle_attachment_chunk(&transfer_id, index, &data)
+                    .await;
+                return Ok(()); // No response for individual chunks
+            }
+            ClientMessage::AttachmentEnd {
+                transfer_id,
+                sha256,
+            } => {
+                if let Some(sid) = session_id {
+                    self.commands
+                        .handle_attachment_end(sid, &transfer_id, &sha256)
+                        .await;
+                }
+                return Ok(()); // Completion is announced via AttachmentReceived
+            }
             // Messages that shouldn't be sent when authenticated
             ClientMessage::ClientHello { .. }
             | ClientMessage::Authenticate { .. }
@@ -777,6 +1061,14 @@ impl<S: Storage + 'static> Connection<S> {
                 target: e.target.clone(),
                 deleted_by: e.deleted_by.to_string(),
             }),
+            EventPayload::AttachmentReceived(e) => Some(ServerMessage::AttachmentReceived {
+                transfer_id: e.transfer_id.to_string(),
+                message_id: e.message_id.to_string(),
+                target: e.target.clone(),
+                file_name: e.file_name.clone(),
+                mime_type: e.mime_type.clone(),
+                size: e.size,
+            }),
             EventPayload::UserJoinedRoom(e) => Some(ServerMessage::UserJoinedRoom {
                 room_id: e.room_id.to_string(),
                 user: e.user.clone(),
@@ -804,6 +1096,11 @@ impl<S: Storage + 'static> Connection<S> {
                 room_name: e.room_name.clone(),
                 deleted_by: e.deleted_by.to_string(),
             }),
+            EventPayload::RoomTombstone(e) => Some(ServerMessage::RoomTombstone {
+                room_id: e.room_id.to_string(),
+                replacement_room_id: e.replacement_room_id.to_string(),
+                reason: e.reason.clone(),
+            }),
             EventPayload::UserOnline(e) => Some(ServerMessage::UserOnline {
                 user_id: e.user_id.to_string(),
                 username: e.username.clone(),
@@ -816,6 +1113,13 @@ impl<S: Storage + 'static> Connection<S> {
                 user_id: e.user_id.to_string(),
                 target: e.target.clone(),
             }),
+            EventPayload::MarkerUpdate(e) => Some(ServerMessage::MarkerUpdate {
+                user_id: e.user_id.to_string(),
+                username: e.username.clone(),
+                target: e.target.clone(),
+                marker_kind: e.kind.as_str().to_string(),
+                up_to_message_id: e.up_to_message_id.to_string(),
+            }),
             EventPayload::InvitationReceived(e) => Some(ServerMessage::InvitationReceived {
                 invitation: e.invitation.clone(),
             }),
@@ -823,6 +1127,9 @@ impl<S: Storage + 'static> Connection<S> {
                 message: e.message.clone(),
                 severity: e.severity.to_string(),
             }),
+            EventPayload::SessionRevoked(e) => Some(ServerMessage::SessionRevoked {
+                session_id: e.session_id.to_string(),
+            }),
             // Session-specific events are not broadcast to connections
             EventPayload::InvitationCancelled(_) | EventPayload::SessionExpiring(_) => None,
         }
@@ -833,6 +1140,7 @@ impl<S: Storage + 'static> Connection<S> {
         mut event_rx: tokio::sync::broadcast::Receiver<Event>,
         outgoing_tx: mpsc::Sender<ServerMessage>,
         user_id: crate::domain::UserId,
+        session_id: SessionId,
         storage: Arc<S>,
     ) {
         loop {
@@ -846,17 +1154,25 @@ impl<S: Storage + 'static> Connection<S> {
                     );
 
                     // Fetch user's current room memberships for filtering
-                    let user_rooms: Vec<RoomId> =
-                        match RoomRepository::list_for_user(&*storage, user_id, Pagination { offset: 0, limit: u32::MAX }).await {
-                            Ok(rooms) => rooms.into_iter().map(|r| r.id).collect(),
-                            Err(e) => {
-                                debug!("Failed to fetch user rooms for event filtering: {}", e);
-                                Vec::new()
-                            }
-                        };
+                    let user_rooms: Vec<RoomId> = match RoomRepository::list_for_user(
+                        &*storage,
+                        user_id,
+                        Pagination {
+                            offset: 0,
+                            limit: u32::MAX,
+                        },
+                    )
+                    .await
+                    {
+                        Ok(rooms) => rooms.into_iter().map(|r| r.id).collect(),
+                        Err(e) => {
+                            debug!("Failed to fetch user rooms for event filtering: {}", e);
+                            Vec::new()
+                        }
+                    };
 
                     // Check if this user should receive this event
-                    if !should_receive_event(&event, user_id, &user_rooms) {
+                    if !should_receive_event(&event, user_id, &user_rooms, Some(session_id)) {
                         debug!(
                             "Event filtered out for user {}: {:?}",
                             user_id,
@@ -908,7 +1224,7 @@ impl<S: Storage + 'static> Connection<S> {
     }
 
     /// Spawn the event listener task after successful authentication.
-    fn spawn_event_listener(&mut self, user_id: crate::domain::UserId) {
+    fn spawn_event_listener(&mut self, user_id: crate::domain::UserId, session_id: SessionId) {
         info!("Spawning event listener for user {}", user_id);
         let event_rx = self.events.subscribe();
         let outgoing_tx = self.outgoing_tx.clone();
@@ -918,6 +1234,7 @@ impl<S: Storage + 'static> Connection<S> {
             event_rx,
             outgoing_tx,
             user_id,
+            session_id,
             storage,
         ));
 
@@ -925,3 +1242,83 @@ impl<S: Storage + 'static> Connection<S> {
         debug!("Event listener spawned for user {}", user_id);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::engine::ChatEngine;
+    use crate::storage::sqlite::SqliteStorage;
+    use base64::prelude::*;
+    use ed25519_dalek::{Signer, SigningKey};
+    use rand::rngs::OsRng;
+
+    const TEST_JWT_SECRET: &str = "test-jwt-secret-for-unit-tests-only";
+
+    async fn test_connection() -> Connection<SqliteStorage> {
+        let storage = SqliteStorage::in_memory().await.unwrap();
+        let engine = Arc::new(ChatEngine::new(Arc::new(storage), TEST_JWT_SECRET));
+        let (outgoing_tx, _outgoing_rx) = mpsc::channel::<ServerMessage>(100);
+        Connection {
+            addr: "127.0.0.1:0".parse().unwrap(),
+            state: ConnectionState::Authenticated,
+            user: None,
+            session: None,
+            commands: CommandHandler::new(engine.clone()),
+            outgoing_tx,
+            encryption_enabled: false,
+            length_hiding_enabled: false,
+            keypair: None,
+            wants_length_hiding: false,
+            encryption_state: Arc::new(RwLock::new(EncryptionState::default())),
+            event_task: None,
+            storage: engine.storage_clone(),
+            events: engine.events_clone(),
+            signing_key: None,
+            server_hello_bytes: Vec::new(),
+            client_hello_bytes: None,
+        }
+    }
+
+    fn signed_json(signing_key: &SigningKey) -> String {
+        let message = serde_json::json!({"type": "typing", "target": {"type": "room", "room_id": "123e4567-e89b-12d3-a456-426614174000"}});
+        let bytes = crate::adapters::tcp::signing::canonicalize(&message).into_bytes();
+        let signature = signing_key.sign(&bytes);
+        serde_json::to_string(&serde_json::json!({
+            "message": message,
+            "public_key": BASE64_STANDARD.encode(signing_key.verifying_key().to_bytes()),
+            "signature": BASE64_STANDARD.encode(signature.to_bytes()),
+        }))
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_unsigned_message_accepted_before_key_pinned() {
+        let mut conn = test_connection().await;
+        let result = conn.parse_message(r#"{"type": "typing", "target": {"type": "room", "room_id": "123e4567-e89b-12d3-a456-426614174000"}}"#);
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_unsigned_message_rejected_once_key_pinned() {
+        let mut conn = test_connection().await;
+        let signing_key = SigningKey::generate(&mut OsRng);
+        conn.signing_key = Some(signing_key.verifying_key().to_bytes());
+
+        let err = conn
+            .parse_message(
+                r#"{"type": "typing", "target": {"type": "room", "room_id": "123e4567-e89b-12d3-a456-426614174000"}}"#,
+            )
+            .unwrap_err();
+        assert!(matches!(err, ProtocolError::SignatureInvalid(_)));
+    }
+
+    #[tokio::test]
+    async fn test_signed_message_accepted_once_key_pinned() {
+        let mut conn = test_connection().await;
+        let signing_key = SigningKey::generate(&mut OsRng);
+        conn.signing_key = Some(signing_key.verifying_key().to_bytes());
+
+        let result = conn.parse_message(&signed_json(&signing_key));
+        assert!(result.is_ok());
+    }
+}
@@ -30,6 +30,7 @@ pub mod commands;
 pub mod connection;
 pub mod protocol;
 pub mod server;
+pub mod signing;
 
 pub use server::TcpServer;
 
@@ -14,7 +14,7 @@
 use serde::{Deserialize, Serialize};
 use tokio::io::{AsyncReadExt, AsyncWriteExt};
 
-use crate::crypto::{Cipher, NONCE_SIZE};
+use crate::crypto::{Cipher, NONCE_SIZE, TAG_SIZE};
 use crate::domain::{
     Invitation, Message, MessageTarget, Room, RoomMembership, RoomSettings, Session, User,
 };
@@ -22,6 +22,18 @@ use crate::domain::{
 /// Maximum message size (1 MB).
 pub const MAX_MESSAGE_SIZE: u32 = 1_048_576;
 
+/// Maximum total size of a chunked attachment transfer (100 MB). Checked
+/// against `AttachmentBegin.total_size` before allocating any reassembly
+/// buffer, since that field is client-declared and otherwise unbounded.
+pub const MAX_ATTACHMENT_SIZE: u64 = 100 * 1024 * 1024;
+
+/// Maximum number of chunks a single attachment transfer may declare.
+/// Bounds the reassembly buffer allocated per transfer independently of
+/// `MAX_ATTACHMENT_SIZE`, since `AttachmentBegin.total_chunks` is also
+/// client-declared and a small `total_size` with a huge `total_chunks`
+/// would otherwise still allocate an oversized `Vec`.
+pub const MAX_ATTACHMENT_CHUNKS: u32 = 10_000;
+
 /// Protocol version.
 pub const PROTOCOL_VERSION: &str = "1.0";
 
@@ -219,6 +231,155 @@ pub async fn write_encrypted_message<W: AsyncWriteExt + Unpin>(
     Ok(())
 }
 
+// ============================================================================
+// Length-Hiding Frame Reading/Writing
+// ============================================================================
+
+/// Plaintext layout sealed inside a length-hiding frame's header: the
+/// true (unpadded) body length, followed by the wire (possibly padded)
+/// body length, both big-endian `u32`.
+const HEADER_PLAINTEXT_LEN: usize = 8;
+
+/// Size of a sealed header: a random nonce, the 8-byte plaintext above,
+/// and the AEAD tag. Fixed-size and indistinguishable from random bytes,
+/// so unlike [`read_message`]'s 4-byte cleartext length prefix it reveals
+/// nothing about the frame that follows.
+pub const SEALED_HEADER_LEN: usize = NONCE_SIZE + HEADER_PLAINTEXT_LEN + TAG_SIZE;
+
+/// Seal `(true_len, wire_len)` into a fixed-size header under
+/// `header_cipher`, so an observer on the wire can't read frame lengths
+/// in cleartext the way they can with [`read_message`]'s 4-byte prefix.
+fn seal_header(
+    header_cipher: &Cipher,
+    true_len: u32,
+    wire_len: u32,
+) -> Result<Vec<u8>, ProtocolError> {
+    let mut plaintext = [0u8; HEADER_PLAINTEXT_LEN];
+    plaintext[0..4].copy_from_slice(&true_len.to_be_bytes());
+    plaintext[4..8].copy_from_slice(&wire_len.to_be_bytes());
+
+    let (nonce, ciphertext) = header_cipher
+        .encrypt(&plaintext)
+        .map_err(|e| ProtocolError::HeaderSealFailed(e.to_string()))?;
+
+    let mut sealed = Vec::with_capacity(SEALED_HEADER_LEN);
+    sealed.extend_from_slice(&nonce);
+    sealed.extend_from_slice(&ciphertext);
+    Ok(sealed)
+}
+
+/// Open a sealed header, recovering `(true_len, wire_len)`. A failing AEAD
+/// tag means the header isn't trustworthy, not just malformed, so it's
+/// reported as [`ProtocolError::DecryptionFailed`] the same as a failing
+/// payload tag.
+fn open_header(header_cipher: &Cipher, sealed: &[u8]) -> Result<(u32, u32), ProtocolError> {
+    let (nonce, ciphertext) = sealed.split_at(NONCE_SIZE);
+    let plaintext = header_cipher
+        .decrypt(nonce, ciphertext)
+        .map_err(|e| ProtocolError::DecryptionFailed(e.to_string()))?;
+
+    let true_len = u32::from_be_bytes(plaintext[0..4].try_into().unwrap());
+    let wire_len = u32::from_be_bytes(plaintext[4..8].try_into().unwrap());
+    Ok((true_len, wire_len))
+}
+
+/// Read a length-hiding message: a sealed header (hiding the body length)
+/// followed by an encrypted body, itself `nonce || ciphertext+tag` under
+/// `payload_cipher` just like [`read_encrypted_message`]'s frame. The body
+/// may be padded with trailing zero bytes up to `wire_len`; those are
+/// stripped before decryption using the `true_len` recovered from the
+/// header.
+pub async fn read_length_hidden_message<R: AsyncReadExt + Unpin>(
+    reader: &mut R,
+    header_cipher: &Cipher,
+    payload_cipher: &Cipher,
+) -> Result<String, ProtocolError> {
+    let mut sealed_header = [0u8; SEALED_HEADER_LEN];
+    reader.read_exact(&mut sealed_header).await.map_err(|e| {
+        if e.kind() == std::io::ErrorKind::UnexpectedEof {
+            ProtocolError::ConnectionClosed
+        } else {
+            ProtocolError::Io(e)
+        }
+    })?;
+
+    let (true_len, wire_len) = open_header(header_cipher, &sealed_header)?;
+
+    if wire_len > MAX_MESSAGE_SIZE || true_len > wire_len {
+        return Err(ProtocolError::InvalidHeader);
+    }
+    if (true_len as usize) < MIN_ENCRYPTED_SIZE {
+        return Err(ProtocolError::EncryptedMessageTooSmall);
+    }
+
+    let mut wire_body = vec![0u8; wire_len as usize];
+    reader.read_exact(&mut wire_body).await.map_err(|e| {
+        if e.kind() == std::io::ErrorKind::UnexpectedEof {
+            ProtocolError::ConnectionClosed
+        } else {
+            ProtocolError::Io(e)
+        }
+    })?;
+    wire_body.truncate(true_len as usize);
+
+    let (nonce, ciphertext) = wire_body.split_at(NONCE_SIZE);
+    let plaintext = payload_cipher
+        .decrypt(nonce, ciphertext)
+        .map_err(|e| ProtocolError::DecryptionFailed(e.to_string()))?;
+
+    String::from_utf8(plaintext).map_err(|_| ProtocolError::InvalidUtf8)
+}
+
+/// Write a length-hiding message. `pad_to`, if set, rounds the encrypted
+/// body up to the next multiple of that many bytes with trailing zeros
+/// before writing, so frame sizes cluster into buckets instead of
+/// revealing the exact payload length; the true length still travels
+/// (sealed) inside the header.
+pub async fn write_length_hidden_message<W: AsyncWriteExt + Unpin>(
+    writer: &mut W,
+    payload: &str,
+    header_cipher: &Cipher,
+    payload_cipher: &Cipher,
+    pad_to: Option<usize>,
+) -> Result<(), ProtocolError> {
+    let (nonce, ciphertext) = payload_cipher
+        .encrypt(payload.as_bytes())
+        .map_err(|e| ProtocolError::EncryptionFailed(e.to_string()))?;
+
+    let mut wire_body = Vec::with_capacity(NONCE_SIZE + ciphertext.len());
+    wire_body.extend_from_slice(&nonce);
+    wire_body.extend_from_slice(&ciphertext);
+
+    let true_len = wire_body.len();
+    let wire_len = match pad_to {
+        Some(bucket) if bucket > 0 => true_len.div_ceil(bucket) * bucket,
+        _ => true_len,
+    };
+
+    if wire_len > MAX_MESSAGE_SIZE as usize {
+        return Err(ProtocolError::MessageTooLarge {
+            size: wire_len as u32,
+            max: MAX_MESSAGE_SIZE,
+        });
+    }
+
+    wire_body.resize(wire_len, 0);
+
+    let sealed_header = seal_header(header_cipher, true_len as u32, wire_len as u32)?;
+
+    writer
+        .write_all(&sealed_header)
+        .await
+        .map_err(ProtocolError::Io)?;
+    writer
+        .write_all(&wire_body)
+        .await
+        .map_err(ProtocolError::Io)?;
+    writer.flush().await.map_err(ProtocolError::Io)?;
+
+    Ok(())
+}
+
 // ============================================================================
 // Protocol Errors
 // ============================================================================
@@ -250,6 +411,14 @@ pub enum ProtocolError {
     DecryptionFailed(String),
     /// Key exchange failed.
     KeyExchangeFailed(String),
+    /// A `SignedMessage` envelope's signature didn't verify.
+    SignatureInvalid(String),
+    /// Sealing a length-hiding frame's header failed.
+    HeaderSealFailed(String),
+    /// A length-hiding frame's header decrypted to an inconsistent
+    /// `(true_len, wire_len)` pair (e.g. `true_len > wire_len`) or a
+    /// `wire_len` exceeding [`MAX_MESSAGE_SIZE`].
+    InvalidHeader,
 }
 
 impl std::fmt::Display for ProtocolError {
@@ -271,6 +440,9 @@ impl std::fmt::Display for ProtocolError {
             Self::EncryptionFailed(e) => write!(f, "Encryption failed: {}", e),
             Self::DecryptionFailed(e) => write!(f, "Decryption failed: {}", e),
             Self::KeyExchangeFailed(e) => write!(f, "Key exchange failed: {}", e),
+            Self::SignatureInvalid(e) => write!(f, "Signature verification failed: {}", e),
+            Self::HeaderSealFailed(e) => write!(f, "Failed to seal frame header: {}", e),
+            Self::InvalidHeader => write!(f, "Length-hiding frame header is inconsistent"),
         }
     }
 }
@@ -291,6 +463,10 @@ pub enum ClientMessage {
         client_name: Option<String>,
         #[serde(default)]
         features: Vec<String>,
+        /// Base64-encoded long-term ed25519 public key this connection will
+        /// sign subsequent messages with, if message signing is enabled.
+        #[serde(default)]
+        signing_public_key: Option<String>,
     },
 
     // Authentication
@@ -318,7 +494,7 @@ pub enum ClientMessage {
     EditMessage {
         request_id: Option<String>,
         message_id: String,
-        content: String,
+        new_content: String,
     },
     DeleteMessage {
         request_id: Option<String>,
@@ -361,6 +537,16 @@ pub enum ClientMessage {
         request_id: Option<String>,
         room_id: String,
     },
+    /// Supersede `room_id` with a freshly created successor room, for
+    /// settings changes that can't be applied in place. The server
+    /// auto-migrates membership to the successor and broadcasts
+    /// `ServerMessage::RoomTombstone` to the old room.
+    UpgradeRoom {
+        request_id: Option<String>,
+        room_id: String,
+        #[serde(default)]
+        reason: Option<String>,
+    },
 
     // Invitations
     InviteToRoom {
@@ -403,6 +589,41 @@ pub enum ClientMessage {
     Typing {
         target: MessageTarget,
     },
+    MarkDelivered {
+        target: MessageTarget,
+        up_to_message_id: String,
+    },
+    MarkRead {
+        target: MessageTarget,
+        up_to_message_id: String,
+    },
+
+    // Attachments. A file/image larger than one frame is split client-side
+    // into `AttachmentBegin` + a run of `AttachmentChunk`s + a trailing
+    // `AttachmentEnd`. The server reassembles and verifies the checksum
+    // before turning the upload into a visible message; raw bytes are
+    // never persisted.
+    AttachmentBegin {
+        target: MessageTarget,
+        transfer_id: String,
+        file_name: String,
+        mime_type: String,
+        total_size: u64,
+        total_chunks: u32,
+        #[serde(default)]
+        thumbnail: Option<String>,
+    },
+    AttachmentChunk {
+        transfer_id: String,
+        index: u32,
+        /// Base64-encoded chunk bytes.
+        data: String,
+    },
+    AttachmentEnd {
+        transfer_id: String,
+        /// Hex-encoded SHA-256 of the full reassembled file.
+        sha256: String,
+    },
 
     // Keepalive
     Ping,
@@ -578,6 +799,14 @@ pub enum ServerMessage {
         #[serde(skip_serializing_if = "Option::is_none")]
         error: Option<ErrorInfo>,
     },
+    UpgradeRoomResponse {
+        request_id: Option<String>,
+        success: bool,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        room: Option<Room>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        error: Option<ErrorInfo>,
+    },
 
     // Invitation responses
     InviteToRoomResponse {
@@ -649,6 +878,21 @@ pub enum ServerMessage {
     // Key Exchange Response
     KeyExchangeResponse {
         public_key: String,
+        /// Base64-encoded ed25519 public key identifying this server.
+        identity_key: String,
+        /// Base64-encoded ed25519 signature over the handshake transcript
+        /// (SHA-256 of the `ServerHello`, the `ClientHello`, and the client's
+        /// and server's ephemeral public keys),
+        /// proving `identity_key` actually generated `public_key`.
+        signature: String,
+        /// Whether the server also enabled length-hiding framing for this
+        /// connection, i.e. the client requested the `length_hiding`
+        /// feature and the server honored it. Frames after this message
+        /// use [`write_length_hidden_message`]/[`read_length_hidden_message`]
+        /// instead of [`write_encrypted_message`]/[`read_encrypted_message`]
+        /// when true.
+        #[serde(default)]
+        length_hiding: bool,
     },
 
     // Events (server-initiated)
@@ -665,6 +909,16 @@ pub enum ServerMessage {
         target: MessageTarget,
         deleted_by: String,
     },
+    /// Every chunk of an attachment transfer arrived and passed checksum
+    /// verification; `message_id` is the now-visible message carrying it.
+    AttachmentReceived {
+        transfer_id: String,
+        message_id: String,
+        target: MessageTarget,
+        file_name: String,
+        mime_type: String,
+        size: u64,
+    },
     UserJoinedRoom {
         room_id: String,
         user: User,
@@ -684,6 +938,15 @@ pub enum ServerMessage {
         room_name: String,
         deleted_by: String,
     },
+    /// `room_id` has been superseded by `replacement_room_id`; clients treat
+    /// it as frozen (read-only) and offer to join the replacement. The
+    /// server does not itself reject joins or messages to the old room.
+    RoomTombstone {
+        room_id: String,
+        replacement_room_id: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        reason: Option<String>,
+    },
     UserOnline {
         user_id: String,
         username: String,
@@ -696,6 +959,14 @@ pub enum ServerMessage {
         user_id: String,
         target: MessageTarget,
     },
+    MarkerUpdate {
+        user_id: String,
+        username: String,
+        target: MessageTarget,
+        marker_kind: String,
+        #[serde(rename = "message_id")]
+        up_to_message_id: String,
+    },
     InvitationReceived {
         invitation: Invitation,
     },
@@ -703,6 +974,12 @@ pub enum ServerMessage {
         message: String,
         severity: String,
     },
+    /// This connection's session was revoked (e.g. a remote logout from
+    /// another device). The client should treat this as an immediate
+    /// logout and close the connection.
+    SessionRevoked {
+        session_id: String,
+    },
 
     // Error
     Error {
@@ -777,7 +1054,7 @@ impl ServerMessage {
         Self::ServerHello {
             version: PROTOCOL_VERSION.to_string(),
             server_name: SERVER_NAME.to_string(),
-            features: vec!["encryption".to_string()],
+            features: vec!["encryption".to_string(), "length_hiding".to_string()],
             encryption_required: false,
         }
     }
@@ -868,6 +1145,16 @@ mod tests {
         assert!(json.contains("not_found"));
     }
 
+    #[test]
+    fn test_serialize_session_revoked() {
+        let msg = ServerMessage::SessionRevoked {
+            session_id: "abc-123".to_string(),
+        };
+        let json = msg.to_json().unwrap();
+        assert!(json.contains("session_revoked"));
+        assert!(json.contains("abc-123"));
+    }
+
     #[tokio::test]
     async fn test_read_write_message() {
         use tokio::io::duplex;
@@ -0,0 +1,167 @@
+//! Verification of detached ed25519 signatures over a canonical JSON
+//! encoding of a [`ClientMessage`], mirroring
+//! `lair-chat-client`'s `protocol::signing` module.
+//!
+//! Transport encryption only protects the link to whatever terminates it,
+//! which may be a relay rather than this server. A signature over the
+//! message content lets us detect tampering regardless of where
+//! encryption ends. We verify against the raw JSON `message` value rather
+//! than re-serializing it through our own [`ClientMessage`], so
+//! verification doesn't depend on our copy of the type matching the
+//! client's byte-for-byte.
+
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use ed25519_dalek::{Signature, Verifier, VerifyingKey};
+use serde::Deserialize;
+use serde_json::Value;
+
+use super::protocol::ClientMessage;
+
+/// Errors from verifying a [`SignedMessage`].
+#[derive(Debug, thiserror::Error)]
+pub enum SigningError {
+    #[error("failed to parse canonicalized message: {0}")]
+    Canonicalization(#[from] serde_json::Error),
+
+    #[error("invalid base64 in {0}: {1}")]
+    InvalidBase64(&'static str, base64::DecodeError),
+
+    #[error("public key must be 32 bytes, got {0}")]
+    InvalidPublicKeyLength(usize),
+
+    #[error("signature must be 64 bytes, got {0}")]
+    InvalidSignatureLength(usize),
+
+    #[error("invalid public key: {0}")]
+    InvalidPublicKey(ed25519_dalek::SignatureError),
+
+    #[error("signature verification failed")]
+    VerificationFailed,
+}
+
+/// A [`ClientMessage`] paired with a detached signature over its canonical
+/// JSON encoding, plus the base64-encoded public key to verify it with.
+/// `message` is kept as a raw [`Value`] until verification succeeds, so
+/// the signed bytes are exactly what the client signed regardless of how
+/// our own `ClientMessage` deserializes it.
+#[derive(Debug, Clone, Deserialize)]
+pub struct SignedMessage {
+    pub message: Value,
+    /// Base64-encoded ed25519 public key, as advertised in `ClientHello`.
+    pub public_key: String,
+    /// Base64-encoded ed25519 signature over `canonical_json(&message)`.
+    pub signature: String,
+}
+
+/// Serialize `value` to a canonical JSON byte string: object keys sorted
+/// by UTF-8 byte order at every nesting level, no insignificant
+/// whitespace. Must match the client's own `canonical_json` exactly.
+pub(crate) fn canonicalize(value: &Value) -> String {
+    match value {
+        Value::Object(map) => {
+            let mut entries: Vec<(&String, &Value)> = map.iter().collect();
+            entries.sort_by(|(a, _), (b, _)| a.as_bytes().cmp(b.as_bytes()));
+            let body: Vec<String> = entries
+                .into_iter()
+                .map(|(k, v)| format!("{}:{}", serde_json::to_string(k).unwrap(), canonicalize(v)))
+                .collect();
+            format!("{{{}}}", body.join(","))
+        }
+        Value::Array(items) => {
+            let body: Vec<String> = items.iter().map(canonicalize).collect();
+            format!("[{}]", body.join(","))
+        }
+        leaf => serde_json::to_string(leaf).unwrap(),
+    }
+}
+
+impl SignedMessage {
+    /// Decode [`Self::public_key`] into raw bytes.
+    pub fn public_key_bytes(&self) -> Result<[u8; 32], SigningError> {
+        let bytes = BASE64
+            .decode(&self.public_key)
+            .map_err(|e| SigningError::InvalidBase64("public_key", e))?;
+        bytes
+            .try_into()
+            .map_err(|v: Vec<u8>| SigningError::InvalidPublicKeyLength(v.len()))
+    }
+}
+
+/// Verify `signed`'s signature against its own advertised public key, and
+/// return the parsed [`ClientMessage`] on success.
+pub fn verify_message(signed: &SignedMessage) -> Result<ClientMessage, SigningError> {
+    let key_bytes = signed.public_key_bytes()?;
+    let verifying_key =
+        VerifyingKey::from_bytes(&key_bytes).map_err(SigningError::InvalidPublicKey)?;
+
+    let sig_bytes = BASE64
+        .decode(&signed.signature)
+        .map_err(|e| SigningError::InvalidBase64("signature", e))?;
+    let sig_bytes: [u8; 64] = sig_bytes
+        .try_into()
+        .map_err(|v: Vec<u8>| SigningError::InvalidSignatureLength(v.len()))?;
+    let signature = Signature::from_bytes(&sig_bytes);
+
+    let bytes = canonicalize(&signed.message).into_bytes();
+    verifying_key
+        .verify(&bytes, &signature)
+        .map_err(|_| SigningError::VerificationFailed)?;
+
+    Ok(serde_json::from_value(signed.message.clone())?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use base64::prelude::*;
+    use ed25519_dalek::{Signer, SigningKey};
+    use rand::rngs::OsRng;
+
+    fn sample_message_json() -> Value {
+        serde_json::json!({
+            "type": "send_message",
+            "request_id": null,
+            "target": {"type": "room", "room_id": "123e4567-e89b-12d3-a456-426614174000"},
+            "content": "hello",
+        })
+    }
+
+    fn sign(message: Value, signing_key: &SigningKey) -> SignedMessage {
+        let bytes = canonicalize(&message).into_bytes();
+        let signature = signing_key.sign(&bytes);
+        SignedMessage {
+            message,
+            public_key: BASE64_STANDARD.encode(signing_key.verifying_key().to_bytes()),
+            signature: BASE64_STANDARD.encode(signature.to_bytes()),
+        }
+    }
+
+    #[test]
+    fn test_verify_message_round_trip() {
+        let signing_key = SigningKey::generate(&mut OsRng);
+        let signed = sign(sample_message_json(), &signing_key);
+        let message = verify_message(&signed).unwrap();
+        assert!(matches!(message, ClientMessage::SendMessage { .. }));
+    }
+
+    #[test]
+    fn test_verify_message_rejects_tampered_content() {
+        let signing_key = SigningKey::generate(&mut OsRng);
+        let mut signed = sign(sample_message_json(), &signing_key);
+        signed.message["content"] = serde_json::json!("tampered");
+
+        let err = verify_message(&signed).unwrap_err();
+        assert!(matches!(err, SigningError::VerificationFailed));
+    }
+
+    #[test]
+    fn test_verify_message_rejects_wrong_key() {
+        let signing_key = SigningKey::generate(&mut OsRng);
+        let other_key = SigningKey::generate(&mut OsRng);
+        let mut signed = sign(sample_message_json(), &signing_key);
+        signed.public_key = BASE64_STANDARD.encode(other_key.verifying_key().to_bytes());
+
+        let err = verify_message(&signed).unwrap_err();
+        assert!(matches!(err, SigningError::VerificationFailed));
+    }
+}
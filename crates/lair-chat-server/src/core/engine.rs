@@ -11,8 +11,9 @@
 use std::sync::Arc;
 
 use crate::domain::{
+    events::{Event, EventPayload, SessionRevokedEvent},
     EnrichedInvitation, Invitation, InvitationId, Message, MessageId, MessageTarget, Pagination,
-    Role, Room, RoomId, RoomMembership, RoomSettings, Session, SessionId, User, UserId,
+    Role, Room, RoomId, RoomMembership, RoomSettings, Session, SessionId, TransferId, User, UserId,
 };
 use crate::storage::{RoomRepository, Storage, UserRepository};
 use crate::Result;
@@ -118,6 +119,24 @@ impl<S: Storage + 'static> ChatEngine<S> {
         self.auth.register(username, email, password).await
     }
 
+    /// Register a new user account, recording device/client identification
+    /// against the resulting session.
+    pub async fn register_with_device(
+        &self,
+        username: &str,
+        email: &str,
+        password: &str,
+        ip_address: Option<String>,
+        device_name: Option<String>,
+    ) -> Result<(User, Session, String)> {
+        let (user, session, token) = self.register(username, email, password).await?;
+        let session = self
+            .sessions
+            .set_device_info(session.id, ip_address, device_name)
+            .await?;
+        Ok((user, session, token))
+    }
+
     /// Authenticate a user and create a session.
     ///
     /// The identifier can be either a username or email.
@@ -125,6 +144,23 @@ impl<S: Storage + 'static> ChatEngine<S> {
         self.auth.login(identifier, password).await
     }
 
+    /// Authenticate a user and create a session, recording device/client
+    /// identification against the resulting session.
+    pub async fn login_with_device(
+        &self,
+        identifier: &str,
+        password: &str,
+        ip_address: Option<String>,
+        device_name: Option<String>,
+    ) -> Result<(User, Session, String)> {
+        let (user, session, token) = self.login(identifier, password).await?;
+        let session = self
+            .sessions
+            .set_device_info(session.id, ip_address, device_name)
+            .await?;
+        Ok((user, session, token))
+    }
+
     /// End a session.
     pub async fn logout(&self, session_id: SessionId) -> Result<()> {
         self.sessions.logout(session_id).await
@@ -161,6 +197,37 @@ impl<S: Storage + 'static> ChatEngine<S> {
         self.auth.jwt_service()
     }
 
+    /// List all active sessions (devices) for the currently authenticated user.
+    pub async fn list_sessions(&self, session_id: SessionId) -> Result<Vec<Session>> {
+        let (_, user) = self.sessions.validate(session_id).await?;
+        self.sessions.list_for_user(user.id).await
+    }
+
+    /// Revoke one of the current user's sessions (e.g. logging out a lost
+    /// device remotely). The target session must belong to the same user as
+    /// `session_id`.
+    pub async fn revoke_session(
+        &self,
+        session_id: SessionId,
+        target_session_id: SessionId,
+    ) -> Result<()> {
+        let (_, user) = self.sessions.validate(session_id).await?;
+        let (target_session, _) = self.sessions.validate(target_session_id).await?;
+
+        if target_session.user_id != user.id {
+            return Err(crate::Error::PermissionDenied);
+        }
+
+        self.sessions.revoke(target_session_id).await?;
+        self.events
+            .dispatch(Event::new(EventPayload::SessionRevoked(
+                SessionRevokedEvent::new(target_session_id),
+            )))
+            .await;
+
+        Ok(())
+    }
+
     // ========================================================================
     // User Operations
     // ========================================================================
@@ -274,6 +341,17 @@ impl<S: Storage + 'static> ChatEngine<S> {
         self.rooms.get_members(room_id).await
     }
 
+    /// Supersede a room with a freshly created successor.
+    pub async fn upgrade_room(
+        &self,
+        session_id: SessionId,
+        room_id: RoomId,
+        reason: Option<String>,
+    ) -> Result<Room> {
+        let (_, user) = self.sessions.validate(session_id).await?;
+        self.rooms.upgrade(user.id, room_id, reason).await
+    }
+
     // ========================================================================
     // Messaging Operations
     // ========================================================================
@@ -306,6 +384,23 @@ impl<S: Storage + 'static> ChatEngine<S> {
         self.messaging.delete(user.id, message_id).await
     }
 
+    /// Finalize a chunked attachment upload into a visible message.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn send_attachment(
+        &self,
+        session_id: SessionId,
+        target: MessageTarget,
+        transfer_id: TransferId,
+        file_name: &str,
+        mime_type: &str,
+        size: u64,
+    ) -> Result<Message> {
+        let (_, user) = self.sessions.validate(session_id).await?;
+        self.messaging
+            .send_attachment(user.id, target, transfer_id, file_name, mime_type, size)
+            .await
+    }
+
     /// Get messages for a target (room or DM).
     pub async fn get_messages(
         &self,
@@ -319,6 +414,30 @@ impl<S: Storage + 'static> ChatEngine<S> {
             .await
     }
 
+    /// Mark messages in a target as delivered up to and including `message_id`.
+    pub async fn mark_delivered(
+        &self,
+        session_id: SessionId,
+        target: MessageTarget,
+        message_id: MessageId,
+    ) -> Result<()> {
+        let (_, user) = self.sessions.validate(session_id).await?;
+        self.messaging
+            .mark_delivered(user.id, target, message_id)
+            .await
+    }
+
+    /// Mark messages in a target as read up to and including `message_id`.
+    pub async fn mark_read(
+        &self,
+        session_id: SessionId,
+        target: MessageTarget,
+        message_id: MessageId,
+    ) -> Result<()> {
+        let (_, user) = self.sessions.validate(session_id).await?;
+        self.messaging.mark_read(user.id, target, message_id).await
+    }
+
     // ========================================================================
     // Invitation Operations
     // ========================================================================
@@ -15,7 +15,7 @@ use crate::domain::{
     events::{
         Event, EventPayload, EventTarget, UserOfflineEvent, UserOnlineEvent, UserTypingEvent,
     },
-    MessageTarget, UserId,
+    MessageTarget, SessionId, UserId,
 };
 
 // ============================================================================
@@ -159,10 +159,13 @@ impl Default for EventDispatcher {
 /// Check if a user should receive an event.
 ///
 /// This is used by protocol adapters to filter events for their connections.
+/// `session_id` is the connection's own session, used to match
+/// session-targeted events (e.g. [`crate::domain::events::SessionRevokedEvent`]).
 pub fn should_receive_event(
     event: &Event,
     user_id: UserId,
     user_rooms: &[crate::domain::RoomId],
+    session_id: Option<SessionId>,
 ) -> bool {
     match event.target() {
         EventTarget::User(target_user) => target_user == user_id,
@@ -174,10 +177,7 @@ pub fn should_receive_event(
             // For now, broadcast to everyone (will be filtered by client interest)
             true
         }
-        EventTarget::Session(_session_id) => {
-            // Session-specific events are handled separately
-            false
-        }
+        EventTarget::Session(target_session) => session_id == Some(target_session),
         EventTarget::Broadcast => true,
     }
 }
@@ -244,4 +244,24 @@ mod tests {
         let received = receiver.try_recv().unwrap();
         assert_eq!(received.id, event.id);
     }
+
+    #[test]
+    fn test_should_receive_event_session_match() {
+        use crate::domain::events::SessionRevokedEvent;
+
+        let user_id = UserId::new();
+        let session_id = SessionId::new();
+        let event = Event::new(EventPayload::SessionRevoked(SessionRevokedEvent::new(
+            session_id,
+        )));
+
+        assert!(should_receive_event(&event, user_id, &[], Some(session_id)));
+        assert!(!should_receive_event(
+            &event,
+            user_id,
+            &[],
+            Some(SessionId::new())
+        ));
+        assert!(!should_receive_event(&event, user_id, &[], None));
+    }
 }
@@ -9,10 +9,16 @@
 use std::sync::Arc;
 
 use crate::domain::{
-    events::{Event, EventPayload, MessageDeletedEvent, MessageEditedEvent, MessageReceivedEvent},
-    Message, MessageContent, MessageId, MessageTarget, Pagination, RoomId, UserId,
+    events::{
+        AttachmentReceivedEvent, Event, EventPayload, MarkerUpdateEvent, MessageDeletedEvent,
+        MessageEditedEvent, MessageReceivedEvent,
+    },
+    MarkerKind, Message, MessageContent, MessageId, MessageTarget, Pagination, RoomId, TransferId,
+    UserId,
+};
+use crate::storage::{
+    MarkerRepository, MembershipRepository, MessageRepository, Storage, UserRepository,
 };
-use crate::storage::{MembershipRepository, MessageRepository, Storage, UserRepository};
 use crate::{Error, Result};
 
 use super::events::EventDispatcher;
@@ -84,6 +90,44 @@ impl<S: Storage + 'static> MessagingService<S> {
         Ok(message)
     }
 
+    /// Finalize a chunked attachment upload into a visible message.
+    ///
+    /// Called once every chunk has arrived and passed checksum
+    /// verification. Raw bytes are never persisted; `file_name` becomes
+    /// the message's (placeholder) content, and `mime_type`/`size` travel
+    /// only on the `AttachmentReceived` event for rich rendering.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`send`](Self::send).
+    #[allow(clippy::too_many_arguments)]
+    pub async fn send_attachment(
+        &self,
+        author_id: UserId,
+        target: MessageTarget,
+        transfer_id: TransferId,
+        file_name: &str,
+        mime_type: &str,
+        size: u64,
+    ) -> Result<Message> {
+        let message = self.send(author_id, target.clone(), file_name).await?;
+
+        let event = Event::new(EventPayload::AttachmentReceived(
+            AttachmentReceivedEvent::new(
+                transfer_id,
+                message.id,
+                target,
+                author_id,
+                file_name,
+                mime_type,
+                size,
+            ),
+        ));
+        self.events.dispatch(event).await;
+
+        Ok(message)
+    }
+
     /// Edit a message.
     ///
     /// Only the message author can edit their messages.
@@ -248,6 +292,87 @@ impl<S: Storage + 'static> MessagingService<S> {
     ) -> Result<Vec<Message>> {
         MessageRepository::find_direct_messages(&*self.storage, user1, user2, pagination).await
     }
+
+    /// Record that `user_id` has a delivery/read marker up to `message_id` for `target`.
+    ///
+    /// Shared by [`mark_delivered`](Self::mark_delivered) and
+    /// [`mark_read`](Self::mark_read); the only difference between the two
+    /// is the [`MarkerKind`] stored and broadcast.
+    ///
+    /// # Errors
+    ///
+    /// - `RoomNotFound` / `NotRoomMember` - User is not a member of the target room
+    /// - `UserNotFound` - DM recipient doesn't exist
+    async fn mark(
+        &self,
+        user_id: UserId,
+        target: MessageTarget,
+        kind: MarkerKind,
+        message_id: MessageId,
+    ) -> Result<()> {
+        match &target {
+            MessageTarget::Room { room_id } => {
+                if !MembershipRepository::is_member(&*self.storage, *room_id, user_id).await? {
+                    return Err(Error::NotRoomMember);
+                }
+            }
+            MessageTarget::DirectMessage { recipient } => {
+                if UserRepository::find_by_id(&*self.storage, *recipient)
+                    .await?
+                    .is_none()
+                {
+                    return Err(Error::UserNotFound);
+                }
+            }
+        }
+
+        MarkerRepository::set_marker(&*self.storage, user_id, &target, kind, message_id).await?;
+
+        let username = UserRepository::find_by_id(&*self.storage, user_id)
+            .await?
+            .ok_or(Error::UserNotFound)?
+            .username
+            .to_string();
+
+        let event = Event::new(EventPayload::MarkerUpdate(MarkerUpdateEvent::new(
+            target, user_id, username, kind, message_id,
+        )));
+        self.events.dispatch(event).await;
+
+        Ok(())
+    }
+
+    /// Mark messages in `target` as delivered up to and including `message_id`.
+    ///
+    /// # Errors
+    ///
+    /// - `RoomNotFound` / `NotRoomMember` - User is not a member of the target room
+    /// - `UserNotFound` - DM recipient doesn't exist
+    pub async fn mark_delivered(
+        &self,
+        user_id: UserId,
+        target: MessageTarget,
+        message_id: MessageId,
+    ) -> Result<()> {
+        self.mark(user_id, target, MarkerKind::Delivered, message_id)
+            .await
+    }
+
+    /// Mark messages in `target` as read up to and including `message_id`.
+    ///
+    /// # Errors
+    ///
+    /// - `RoomNotFound` / `NotRoomMember` - User is not a member of the target room
+    /// - `UserNotFound` - DM recipient doesn't exist
+    pub async fn mark_read(
+        &self,
+        user_id: UserId,
+        target: MessageTarget,
+        message_id: MessageId,
+    ) -> Result<()> {
+        self.mark(user_id, target, MarkerKind::Read, message_id)
+            .await
+    }
 }
 
 // ============================================================================
@@ -333,6 +458,31 @@ mod tests {
         assert_eq!(message.content.as_str(), "Hello DM!");
     }
 
+    #[tokio::test]
+    async fn test_send_attachment_success() {
+        let storage = create_test_storage().await;
+        let user = create_test_user(&storage, "uploader", "uploader@test.com").await;
+        let room = create_test_room(&storage, user.id, "attachroom").await;
+        let service = create_messaging_service(storage);
+
+        let target = MessageTarget::Room { room_id: room.id };
+        let result = service
+            .send_attachment(
+                user.id,
+                target,
+                TransferId::new(),
+                "photo.png",
+                "image/png",
+                1024,
+            )
+            .await;
+
+        assert!(result.is_ok());
+        let message = result.unwrap();
+        assert_eq!(message.content.as_str(), "photo.png");
+        assert_eq!(message.author, user.id);
+    }
+
     #[tokio::test]
     async fn test_send_message_empty_content() {
         let storage = create_test_storage().await;
@@ -10,8 +10,8 @@ use std::sync::Arc;
 
 use crate::domain::{
     events::{
-        Event, EventPayload, InvitationReceivedEvent, RoomDeletedEvent, UserJoinedRoomEvent,
-        UserLeftRoomEvent,
+        Event, EventPayload, InvitationReceivedEvent, RoomDeletedEvent, RoomTombstoneEvent,
+        UserJoinedRoomEvent, UserLeftRoomEvent,
     },
     EnrichedInvitation, Invitation, InvitationId, Pagination, Room, RoomId, RoomMembership,
     RoomName, RoomSettings, User, UserId,
@@ -298,6 +298,86 @@ impl<S: Storage + 'static> RoomService<S> {
         Ok(())
     }
 
+    /// Supersede a room with a freshly created successor.
+    ///
+    /// For settings changes that can't be applied to an existing room in
+    /// place. Creates a new room owned by the same user with the same
+    /// settings, migrates every member across, then tombstones the
+    /// original (it stays around, frozen, so old links and history still
+    /// resolve).
+    ///
+    /// Only room owners and moderators can upgrade rooms.
+    ///
+    /// # Errors
+    ///
+    /// - `RoomNotFound` - Room doesn't exist
+    /// - `RoomAlreadyTombstoned` - Room was already upgraded
+    /// - `NotRoomMember` / `PermissionDenied` - User lacks moderator rights
+    pub async fn upgrade(
+        &self,
+        user_id: UserId,
+        room_id: RoomId,
+        reason: Option<String>,
+    ) -> Result<Room> {
+        // Get room
+        let mut room = RoomRepository::find_by_id(&*self.storage, room_id)
+            .await?
+            .ok_or(Error::RoomNotFound)?;
+
+        if room.is_tombstoned() {
+            return Err(Error::RoomAlreadyTombstoned);
+        }
+
+        // Check permissions
+        let membership = MembershipRepository::get_membership(&*self.storage, room_id, user_id)
+            .await?
+            .ok_or(Error::NotRoomMember)?;
+
+        if !membership.is_moderator() {
+            return Err(Error::PermissionDenied);
+        }
+
+        // Create the successor room under a free "<name>-vN" name
+        let successor_name = self.next_successor_name(room.name.as_str()).await?;
+        let successor = Room::new(successor_name, room.owner, room.settings.clone());
+        RoomRepository::create(&*self.storage, &successor).await?;
+
+        // Migrate membership across, preserving each member's role
+        let members = MembershipRepository::list_members(&*self.storage, room_id).await?;
+        for member in &members {
+            let migrated = RoomMembership::new(successor.id, member.user_id, member.role);
+            MembershipRepository::add_member(&*self.storage, &migrated).await?;
+        }
+
+        // Tombstone the original room
+        room.successor_room_id = Some(successor.id);
+        RoomRepository::update(&*self.storage, &room).await?;
+
+        // Emit event
+        let event = Event::new(EventPayload::RoomTombstone(RoomTombstoneEvent::new(
+            room_id,
+            successor.id,
+            reason,
+        )));
+        self.events.dispatch(event).await;
+
+        Ok(successor)
+    }
+
+    /// Find the first unused `<base>-vN` name, starting at `v2`.
+    async fn next_successor_name(&self, base: &str) -> Result<RoomName> {
+        let mut version = 2u32;
+        loop {
+            let candidate = format!("{base}-v{version}");
+            if !RoomRepository::name_exists(&*self.storage, &candidate).await? {
+                return RoomName::new(candidate).map_err(|e| Error::RoomNameInvalid {
+                    reason: e.to_string(),
+                });
+            }
+            version += 1;
+        }
+    }
+
     /// Get room members with user details.
     pub async fn get_members(&self, room_id: RoomId) -> Result<Vec<(User, RoomMembership)>> {
         MembershipRepository::list_members_with_users(&*self.storage, room_id).await
@@ -790,6 +870,70 @@ mod tests {
         assert!(matches!(result, Err(Error::PermissionDenied)));
     }
 
+    // ========================================================================
+    // Room Upgrade Tests
+    // ========================================================================
+
+    #[tokio::test]
+    async fn test_upgrade_room_success() {
+        let (service, storage) = create_test_service().await;
+        let alice_id = create_user(&storage, "alice", "alice@example.com").await;
+        let bob_id = create_user(&storage, "bob", "bob@example.com").await;
+
+        let room = service
+            .create(alice_id, "general", None, None)
+            .await
+            .unwrap();
+        let _ = service.join(bob_id, room.id).await.unwrap();
+
+        let successor = service
+            .upgrade(alice_id, room.id, Some("migrating settings".to_string()))
+            .await
+            .unwrap();
+
+        assert_eq!(successor.name.as_str(), "general-v2");
+        assert_eq!(successor.owner, alice_id);
+
+        // Old room is tombstoned, pointing at the successor
+        let old_room = service.get(room.id).await.unwrap().unwrap();
+        assert_eq!(old_room.successor_room_id, Some(successor.id));
+
+        // Membership migrated across
+        let members = service.get_members(successor.id).await.unwrap();
+        assert_eq!(members.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_upgrade_room_permission_denied() {
+        let (service, storage) = create_test_service().await;
+        let alice_id = create_user(&storage, "alice", "alice@example.com").await;
+        let bob_id = create_user(&storage, "bob", "bob@example.com").await;
+
+        let room = service
+            .create(alice_id, "general", None, None)
+            .await
+            .unwrap();
+        let _ = service.join(bob_id, room.id).await.unwrap();
+
+        let result = service.upgrade(bob_id, room.id, None).await;
+        assert!(matches!(result, Err(Error::PermissionDenied)));
+    }
+
+    #[tokio::test]
+    async fn test_upgrade_room_already_tombstoned() {
+        let (service, storage) = create_test_service().await;
+        let alice_id = create_user(&storage, "alice", "alice@example.com").await;
+
+        let room = service
+            .create(alice_id, "general", None, None)
+            .await
+            .unwrap();
+        let _ = service.upgrade(alice_id, room.id, None).await.unwrap();
+
+        let result = service.upgrade(alice_id, room.id, None).await;
+        assert!(matches!(result, Err(Error::RoomAlreadyTombstoned)));
+    }
+
     // ========================================================================
     // Invitation Tests
     // ========================================================================
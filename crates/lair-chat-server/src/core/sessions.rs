@@ -106,6 +106,31 @@ impl<S: Storage + 'static> SessionManager<S> {
         SessionRepository::list_by_user(&*self.storage, user_id).await
     }
 
+    /// Record device/client identification against a session (set once at
+    /// login, after the session has already been persisted).
+    pub async fn set_device_info(
+        &self,
+        session_id: SessionId,
+        ip_address: Option<String>,
+        user_agent: Option<String>,
+    ) -> Result<Session> {
+        let session = SessionRepository::find_by_id(&*self.storage, session_id)
+            .await?
+            .ok_or(Error::SessionNotFound)?
+            .with_device(ip_address, user_agent);
+
+        SessionRepository::update(&*self.storage, &session).await?;
+
+        Ok(session)
+    }
+
+    /// Revoke another one of a user's sessions (e.g. logging out a lost
+    /// device remotely). Unlike [`Self::logout`], the caller must verify the
+    /// session being revoked actually belongs to the requesting user.
+    pub async fn revoke(&self, session_id: SessionId) -> Result<()> {
+        self.logout(session_id).await
+    }
+
     /// Count active sessions for a user.
     pub async fn count_for_user(&self, user_id: UserId) -> Result<u32> {
         SessionRepository::count_by_user(&*self.storage, user_id).await
@@ -370,6 +395,63 @@ mod tests {
         assert!(sessions.is_empty());
     }
 
+    #[tokio::test]
+    async fn test_set_device_info_success() {
+        let storage = create_test_storage().await;
+        let user = create_test_user(&storage).await;
+        let session = create_test_session(&storage, user.id).await;
+        let manager = SessionManager::new(storage);
+
+        let updated = manager
+            .set_device_info(
+                session.id,
+                Some("127.0.0.1".to_string()),
+                Some("alice-laptop".to_string()),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(updated.ip_address, Some("127.0.0.1".to_string()));
+        assert_eq!(updated.user_agent, Some("alice-laptop".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_set_device_info_session_not_found() {
+        let storage = create_test_storage().await;
+        let manager = SessionManager::new(storage);
+
+        let result = manager
+            .set_device_info(SessionId::new(), None, Some("alice-laptop".to_string()))
+            .await;
+
+        assert!(matches!(result, Err(Error::SessionNotFound)));
+    }
+
+    #[tokio::test]
+    async fn test_revoke_success() {
+        let storage = create_test_storage().await;
+        let user = create_test_user(&storage).await;
+        let session = create_test_session(&storage, user.id).await;
+        let manager = SessionManager::new(storage.clone());
+
+        manager.revoke(session.id).await.unwrap();
+
+        let found = SessionRepository::find_by_id(&*storage, session.id)
+            .await
+            .unwrap();
+        assert!(found.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_revoke_session_not_found() {
+        let storage = create_test_storage().await;
+        let manager = SessionManager::new(storage);
+
+        let result = manager.revoke(SessionId::new()).await;
+
+        assert!(matches!(result, Err(Error::SessionNotFound)));
+    }
+
     #[tokio::test]
     async fn test_cleanup_expired() {
         use chrono::{Duration, Utc};
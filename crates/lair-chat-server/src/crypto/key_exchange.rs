@@ -1,9 +1,22 @@
-//! X25519 key exchange
+//! X25519 key exchange, authenticated with the server's long-term ed25519
+//! identity key.
 //!
-//! Implements ephemeral Diffie-Hellman key exchange using X25519.
+//! A bare X25519 exchange can't tell a real server from an active MITM
+//! swapping in its own ephemeral key, so [`sign_handshake`] signs a
+//! transcript of the `ServerHello`, `ClientHello`, and both ephemeral
+//! public keys with a process-wide identity key, and the client verifies
+//! that signature before trusting the derived shared secret. Covering the
+//! hellos (not just the ephemeral keys) binds feature negotiation —
+//! `ClientHello.features` in particular — to the signature, so a MITM
+//! can't silently strip a feature like length-hiding and have the
+//! signature still check out.
 
 use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use ed25519_dalek::{Signer, SigningKey};
+use hkdf::Hkdf;
+use once_cell::sync::Lazy;
 use rand::rngs::OsRng;
+use sha2::{Digest, Sha256};
 use x25519_dalek::{EphemeralSecret, PublicKey, SharedSecret};
 
 /// Errors that can occur during key exchange.
@@ -16,6 +29,140 @@ pub enum KeyExchangeError {
     Base64DecodeError(#[from] base64::DecodeError),
 }
 
+/// This server's long-term ed25519 identity key, generated once per
+/// process and used to sign the transcript of every key exchange. Clients
+/// pin this key (or trust it on first use) so a MITM can't silently
+/// substitute its own ephemeral X25519 key during the handshake.
+static SERVER_IDENTITY: Lazy<SigningKey> = Lazy::new(|| SigningKey::generate(&mut OsRng));
+
+/// This server's identity public key, base64-encoded, as sent in every
+/// `KeyExchangeResponse`.
+pub fn server_identity_public_key_base64() -> String {
+    BASE64.encode(SERVER_IDENTITY.verifying_key().to_bytes())
+}
+
+/// Canonical, field-order-independent encoding of a `ClientHello`'s
+/// handshake-relevant fields, for mixing into [`handshake_transcript`].
+/// Hand-built rather than serialized via `serde_json` from the protocol
+/// type directly, so the encoding doesn't depend on the client's and
+/// server's independently declared `ClientHello` structs happening to
+/// serialize their fields in the same order.
+pub fn client_hello_bytes(
+    version: &str,
+    client_name: Option<&str>,
+    features: &[String],
+    signing_public_key: Option<&str>,
+) -> Vec<u8> {
+    format!(
+        r#"{{"client_name":{},"features":{},"signing_public_key":{},"version":{}}}"#,
+        serde_json::to_string(&client_name).expect("Option<&str> always serializes"),
+        serde_json::to_string(features).expect("string slice always serializes"),
+        serde_json::to_string(&signing_public_key).expect("Option<&str> always serializes"),
+        serde_json::to_string(version).expect("str always serializes"),
+    )
+    .into_bytes()
+}
+
+/// Canonical encoding of a `ServerHello`'s handshake-relevant fields, for
+/// mixing into [`handshake_transcript`]. See [`client_hello_bytes`] for
+/// why this is hand-built rather than derived.
+pub fn server_hello_bytes(
+    version: &str,
+    server_name: &str,
+    features: &[String],
+    encryption_required: bool,
+) -> Vec<u8> {
+    format!(
+        r#"{{"encryption_required":{},"features":{},"server_name":{},"version":{}}}"#,
+        encryption_required,
+        serde_json::to_string(features).expect("string slice always serializes"),
+        serde_json::to_string(server_name).expect("str always serializes"),
+        serde_json::to_string(version).expect("str always serializes"),
+    )
+    .into_bytes()
+}
+
+/// Compute the transcript both sides sign: the SHA-256 of the `ServerHello`,
+/// the `ClientHello`, and the client's and server's ephemeral X25519 public
+/// keys, in that order. Covering the hellos closes the gap a transcript of
+/// only the two ephemeral keys would leave: without it, a MITM could alter
+/// `ClientHello.features` (e.g. to strip `length_hiding`) and the
+/// signature would still verify. Binding the signature to both ephemeral
+/// keys (not just the server's own) also stops an attacker from replaying
+/// a valid signature against a different client key. Must match the
+/// client's own `handshake_transcript` exactly.
+pub fn handshake_transcript(
+    client_ephemeral: &PublicKey,
+    server_ephemeral: &PublicKey,
+    server_hello: &[u8],
+    client_hello: &[u8],
+) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(server_hello);
+    hasher.update(client_hello);
+    hasher.update(client_ephemeral.as_bytes());
+    hasher.update(server_ephemeral.as_bytes());
+    hasher.finalize().into()
+}
+
+/// Sign `transcript` with this server's identity key.
+pub fn sign_handshake(transcript: &[u8; 32]) -> [u8; 64] {
+    SERVER_IDENTITY.sign(transcript).to_bytes()
+}
+
+/// HKDF info label for the client->server directional key.
+const HKDF_INFO_C2S: &[u8] = b"lair c2s v1";
+/// HKDF info label for the server->client directional key.
+const HKDF_INFO_S2C: &[u8] = b"lair s2c v1";
+
+/// Derive independent client->server and server->client AEAD keys from the
+/// raw X25519 shared secret via HKDF-SHA256, using the handshake transcript
+/// as salt and distinct info labels per direction. Returns
+/// `(c2s_key, s2c_key)` — the server decrypts inbound frames with
+/// `c2s_key` and encrypts outbound frames with `s2c_key`; the client's own
+/// `derive_directional_keys` derives the same two keys in the opposite
+/// roles.
+pub fn derive_directional_keys(
+    shared_secret: &[u8],
+    transcript: &[u8; 32],
+) -> ([u8; 32], [u8; 32]) {
+    let hk = Hkdf::<Sha256>::new(Some(transcript), shared_secret);
+
+    let mut c2s_key = [0u8; 32];
+    hk.expand(HKDF_INFO_C2S, &mut c2s_key)
+        .expect("32 is a valid HKDF-SHA256 output length");
+    let mut s2c_key = [0u8; 32];
+    hk.expand(HKDF_INFO_S2C, &mut s2c_key)
+        .expect("32 is a valid HKDF-SHA256 output length");
+
+    (c2s_key, s2c_key)
+}
+
+/// HKDF info label for the client->server frame-length-header key.
+const HKDF_INFO_C2S_HEADER: &[u8] = b"lair hdr c2s v1";
+/// HKDF info label for the server->client frame-length-header key.
+const HKDF_INFO_S2C_HEADER: &[u8] = b"lair hdr s2c v1";
+
+/// Derive the directional keys used to seal a frame's length header in
+/// length-hiding framing mode, from the same shared secret and transcript
+/// as [`derive_directional_keys`] but under their own info labels. Kept
+/// separate from the payload keys so a header key never doubles as a
+/// payload key (or vice versa). Returns `(c2s_header_key, s2c_header_key)`
+/// — the server opens inbound headers with `c2s_header_key` and seals
+/// outbound headers with `s2c_header_key`; the client does the opposite.
+pub fn derive_header_keys(shared_secret: &[u8], transcript: &[u8; 32]) -> ([u8; 32], [u8; 32]) {
+    let hk = Hkdf::<Sha256>::new(Some(transcript), shared_secret);
+
+    let mut c2s_header_key = [0u8; 32];
+    hk.expand(HKDF_INFO_C2S_HEADER, &mut c2s_header_key)
+        .expect("32 is a valid HKDF-SHA256 output length");
+    let mut s2c_header_key = [0u8; 32];
+    hk.expand(HKDF_INFO_S2C_HEADER, &mut s2c_header_key)
+        .expect("32 is a valid HKDF-SHA256 output length");
+
+    (c2s_header_key, s2c_header_key)
+}
+
 /// An X25519 keypair for ephemeral key exchange.
 pub struct KeyPair {
     secret: EphemeralSecret,
@@ -157,4 +304,121 @@ mod tests {
 
         assert_eq!(shared.len(), 32);
     }
+
+    fn sample_client_hello() -> Vec<u8> {
+        client_hello_bytes(
+            "1.0",
+            Some("test client"),
+            &["encryption".to_string()],
+            None,
+        )
+    }
+
+    fn sample_server_hello() -> Vec<u8> {
+        server_hello_bytes(
+            "1.0",
+            "Lair Chat Server",
+            &["encryption".to_string()],
+            false,
+        )
+    }
+
+    #[test]
+    fn test_handshake_transcript_is_order_sensitive() {
+        let client = KeyPair::generate();
+        let server = KeyPair::generate();
+        let client_public = parse_public_key(&client.public_key_base64()).unwrap();
+        let server_public = parse_public_key(&server.public_key_base64()).unwrap();
+
+        let transcript = handshake_transcript(
+            &client_public,
+            &server_public,
+            &sample_server_hello(),
+            &sample_client_hello(),
+        );
+        let swapped = handshake_transcript(
+            &server_public,
+            &client_public,
+            &sample_server_hello(),
+            &sample_client_hello(),
+        );
+        assert_ne!(transcript, swapped);
+    }
+
+    #[test]
+    fn test_handshake_transcript_is_sensitive_to_client_hello() {
+        let client = KeyPair::generate();
+        let server = KeyPair::generate();
+        let client_public = parse_public_key(&client.public_key_base64()).unwrap();
+        let server_public = parse_public_key(&server.public_key_base64()).unwrap();
+
+        let transcript = handshake_transcript(
+            &client_public,
+            &server_public,
+            &sample_server_hello(),
+            &sample_client_hello(),
+        );
+        let tampered_hello = client_hello_bytes("1.0", Some("test client"), &[], None);
+        let tampered = handshake_transcript(
+            &client_public,
+            &server_public,
+            &sample_server_hello(),
+            &tampered_hello,
+        );
+        assert_ne!(transcript, tampered);
+    }
+
+    #[test]
+    fn test_sign_handshake_verifies_against_published_identity() {
+        use ed25519_dalek::{Signature, Verifier, VerifyingKey};
+
+        let client = KeyPair::generate();
+        let server = KeyPair::generate();
+        let client_public = parse_public_key(&client.public_key_base64()).unwrap();
+        let server_public = parse_public_key(&server.public_key_base64()).unwrap();
+        let transcript = handshake_transcript(
+            &client_public,
+            &server_public,
+            &sample_server_hello(),
+            &sample_client_hello(),
+        );
+
+        let signature_bytes = sign_handshake(&transcript);
+        let identity_bytes = BASE64.decode(server_identity_public_key_base64()).unwrap();
+        let verifying_key = VerifyingKey::from_bytes(&identity_bytes.try_into().unwrap()).unwrap();
+
+        verifying_key
+            .verify(&transcript, &Signature::from_bytes(&signature_bytes))
+            .unwrap();
+    }
+
+    #[test]
+    fn test_derive_directional_keys_are_independent_and_deterministic() {
+        let shared_secret = [3u8; 32];
+        let transcript = [9u8; 32];
+
+        let (c2s_key, s2c_key) = derive_directional_keys(&shared_secret, &transcript);
+        let (c2s_key_again, s2c_key_again) = derive_directional_keys(&shared_secret, &transcript);
+
+        assert_ne!(c2s_key, s2c_key);
+        assert_eq!(c2s_key, c2s_key_again);
+        assert_eq!(s2c_key, s2c_key_again);
+    }
+
+    #[test]
+    fn test_derive_header_keys_are_independent_of_payload_keys() {
+        let shared_secret = [3u8; 32];
+        let transcript = [9u8; 32];
+
+        let (c2s_key, s2c_key) = derive_directional_keys(&shared_secret, &transcript);
+        let (c2s_header_key, s2c_header_key) = derive_header_keys(&shared_secret, &transcript);
+        let (c2s_header_key_again, s2c_header_key_again) =
+            derive_header_keys(&shared_secret, &transcript);
+
+        assert_ne!(c2s_header_key, s2c_header_key);
+        assert_ne!(c2s_header_key, c2s_key);
+        assert_ne!(s2c_header_key, s2c_key);
+        assert_eq!(c2s_header_key, c2s_header_key_again);
+        assert_eq!(s2c_header_key, s2c_header_key_again);
+    }
 }
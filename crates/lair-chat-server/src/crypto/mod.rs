@@ -3,4 +3,8 @@ pub mod aes_gcm;
 pub mod key_exchange;
 
 pub use aes_gcm::{Cipher, CryptoError, NONCE_SIZE, TAG_SIZE};
-pub use key_exchange::{parse_public_key, KeyExchangeError, KeyPair};
+pub use key_exchange::{
+    client_hello_bytes, derive_directional_keys, derive_header_keys, handshake_transcript,
+    parse_public_key, server_hello_bytes, server_identity_public_key_base64, sign_handshake,
+    KeyExchangeError, KeyPair,
+};
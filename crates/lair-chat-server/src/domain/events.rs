@@ -8,8 +8,8 @@ use std::fmt::{self, Display, Formatter};
 use uuid::Uuid;
 
 use super::{
-    EnrichedInvitation, InvitationId, Message, MessageId, MessageTarget, Room, RoomId,
-    RoomMembership, RoomRole, SessionId, User, UserId, ValidationError,
+    EnrichedInvitation, InvitationId, MarkerKind, Message, MessageId, MessageTarget, Room, RoomId,
+    RoomMembership, RoomRole, SessionId, TransferId, User, UserId, ValidationError,
 };
 
 // ============================================================================
@@ -113,6 +113,7 @@ pub enum EventPayload {
     MessageReceived(MessageReceivedEvent),
     MessageEdited(MessageEditedEvent),
     MessageDeleted(MessageDeletedEvent),
+    AttachmentReceived(AttachmentReceivedEvent),
 
     // Room events
     UserJoinedRoom(UserJoinedRoomEvent),
@@ -120,11 +121,13 @@ pub enum EventPayload {
     MemberRoleChanged(MemberRoleChangedEvent),
     RoomUpdated(RoomUpdatedEvent),
     RoomDeleted(RoomDeletedEvent),
+    RoomTombstone(RoomTombstoneEvent),
 
     // User presence events
     UserOnline(UserOnlineEvent),
     UserOffline(UserOfflineEvent),
     UserTyping(UserTypingEvent),
+    MarkerUpdate(MarkerUpdateEvent),
 
     // Invitation events
     InvitationReceived(InvitationReceivedEvent),
@@ -133,6 +136,7 @@ pub enum EventPayload {
     // System events
     ServerNotice(ServerNoticeEvent),
     SessionExpiring(SessionExpiringEvent),
+    SessionRevoked(SessionRevokedEvent),
 }
 
 impl EventPayload {
@@ -142,18 +146,22 @@ impl EventPayload {
             EventPayload::MessageReceived(_) => "message_received",
             EventPayload::MessageEdited(_) => "message_edited",
             EventPayload::MessageDeleted(_) => "message_deleted",
+            EventPayload::AttachmentReceived(_) => "attachment_received",
             EventPayload::UserJoinedRoom(_) => "user_joined_room",
             EventPayload::UserLeftRoom(_) => "user_left_room",
             EventPayload::MemberRoleChanged(_) => "member_role_changed",
             EventPayload::RoomUpdated(_) => "room_updated",
             EventPayload::RoomDeleted(_) => "room_deleted",
+            EventPayload::RoomTombstone(_) => "room_tombstone",
             EventPayload::UserOnline(_) => "user_online",
             EventPayload::UserOffline(_) => "user_offline",
             EventPayload::UserTyping(_) => "user_typing",
+            EventPayload::MarkerUpdate(_) => "marker_update",
             EventPayload::InvitationReceived(_) => "invitation_received",
             EventPayload::InvitationCancelled(_) => "invitation_cancelled",
             EventPayload::ServerNotice(_) => "server_notice",
             EventPayload::SessionExpiring(_) => "session_expiring",
+            EventPayload::SessionRevoked(_) => "session_revoked",
         }
     }
 
@@ -163,18 +171,22 @@ impl EventPayload {
             EventPayload::MessageReceived(e) => e.target(),
             EventPayload::MessageEdited(e) => e.target(),
             EventPayload::MessageDeleted(e) => e.target(),
+            EventPayload::AttachmentReceived(e) => e.target(),
             EventPayload::UserJoinedRoom(e) => EventTarget::Room(e.room_id),
             EventPayload::UserLeftRoom(e) => EventTarget::Room(e.room_id),
             EventPayload::MemberRoleChanged(e) => EventTarget::Room(e.room_id),
             EventPayload::RoomUpdated(e) => EventTarget::Room(e.room.id),
             EventPayload::RoomDeleted(e) => EventTarget::Room(e.room_id),
+            EventPayload::RoomTombstone(e) => EventTarget::Room(e.room_id),
             EventPayload::UserOnline(e) => EventTarget::UserConnections(e.user_id),
             EventPayload::UserOffline(e) => EventTarget::UserConnections(e.user_id),
             EventPayload::UserTyping(e) => e.target(),
+            EventPayload::MarkerUpdate(e) => e.target(),
             EventPayload::InvitationReceived(e) => EventTarget::User(e.invitation.invitee_id),
             EventPayload::InvitationCancelled(e) => EventTarget::User(e.invitee),
             EventPayload::ServerNotice(_) => EventTarget::Broadcast,
             EventPayload::SessionExpiring(e) => EventTarget::Session(e.session_id),
+            EventPayload::SessionRevoked(e) => EventTarget::Session(e.session_id),
         }
     }
 }
@@ -281,6 +293,52 @@ impl MessageDeletedEvent {
     }
 }
 
+/// Every chunk of an attachment transfer arrived and passed checksum
+/// verification; `message_id` is the now-visible message carrying it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AttachmentReceivedEvent {
+    pub transfer_id: TransferId,
+    pub message_id: MessageId,
+    pub target: MessageTarget,
+    pub uploader: UserId,
+    pub file_name: String,
+    pub mime_type: String,
+    pub size: u64,
+}
+
+impl AttachmentReceivedEvent {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        transfer_id: TransferId,
+        message_id: MessageId,
+        target: MessageTarget,
+        uploader: UserId,
+        file_name: impl Into<String>,
+        mime_type: impl Into<String>,
+        size: u64,
+    ) -> Self {
+        Self {
+            transfer_id,
+            message_id,
+            target,
+            uploader,
+            file_name: file_name.into(),
+            mime_type: mime_type.into(),
+            size,
+        }
+    }
+
+    pub fn target(&self) -> EventTarget {
+        match &self.target {
+            MessageTarget::Room { room_id } => EventTarget::Room(*room_id),
+            MessageTarget::DirectMessage { recipient } => EventTarget::DirectMessage {
+                user1: self.uploader,
+                user2: *recipient,
+            },
+        }
+    }
+}
+
 // ============================================================================
 // Room Events
 // ============================================================================
@@ -451,6 +509,24 @@ impl RoomDeletedEvent {
     }
 }
 
+/// A room was superseded by a freshly created successor room.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RoomTombstoneEvent {
+    pub room_id: RoomId,
+    pub replacement_room_id: RoomId,
+    pub reason: Option<String>,
+}
+
+impl RoomTombstoneEvent {
+    pub fn new(room_id: RoomId, replacement_room_id: RoomId, reason: Option<String>) -> Self {
+        Self {
+            room_id,
+            replacement_room_id,
+            reason,
+        }
+    }
+}
+
 // ============================================================================
 // User Presence Events
 // ============================================================================
@@ -504,6 +580,45 @@ impl UserTypingEvent {
     }
 }
 
+/// A user marked messages in a target as delivered or read, up to and
+/// including a given message.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MarkerUpdateEvent {
+    pub target: MessageTarget,
+    pub user_id: UserId,
+    pub username: String,
+    pub kind: MarkerKind,
+    pub up_to_message_id: MessageId,
+}
+
+impl MarkerUpdateEvent {
+    pub fn new(
+        target: MessageTarget,
+        user_id: UserId,
+        username: String,
+        kind: MarkerKind,
+        up_to_message_id: MessageId,
+    ) -> Self {
+        Self {
+            target,
+            user_id,
+            username,
+            kind,
+            up_to_message_id,
+        }
+    }
+
+    pub fn target(&self) -> EventTarget {
+        match &self.target {
+            MessageTarget::Room { room_id } => EventTarget::Room(*room_id),
+            MessageTarget::DirectMessage { recipient } => EventTarget::DirectMessage {
+                user1: self.user_id,
+                user2: *recipient,
+            },
+        }
+    }
+}
+
 // ============================================================================
 // Invitation Events
 // ============================================================================
@@ -624,6 +739,20 @@ impl SessionExpiringEvent {
     }
 }
 
+/// A session was revoked, either by the user (remote logout of a lost
+/// device) or by the system. The affected connection should treat this as
+/// an immediate logout.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionRevokedEvent {
+    pub session_id: SessionId,
+}
+
+impl SessionRevokedEvent {
+    pub fn new(session_id: SessionId) -> Self {
+        Self { session_id }
+    }
+}
+
 // ============================================================================
 // Tests
 // ============================================================================
@@ -840,6 +969,31 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_attachment_received_event() {
+        let transfer_id = TransferId::new();
+        let message_id = MessageId::new();
+        let room_id = RoomId::new();
+        let uploader = UserId::new();
+        let target = MessageTarget::Room { room_id };
+        let event = AttachmentReceivedEvent::new(
+            transfer_id,
+            message_id,
+            target,
+            uploader,
+            "photo.png",
+            "image/png",
+            1024,
+        );
+
+        assert_eq!(event.transfer_id, transfer_id);
+        assert_eq!(event.message_id, message_id);
+        assert_eq!(event.file_name, "photo.png");
+        assert_eq!(event.mime_type, "image/png");
+        assert_eq!(event.size, 1024);
+        assert!(matches!(event.target(), EventTarget::Room(id) if id == room_id));
+    }
+
     #[test]
     fn test_user_typing_event_room() {
         let user_id = UserId::new();
@@ -867,6 +1021,47 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_marker_update_event_room() {
+        let user_id = UserId::new();
+        let room_id = RoomId::new();
+        let message_id = MessageId::new();
+        let event = MarkerUpdateEvent::new(
+            MessageTarget::Room { room_id },
+            user_id,
+            "alice".to_string(),
+            MarkerKind::Read,
+            message_id,
+        );
+
+        assert_eq!(event.up_to_message_id, message_id);
+        assert!(matches!(event.target(), EventTarget::Room(id) if id == room_id));
+
+        let payload = EventPayload::MarkerUpdate(event);
+        assert_eq!(payload.event_type(), "marker_update");
+    }
+
+    #[test]
+    fn test_marker_update_event_dm() {
+        let user_id = UserId::new();
+        let recipient = UserId::new();
+        let event = MarkerUpdateEvent::new(
+            MessageTarget::DirectMessage { recipient },
+            user_id,
+            "bob".to_string(),
+            MarkerKind::Delivered,
+            MessageId::new(),
+        );
+
+        match event.target() {
+            EventTarget::DirectMessage { user1, user2 } => {
+                assert_eq!(user1, user_id);
+                assert_eq!(user2, recipient);
+            }
+            _ => panic!("Expected DirectMessage target"),
+        }
+    }
+
     #[test]
     fn test_user_online_event() {
         let user_id = UserId::new();
@@ -894,6 +1089,17 @@ mod tests {
         assert_eq!(event.deleted_by, deleted_by);
     }
 
+    #[test]
+    fn test_room_tombstone_event() {
+        let room_id = RoomId::new();
+        let replacement_room_id = RoomId::new();
+        let event = RoomTombstoneEvent::new(room_id, replacement_room_id, Some("renamed".into()));
+
+        assert_eq!(event.room_id, room_id);
+        assert_eq!(event.replacement_room_id, replacement_room_id);
+        assert_eq!(event.reason, Some("renamed".to_string()));
+    }
+
     #[test]
     fn test_session_expiring_event() {
         use chrono::Utc;
@@ -906,6 +1112,18 @@ mod tests {
         assert_eq!(event.expires_at, expires_at);
     }
 
+    #[test]
+    fn test_session_revoked_event() {
+        let session_id = SessionId::new();
+        let event = SessionRevokedEvent::new(session_id);
+
+        assert_eq!(event.session_id, session_id);
+
+        let payload = EventPayload::SessionRevoked(event);
+        assert_eq!(payload.event_type(), "session_revoked");
+        assert!(matches!(payload.target(), EventTarget::Session(id) if id == session_id));
+    }
+
     #[test]
     fn test_cancel_reason_display() {
         assert_eq!(
@@ -986,6 +1204,10 @@ mod tests {
                 )),
                 "session_expiring",
             ),
+            (
+                EventPayload::SessionRevoked(SessionRevokedEvent::new(session_id)),
+                "session_revoked",
+            ),
         ];
 
         for (payload, expected_type) in events {
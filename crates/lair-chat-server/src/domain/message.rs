@@ -7,7 +7,7 @@ use serde::{Deserialize, Serialize};
 use std::fmt::{self, Display, Formatter};
 use uuid::Uuid;
 
-use super::{RoomId, UserId, ValidationError};
+use super::{Pagination, RoomId, UserId, ValidationError};
 
 // ============================================================================
 // MessageId
@@ -62,6 +62,46 @@ impl From<Uuid> for MessageId {
     }
 }
 
+// ============================================================================
+// TransferId
+// ============================================================================
+
+/// Correlates the `AttachmentBegin`/`AttachmentChunk`/`AttachmentEnd`
+/// messages of a single chunked attachment upload. Never persisted —
+/// chunks are reassembled and checksum-verified in memory, then discarded
+/// once the resulting message is created.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct TransferId(Uuid);
+
+impl TransferId {
+    /// Create a new random TransferId.
+    pub fn new() -> Self {
+        Self(Uuid::new_v4())
+    }
+
+    /// Parse a TransferId from a string.
+    pub fn parse(s: &str) -> Result<Self, ValidationError> {
+        Uuid::parse_str(s)
+            .map(Self)
+            .map_err(|_| ValidationError::InvalidFormat {
+                reason: "invalid UUID format".into(),
+            })
+    }
+}
+
+impl Default for TransferId {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Display for TransferId {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
 // ============================================================================
 // MessageContent
 // ============================================================================
@@ -209,6 +249,206 @@ impl Display for MessageTarget {
     }
 }
 
+// ============================================================================
+// MarkerKind
+// ============================================================================
+
+/// The kind of read/delivery marker a user has placed on a [`MessageTarget`].
+///
+/// Markers are "up to" cumulative: marking `up_to_message_id` implies every
+/// earlier message in the target carries the same marker, so storage only
+/// needs to track the latest one per `(user, target, kind)` rather than one
+/// per message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MarkerKind {
+    /// The message reached the user's client.
+    Delivered,
+    /// The user has read the message.
+    Read,
+}
+
+impl MarkerKind {
+    /// Get the marker kind as a string for database storage.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MarkerKind::Delivered => "delivered",
+            MarkerKind::Read => "read",
+        }
+    }
+
+    /// Parse a marker kind from a database string.
+    pub fn parse(s: &str) -> Self {
+        match s.to_lowercase().as_str() {
+            "read" => MarkerKind::Read,
+            _ => MarkerKind::Delivered,
+        }
+    }
+}
+
+impl Display for MarkerKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+// ============================================================================
+// MessageCursor
+// ============================================================================
+
+/// A keyset-pagination cursor into reverse-chronological message history.
+///
+/// Pairs a timestamp with the message id so ties at the same `created_at`
+/// are still ordered deterministically, giving stable "load older" paging
+/// that doesn't skip or duplicate rows as new messages arrive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MessageCursor {
+    /// Unix timestamp (seconds) of the message to page before.
+    pub created_at: i64,
+    /// Id of the message to page before, used as a tiebreaker.
+    pub id: MessageId,
+}
+
+impl MessageCursor {
+    /// Create a cursor pointing just before the given message.
+    pub fn new(created_at: i64, id: MessageId) -> Self {
+        Self { created_at, id }
+    }
+
+    /// Create a cursor positioned just before a message.
+    pub fn before(message: &Message) -> Self {
+        Self::new(message.created_at.timestamp(), message.id)
+    }
+}
+
+// ============================================================================
+// MessageRevision
+// ============================================================================
+
+/// What happened to produce a [`MessageRevision`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RevisionKind {
+    /// The message content was changed.
+    Edit,
+    /// The message was deleted.
+    Delete,
+}
+
+/// A prior version of a message, preserved for moderation audit purposes.
+///
+/// Revisions are appended whenever a message is edited or (soft-)deleted,
+/// capturing the content as it stood immediately before the mutation.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MessageRevision {
+    /// The message this revision belongs to.
+    pub message_id: MessageId,
+    /// The content as it was before this operation.
+    pub content: MessageContent,
+    /// Whether this revision was recorded for an edit or a delete.
+    pub kind: RevisionKind,
+    /// When this revision was recorded.
+    pub recorded_at: DateTime<Utc>,
+}
+
+// ============================================================================
+// PinnedMessage
+// ============================================================================
+
+/// A message pinned to a room for easy reference.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PinnedMessage {
+    /// The room the message is pinned in.
+    pub room_id: RoomId,
+    /// The pinned message.
+    pub message_id: MessageId,
+    /// When it was pinned.
+    pub pinned_at: DateTime<Utc>,
+    /// Who pinned it.
+    pub pinned_by: UserId,
+}
+
+// ============================================================================
+// MessageQuery
+// ============================================================================
+
+/// A typed, composable search over message history.
+///
+/// Each filter maps to a column in the `messages` table (`text` instead
+/// matches against the FTS5 index kept in sync alongside it), and
+/// [`MessageRepository::search`](crate::storage::MessageRepository::search)
+/// compiles whichever fields are set into a single parameterized SQL query
+/// rather than dispatching to a different method per filter combination.
+/// Unset fields are unrestricted.
+#[derive(Debug, Clone, Default)]
+pub struct MessageQuery {
+    /// Restrict to messages sent by this user.
+    pub sender: Option<UserId>,
+    /// Restrict to messages sent in this room.
+    pub room: Option<RoomId>,
+    /// Restrict to messages sent at or after this time (inclusive).
+    pub from: Option<DateTime<Utc>>,
+    /// Restrict to messages sent before this time (exclusive).
+    pub to: Option<DateTime<Utc>>,
+    /// Free-text match against message body.
+    pub text: Option<String>,
+    /// Page through results.
+    pub pagination: Pagination,
+}
+
+impl MessageQuery {
+    /// Start building a query with no filters set.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Restrict to messages from this sender.
+    pub fn sender(mut self, sender: UserId) -> Self {
+        self.sender = Some(sender);
+        self
+    }
+
+    /// Restrict to messages in this room.
+    pub fn room(mut self, room: RoomId) -> Self {
+        self.room = Some(room);
+        self
+    }
+
+    /// Restrict to messages sent within `[from, to)`.
+    pub fn date_range(mut self, from: DateTime<Utc>, to: DateTime<Utc>) -> Self {
+        self.from = Some(from);
+        self.to = Some(to);
+        self
+    }
+
+    /// Restrict to messages whose body matches `text`. A blank string
+    /// leaves the filter unset rather than matching everything.
+    pub fn text(mut self, text: impl Into<String>) -> Self {
+        let text = text.into();
+        self.text = if text.trim().is_empty() {
+            None
+        } else {
+            Some(text)
+        };
+        self
+    }
+
+    /// Set the page of results to return.
+    pub fn pagination(mut self, pagination: Pagination) -> Self {
+        self.pagination = pagination;
+        self
+    }
+
+    /// Whether no filters are set, i.e. this query matches every message.
+    pub fn is_unrestricted(&self) -> bool {
+        self.sender.is_none()
+            && self.room.is_none()
+            && self.from.is_none()
+            && self.to.is_none()
+            && self.text.is_none()
+    }
+}
+
 // ============================================================================
 // Message
 // ============================================================================
@@ -301,6 +541,18 @@ mod tests {
         assert!(MessageId::parse("not-a-uuid").is_err());
     }
 
+    #[test]
+    fn test_transfer_id() {
+        let id1 = TransferId::new();
+        let id2 = TransferId::new();
+        assert_ne!(id1, id2);
+
+        let parsed = TransferId::parse(&id1.to_string()).unwrap();
+        assert_eq!(id1, parsed);
+
+        assert!(TransferId::parse("not-a-uuid").is_err());
+    }
+
     #[test]
     fn test_message_content_valid() {
         assert!(MessageContent::new("Hello").is_ok());
@@ -546,4 +798,30 @@ mod tests {
         let dm_target: MessageTarget = serde_json::from_str(dm_json).unwrap();
         assert!(dm_target.is_dm());
     }
+
+    #[test]
+    fn test_message_query_builder() {
+        let sender = UserId::new();
+        let room = RoomId::new();
+
+        let query = MessageQuery::new().sender(sender).room(room).text("hello");
+
+        assert_eq!(query.sender, Some(sender));
+        assert_eq!(query.room, Some(room));
+        assert_eq!(query.text.as_deref(), Some("hello"));
+        assert!(query.from.is_none());
+        assert!(query.to.is_none());
+    }
+
+    #[test]
+    fn test_message_query_blank_text_is_unset() {
+        let query = MessageQuery::new().text("   ");
+        assert!(query.text.is_none());
+    }
+
+    #[test]
+    fn test_message_query_is_unrestricted() {
+        assert!(MessageQuery::new().is_unrestricted());
+        assert!(!MessageQuery::new().sender(UserId::new()).is_unrestricted());
+    }
 }
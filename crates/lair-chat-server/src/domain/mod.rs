@@ -33,7 +33,10 @@ pub mod user;
 // Re-export commonly used types
 pub use events::{Event, EventId, EventPayload, InvitationReceivedEvent};
 pub use invitation::{EnrichedInvitation, Invitation, InvitationId, InvitationStatus, RoomMember};
-pub use message::{Message, MessageContent, MessageId, MessageTarget};
+pub use message::{
+    MarkerKind, Message, MessageContent, MessageCursor, MessageId, MessageQuery, MessageRevision,
+    MessageTarget, PinnedMessage, RevisionKind, TransferId,
+};
 pub use room::{Room, RoomId, RoomMembership, RoomName, RoomRole, RoomSettings};
 pub use session::{Protocol, Session, SessionId};
 pub use user::{Email, Role, User, UserId, Username};
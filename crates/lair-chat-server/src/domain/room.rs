@@ -306,6 +306,12 @@ pub struct Room {
     pub settings: RoomSettings,
     /// Creation timestamp.
     pub created_at: DateTime<Utc>,
+    /// Set once this room has been superseded by an [`RoomService::upgrade`](
+    /// crate::core::rooms::RoomService::upgrade) call. A tombstoned room
+    /// still exists (so old links and history resolve). A second upgrade of
+    /// it is refused; joins and messages to it are not rejected.
+    #[serde(default)]
+    pub successor_room_id: Option<RoomId>,
 }
 
 impl Room {
@@ -317,6 +323,7 @@ impl Room {
             owner,
             settings,
             created_at: Utc::now(),
+            successor_room_id: None,
         }
     }
 
@@ -347,6 +354,11 @@ impl Room {
             .map(|max| current_members >= max)
             .unwrap_or(false)
     }
+
+    /// Check if this room has been superseded by a successor.
+    pub fn is_tombstoned(&self) -> bool {
+        self.successor_room_id.is_some()
+    }
 }
 
 // ============================================================================
@@ -633,6 +645,18 @@ mod tests {
         assert!(!room.is_public());
     }
 
+    #[test]
+    fn test_room_tombstone() {
+        let name = RoomName::new("Old Room").unwrap();
+        let owner = UserId::new();
+        let mut room = Room::public(name, owner);
+
+        assert!(!room.is_tombstoned());
+
+        room.successor_room_id = Some(RoomId::new());
+        assert!(room.is_tombstoned());
+    }
+
     #[test]
     fn test_room_no_max_members() {
         let name = RoomName::new("Unlimited").unwrap();
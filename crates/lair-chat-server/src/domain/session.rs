@@ -113,12 +113,18 @@ pub struct Session {
     pub user_id: UserId,
     /// Which protocol created this session.
     pub protocol: Protocol,
+    /// Client address the session was established from, if known.
+    pub ip_address: Option<String>,
+    /// Human-readable device/client name, if the client provided one at
+    /// login (e.g. "alice-laptop"). Lets a user tell their active sessions
+    /// apart when listing or revoking devices remotely.
+    pub user_agent: Option<String>,
     /// When the session was created.
     pub created_at: DateTime<Utc>,
     /// When the session expires.
     pub expires_at: DateTime<Utc>,
     /// Last activity timestamp.
-    pub last_active: DateTime<Utc>,
+    pub last_active_at: DateTime<Utc>,
 }
 
 impl Session {
@@ -135,9 +141,11 @@ impl Session {
             id: SessionId::new(),
             user_id,
             protocol,
+            ip_address: None,
+            user_agent: None,
             created_at: now,
             expires_at: now + Self::DEFAULT_DURATION,
-            last_active: now,
+            last_active_at: now,
         }
     }
 
@@ -148,9 +156,11 @@ impl Session {
             id: SessionId::new(),
             user_id,
             protocol,
+            ip_address: None,
+            user_agent: None,
             created_at: now,
             expires_at: now + Self::EXTENDED_DURATION,
-            last_active: now,
+            last_active_at: now,
         }
     }
 
@@ -161,12 +171,22 @@ impl Session {
             id: SessionId::new(),
             user_id,
             protocol,
+            ip_address: None,
+            user_agent: None,
             created_at: now,
             expires_at: now + duration,
-            last_active: now,
+            last_active_at: now,
         }
     }
 
+    /// Attach device/client identification to this session, for display in
+    /// a user's session list.
+    pub fn with_device(mut self, ip_address: Option<String>, user_agent: Option<String>) -> Self {
+        self.ip_address = ip_address;
+        self.user_agent = user_agent;
+        self
+    }
+
     /// Check if the session has expired.
     pub fn is_expired(&self) -> bool {
         Utc::now() > self.expires_at
@@ -179,7 +199,7 @@ impl Session {
 
     /// Update the last activity timestamp.
     pub fn touch(&mut self) {
-        self.last_active = Utc::now();
+        self.last_active_at = Utc::now();
     }
 
     /// Extend the session expiration.
@@ -190,7 +210,7 @@ impl Session {
     /// Extend the session to the default duration from now.
     pub fn refresh(&mut self) {
         let now = Utc::now();
-        self.last_active = now;
+        self.last_active_at = now;
         self.expires_at = now + Self::DEFAULT_DURATION;
     }
 
@@ -282,13 +302,13 @@ mod tests {
     fn test_session_touch() {
         let user_id = UserId::new();
         let mut session = Session::new(user_id, Protocol::Tcp);
-        let original_last_active = session.last_active;
+        let original_last_active = session.last_active_at;
 
         // Small delay to ensure time difference
         std::thread::sleep(std::time::Duration::from_millis(10));
         session.touch();
 
-        assert!(session.last_active > original_last_active);
+        assert!(session.last_active_at > original_last_active);
     }
 
     #[test]
@@ -314,6 +334,27 @@ mod tests {
         assert_eq!(session.time_remaining(), Duration::zero());
     }
 
+    #[test]
+    fn test_session_with_device() {
+        let user_id = UserId::new();
+        let session = Session::new(user_id, Protocol::Tcp).with_device(
+            Some("127.0.0.1".to_string()),
+            Some("alice-laptop".to_string()),
+        );
+
+        assert_eq!(session.ip_address, Some("127.0.0.1".to_string()));
+        assert_eq!(session.user_agent, Some("alice-laptop".to_string()));
+    }
+
+    #[test]
+    fn test_session_without_device_defaults_to_none() {
+        let user_id = UserId::new();
+        let session = Session::new(user_id, Protocol::Tcp);
+
+        assert_eq!(session.ip_address, None);
+        assert_eq!(session.user_agent, None);
+    }
+
     #[test]
     fn test_protocol_serialization() {
         let tcp = Protocol::Tcp;
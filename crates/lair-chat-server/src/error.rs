@@ -126,6 +126,9 @@ pub enum Error {
     #[error("Cannot leave as only owner")]
     LastOwner,
 
+    #[error("Room has already been upgraded")]
+    RoomAlreadyTombstoned,
+
     #[error("Message already deleted")]
     MessageDeleted,
 
@@ -188,6 +191,7 @@ impl Error {
             Error::RoomFull => "room_full",
             Error::RoomPrivate => "room_private",
             Error::LastOwner => "last_owner",
+            Error::RoomAlreadyTombstoned => "room_already_tombstoned",
             Error::MessageDeleted => "message_deleted",
             Error::InvitationExpired => "invitation_expired",
             Error::UserBlocked => "user_blocked",
@@ -228,6 +232,7 @@ impl Error {
             Error::RoomFull
             | Error::RoomPrivate
             | Error::LastOwner
+            | Error::RoomAlreadyTombstoned
             | Error::MessageDeleted
             | Error::InvitationExpired
             | Error::UserBlocked => 409,
@@ -311,6 +316,10 @@ mod tests {
         assert_eq!(Error::RoomFull.code(), "room_full");
         assert_eq!(Error::RoomPrivate.code(), "room_private");
         assert_eq!(Error::LastOwner.code(), "last_owner");
+        assert_eq!(
+            Error::RoomAlreadyTombstoned.code(),
+            "room_already_tombstoned"
+        );
         assert_eq!(Error::MessageDeleted.code(), "message_deleted");
         assert_eq!(Error::InvitationExpired.code(), "invitation_expired");
         assert_eq!(Error::UserBlocked.code(), "user_blocked");
@@ -388,6 +397,7 @@ mod tests {
         assert_eq!(Error::RoomFull.status_code(), 409);
         assert_eq!(Error::RoomPrivate.status_code(), 409);
         assert_eq!(Error::LastOwner.status_code(), 409);
+        assert_eq!(Error::RoomAlreadyTombstoned.status_code(), 409);
         assert_eq!(Error::MessageDeleted.status_code(), 409);
         assert_eq!(Error::InvitationExpired.status_code(), 409);
         assert_eq!(Error::UserBlocked.status_code(), 409);
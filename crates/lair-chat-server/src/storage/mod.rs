@@ -38,6 +38,6 @@ pub mod traits;
 
 // Re-export traits
 pub use traits::{
-    InvitationRepository, MembershipRepository, MessageRepository, RoomRepository,
-    SessionRepository, Storage, Transaction, TransactionalStorage, UserRepository,
+    InvitationRepository, MarkerRepository, MembershipRepository, MessageRepository,
+    RoomRepository, SessionRepository, Storage, Transaction, TransactionalStorage, UserRepository,
 };
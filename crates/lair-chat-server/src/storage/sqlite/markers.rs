@@ -0,0 +1,72 @@
+//! SQLite marker repository implementation.
+
+use async_trait::async_trait;
+use sqlx::Row;
+
+use super::messages::target_to_db;
+use super::SqliteStorage;
+use crate::domain::{MarkerKind, MessageId, MessageTarget, UserId};
+use crate::storage::MarkerRepository;
+use crate::Result;
+
+#[async_trait]
+impl MarkerRepository for SqliteStorage {
+    async fn set_marker(
+        &self,
+        user_id: UserId,
+        target: &MessageTarget,
+        kind: MarkerKind,
+        message_id: MessageId,
+    ) -> Result<()> {
+        let (target_type, target_id) = target_to_db(target);
+        let now = chrono::Utc::now().timestamp();
+
+        sqlx::query(
+            r#"
+            INSERT INTO message_markers (user_id, target_type, target_id, kind, message_id, updated_at)
+            VALUES (?, ?, ?, ?, ?, ?)
+            ON CONFLICT(user_id, target_type, target_id, kind) DO UPDATE SET
+                message_id = excluded.message_id,
+                updated_at = excluded.updated_at
+            "#,
+        )
+        .bind(user_id.to_string())
+        .bind(target_type)
+        .bind(target_id)
+        .bind(kind.as_str())
+        .bind(message_id.to_string())
+        .bind(now)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn get_marker(
+        &self,
+        user_id: UserId,
+        target: &MessageTarget,
+        kind: MarkerKind,
+    ) -> Result<Option<MessageId>> {
+        let (target_type, target_id) = target_to_db(target);
+
+        let row = sqlx::query(
+            r#"
+            SELECT message_id FROM message_markers
+            WHERE user_id = ? AND target_type = ? AND target_id = ? AND kind = ?
+            "#,
+        )
+        .bind(user_id.to_string())
+        .bind(target_type)
+        .bind(target_id)
+        .bind(kind.as_str())
+        .fetch_optional(&self.pool)
+        .await?;
+
+        row.map(|r| {
+            let id: String = r.get("message_id");
+            MessageId::parse(&id).map_err(|e| crate::Error::Internal(e.to_string()))
+        })
+        .transpose()
+    }
+}
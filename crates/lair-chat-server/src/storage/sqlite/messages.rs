@@ -5,7 +5,8 @@ use sqlx::Row;
 
 use super::SqliteStorage;
 use crate::domain::{
-    Message, MessageContent, MessageId, MessageTarget, Pagination, RoomId, UserId,
+    Message, MessageContent, MessageCursor, MessageId, MessageQuery, MessageRevision,
+    MessageTarget, Pagination, RevisionKind, RoomId, UserId,
 };
 use crate::storage::MessageRepository;
 use crate::Result;
@@ -33,14 +34,46 @@ impl MessageRepository for SqliteStorage {
         .execute(&self.pool)
         .await?;
 
+        index_for_search(&self.pool, message.id, message.content.as_str()).await?;
+
         Ok(())
     }
 
+    async fn create_if_absent(&self, message: &Message) -> Result<bool> {
+        let now = chrono::Utc::now().timestamp();
+        let (target_type, target_id) = target_to_db(&message.target);
+
+        let result = sqlx::query(
+            r#"
+            INSERT INTO messages (id, author_id, target_type, target_id, content, is_edited, created_at, updated_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+            ON CONFLICT(id) DO NOTHING
+            "#,
+        )
+        .bind(message.id.to_string())
+        .bind(message.author.to_string())
+        .bind(target_type)
+        .bind(&target_id)
+        .bind(message.content.as_str())
+        .bind(message.is_edited)
+        .bind(now)
+        .bind(now)
+        .execute(&self.pool)
+        .await?;
+
+        let inserted = result.rows_affected() > 0;
+        if inserted {
+            index_for_search(&self.pool, message.id, message.content.as_str()).await?;
+        }
+
+        Ok(inserted)
+    }
+
     async fn find_by_id(&self, id: MessageId) -> Result<Option<Message>> {
         let row = sqlx::query(
             r#"
             SELECT id, author_id, target_type, target_id, content, is_edited, created_at, updated_at
-            FROM messages WHERE id = ?
+            FROM messages WHERE id = ? AND deleted_at IS NULL
             "#,
         )
         .bind(id.to_string())
@@ -56,6 +89,23 @@ impl MessageRepository for SqliteStorage {
     async fn update(&self, message: &Message) -> Result<()> {
         let now = chrono::Utc::now().timestamp();
 
+        let previous_content: Option<String> = sqlx::query_scalar(
+            "SELECT content FROM messages WHERE id = ? AND deleted_at IS NULL",
+        )
+        .bind(message.id.to_string())
+        .fetch_optional(&self.pool)
+        .await?;
+        let previous_content = previous_content.ok_or(crate::Error::MessageNotFound)?;
+
+        record_revision(
+            &self.pool,
+            message.id,
+            &previous_content,
+            RevisionKind::Edit,
+            now,
+        )
+        .await?;
+
         let result = sqlx::query(
             r#"
             UPDATE messages
@@ -73,10 +123,42 @@ impl MessageRepository for SqliteStorage {
             return Err(crate::Error::MessageNotFound);
         }
 
+        index_for_search(&self.pool, message.id, message.content.as_str()).await?;
+
         Ok(())
     }
 
     async fn delete(&self, id: MessageId) -> Result<()> {
+        let now = chrono::Utc::now().timestamp();
+
+        let content: Option<String> = sqlx::query_scalar(
+            "SELECT content FROM messages WHERE id = ? AND deleted_at IS NULL",
+        )
+        .bind(id.to_string())
+        .fetch_optional(&self.pool)
+        .await?;
+        let content = content.ok_or(crate::Error::MessageNotFound)?;
+
+        record_revision(&self.pool, id, &content, RevisionKind::Delete, now).await?;
+
+        sqlx::query("UPDATE messages SET deleted_at = ? WHERE id = ?")
+            .bind(now)
+            .bind(id.to_string())
+            .execute(&self.pool)
+            .await?;
+
+        // The row survives the soft-delete, but it should no longer show up
+        // as pinned or searchable.
+        sqlx::query("DELETE FROM pinned_messages WHERE message_id = ?")
+            .bind(id.to_string())
+            .execute(&self.pool)
+            .await?;
+        unindex_for_search(&self.pool, id).await?;
+
+        Ok(())
+    }
+
+    async fn hard_delete(&self, id: MessageId) -> Result<()> {
         let result = sqlx::query("DELETE FROM messages WHERE id = ?")
             .bind(id.to_string())
             .execute(&self.pool)
@@ -86,15 +168,35 @@ impl MessageRepository for SqliteStorage {
             return Err(crate::Error::MessageNotFound);
         }
 
+        unindex_for_search(&self.pool, id).await?;
+
         Ok(())
     }
 
+    async fn find_history(&self, message_id: MessageId) -> Result<Vec<MessageRevision>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT content, operation, recorded_at
+            FROM message_history
+            WHERE message_id = ?
+            ORDER BY recorded_at ASC, id ASC
+            "#,
+        )
+        .bind(message_id.to_string())
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.into_iter()
+            .map(|row| row_to_revision(message_id, row))
+            .collect()
+    }
+
     async fn find_by_room(&self, room_id: RoomId, pagination: Pagination) -> Result<Vec<Message>> {
         let rows = sqlx::query(
             r#"
             SELECT id, author_id, target_type, target_id, content, is_edited, created_at, updated_at
             FROM messages
-            WHERE target_type = 'room' AND target_id = ?
+            WHERE target_type = 'room' AND target_id = ? AND deleted_at IS NULL
             ORDER BY created_at DESC
             LIMIT ? OFFSET ?
             "#,
@@ -120,7 +222,7 @@ impl MessageRepository for SqliteStorage {
             r#"
             SELECT id, author_id, target_type, target_id, content, is_edited, created_at, updated_at
             FROM messages
-            WHERE target_type = 'dm' AND (
+            WHERE target_type = 'dm' AND deleted_at IS NULL AND (
                 (author_id = ? AND target_id = ?) OR
                 (author_id = ? AND target_id = ?)
             )
@@ -140,6 +242,170 @@ impl MessageRepository for SqliteStorage {
         rows.into_iter().map(row_to_message).collect()
     }
 
+    async fn find_by_room_before(
+        &self,
+        room_id: RoomId,
+        cursor: Option<MessageCursor>,
+        limit: u32,
+    ) -> Result<Vec<Message>> {
+        let rows = match cursor {
+            Some(cursor) => {
+                sqlx::query(
+                    r#"
+                    SELECT id, author_id, target_type, target_id, content, is_edited, created_at, updated_at
+                    FROM messages
+                    WHERE target_type = 'room' AND target_id = ? AND deleted_at IS NULL
+                        AND (created_at, id) < (?, ?)
+                    ORDER BY created_at DESC, id DESC
+                    LIMIT ?
+                    "#,
+                )
+                .bind(room_id.to_string())
+                .bind(cursor.created_at)
+                .bind(cursor.id.to_string())
+                .bind(limit as i64)
+                .fetch_all(&self.pool)
+                .await?
+            }
+            None => {
+                sqlx::query(
+                    r#"
+                    SELECT id, author_id, target_type, target_id, content, is_edited, created_at, updated_at
+                    FROM messages
+                    WHERE target_type = 'room' AND target_id = ? AND deleted_at IS NULL
+                    ORDER BY created_at DESC, id DESC
+                    LIMIT ?
+                    "#,
+                )
+                .bind(room_id.to_string())
+                .bind(limit as i64)
+                .fetch_all(&self.pool)
+                .await?
+            }
+        };
+
+        rows.into_iter().map(row_to_message).collect()
+    }
+
+    async fn find_direct_messages_before(
+        &self,
+        user1: UserId,
+        user2: UserId,
+        cursor: Option<MessageCursor>,
+        limit: u32,
+    ) -> Result<Vec<Message>> {
+        let rows = match cursor {
+            Some(cursor) => {
+                sqlx::query(
+                    r#"
+                    SELECT id, author_id, target_type, target_id, content, is_edited, created_at, updated_at
+                    FROM messages
+                    WHERE target_type = 'dm' AND deleted_at IS NULL AND (
+                        (author_id = ? AND target_id = ?) OR
+                        (author_id = ? AND target_id = ?)
+                    ) AND (created_at, id) < (?, ?)
+                    ORDER BY created_at DESC, id DESC
+                    LIMIT ?
+                    "#,
+                )
+                .bind(user1.to_string())
+                .bind(user2.to_string())
+                .bind(user2.to_string())
+                .bind(user1.to_string())
+                .bind(cursor.created_at)
+                .bind(cursor.id.to_string())
+                .bind(limit as i64)
+                .fetch_all(&self.pool)
+                .await?
+            }
+            None => {
+                sqlx::query(
+                    r#"
+                    SELECT id, author_id, target_type, target_id, content, is_edited, created_at, updated_at
+                    FROM messages
+                    WHERE target_type = 'dm' AND deleted_at IS NULL AND (
+                        (author_id = ? AND target_id = ?) OR
+                        (author_id = ? AND target_id = ?)
+                    )
+                    ORDER BY created_at DESC, id DESC
+                    LIMIT ?
+                    "#,
+                )
+                .bind(user1.to_string())
+                .bind(user2.to_string())
+                .bind(user2.to_string())
+                .bind(user1.to_string())
+                .bind(limit as i64)
+                .fetch_all(&self.pool)
+                .await?
+            }
+        };
+
+        rows.into_iter().map(row_to_message).collect()
+    }
+
+    async fn find_by_room_range(
+        &self,
+        room_id: RoomId,
+        from: chrono::DateTime<chrono::Utc>,
+        to: chrono::DateTime<chrono::Utc>,
+        pagination: Pagination,
+    ) -> Result<Vec<Message>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT id, author_id, target_type, target_id, content, is_edited, created_at, updated_at
+            FROM messages
+            WHERE target_type = 'room' AND target_id = ? AND deleted_at IS NULL
+                AND created_at >= ? AND created_at < ?
+            ORDER BY created_at DESC
+            LIMIT ? OFFSET ?
+            "#,
+        )
+        .bind(room_id.to_string())
+        .bind(from.timestamp())
+        .bind(to.timestamp())
+        .bind(pagination.limit as i64)
+        .bind(pagination.offset as i64)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.into_iter().map(row_to_message).collect()
+    }
+
+    async fn find_direct_messages_range(
+        &self,
+        user1: UserId,
+        user2: UserId,
+        from: chrono::DateTime<chrono::Utc>,
+        to: chrono::DateTime<chrono::Utc>,
+        pagination: Pagination,
+    ) -> Result<Vec<Message>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT id, author_id, target_type, target_id, content, is_edited, created_at, updated_at
+            FROM messages
+            WHERE target_type = 'dm' AND deleted_at IS NULL AND (
+                (author_id = ? AND target_id = ?) OR
+                (author_id = ? AND target_id = ?)
+            ) AND created_at >= ? AND created_at < ?
+            ORDER BY created_at DESC
+            LIMIT ? OFFSET ?
+            "#,
+        )
+        .bind(user1.to_string())
+        .bind(user2.to_string())
+        .bind(user2.to_string())
+        .bind(user1.to_string())
+        .bind(from.timestamp())
+        .bind(to.timestamp())
+        .bind(pagination.limit as i64)
+        .bind(pagination.offset as i64)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.into_iter().map(row_to_message).collect()
+    }
+
     async fn find_by_target(
         &self,
         target: &MessageTarget,
@@ -151,7 +417,7 @@ impl MessageRepository for SqliteStorage {
             r#"
             SELECT id, author_id, target_type, target_id, content, is_edited, created_at, updated_at
             FROM messages
-            WHERE target_type = ? AND target_id = ?
+            WHERE target_type = ? AND target_id = ? AND deleted_at IS NULL
             ORDER BY created_at DESC
             LIMIT ? OFFSET ?
             "#,
@@ -168,7 +434,7 @@ impl MessageRepository for SqliteStorage {
 
     async fn count_by_room(&self, room_id: RoomId) -> Result<u64> {
         let count: i64 = sqlx::query_scalar(
-            "SELECT COUNT(*) FROM messages WHERE target_type = 'room' AND target_id = ?",
+            "SELECT COUNT(*) FROM messages WHERE target_type = 'room' AND target_id = ? AND deleted_at IS NULL",
         )
         .bind(room_id.to_string())
         .fetch_one(&self.pool)
@@ -181,7 +447,7 @@ impl MessageRepository for SqliteStorage {
         let count: i64 = sqlx::query_scalar(
             r#"
             SELECT COUNT(*) FROM messages
-            WHERE target_type = 'dm' AND (
+            WHERE target_type = 'dm' AND deleted_at IS NULL AND (
                 (author_id = ? AND target_id = ?) OR
                 (author_id = ? AND target_id = ?)
             )
@@ -202,7 +468,7 @@ impl MessageRepository for SqliteStorage {
             r#"
             SELECT id, author_id, target_type, target_id, content, is_edited, created_at, updated_at
             FROM messages
-            WHERE target_type = 'room' AND target_id = ?
+            WHERE target_type = 'room' AND target_id = ? AND deleted_at IS NULL
             ORDER BY created_at DESC
             LIMIT 1
             "#,
@@ -218,6 +484,14 @@ impl MessageRepository for SqliteStorage {
     }
 
     async fn delete_by_room(&self, room_id: RoomId) -> Result<u64> {
+        sqlx::query(
+            "DELETE FROM messages_fts WHERE message_id IN \
+             (SELECT id FROM messages WHERE target_type = 'room' AND target_id = ?)",
+        )
+        .bind(room_id.to_string())
+        .execute(&self.pool)
+        .await?;
+
         let result =
             sqlx::query("DELETE FROM messages WHERE target_type = 'room' AND target_id = ?")
                 .bind(room_id.to_string())
@@ -228,6 +502,14 @@ impl MessageRepository for SqliteStorage {
     }
 
     async fn delete_by_author(&self, author_id: UserId) -> Result<u64> {
+        sqlx::query(
+            "DELETE FROM messages_fts WHERE message_id IN \
+             (SELECT id FROM messages WHERE author_id = ?)",
+        )
+        .bind(author_id.to_string())
+        .execute(&self.pool)
+        .await?;
+
         let result = sqlx::query("DELETE FROM messages WHERE author_id = ?")
             .bind(author_id.to_string())
             .execute(&self.pool)
@@ -235,16 +517,239 @@ impl MessageRepository for SqliteStorage {
 
         Ok(result.rows_affected())
     }
+
+    async fn pin_message(
+        &self,
+        room_id: RoomId,
+        message_id: MessageId,
+        pinned_by: UserId,
+    ) -> Result<()> {
+        let now = chrono::Utc::now().timestamp();
+
+        sqlx::query(
+            r#"
+            INSERT INTO pinned_messages (room_id, message_id, pinned_at, pinned_by)
+            VALUES (?, ?, ?, ?)
+            ON CONFLICT(room_id, message_id) DO UPDATE SET
+                pinned_at = excluded.pinned_at,
+                pinned_by = excluded.pinned_by
+            "#,
+        )
+        .bind(room_id.to_string())
+        .bind(message_id.to_string())
+        .bind(now)
+        .bind(pinned_by.to_string())
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn unpin_message(&self, room_id: RoomId, message_id: MessageId) -> Result<()> {
+        sqlx::query("DELETE FROM pinned_messages WHERE room_id = ? AND message_id = ?")
+            .bind(room_id.to_string())
+            .bind(message_id.to_string())
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn list_pinned(&self, room_id: RoomId) -> Result<Vec<Message>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT m.id, m.author_id, m.target_type, m.target_id, m.content, m.is_edited,
+                   m.created_at, m.updated_at
+            FROM pinned_messages p
+            JOIN messages m ON m.id = p.message_id
+            WHERE p.room_id = ? AND m.deleted_at IS NULL
+            ORDER BY p.pinned_at DESC
+            "#,
+        )
+        .bind(room_id.to_string())
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.into_iter().map(row_to_message).collect()
+    }
+
+    async fn move_message(&self, message_id: MessageId, new_target: MessageTarget) -> Result<()> {
+        let (target_type, target_id) = target_to_db(&new_target);
+
+        let result = sqlx::query(
+            "UPDATE messages SET target_type = ?, target_id = ? WHERE id = ? AND deleted_at IS NULL",
+        )
+        .bind(target_type)
+        .bind(target_id)
+        .bind(message_id.to_string())
+        .execute(&self.pool)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(crate::Error::MessageNotFound);
+        }
+
+        // A relocated message no longer belongs to whatever room it was
+        // pinned in.
+        sqlx::query("DELETE FROM pinned_messages WHERE message_id = ?")
+            .bind(message_id.to_string())
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn search(&self, query: &MessageQuery) -> Result<Vec<Message>> {
+        let mut sql = String::from(
+            "SELECT m.id, m.author_id, m.target_type, m.target_id, m.content, m.is_edited, \
+             m.created_at, m.updated_at FROM messages m",
+        );
+        if query.text.is_some() {
+            sql.push_str(" JOIN messages_fts f ON f.message_id = m.id");
+        }
+        sql.push_str(" WHERE m.deleted_at IS NULL");
+        if query.sender.is_some() {
+            sql.push_str(" AND m.author_id = ?");
+        }
+        if query.room.is_some() {
+            sql.push_str(" AND m.target_type = 'room' AND m.target_id = ?");
+        }
+        if query.from.is_some() {
+            sql.push_str(" AND m.created_at >= ?");
+        }
+        if query.to.is_some() {
+            sql.push_str(" AND m.created_at < ?");
+        }
+        if query.text.is_some() {
+            sql.push_str(" AND f.content MATCH ?");
+        }
+        sql.push_str(" ORDER BY m.created_at DESC LIMIT ? OFFSET ?");
+
+        let mut q = sqlx::query(&sql);
+        if let Some(sender) = query.sender {
+            q = q.bind(sender.to_string());
+        }
+        if let Some(room) = query.room {
+            q = q.bind(room.to_string());
+        }
+        if let Some(from) = query.from {
+            q = q.bind(from.timestamp());
+        }
+        if let Some(to) = query.to {
+            q = q.bind(to.timestamp());
+        }
+        if let Some(text) = &query.text {
+            q = q.bind(fts_match_phrase(text));
+        }
+        q = q
+            .bind(query.pagination.limit as i64)
+            .bind(query.pagination.offset as i64);
+
+        let rows = q.fetch_all(&self.pool).await?;
+
+        rows.into_iter().map(row_to_message).collect()
+    }
+}
+
+/// Quote free text as a single FTS5 phrase so user input can't smuggle in
+/// query operators (`OR`, `NEAR`, column filters, ...) through `MATCH`.
+fn fts_match_phrase(text: &str) -> String {
+    format!("\"{}\"", text.replace('"', "\"\""))
 }
 
 /// Convert MessageTarget to database representation.
-fn target_to_db(target: &MessageTarget) -> (&'static str, String) {
+pub(super) fn target_to_db(target: &MessageTarget) -> (&'static str, String) {
     match target {
         MessageTarget::Room { room_id } => ("room", room_id.to_string()),
         MessageTarget::DirectMessage { recipient } => ("dm", recipient.to_string()),
     }
 }
 
+/// Add or refresh a message's entry in the full-text search index.
+async fn index_for_search(
+    pool: &sqlx::SqlitePool,
+    message_id: MessageId,
+    content: &str,
+) -> Result<()> {
+    sqlx::query("DELETE FROM messages_fts WHERE message_id = ?")
+        .bind(message_id.to_string())
+        .execute(pool)
+        .await?;
+    sqlx::query("INSERT INTO messages_fts(message_id, content) VALUES (?, ?)")
+        .bind(message_id.to_string())
+        .bind(content)
+        .execute(pool)
+        .await?;
+
+    Ok(())
+}
+
+/// Remove a message from the full-text search index.
+async fn unindex_for_search(pool: &sqlx::SqlitePool, message_id: MessageId) -> Result<()> {
+    sqlx::query("DELETE FROM messages_fts WHERE message_id = ?")
+        .bind(message_id.to_string())
+        .execute(pool)
+        .await?;
+
+    Ok(())
+}
+
+/// Append a revision row capturing content as it stood before an edit/delete.
+async fn record_revision(
+    pool: &sqlx::SqlitePool,
+    message_id: MessageId,
+    content: &str,
+    kind: RevisionKind,
+    recorded_at: i64,
+) -> Result<()> {
+    sqlx::query(
+        r#"
+        INSERT INTO message_history (message_id, content, operation, recorded_at)
+        VALUES (?, ?, ?, ?)
+        "#,
+    )
+    .bind(message_id.to_string())
+    .bind(content)
+    .bind(revision_kind_to_db(kind))
+    .bind(recorded_at)
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+/// Convert RevisionKind to database representation.
+fn revision_kind_to_db(kind: RevisionKind) -> &'static str {
+    match kind {
+        RevisionKind::Edit => "edit",
+        RevisionKind::Delete => "delete",
+    }
+}
+
+/// Convert a database row to a MessageRevision.
+fn row_to_revision(message_id: MessageId, row: sqlx::sqlite::SqliteRow) -> Result<MessageRevision> {
+    let content: String = row.get("content");
+    let operation: String = row.get("operation");
+    let recorded_at: i64 = row.get("recorded_at");
+
+    let kind = match operation.as_str() {
+        "edit" => RevisionKind::Edit,
+        "delete" => RevisionKind::Delete,
+        _ => {
+            return Err(crate::Error::Internal(format!(
+                "Unknown revision operation: {operation}"
+            )))
+        }
+    };
+
+    Ok(MessageRevision {
+        message_id,
+        content: MessageContent::new_unchecked(content),
+        kind,
+        recorded_at: chrono::DateTime::from_timestamp(recorded_at, 0).unwrap_or_default(),
+    })
+}
+
 /// Convert a database row to a Message.
 fn row_to_message(row: sqlx::sqlite::SqliteRow) -> Result<Message> {
     let id: String = row.get("id");
@@ -549,4 +1054,375 @@ mod tests {
             .unwrap();
         assert_eq!(page4.len(), 1);
     }
+
+    #[tokio::test]
+    async fn test_find_by_room_before_pages_through_history() {
+        let storage = setup().await;
+
+        let user = test_user("sender");
+        UserRepository::create(&storage, &user, "password")
+            .await
+            .unwrap();
+
+        let room = test_room("general", &user);
+        RoomRepository::create(&storage, &room).await.unwrap();
+
+        for i in 0..5 {
+            let message = test_message(&user, &room, &format!("Message {i}"));
+            MessageRepository::create(&storage, &message).await.unwrap();
+        }
+
+        // First page: most recent 2.
+        let page1 = MessageRepository::find_by_room_before(&storage, room.id, None, 2)
+            .await
+            .unwrap();
+        assert_eq!(page1.len(), 2);
+
+        // Next page, paging before the oldest message we've seen.
+        let cursor = MessageCursor::before(&page1[1]);
+        let page2 = MessageRepository::find_by_room_before(&storage, room.id, Some(cursor), 2)
+            .await
+            .unwrap();
+        assert_eq!(page2.len(), 2);
+
+        // No duplicates across pages.
+        assert!(page1.iter().all(|m| !page2.iter().any(|n| n.id == m.id)));
+    }
+
+    #[tokio::test]
+    async fn test_pin_unpin_and_list_pinned() {
+        let storage = setup().await;
+
+        let user = test_user("sender");
+        UserRepository::create(&storage, &user, "password")
+            .await
+            .unwrap();
+
+        let room = test_room("general", &user);
+        RoomRepository::create(&storage, &room).await.unwrap();
+
+        let message = test_message(&user, &room, "Pin me");
+        MessageRepository::create(&storage, &message).await.unwrap();
+
+        MessageRepository::pin_message(&storage, room.id, message.id, user.id)
+            .await
+            .unwrap();
+
+        let pinned = MessageRepository::list_pinned(&storage, room.id)
+            .await
+            .unwrap();
+        assert_eq!(pinned.len(), 1);
+        assert_eq!(pinned[0].id, message.id);
+
+        // Pinning again is idempotent, not a duplicate/error.
+        MessageRepository::pin_message(&storage, room.id, message.id, user.id)
+            .await
+            .unwrap();
+        let pinned = MessageRepository::list_pinned(&storage, room.id)
+            .await
+            .unwrap();
+        assert_eq!(pinned.len(), 1);
+
+        MessageRepository::unpin_message(&storage, room.id, message.id)
+            .await
+            .unwrap();
+        let pinned = MessageRepository::list_pinned(&storage, room.id)
+            .await
+            .unwrap();
+        assert!(pinned.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_delete_unpins_message() {
+        let storage = setup().await;
+
+        let user = test_user("sender");
+        UserRepository::create(&storage, &user, "password")
+            .await
+            .unwrap();
+
+        let room = test_room("general", &user);
+        RoomRepository::create(&storage, &room).await.unwrap();
+
+        let message = test_message(&user, &room, "Pin then delete");
+        MessageRepository::create(&storage, &message).await.unwrap();
+        MessageRepository::pin_message(&storage, room.id, message.id, user.id)
+            .await
+            .unwrap();
+
+        MessageRepository::delete(&storage, message.id)
+            .await
+            .unwrap();
+
+        let pinned = MessageRepository::list_pinned(&storage, room.id)
+            .await
+            .unwrap();
+        assert!(pinned.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_move_message_between_rooms() {
+        let storage = setup().await;
+
+        let user = test_user("sender");
+        UserRepository::create(&storage, &user, "password")
+            .await
+            .unwrap();
+
+        let room = test_room("general", &user);
+        RoomRepository::create(&storage, &room).await.unwrap();
+
+        let mod_room = test_room("moderation", &user);
+        RoomRepository::create(&storage, &mod_room).await.unwrap();
+
+        let message = test_message(&user, &room, "Bad post");
+        MessageRepository::create(&storage, &message).await.unwrap();
+
+        MessageRepository::move_message(
+            &storage,
+            message.id,
+            MessageTarget::Room {
+                room_id: mod_room.id,
+            },
+        )
+        .await
+        .unwrap();
+
+        let moved = MessageRepository::find_by_id(&storage, message.id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(moved.room_id(), Some(mod_room.id));
+
+        let count = MessageRepository::count_by_room(&storage, room.id)
+            .await
+            .unwrap();
+        assert_eq!(count, 0);
+    }
+
+    #[tokio::test]
+    async fn test_create_if_absent_is_idempotent() {
+        let storage = setup().await;
+
+        let user = test_user("sender");
+        UserRepository::create(&storage, &user, "password")
+            .await
+            .unwrap();
+
+        let room = test_room("general", &user);
+        RoomRepository::create(&storage, &room).await.unwrap();
+
+        let message = test_message(&user, &room, "Resent message");
+
+        let inserted = MessageRepository::create_if_absent(&storage, &message)
+            .await
+            .unwrap();
+        assert!(inserted);
+
+        // A retry/replay with the same id is a no-op, not an error.
+        let inserted_again = MessageRepository::create_if_absent(&storage, &message)
+            .await
+            .unwrap();
+        assert!(!inserted_again);
+
+        let count = MessageRepository::count_by_room(&storage, room.id)
+            .await
+            .unwrap();
+        assert_eq!(count, 1);
+    }
+
+    #[tokio::test]
+    async fn test_find_by_room_range() {
+        let storage = setup().await;
+
+        let user = test_user("sender");
+        UserRepository::create(&storage, &user, "password")
+            .await
+            .unwrap();
+
+        let room = test_room("general", &user);
+        RoomRepository::create(&storage, &room).await.unwrap();
+
+        let message = test_message(&user, &room, "In range");
+        MessageRepository::create(&storage, &message).await.unwrap();
+
+        let now = chrono::Utc::now();
+        let in_range = MessageRepository::find_by_room_range(
+            &storage,
+            room.id,
+            now - chrono::Duration::minutes(1),
+            now + chrono::Duration::minutes(1),
+            Pagination::default(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(in_range.len(), 1);
+
+        let out_of_range = MessageRepository::find_by_room_range(
+            &storage,
+            room.id,
+            now + chrono::Duration::minutes(1),
+            now + chrono::Duration::minutes(2),
+            Pagination::default(),
+        )
+        .await
+        .unwrap();
+        assert!(out_of_range.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_update_records_history() {
+        let storage = setup().await;
+
+        let user = test_user("sender");
+        UserRepository::create(&storage, &user, "password")
+            .await
+            .unwrap();
+
+        let room = test_room("general", &user);
+        RoomRepository::create(&storage, &room).await.unwrap();
+
+        let mut message = test_message(&user, &room, "Original");
+        MessageRepository::create(&storage, &message).await.unwrap();
+
+        message.content = MessageContent::new("Edited").unwrap();
+        MessageRepository::update(&storage, &message).await.unwrap();
+
+        let history = MessageRepository::find_history(&storage, message.id)
+            .await
+            .unwrap();
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].content.as_str(), "Original");
+        assert_eq!(history[0].kind, RevisionKind::Edit);
+    }
+
+    #[tokio::test]
+    async fn test_delete_is_soft_and_preserves_history() {
+        let storage = setup().await;
+
+        let user = test_user("sender");
+        UserRepository::create(&storage, &user, "password")
+            .await
+            .unwrap();
+
+        let room = test_room("general", &user);
+        RoomRepository::create(&storage, &room).await.unwrap();
+
+        let message = test_message(&user, &room, "To be deleted");
+        MessageRepository::create(&storage, &message).await.unwrap();
+
+        MessageRepository::delete(&storage, message.id)
+            .await
+            .unwrap();
+
+        // Hidden from normal reads...
+        let found = MessageRepository::find_by_id(&storage, message.id)
+            .await
+            .unwrap();
+        assert!(found.is_none());
+
+        // ...but its final content survives as a revision.
+        let history = MessageRepository::find_history(&storage, message.id)
+            .await
+            .unwrap();
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].content.as_str(), "To be deleted");
+        assert_eq!(history[0].kind, RevisionKind::Delete);
+
+        // Deleting again fails: it's already gone from the live set.
+        assert!(MessageRepository::delete(&storage, message.id)
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_hard_delete_removes_row() {
+        let storage = setup().await;
+
+        let user = test_user("sender");
+        UserRepository::create(&storage, &user, "password")
+            .await
+            .unwrap();
+
+        let room = test_room("general", &user);
+        RoomRepository::create(&storage, &room).await.unwrap();
+
+        let message = test_message(&user, &room, "Gone for good");
+        MessageRepository::create(&storage, &message).await.unwrap();
+
+        MessageRepository::hard_delete(&storage, message.id)
+            .await
+            .unwrap();
+
+        assert!(MessageRepository::hard_delete(&storage, message.id)
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_search_by_text_and_sender() {
+        let storage = setup().await;
+
+        let alice = test_user("alice");
+        let bob = test_user("bob");
+        UserRepository::create(&storage, &alice, "password")
+            .await
+            .unwrap();
+        UserRepository::create(&storage, &bob, "password")
+            .await
+            .unwrap();
+
+        let room = test_room("general", &alice);
+        RoomRepository::create(&storage, &room).await.unwrap();
+
+        let from_alice = test_message(&alice, &room, "the quick brown fox");
+        MessageRepository::create(&storage, &from_alice)
+            .await
+            .unwrap();
+        let from_bob = test_message(&bob, &room, "a lazy dog");
+        MessageRepository::create(&storage, &from_bob)
+            .await
+            .unwrap();
+
+        let by_text = MessageRepository::search(&storage, &MessageQuery::new().text("quick"))
+            .await
+            .unwrap();
+        assert_eq!(by_text.len(), 1);
+        assert_eq!(by_text[0].id, from_alice.id);
+
+        let by_sender = MessageRepository::search(&storage, &MessageQuery::new().sender(bob.id))
+            .await
+            .unwrap();
+        assert_eq!(by_sender.len(), 1);
+        assert_eq!(by_sender[0].id, from_bob.id);
+
+        let no_match = MessageRepository::search(&storage, &MessageQuery::new().text("elephant"))
+            .await
+            .unwrap();
+        assert!(no_match.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_search_excludes_deleted_messages() {
+        let storage = setup().await;
+
+        let user = test_user("sender");
+        UserRepository::create(&storage, &user, "password")
+            .await
+            .unwrap();
+
+        let room = test_room("general", &user);
+        RoomRepository::create(&storage, &room).await.unwrap();
+
+        let message = test_message(&user, &room, "searchable content");
+        MessageRepository::create(&storage, &message).await.unwrap();
+        MessageRepository::delete(&storage, message.id)
+            .await
+            .unwrap();
+
+        let results = MessageRepository::search(&storage, &MessageQuery::new().text("searchable"))
+            .await
+            .unwrap();
+        assert!(results.is_empty());
+    }
 }
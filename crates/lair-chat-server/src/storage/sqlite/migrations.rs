@@ -10,6 +10,11 @@ pub fn all() -> Vec<(&'static str, &'static str)> {
     vec![
         ("001_initial_schema", MIGRATION_001),
         ("002_add_indexes", MIGRATION_002),
+        ("003_message_history", MIGRATION_003),
+        ("004_pinned_messages", MIGRATION_004),
+        ("005_message_search_index", MIGRATION_005),
+        ("006_message_markers", MIGRATION_006),
+        ("007_room_succession", MIGRATION_007),
     ]
 }
 
@@ -117,3 +122,80 @@ CREATE INDEX IF NOT EXISTS idx_invitations_invitee_id ON invitations(invitee_id)
 CREATE INDEX IF NOT EXISTS idx_invitations_inviter_id ON invitations(inviter_id);
 CREATE INDEX IF NOT EXISTS idx_invitations_status ON invitations(status)
 "#;
+
+/// Edit/delete audit log for messages, plus the soft-delete marker column.
+const MIGRATION_003: &str = r#"
+-- Soft-delete marker: NULL means the message is still live.
+ALTER TABLE messages ADD COLUMN deleted_at INTEGER;
+
+-- Prior versions of a message, recorded before an edit or delete.
+CREATE TABLE IF NOT EXISTS message_history (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    message_id TEXT NOT NULL REFERENCES messages(id) ON DELETE CASCADE,
+    content TEXT NOT NULL,
+    operation TEXT NOT NULL,
+    recorded_at INTEGER NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_message_history_message_id ON message_history(message_id)
+"#;
+
+/// Pinned messages, keyed per room.
+const MIGRATION_004: &str = r#"
+CREATE TABLE IF NOT EXISTS pinned_messages (
+    room_id TEXT NOT NULL REFERENCES rooms(id) ON DELETE CASCADE,
+    message_id TEXT NOT NULL REFERENCES messages(id) ON DELETE CASCADE,
+    pinned_at INTEGER NOT NULL,
+    pinned_by TEXT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    PRIMARY KEY (room_id, message_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_pinned_messages_room_id ON pinned_messages(room_id)
+"#;
+
+/// Full-text search index over message content.
+///
+/// `messages.id` is a TEXT uuid rather than an integer rowid, so this uses a
+/// standalone FTS5 table (not an "external content" table keyed by
+/// `content_rowid`). `apply_migration` splits each migration on bare `;`,
+/// which would mangle multi-statement `CREATE TRIGGER ... BEGIN ... END`
+/// bodies, so the index is kept in sync from the repository methods instead
+/// (the same approach already used to keep `pinned_messages` in sync on
+/// delete/move) rather than with triggers.
+const MIGRATION_005: &str = r#"
+CREATE VIRTUAL TABLE IF NOT EXISTS messages_fts USING fts5(
+    message_id UNINDEXED,
+    content
+);
+
+INSERT INTO messages_fts(message_id, content)
+SELECT id, content FROM messages WHERE deleted_at IS NULL
+"#;
+
+/// Delivery/read markers, one row per `(user, target, kind)`.
+///
+/// Markers are "up to" cumulative, so only the latest `message_id` per
+/// triple is kept; a new marker overwrites rather than appends (see
+/// `MarkerRepository::set_marker`).
+const MIGRATION_006: &str = r#"
+CREATE TABLE IF NOT EXISTS message_markers (
+    user_id TEXT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    target_type TEXT NOT NULL,
+    target_id TEXT NOT NULL,
+    kind TEXT NOT NULL,
+    message_id TEXT NOT NULL REFERENCES messages(id) ON DELETE CASCADE,
+    updated_at INTEGER NOT NULL,
+    PRIMARY KEY (user_id, target_type, target_id, kind)
+);
+
+CREATE INDEX IF NOT EXISTS idx_message_markers_target ON message_markers(target_type, target_id)
+"#;
+
+/// Room succession: the column a tombstoned room points its successor through.
+///
+/// NULL means the room is live; once set it never changes back, so no index
+/// is needed beyond the implicit one on the foreign key lookups that already
+/// go through `id`.
+const MIGRATION_007: &str = r#"
+ALTER TABLE rooms ADD COLUMN successor_room_id TEXT REFERENCES rooms(id)
+"#;
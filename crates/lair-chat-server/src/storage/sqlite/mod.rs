@@ -24,6 +24,7 @@
 //! - `:memory:`: In-memory database (for testing)
 
 mod invitations;
+mod markers;
 mod messages;
 mod migrations;
 mod rooms;
@@ -18,8 +18,8 @@ impl RoomRepository for SqliteStorage {
 
         sqlx::query(
             r#"
-            INSERT INTO rooms (id, name, description, owner_id, is_private, max_members, created_at, updated_at)
-            VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+            INSERT INTO rooms (id, name, description, owner_id, is_private, max_members, created_at, updated_at, successor_room_id)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
             "#,
         )
         .bind(room.id.to_string())
@@ -30,6 +30,7 @@ impl RoomRepository for SqliteStorage {
         .bind(room.settings.max_members.map(|m| m as i64))
         .bind(now)
         .bind(now)
+        .bind(room.successor_room_id.map(|id| id.to_string()))
         .execute(&self.pool)
         .await?;
 
@@ -39,7 +40,7 @@ impl RoomRepository for SqliteStorage {
     async fn find_by_id(&self, id: RoomId) -> Result<Option<Room>> {
         let row = sqlx::query(
             r#"
-            SELECT id, name, description, owner_id, is_private, max_members, created_at, updated_at
+            SELECT id, name, description, owner_id, is_private, max_members, created_at, updated_at, successor_room_id
             FROM rooms WHERE id = ?
             "#,
         )
@@ -56,7 +57,7 @@ impl RoomRepository for SqliteStorage {
     async fn find_by_name(&self, name: &str) -> Result<Option<Room>> {
         let row = sqlx::query(
             r#"
-            SELECT id, name, description, owner_id, is_private, max_members, created_at, updated_at
+            SELECT id, name, description, owner_id, is_private, max_members, created_at, updated_at, successor_room_id
             FROM rooms WHERE name = ? COLLATE NOCASE
             "#,
         )
@@ -76,7 +77,7 @@ impl RoomRepository for SqliteStorage {
         let result = sqlx::query(
             r#"
             UPDATE rooms
-            SET name = ?, description = ?, owner_id = ?, is_private = ?, max_members = ?, updated_at = ?
+            SET name = ?, description = ?, owner_id = ?, is_private = ?, max_members = ?, updated_at = ?, successor_room_id = ?
             WHERE id = ?
             "#,
         )
@@ -86,6 +87,7 @@ impl RoomRepository for SqliteStorage {
         .bind(room.settings.is_private)
         .bind(room.settings.max_members.map(|m| m as i64))
         .bind(now)
+        .bind(room.successor_room_id.map(|id| id.to_string()))
         .bind(room.id.to_string())
         .execute(&self.pool)
         .await?;
@@ -113,7 +115,7 @@ impl RoomRepository for SqliteStorage {
     async fn list_public(&self, pagination: Pagination) -> Result<Vec<Room>> {
         let rows = sqlx::query(
             r#"
-            SELECT id, name, description, owner_id, is_private, max_members, created_at, updated_at
+            SELECT id, name, description, owner_id, is_private, max_members, created_at, updated_at, successor_room_id
             FROM rooms
             WHERE is_private = 0
             ORDER BY name ASC
@@ -131,7 +133,7 @@ impl RoomRepository for SqliteStorage {
     async fn list_for_user(&self, user_id: UserId) -> Result<Vec<Room>> {
         let rows = sqlx::query(
             r#"
-            SELECT r.id, r.name, r.description, r.owner_id, r.is_private, r.max_members, r.created_at, r.updated_at
+            SELECT r.id, r.name, r.description, r.owner_id, r.is_private, r.max_members, r.created_at, r.updated_at, r.successor_room_id
             FROM rooms r
             INNER JOIN room_memberships m ON r.id = m.room_id
             WHERE m.user_id = ?
@@ -314,6 +316,7 @@ fn row_to_room(row: sqlx::sqlite::SqliteRow) -> Result<Room> {
     let is_private: bool = row.get("is_private");
     let max_members: Option<i64> = row.get("max_members");
     let created_at: i64 = row.get("created_at");
+    let successor_room_id: Option<String> = row.get("successor_room_id");
 
     Ok(Room {
         id: RoomId::parse(&id).map_err(|e| crate::Error::Internal(e.to_string()))?,
@@ -325,6 +328,9 @@ fn row_to_room(row: sqlx::sqlite::SqliteRow) -> Result<Room> {
             max_members: max_members.map(|m| m as u32),
         },
         created_at: chrono::DateTime::from_timestamp(created_at, 0).unwrap_or_default(),
+        successor_room_id: successor_room_id
+            .map(|id| RoomId::parse(&id).map_err(|e| crate::Error::Internal(e.to_string())))
+            .transpose()?,
     })
 }
 
@@ -52,12 +52,14 @@ impl SessionRepository for SqliteStorage {
         let result = sqlx::query(
             r#"
             UPDATE sessions
-            SET expires_at = ?, last_active_at = ?
+            SET expires_at = ?, last_active_at = ?, ip_address = ?, user_agent = ?
             WHERE id = ?
             "#,
         )
         .bind(session.expires_at.timestamp())
         .bind(session.last_active_at.timestamp())
+        .bind(&session.ip_address)
+        .bind(&session.user_agent)
         .bind(session.id.to_string())
         .execute(&self.pool)
         .await?;
@@ -264,6 +266,55 @@ mod tests {
         assert!(found.last_active_at >= found.created_at);
     }
 
+    #[tokio::test]
+    async fn test_create_session_persists_device_info() {
+        let storage = setup().await;
+
+        let user = test_user();
+        UserRepository::create(&storage, &user, "password")
+            .await
+            .unwrap();
+
+        let session = test_session(&user).with_device(
+            Some("10.0.0.5".to_string()),
+            Some("pixel-phone".to_string()),
+        );
+        SessionRepository::create(&storage, &session).await.unwrap();
+
+        let found = SessionRepository::find_by_id(&storage, session.id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(found.ip_address, Some("10.0.0.5".to_string()));
+        assert_eq!(found.user_agent, Some("pixel-phone".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_update_session_persists_device_info() {
+        let storage = setup().await;
+
+        let user = test_user();
+        UserRepository::create(&storage, &user, "password")
+            .await
+            .unwrap();
+
+        let session = test_session(&user);
+        SessionRepository::create(&storage, &session).await.unwrap();
+
+        let updated = session.with_device(
+            Some("10.0.0.5".to_string()),
+            Some("pixel-phone".to_string()),
+        );
+        SessionRepository::update(&storage, &updated).await.unwrap();
+
+        let found = SessionRepository::find_by_id(&storage, updated.id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(found.ip_address, Some("10.0.0.5".to_string()));
+        assert_eq!(found.user_agent, Some("pixel-phone".to_string()));
+    }
+
     #[tokio::test]
     async fn test_delete_session() {
         let storage = setup().await;
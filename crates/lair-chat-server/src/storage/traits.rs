@@ -6,9 +6,12 @@
 
 use async_trait::async_trait;
 
+use chrono::{DateTime, Utc};
+
 use crate::domain::{
-    Invitation, InvitationId, InvitationStatus, Message, MessageId, MessageTarget, Pagination,
-    Room, RoomId, RoomMembership, RoomRole, Session, SessionId, User, UserId,
+    Invitation, InvitationId, InvitationStatus, MarkerKind, Message, MessageCursor, MessageId,
+    MessageQuery, MessageRevision, MessageTarget, Pagination, Room, RoomId, RoomMembership,
+    RoomRole, Session, SessionId, User, UserId,
 };
 use crate::Result;
 
@@ -142,15 +145,39 @@ pub trait MessageRepository: Send + Sync {
     /// Create a new message.
     async fn create(&self, message: &Message) -> Result<()>;
 
+    /// Insert a message only if its id isn't already present.
+    ///
+    /// Returns `true` if the message was newly inserted, `false` if a
+    /// message with this id already existed (the existing row is left
+    /// untouched). Lets callers safely retry delivery or ingest the same
+    /// message id from multiple sources — e.g. a client resend or a
+    /// cross-node federation replay — without erroring or duplicating.
+    async fn create_if_absent(&self, message: &Message) -> Result<bool>;
+
     /// Find a message by its ID.
     async fn find_by_id(&self, id: MessageId) -> Result<Option<Message>>;
 
     /// Update a message (for edits).
+    ///
+    /// The prior content is recorded as a [`MessageRevision`] before the
+    /// update is applied, so `find_history` can show what it said before.
     async fn update(&self, message: &Message) -> Result<()>;
 
-    /// Delete a message.
+    /// Soft-delete a message, preserving its final content as a revision.
+    ///
+    /// The underlying row is kept (marked deleted) so moderators can still
+    /// inspect its history; use [`hard_delete`](Self::hard_delete) to remove
+    /// a message and its history permanently.
     async fn delete(&self, id: MessageId) -> Result<()>;
 
+    /// Permanently remove a message and its revision history.
+    async fn hard_delete(&self, id: MessageId) -> Result<()>;
+
+    /// Get the ordered history of prior revisions for a message.
+    ///
+    /// Revisions are returned oldest-first, one per edit or delete.
+    async fn find_history(&self, message_id: MessageId) -> Result<Vec<MessageRevision>>;
+
     /// Get messages for a room with pagination.
     ///
     /// Messages are returned in reverse chronological order (newest first).
@@ -166,6 +193,53 @@ pub trait MessageRepository: Send + Sync {
         pagination: Pagination,
     ) -> Result<Vec<Message>>;
 
+    /// Get messages for a room older than `cursor`, newest first.
+    ///
+    /// Keyset (`created_at`, `id`) pagination for scrollback: unlike
+    /// offset-based `find_by_room`, this stays stable as new messages are
+    /// inserted concurrently. Pass `None` to start from the most recent
+    /// message.
+    async fn find_by_room_before(
+        &self,
+        room_id: RoomId,
+        cursor: Option<MessageCursor>,
+        limit: u32,
+    ) -> Result<Vec<Message>>;
+
+    /// Get direct messages between two users older than `cursor`, newest first.
+    ///
+    /// See [`find_by_room_before`](Self::find_by_room_before) for the paging
+    /// semantics.
+    async fn find_direct_messages_before(
+        &self,
+        user1: UserId,
+        user2: UserId,
+        cursor: Option<MessageCursor>,
+        limit: u32,
+    ) -> Result<Vec<Message>>;
+
+    /// Get messages for a room sent within `[from, to)`, newest first.
+    ///
+    /// Intended for date-filtered export, "jump to date" navigation, and
+    /// incremental sync, where pulling the whole room is wasteful.
+    async fn find_by_room_range(
+        &self,
+        room_id: RoomId,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+        pagination: Pagination,
+    ) -> Result<Vec<Message>>;
+
+    /// Get direct messages between two users sent within `[from, to)`, newest first.
+    async fn find_direct_messages_range(
+        &self,
+        user1: UserId,
+        user2: UserId,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+        pagination: Pagination,
+    ) -> Result<Vec<Message>>;
+
     /// Get all messages for a target (room or DM).
     async fn find_by_target(
         &self,
@@ -187,6 +261,56 @@ pub trait MessageRepository: Send + Sync {
 
     /// Delete all messages by a user.
     async fn delete_by_author(&self, author_id: UserId) -> Result<u64>;
+
+    /// Pin a message to a room.
+    async fn pin_message(&self, room_id: RoomId, message_id: MessageId, pinned_by: UserId) -> Result<()>;
+
+    /// Unpin a message from a room.
+    async fn unpin_message(&self, room_id: RoomId, message_id: MessageId) -> Result<()>;
+
+    /// List pinned messages in a room, most recently pinned first.
+    async fn list_pinned(&self, room_id: RoomId) -> Result<Vec<Message>>;
+
+    /// Relocate a message to a different target (e.g. moving a bad post
+    /// into a moderation room).
+    async fn move_message(&self, message_id: MessageId, new_target: MessageTarget) -> Result<()>;
+
+    /// Search messages matching a [`MessageQuery`], newest first.
+    ///
+    /// Unlike the `find_*` family, which each cover one fixed access
+    /// pattern, this compiles whichever fields are set on `query` into a
+    /// single filtered lookup — sender, room, date range and free text can
+    /// be combined freely.
+    async fn search(&self, query: &MessageQuery) -> Result<Vec<Message>>;
+}
+
+// ============================================================================
+// Marker Repository
+// ============================================================================
+
+/// Repository for delivery/read marker operations.
+///
+/// Markers are "up to" cumulative (see [`MarkerKind`]), so only the latest
+/// marker per `(user, target, kind)` is ever stored — setting a new one
+/// overwrites the old rather than appending.
+#[async_trait]
+pub trait MarkerRepository: Send + Sync {
+    /// Record that `user_id` has the given marker up to `message_id` for `target`.
+    async fn set_marker(
+        &self,
+        user_id: UserId,
+        target: &MessageTarget,
+        kind: MarkerKind,
+        message_id: MessageId,
+    ) -> Result<()>;
+
+    /// Get the latest message a user has marked for a target, if any.
+    async fn get_marker(
+        &self,
+        user_id: UserId,
+        target: &MessageTarget,
+        kind: MarkerKind,
+    ) -> Result<Option<MessageId>>;
 }
 
 // ============================================================================
@@ -278,6 +402,7 @@ pub trait Storage:
     + RoomRepository
     + MembershipRepository
     + MessageRepository
+    + MarkerRepository
     + SessionRepository
     + InvitationRepository
 {
@@ -289,6 +414,7 @@ impl<T> Storage for T where
         + RoomRepository
         + MembershipRepository
         + MessageRepository
+        + MarkerRepository
         + SessionRepository
         + InvitationRepository
 {
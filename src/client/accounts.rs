@@ -0,0 +1,228 @@
+//! Saved account module for Lair-Chat
+//! Provides persistent storage and management of previously used accounts
+//! so the login screen can offer a picker instead of retyping credentials.
+
+use std::path::PathBuf;
+use serde::{Deserialize, Serialize};
+use directories::ProjectDirs;
+use tokio::fs;
+use thiserror::Error;
+
+/// Error types for saved account operations
+#[derive(Debug, Error)]
+pub enum AccountError {
+    #[error("Failed to create accounts directory: {0}")]
+    DirectoryCreation(std::io::Error),
+
+    #[error("Failed to read accounts file: {0}")]
+    FileRead(std::io::Error),
+
+    #[error("Failed to write accounts file: {0}")]
+    FileWrite(std::io::Error),
+
+    #[error("Failed to serialize accounts: {0}")]
+    Serialization(#[from] serde_json::Error),
+
+    #[error("System error: {0}")]
+    System(String),
+
+    #[error("Account not found: {0}")]
+    NotFound(String),
+}
+
+/// Result type for saved account operations
+pub type AccountResult<T> = Result<T, AccountError>;
+
+/// A previously used account, remembered so it can be picked again without
+/// retyping credentials. `session_token` is an opaque, already-issued
+/// session token and is only present once the account has logged in
+/// successfully at least once.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SavedAccount {
+    /// Display name for the account picker (usually the username)
+    pub name: String,
+    /// Server address the account last connected to, e.g. "127.0.0.1:8080"
+    pub server: String,
+    /// Username used to authenticate
+    pub username: String,
+    /// Opaque saved session token, if the account can resume without
+    /// re-authenticating
+    pub session_token: Option<String>,
+    /// When the account was last used, for sorting the picker
+    pub last_used: u64,
+}
+
+impl SavedAccount {
+    pub fn new(name: String, server: String, username: String) -> Self {
+        Self {
+            name,
+            server,
+            username,
+            session_token: None,
+            last_used: Self::now(),
+        }
+    }
+
+    fn now() -> u64 {
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs()
+    }
+}
+
+/// Repository of saved accounts, persisted to disk as JSON
+#[derive(Clone)]
+pub struct AccountRepository {
+    accounts: Vec<SavedAccount>,
+    accounts_file: PathBuf,
+}
+
+impl AccountRepository {
+    /// Create a new account repository
+    pub fn new() -> AccountResult<Self> {
+        let project_dirs = ProjectDirs::from("com", "lair-chat", "lair-chat")
+            .ok_or_else(|| AccountError::System("Could not determine project directories".into()))?;
+
+        let data_dir = project_dirs.data_dir();
+        std::fs::create_dir_all(data_dir).map_err(AccountError::DirectoryCreation)?;
+
+        let accounts_file = data_dir.join("accounts.json");
+
+        Ok(Self {
+            accounts: Vec::new(),
+            accounts_file,
+        })
+    }
+
+    /// Load saved accounts from disk
+    pub async fn load(&mut self) -> AccountResult<()> {
+        if !self.accounts_file.exists() {
+            return Ok(());
+        }
+
+        let content = fs::read_to_string(&self.accounts_file)
+            .await
+            .map_err(AccountError::FileRead)?;
+
+        self.accounts = serde_json::from_str(&content)?;
+
+        Ok(())
+    }
+
+    /// Save accounts to disk
+    pub async fn save(&self) -> AccountResult<()> {
+        let json = serde_json::to_string_pretty(&self.accounts)?;
+        fs::write(&self.accounts_file, json)
+            .await
+            .map_err(AccountError::FileWrite)?;
+        Ok(())
+    }
+
+    /// Remember an account, replacing any existing entry with the same name
+    pub fn remember(&mut self, account: SavedAccount) {
+        self.accounts.retain(|existing| existing.name != account.name);
+        self.accounts.push(account);
+        self.accounts
+            .sort_by(|a, b| b.last_used.cmp(&a.last_used));
+    }
+
+    /// Mark an account as just used, bumping it to the top of the picker
+    pub fn touch(&mut self, name: &str) {
+        if let Some(account) = self.accounts.iter_mut().find(|account| account.name == name) {
+            account.last_used = SavedAccount::now();
+        }
+        self.accounts
+            .sort_by(|a, b| b.last_used.cmp(&a.last_used));
+    }
+
+    /// Forget a saved account by name
+    pub fn forget(&mut self, name: &str) -> AccountResult<()> {
+        let original_len = self.accounts.len();
+        self.accounts.retain(|account| account.name != name);
+
+        if self.accounts.len() == original_len {
+            return Err(AccountError::NotFound(name.to_string()));
+        }
+
+        Ok(())
+    }
+
+    /// List saved accounts, most recently used first
+    pub fn accounts(&self) -> &[SavedAccount] {
+        &self.accounts
+    }
+
+    /// Look up a saved account by name
+    pub fn get(&self, name: &str) -> Option<&SavedAccount> {
+        self.accounts.iter().find(|account| account.name == name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[tokio::test]
+    async fn test_account_persistence() {
+        let temp_dir = tempdir().unwrap();
+        let accounts_file = temp_dir.path().join("test_accounts.json");
+
+        let mut repository = AccountRepository {
+            accounts: Vec::new(),
+            accounts_file,
+        };
+
+        repository.remember(SavedAccount::new(
+            "alice".to_string(),
+            "127.0.0.1:8080".to_string(),
+            "alice".to_string(),
+        ));
+        repository.save().await.unwrap();
+
+        let mut reloaded = AccountRepository {
+            accounts: Vec::new(),
+            accounts_file: repository.accounts_file.clone(),
+        };
+        reloaded.load().await.unwrap();
+
+        assert_eq!(reloaded.accounts().len(), 1);
+        assert_eq!(reloaded.accounts()[0].name, "alice");
+    }
+
+    #[test]
+    fn test_remember_replaces_existing_by_name() {
+        let mut repository = AccountRepository {
+            accounts: Vec::new(),
+            accounts_file: PathBuf::from("test"),
+        };
+
+        repository.remember(SavedAccount::new(
+            "alice".to_string(),
+            "127.0.0.1:8080".to_string(),
+            "alice".to_string(),
+        ));
+        repository.remember(SavedAccount::new(
+            "alice".to_string(),
+            "example.com:9000".to_string(),
+            "alice".to_string(),
+        ));
+
+        assert_eq!(repository.accounts().len(), 1);
+        assert_eq!(repository.accounts()[0].server, "example.com:9000");
+    }
+
+    #[test]
+    fn test_forget_unknown_account_errors() {
+        let mut repository = AccountRepository {
+            accounts: Vec::new(),
+            accounts_file: PathBuf::from("test"),
+        };
+
+        assert!(matches!(
+            repository.forget("nobody"),
+            Err(AccountError::NotFound(_))
+        ));
+    }
+}
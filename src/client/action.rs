@@ -38,6 +38,8 @@ pub enum Action {
     RegistrationSuccess(String),
     // Message actions
     SendMessage(String),
+    SendMessageWithAttachments(String, Vec<String>),
+    SearchMessages(String),
     ReceiveMessage(String),
     RecordReceivedMessage,
     RecordSentMessage,
@@ -48,4 +50,7 @@ pub enum Action {
     ReturnToLobby,               // Exit DM mode and return to Lobby
     // Connection status actions
     ConnectionStatusChanged(crate::transport::ConnectionStatus),
+    // Saved account actions
+    SelectAccount(String), // Resume a saved account by name
+    ForgetAccount(String), // Remove a saved account by name
 }
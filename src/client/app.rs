@@ -6,6 +6,7 @@ use tokio::sync::mpsc;
 use tracing::{debug, error, info, warn};
 
 use crate::{
+    accounts::{AccountRepository, SavedAccount},
     action::Action,
     auth::{AuthState, Credentials},
     common::{
@@ -22,6 +23,7 @@ use crate::{
     },
     config::Config,
     connection_manager::ConnectionManager,
+    events::{AppEvent, EventDispatcher},
     tui::{Event, Tui},
 };
 
@@ -45,6 +47,7 @@ pub struct App {
     auth_state: AuthState,
     login_screen: LoginScreen,
     auth_status: AuthStatusBar,
+    account_repository: AccountRepository,
 
     // Main application components
     home_component: Home,
@@ -53,6 +56,15 @@ pub struct App {
 
     // Server-provided user list for DM discovery
     connected_users: Vec<String>,
+
+    // Address the login screen's saved accounts are remembered against
+    server_address: String,
+
+    /// Merges terminal input with a `CancellationToken` so `Action::Quit`
+    /// lets in-flight background tasks (login, register, reconnect)
+    /// started with `tokio::spawn` stop cleanly instead of racing the
+    /// process shutting down underneath them.
+    dispatcher: EventDispatcher,
 }
 
 /// Observer for handling ConnectionManager messages and events
@@ -140,10 +152,13 @@ impl App {
 
         // Create modern ConnectionManager with transport
         let connection_config = ConnectionConfig {
-            address: "127.0.0.1:8080".parse().unwrap(),
+            address: "127.0.0.1:8080".to_string(),
             timeout_ms: 5000,
+            max_reconnect_attempts: 5,
+            framing: crate::transport::Framing::LineDelimited,
         };
 
+        let server_address = connection_config.address.clone();
         let mut connection_manager = ConnectionManager::new(connection_config.clone());
         let transport = Box::new(TcpTransport::new(connection_config));
         connection_manager.with_transport(transport);
@@ -172,6 +187,7 @@ impl App {
             auth_state: AuthState::Unauthenticated,
             login_screen: LoginScreen::new(),
             auth_status: AuthStatusBar::new(),
+            account_repository: AccountRepository::new()?,
 
             // Main components
             home_component: Home::new_with_options(text_only),
@@ -180,6 +196,9 @@ impl App {
 
             // Server-provided user list
             connected_users: Vec::new(),
+
+            server_address,
+            dispatcher: EventDispatcher::new(),
         })
     }
 
@@ -194,6 +213,13 @@ impl App {
         let size = tui.size()?;
         self.init_components(size.into())?;
 
+        // Load saved accounts so the login screen can offer a picker
+        if let Err(e) = self.account_repository.load().await {
+            warn!("Failed to load saved accounts: {}", e);
+        }
+        self.login_screen
+            .set_accounts(self.account_repository.accounts().to_vec());
+
         // Set up action sender for transport layer to update status bar (legacy compatibility)
         // This is needed because authentication still uses legacy transport
         // TODO: Remove this once legacy transport is fully eliminated
@@ -249,10 +275,20 @@ impl App {
     }
 
     async fn handle_events(&mut self, tui: &mut Tui) -> Result<()> {
-        let Some(event) = tui.next_event().await else {
+        let Some(event) = self.dispatcher.next_event(tui).await else {
             return Ok(());
         };
 
+        let event = match event {
+            AppEvent::Shutdown => {
+                // The token was already cancelled (e.g. a background task
+                // requested shutdown); make sure the loop actually quits.
+                self.action_tx.send(Action::Quit)?;
+                return Ok(());
+            }
+            AppEvent::Input(event) => event,
+        };
+
         let action_tx = self.action_tx.clone();
         match event {
             Event::Quit => action_tx.send(Action::Quit)?,
@@ -369,6 +405,18 @@ impl App {
                 Ok(None)
             }
             Action::Quit => {
+                // Cancel in-flight background tasks before tearing down so
+                // they don't keep touching state after the TUI exits.
+                self.dispatcher.cancel();
+
+                let connection_manager = Arc::clone(&self.connection_manager);
+                tokio::spawn(async move {
+                    let mut manager = connection_manager.lock().await;
+                    if let Err(e) = manager.disconnect().await {
+                        error!("Error disconnecting during shutdown: {}", e);
+                    }
+                });
+
                 self.should_quit = true;
                 Ok(None)
             }
@@ -450,13 +498,21 @@ impl App {
 
                 // Use modern ConnectionManager to disconnect
                 let connection_manager = Arc::clone(&self.connection_manager);
+                let shutdown = self.dispatcher.token();
                 tokio::spawn(async move {
-                    let mut manager = connection_manager.lock().await;
-                    let disconnect_result = manager.disconnect().await;
-                    if let Err(e) = disconnect_result {
-                        tracing::error!("Error during disconnect: {}", e);
-                    } else {
-                        tracing::info!("Successfully disconnected");
+                    tokio::select! {
+                        _ = shutdown.cancelled() => {
+                            tracing::debug!("Reconnect disconnect cancelled by shutdown");
+                        }
+                        _ = async {
+                            let mut manager = connection_manager.lock().await;
+                            let disconnect_result = manager.disconnect().await;
+                            if let Err(e) = disconnect_result {
+                                tracing::error!("Error during disconnect: {}", e);
+                            } else {
+                                tracing::info!("Successfully disconnected");
+                            }
+                        } => {}
                     }
                 });
 
@@ -544,11 +600,57 @@ impl App {
                     // Server will send welcome message, so we don't add duplicate client messages
 
                     info!("User {} authenticated and ready for chat", profile.username);
+
+                    // Remember this account so it shows up in the login screen's
+                    // saved-account picker next time.
+                    self.account_repository.remember(SavedAccount::new(
+                        profile.username.clone(),
+                        self.server_address.clone(),
+                        profile.username.clone(),
+                    ));
+                    self.login_screen
+                        .set_accounts(self.account_repository.accounts().to_vec());
+                    let account_repository_clone = self.account_repository.clone();
+                    tokio::spawn(async move {
+                        if let Err(e) = account_repository_clone.save().await {
+                            warn!("Failed to save accounts: {}", e);
+                        }
+                    });
                 }
 
                 Ok(None)
             }
 
+            Action::SelectAccount(name) => {
+                info!("Resuming saved account {}", name);
+                self.account_repository.touch(name);
+                self.login_screen
+                    .set_accounts(self.account_repository.accounts().to_vec());
+                let account_repository_clone = self.account_repository.clone();
+                tokio::spawn(async move {
+                    if let Err(e) = account_repository_clone.save().await {
+                        warn!("Failed to save accounts: {}", e);
+                    }
+                });
+                Ok(None)
+            }
+
+            Action::ForgetAccount(name) => {
+                if let Err(e) = self.account_repository.forget(name) {
+                    warn!("Failed to forget account {}: {}", name, e);
+                } else {
+                    self.login_screen
+                        .set_accounts(self.account_repository.accounts().to_vec());
+                    let account_repository_clone = self.account_repository.clone();
+                    tokio::spawn(async move {
+                        if let Err(e) = account_repository_clone.save().await {
+                            warn!("Failed to save accounts: {}", e);
+                        }
+                    });
+                }
+                Ok(None)
+            }
+
             Action::StartDMConversation(username) => {
                 // Handle starting a DM conversation - update status bar
                 self.status_bar
@@ -626,6 +728,24 @@ impl App {
                 Ok(None)
             }
 
+            Action::SendMessageWithAttachments(message, attachments) => {
+                info!(
+                    "DEBUG: SendMessageWithAttachments action received: '{}' ({} attachment(s))",
+                    message,
+                    attachments.len()
+                );
+                // Encode attachments with the same prefix-marker convention
+                // used elsewhere on the wire (DM:, USER_LIST:, etc.) until
+                // attachments get a dedicated protocol message.
+                let wire_message = if attachments.is_empty() {
+                    message.clone()
+                } else {
+                    format!("ATTACH:{}:{}", attachments.join(","), message)
+                };
+                self.handle_modern_send_message_sync(wire_message);
+                Ok(None)
+            }
+
             Action::ReceiveMessage(message) => {
                 // Filter out user list requests immediately - these should never be displayed
                 if message == "REQUEST_USER_LIST" {
@@ -899,7 +1019,11 @@ impl App {
 
                 // Use modern ConnectionManager for disconnection
                 let connection_manager = Arc::clone(&self.connection_manager);
+                let shutdown = self.dispatcher.token();
                 tokio::spawn(async move {
+                    if shutdown.is_cancelled() {
+                        return;
+                    }
                     let mut manager = connection_manager.lock().await;
                     if let Err(e) = manager.disconnect().await {
                         error!("Failed to disconnect: {}", e);
@@ -943,85 +1067,93 @@ impl App {
     fn handle_connection_manager_login(&mut self, credentials: Credentials) {
         let action_tx = self.action_tx.clone();
         let connection_manager = Arc::clone(&self.connection_manager);
+        let shutdown = self.dispatcher.token();
 
         // Set state to authenticating immediately
         self.auth_state = AuthState::Authenticating;
         self.auth_status.update_state(self.auth_state.clone());
 
         tokio::spawn(async move {
-            // Validate credentials
-            if credentials.username.is_empty() || credentials.password.is_empty() {
-                let _ = action_tx.send(Action::AuthenticationFailure(
-                    "Username and password are required".to_string(),
-                ));
-                return;
-            }
-
-            if credentials.username.len() < 3 {
-                let _ = action_tx.send(Action::AuthenticationFailure(
-                    "Username must be at least 3 characters".to_string(),
-                ));
-                return;
-            }
-
-            // Connect using ConnectionManager with server-compatible encryption
-            {
-                let mut manager = connection_manager.lock().await;
-                match manager.connect().await {
-                    Ok(()) => {
-                        info!("Successfully connected to server using ConnectionManager");
-                    }
-                    Err(e) => {
-                        error!("ConnectionManager connection failed: {}", e);
-                        let detailed_error = format!("Connection failed: {}. This could be due to: (1) Server not running - start with 'cargo run --bin lair-chat-server', (2) Server starting up - wait a moment and retry, (3) Port already in use, (4) Firewall blocking connection.", e);
-                        let _ = action_tx.send(Action::AuthenticationFailure(detailed_error));
+            tokio::select! {
+                _ = shutdown.cancelled() => {
+                    debug!("Login cancelled by shutdown");
+                }
+                _ = async {
+                    // Validate credentials
+                    if credentials.username.is_empty() || credentials.password.is_empty() {
+                        let _ = action_tx.send(Action::AuthenticationFailure(
+                            "Username and password are required".to_string(),
+                        ));
                         return;
                     }
-                }
-            }
 
-            // Login using ConnectionManager
-            {
-                let manager = connection_manager.lock().await;
-                match manager.login(credentials.clone()).await {
-                    Ok(()) => {
-                        info!("Login successful for user: {}", credentials.username);
-
-                        // Create a successful auth state
-                        let auth_state = AuthState::Authenticated {
-                            profile: crate::auth::UserProfile {
-                                id: uuid::Uuid::new_v4(),
-                                username: credentials.username.clone(),
-                                roles: vec!["user".to_string()],
-                            },
-                            session: crate::auth::Session {
-                                id: uuid::Uuid::new_v4(),
-                                token: format!("session_{}", credentials.username),
-                                created_at: std::time::SystemTime::now()
-                                    .duration_since(std::time::UNIX_EPOCH)
-                                    .unwrap()
-                                    .as_secs(),
-                                expires_at: std::time::SystemTime::now()
-                                    .duration_since(std::time::UNIX_EPOCH)
-                                    .unwrap()
-                                    .as_secs()
-                                    + 3600, // 1 hour expiration
-                            },
-                        };
+                    if credentials.username.len() < 3 {
+                        let _ = action_tx.send(Action::AuthenticationFailure(
+                            "Username must be at least 3 characters".to_string(),
+                        ));
+                        return;
+                    }
 
-                        // Add stabilization delay to ensure server-side login is complete
-                        // This prevents the first message sending issue after authentication
-                        tokio::time::sleep(std::time::Duration::from_millis(200)).await;
-                        let _ = action_tx.send(Action::AuthenticationSuccess(auth_state));
+                    // Connect using ConnectionManager with server-compatible encryption
+                    {
+                        let mut manager = connection_manager.lock().await;
+                        match manager.connect().await {
+                            Ok(()) => {
+                                info!("Successfully connected to server using ConnectionManager");
+                            }
+                            Err(e) => {
+                                error!("ConnectionManager connection failed: {}", e);
+                                let detailed_error = format!("Connection failed: {}. This could be due to: (1) Server not running - start with 'cargo run --bin lair-chat-server', (2) Server starting up - wait a moment and retry, (3) Port already in use, (4) Firewall blocking connection.", e);
+                                let _ = action_tx.send(Action::AuthenticationFailure(detailed_error));
+                                return;
+                            }
+                        }
                     }
-                    Err(e) => {
-                        error!("Login failed for {}: {}", credentials.username, e);
-                        let _ = action_tx.send(Action::AuthenticationFailure(format!(
-                            "Login failed: {}",
-                            e
-                        )));
+
+                    // Login using ConnectionManager
+                    {
+                        let manager = connection_manager.lock().await;
+                        match manager.login(credentials.clone()).await {
+                            Ok(()) => {
+                                info!("Login successful for user: {}", credentials.username);
+
+                                // Create a successful auth state
+                                let auth_state = AuthState::Authenticated {
+                                    profile: crate::auth::UserProfile {
+                                        id: uuid::Uuid::new_v4(),
+                                        username: credentials.username.clone(),
+                                        roles: vec!["user".to_string()],
+                                    },
+                                    session: crate::auth::Session {
+                                        id: uuid::Uuid::new_v4(),
+                                        token: format!("session_{}", credentials.username),
+                                        created_at: std::time::SystemTime::now()
+                                            .duration_since(std::time::UNIX_EPOCH)
+                                            .unwrap()
+                                            .as_secs(),
+                                        expires_at: std::time::SystemTime::now()
+                                            .duration_since(std::time::UNIX_EPOCH)
+                                            .unwrap()
+                                            .as_secs()
+                                            + 3600, // 1 hour expiration
+                                    },
+                                };
+
+                                // Add stabilization delay to ensure server-side login is complete
+                                // This prevents the first message sending issue after authentication
+                                tokio::time::sleep(std::time::Duration::from_millis(200)).await;
+                                let _ = action_tx.send(Action::AuthenticationSuccess(auth_state));
+                            }
+                            Err(e) => {
+                                error!("Login failed for {}: {}", credentials.username, e);
+                                let _ = action_tx.send(Action::AuthenticationFailure(format!(
+                                    "Login failed: {}",
+                                    e
+                                )));
+                            }
+                        }
                     }
-                }
+                } => {}
             }
         });
     }
@@ -1030,97 +1162,105 @@ impl App {
     fn handle_connection_manager_register(&mut self, credentials: Credentials) {
         let action_tx = self.action_tx.clone();
         let connection_manager = Arc::clone(&self.connection_manager);
+        let shutdown = self.dispatcher.token();
 
         // Set state to authenticating immediately
         self.auth_state = AuthState::Authenticating;
         self.auth_status.update_state(self.auth_state.clone());
 
         tokio::spawn(async move {
-            // Validate credentials
-            if credentials.username.is_empty() || credentials.password.is_empty() {
-                let _ = action_tx.send(Action::AuthenticationFailure(
-                    "Username and password are required".to_string(),
-                ));
-                return;
-            }
+            tokio::select! {
+                _ = shutdown.cancelled() => {
+                    debug!("Register cancelled by shutdown");
+                }
+                _ = async {
+                // Validate credentials
+                if credentials.username.is_empty() || credentials.password.is_empty() {
+                    let _ = action_tx.send(Action::AuthenticationFailure(
+                        "Username and password are required".to_string(),
+                    ));
+                    return;
+                }
 
-            if credentials.username.len() < 3 {
-                let _ = action_tx.send(Action::AuthenticationFailure(
-                    "Username must be at least 3 characters".to_string(),
-                ));
-                return;
-            }
+                if credentials.username.len() < 3 {
+                    let _ = action_tx.send(Action::AuthenticationFailure(
+                        "Username must be at least 3 characters".to_string(),
+                    ));
+                    return;
+                }
 
-            if credentials.password.len() < 6 {
-                let _ = action_tx.send(Action::AuthenticationFailure(
-                    "Password must be at least 6 characters".to_string(),
-                ));
-                return;
-            }
+                if credentials.password.len() < 6 {
+                    let _ = action_tx.send(Action::AuthenticationFailure(
+                        "Password must be at least 6 characters".to_string(),
+                    ));
+                    return;
+                }
 
-            // Connect using ConnectionManager with server-compatible encryption
-            {
-                let mut manager = connection_manager.lock().await;
-                match manager.connect().await {
-                    Ok(()) => {
-                        info!("Successfully connected to server using ConnectionManager");
-                    }
-                    Err(e) => {
-                        error!("ConnectionManager connection failed: {}", e);
-                        let detailed_error = format!("Connection failed: {}. This could be due to: (1) Server not running - start with 'cargo run --bin lair-chat-server', (2) Server starting up - wait a moment and retry, (3) Port already in use, (4) Firewall blocking connection.", e);
-                        let _ = action_tx.send(Action::AuthenticationFailure(detailed_error));
-                        return;
+                // Connect using ConnectionManager with server-compatible encryption
+                {
+                    let mut manager = connection_manager.lock().await;
+                    match manager.connect().await {
+                        Ok(()) => {
+                            info!("Successfully connected to server using ConnectionManager");
+                        }
+                        Err(e) => {
+                            error!("ConnectionManager connection failed: {}", e);
+                            let detailed_error = format!("Connection failed: {}. This could be due to: (1) Server not running - start with 'cargo run --bin lair-chat-server', (2) Server starting up - wait a moment and retry, (3) Port already in use, (4) Firewall blocking connection.", e);
+                            let _ = action_tx.send(Action::AuthenticationFailure(detailed_error));
+                            return;
+                        }
                     }
                 }
-            }
-
-            // Register using ConnectionManager
-            {
-                let manager = connection_manager.lock().await;
-                match manager.register(credentials.clone()).await {
-                    Ok(()) => {
-                        info!("Registration successful for user: {}", credentials.username);
-
-                        // Send registration success notification
-                        let _ = action_tx
-                            .send(Action::RegistrationSuccess(credentials.username.clone()));
-
-                        // Add stabilization delay to ensure server-side registration is complete
-                        // This prevents the first message sending issue after registration
-                        tokio::time::sleep(std::time::Duration::from_millis(200)).await;
 
-                        // Create a successful auth state for auto-login after registration
-                        let auth_state = AuthState::Authenticated {
-                            profile: crate::auth::UserProfile {
-                                id: uuid::Uuid::new_v4(),
-                                username: credentials.username.clone(),
-                                roles: vec!["user".to_string()],
-                            },
-                            session: crate::auth::Session {
-                                id: uuid::Uuid::new_v4(),
-                                token: format!("reg_session_{}", credentials.username),
-                                created_at: std::time::SystemTime::now()
-                                    .duration_since(std::time::UNIX_EPOCH)
-                                    .unwrap()
-                                    .as_secs(),
-                                expires_at: std::time::SystemTime::now()
-                                    .duration_since(std::time::UNIX_EPOCH)
-                                    .unwrap()
-                                    .as_secs()
-                                    + 3600, // 1 hour expiration
-                            },
-                        };
+                // Register using ConnectionManager
+                {
+                    let manager = connection_manager.lock().await;
+                    match manager.register(credentials.clone()).await {
+                        Ok(()) => {
+                            info!("Registration successful for user: {}", credentials.username);
+
+                            // Send registration success notification
+                            let _ = action_tx
+                                .send(Action::RegistrationSuccess(credentials.username.clone()));
+
+                            // Add stabilization delay to ensure server-side registration is complete
+                            // This prevents the first message sending issue after registration
+                            tokio::time::sleep(std::time::Duration::from_millis(200)).await;
+
+                            // Create a successful auth state for auto-login after registration
+                            let auth_state = AuthState::Authenticated {
+                                profile: crate::auth::UserProfile {
+                                    id: uuid::Uuid::new_v4(),
+                                    username: credentials.username.clone(),
+                                    roles: vec!["user".to_string()],
+                                },
+                                session: crate::auth::Session {
+                                    id: uuid::Uuid::new_v4(),
+                                    token: format!("reg_session_{}", credentials.username),
+                                    created_at: std::time::SystemTime::now()
+                                        .duration_since(std::time::UNIX_EPOCH)
+                                        .unwrap()
+                                        .as_secs(),
+                                    expires_at: std::time::SystemTime::now()
+                                        .duration_since(std::time::UNIX_EPOCH)
+                                        .unwrap()
+                                        .as_secs()
+                                        + 3600, // 1 hour expiration
+                                },
+                            };
 
-                        let _ = action_tx.send(Action::AuthenticationSuccess(auth_state));
-                    }
-                    Err(e) => {
-                        error!("Registration failed for {}: {}", credentials.username, e);
-                        let _ = action_tx.send(Action::AuthenticationFailure(format!(
-                            "Registration failed: {}",
-                            e
-                        )));
+                            let _ = action_tx.send(Action::AuthenticationSuccess(auth_state));
+                        }
+                        Err(e) => {
+                            error!("Registration failed for {}: {}", credentials.username, e);
+                            let _ = action_tx.send(Action::AuthenticationFailure(format!(
+                                "Registration failed: {}",
+                                e
+                            )));
+                        }
                     }
                 }
+                } => {}
             }
         });
     }
@@ -1133,28 +1273,33 @@ impl App {
     ) {
         let action_tx = self.action_tx.clone();
         let connection_manager = Arc::clone(&self.connection_manager);
+        let shutdown = self.dispatcher.token();
+        self.server_address = server_address.clone();
 
         // Set state to authenticating immediately
         self.auth_state = AuthState::Authenticating;
         self.auth_status.update_state(self.auth_state.clone());
 
         tokio::spawn(async move {
-            // Parse server address
-            let addr: std::net::SocketAddr = match server_address.parse() {
-                Ok(addr) => addr,
-                Err(_) => {
-                    let _ = action_tx.send(Action::AuthenticationFailure(format!(
-                        "Invalid server address: {}",
-                        server_address
-                    )));
-                    return;
+            tokio::select! {
+                _ = shutdown.cancelled() => {
+                    debug!("Login (with server) cancelled by shutdown");
                 }
-            };
+                _ = async {
+            if server_address.trim().is_empty() {
+                let _ = action_tx.send(Action::AuthenticationFailure(format!(
+                    "Invalid server address: {}",
+                    server_address
+                )));
+                return;
+            }
 
-            // Update connection manager config
+            // Update connection manager config. The address is resolved lazily
+            // at connect time, so a hostname like "chat.example.com:9000" is
+            // accepted here just as readily as a literal socket address.
             {
                 let mut manager = connection_manager.lock().await;
-                let config = crate::transport::ConnectionConfig::new(addr);
+                let config = crate::transport::ConnectionConfig::new(server_address.clone());
                 manager.update_config(config);
             }
 
@@ -1232,6 +1377,8 @@ impl App {
                     }
                 }
             }
+                } => {}
+            }
         });
     }
 
@@ -1243,28 +1390,33 @@ impl App {
     ) {
         let action_tx = self.action_tx.clone();
         let connection_manager = Arc::clone(&self.connection_manager);
+        let shutdown = self.dispatcher.token();
+        self.server_address = server_address.clone();
 
         // Set state to authenticating immediately
         self.auth_state = AuthState::Authenticating;
         self.auth_status.update_state(self.auth_state.clone());
 
         tokio::spawn(async move {
-            // Parse server address
-            let addr: std::net::SocketAddr = match server_address.parse() {
-                Ok(addr) => addr,
-                Err(_) => {
-                    let _ = action_tx.send(Action::AuthenticationFailure(format!(
-                        "Invalid server address: {}",
-                        server_address
-                    )));
-                    return;
+            tokio::select! {
+                _ = shutdown.cancelled() => {
+                    debug!("Register (with server) cancelled by shutdown");
                 }
-            };
+                _ = async {
+            if server_address.trim().is_empty() {
+                let _ = action_tx.send(Action::AuthenticationFailure(format!(
+                    "Invalid server address: {}",
+                    server_address
+                )));
+                return;
+            }
 
-            // Update connection manager config
+            // Update connection manager config. The address is resolved lazily
+            // at connect time, so a hostname like "chat.example.com:9000" is
+            // accepted here just as readily as a literal socket address.
             {
                 let mut manager = connection_manager.lock().await;
-                let config = crate::transport::ConnectionConfig::new(addr);
+                let config = crate::transport::ConnectionConfig::new(server_address.clone());
                 manager.update_config(config);
             }
 
@@ -1364,6 +1516,8 @@ impl App {
                     }
                 }
             }
+                } => {}
+            }
         });
     }
 
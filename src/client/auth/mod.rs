@@ -64,6 +64,7 @@ pub(crate) mod testing {
         Credentials {
             username: "testuser".to_string(),
             password: "password123".to_string(),
+            device_name: None,
         }
     }
 
@@ -123,6 +123,7 @@ mod tests {
         let credentials = Credentials {
             username: "newuser".to_string(),
             password: "newpassword123".to_string(),
+            device_name: None,
         };
 
         let register_request = AuthRequest::register(credentials.clone());
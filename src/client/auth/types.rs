@@ -36,6 +36,9 @@ pub type AuthResult<T> = Result<T, AuthError>;
 pub struct Credentials {
     pub username: String,
     pub password: String,
+    /// Optional human-readable name for the device/client logging in,
+    /// shown alongside the session in the server's session list.
+    pub device_name: Option<String>,
 }
 
 /// User session information
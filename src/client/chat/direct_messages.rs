@@ -355,6 +355,41 @@ impl From<&str> for ConversationId {
     }
 }
 
+/// User-defined label attached to a conversation (e.g. "work", "family").
+/// The tag's name doubles as its identifier, so applying and searching for
+/// a tag both key off the same string.
+#[derive(Debug, Clone, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TagId {
+    name: String,
+}
+
+impl TagId {
+    /// Get the string representation
+    pub fn as_str(&self) -> &str {
+        &self.name
+    }
+}
+
+impl std::fmt::Display for TagId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
+
+impl From<String> for TagId {
+    fn from(name: String) -> Self {
+        Self { name }
+    }
+}
+
+impl From<&str> for TagId {
+    fn from(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+        }
+    }
+}
+
 /// Direct conversation between two users
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DirectConversation {
@@ -374,6 +409,8 @@ pub struct DirectConversation {
     pub is_archived: bool,
     /// Whether conversation is muted
     pub is_muted: bool,
+    /// User-defined labels attached to this conversation
+    pub tags: Vec<TagId>,
 }
 
 impl DirectConversation {
@@ -398,6 +435,7 @@ impl DirectConversation {
             metadata: HashMap::new(),
             is_archived: false,
             is_muted: false,
+            tags: Vec::new(),
         }
     }
 
@@ -511,6 +549,23 @@ impl DirectConversation {
         self.is_muted = false;
     }
 
+    /// Attach a tag to the conversation; no-op if already present
+    pub fn add_tag(&mut self, tag: TagId) {
+        if !self.tags.contains(&tag) {
+            self.tags.push(tag);
+        }
+    }
+
+    /// Remove a tag from the conversation; no-op if not present
+    pub fn remove_tag(&mut self, tag: &TagId) {
+        self.tags.retain(|t| t != tag);
+    }
+
+    /// Check whether the conversation carries a given tag
+    pub fn has_tag(&self, tag: &TagId) -> bool {
+        self.tags.contains(tag)
+    }
+
     /// Set conversation metadata
     pub fn set_metadata(&mut self, key: String, value: String) {
         self.metadata.insert(key, value);
@@ -556,6 +611,8 @@ pub struct ConversationSummary {
     pub unread_count: u32,
     pub is_archived: bool,
     pub is_muted: bool,
+    /// User-defined labels attached to this conversation
+    pub tags: Vec<TagId>,
 }
 
 impl ConversationSummary {
@@ -580,6 +637,7 @@ impl ConversationSummary {
             unread_count: conversation.unread_count_for_user(user_id),
             is_archived: conversation.is_archived,
             is_muted: conversation.is_muted,
+            tags: conversation.tags.clone(),
         })
     }
 
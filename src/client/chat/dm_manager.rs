@@ -12,6 +12,7 @@ use crate::common::protocol::{protocol_utils, MessageEnvelope, MessageRoute, Pro
 use crate::common::transport::{ConnectionObserver, TransportError};
 use crate::connection_manager::ConnectionManager;
 
+use super::search_index::{MessageSearchIndex, SearchHit, SearchQuery};
 use super::{
     ConversationId, ConversationSummary, DirectConversation, DirectMessage, MessageDeliveryStatus,
     MessageId, UserId, UserManager, UserPresence,
@@ -115,6 +116,10 @@ pub struct DirectMessageManager {
     rate_limiter: Arc<RwLock<RateLimiter>>,
     /// Connection retry configuration
     retry_config: RetryConfig,
+    /// Inverted index over every message body, refreshed incrementally as
+    /// messages are sent/received so `search_history` covers the full
+    /// conversation history rather than just loaded summaries
+    search_index: Arc<RwLock<MessageSearchIndex>>,
 }
 
 impl DirectMessageManager {
@@ -133,6 +138,7 @@ impl DirectMessageManager {
             typing_timeout: 5, // 5 seconds
             rate_limiter: Arc::new(RwLock::new(RateLimiter::new(10, Duration::from_secs(60)))), // 10 messages per minute
             retry_config: RetryConfig::default(),
+            search_index: Arc::new(RwLock::new(MessageSearchIndex::new())),
         }
     }
 
@@ -153,6 +159,7 @@ impl DirectMessageManager {
             typing_timeout: 5,
             rate_limiter: Arc::new(RwLock::new(RateLimiter::new(max_messages, window_duration))),
             retry_config: RetryConfig::default(),
+            search_index: Arc::new(RwLock::new(MessageSearchIndex::new())),
         }
     }
 
@@ -207,6 +214,10 @@ impl DirectMessageManager {
                 conversation.add_message(message.clone());
             }
         }
+        self.search_index
+            .write()
+            .await
+            .index_message(conversation_id.clone(), &message);
 
         // Create protocol message
         let timestamp = SystemTime::now()
@@ -272,6 +283,10 @@ impl DirectMessageManager {
                 conversation.add_message(message.clone());
             }
         }
+        self.search_index
+            .write()
+            .await
+            .index_message(conversation_id.clone(), &message);
 
         // Create protocol message with attachment URLs
         let timestamp = SystemTime::now()
@@ -558,6 +573,10 @@ impl DirectMessageManager {
                 conversation.add_message(message.clone());
             }
         }
+        self.search_index
+            .write()
+            .await
+            .index_message(conversation_id.clone(), &message);
 
         // Notify observers
         self.notify_observers(DirectMessageEvent::MessageReceived {
@@ -856,6 +875,111 @@ impl DirectMessageManager {
         Ok(matching_messages)
     }
 
+    /// Search message content across every conversation the current user
+    /// participates in, not just the previews held in conversation
+    /// summaries. Conversations are ranked by their most recent match and,
+    /// within a conversation, messages are newest first.
+    pub async fn search_all_conversations(
+        &self,
+        query: &str,
+        limit_per_conversation: Option<usize>,
+    ) -> DirectMessageResult<Vec<ConversationSearchResult>> {
+        if query.trim().is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let user_id = self
+            .current_user_id
+            .ok_or(DirectMessageError::ConnectionNotAvailable)?;
+
+        let query_lower = query.to_lowercase();
+        let conversations = self.conversations.read().await;
+
+        let mut results: Vec<ConversationSearchResult> = Vec::new();
+        for conversation in conversations.values() {
+            if !conversation.has_participant(user_id) {
+                continue;
+            }
+            let Some(other_user_id) = conversation.other_participant(user_id) else {
+                continue;
+            };
+
+            let mut matching_messages: Vec<DirectMessage> = conversation
+                .messages
+                .iter()
+                .filter(|msg| !msg.is_deleted() && msg.content.to_lowercase().contains(&query_lower))
+                .cloned()
+                .collect();
+
+            if matching_messages.is_empty() {
+                continue;
+            }
+
+            matching_messages.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+            if let Some(limit) = limit_per_conversation {
+                matching_messages.truncate(limit);
+            }
+
+            results.push(ConversationSearchResult {
+                conversation_id: conversation.id.clone(),
+                other_user_id,
+                messages: matching_messages,
+            });
+        }
+
+        // Rank conversations by how recently they matched.
+        results.sort_by(|a, b| {
+            let a_latest = a.messages.first().map(|m| m.created_at).unwrap_or(0);
+            let b_latest = b.messages.first().map(|m| m.created_at).unwrap_or(0);
+            b_latest.cmp(&a_latest)
+        });
+
+        Ok(results)
+    }
+
+    /// Search the full message history index rather than just the loaded
+    /// conversation summaries. Supports quoted phrase queries and a
+    /// `from:username` filter token (see [`SearchQuery`]), and returns at
+    /// most one hit per conversation: its most recent matching message.
+    pub async fn search_history(&self, query: &str) -> DirectMessageResult<Vec<SearchHit>> {
+        self.current_user_id
+            .ok_or(DirectMessageError::ConnectionNotAvailable)?;
+
+        let parsed = SearchQuery::parse(query);
+        if parsed.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let from_user_id = if let Some(username) = &parsed.from_username {
+            self.user_manager
+                .search_users(username.clone())
+                .await
+                .into_iter()
+                .find(|presence| presence.username.eq_ignore_ascii_case(username))
+                .map(|presence| presence.user_id)
+        } else {
+            None
+        };
+
+        let index = self.search_index.read().await;
+        Ok(index.search(&parsed, from_user_id))
+    }
+
+    /// Rebuild the search index from the conversations currently held in
+    /// memory. Incremental indexing at each send/receive path keeps the
+    /// index in sync during normal operation; this exists for cold start
+    /// (e.g. after conversations are hydrated from storage) and tests that
+    /// mutate conversation state directly.
+    pub async fn rebuild_search_index(&self) {
+        let conversations = self.conversations.read().await;
+        let mut index = self.search_index.write().await;
+        index.rebuild(
+            conversations
+                .iter()
+                .map(|(id, conversation)| (id, conversation.messages.as_slice())),
+        );
+    }
+
     /// Get unread message count for current user across all conversations
     pub async fn get_total_unread_count(&self) -> DirectMessageResult<u32> {
         let current_user_id = self
@@ -1467,6 +1591,16 @@ pub struct ConversationActivity {
     pub last_message_time: Option<u64>,
 }
 
+/// Per-conversation hits from [`DirectMessageManager::search_all_conversations`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConversationSearchResult {
+    pub conversation_id: ConversationId,
+    pub other_user_id: UserId,
+    /// Matching messages within this conversation, newest first, already
+    /// capped to the requested per-conversation limit.
+    pub messages: Vec<DirectMessage>,
+}
+
 /// Rate limiter for controlling message sending frequency
 #[derive(Debug)]
 pub struct RateLimiter {
@@ -1787,6 +1921,128 @@ mod tests {
         assert_eq!(search_results.len(), 1);
     }
 
+    #[tokio::test]
+    async fn test_search_all_conversations() {
+        let user_manager = Arc::new(UserManager::new());
+        let config = crate::transport::ConnectionConfig::new("127.0.0.1:8080".parse().unwrap());
+        let connection_manager = Arc::new(Mutex::new(ConnectionManager::new(config)));
+
+        let mut dm_manager = DirectMessageManager::new(user_manager, connection_manager);
+        let user1 = uuid::Uuid::new_v4();
+        let user2 = uuid::Uuid::new_v4();
+        let user3 = uuid::Uuid::new_v4();
+        dm_manager.set_current_user(user1);
+
+        let conversation_a = dm_manager
+            .get_or_create_conversation(user1, user2)
+            .await
+            .unwrap();
+        let conversation_b = dm_manager
+            .get_or_create_conversation(user1, user3)
+            .await
+            .unwrap();
+
+        {
+            let mut conversations = dm_manager.conversations.write().await;
+
+            let conv_a = conversations.get_mut(&conversation_a).unwrap();
+            conv_a.add_message(DirectMessage::new_text(
+                user1,
+                user2,
+                "let's grab coffee tomorrow".to_string(),
+            ));
+            conv_a.add_message(DirectMessage::new_text(
+                user2,
+                user1,
+                "sounds good".to_string(),
+            ));
+
+            let conv_b = conversations.get_mut(&conversation_b).unwrap();
+            conv_b.add_message(DirectMessage::new_text(
+                user1,
+                user3,
+                "did you finish the coffee order?".to_string(),
+            ));
+        }
+
+        // Full-text search spans every conversation, not just the last
+        // message held in a summary.
+        let results = dm_manager
+            .search_all_conversations("coffee", None)
+            .await
+            .unwrap();
+        assert_eq!(results.len(), 2);
+        assert!(results
+            .iter()
+            .all(|result| !result.messages.is_empty()
+                && result
+                    .messages
+                    .iter()
+                    .all(|msg| msg.content.to_lowercase().contains("coffee"))));
+
+        let no_results = dm_manager
+            .search_all_conversations("nonexistent", None)
+            .await
+            .unwrap();
+        assert!(no_results.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_search_history_uses_rebuilt_index_with_phrase_and_from_filter() {
+        let user_manager = Arc::new(UserManager::new());
+        let config = crate::transport::ConnectionConfig::new("127.0.0.1:8080".parse().unwrap());
+        let connection_manager = Arc::new(Mutex::new(ConnectionManager::new(config)));
+
+        let mut dm_manager = DirectMessageManager::new(user_manager.clone(), connection_manager);
+        let user1 = uuid::Uuid::new_v4();
+        let user2 = uuid::Uuid::new_v4();
+        dm_manager.set_current_user(user1);
+        user_manager
+            .update_user_presence(UserPresence::new(user2, "bob".to_string()))
+            .await;
+
+        let conversation_id = dm_manager
+            .get_or_create_conversation(user1, user2)
+            .await
+            .unwrap();
+
+        {
+            let mut conversations = dm_manager.conversations.write().await;
+            let conversation = conversations.get_mut(&conversation_id).unwrap();
+            conversation.add_message(DirectMessage::new_text(
+                user1,
+                user2,
+                "let's grab lunch tomorrow".to_string(),
+            ));
+            conversation.add_message(DirectMessage::new_text(
+                user2,
+                user1,
+                "lunch plans: grab food at noon".to_string(),
+            ));
+        }
+
+        // Conversations mutated directly (bypassing the manager's own
+        // send/receive paths) aren't picked up until the index is rebuilt.
+        assert!(dm_manager
+            .search_history(r#""grab lunch""#)
+            .await
+            .unwrap()
+            .is_empty());
+
+        dm_manager.rebuild_search_index().await;
+
+        let phrase_hits = dm_manager.search_history(r#""grab lunch""#).await.unwrap();
+        assert_eq!(phrase_hits.len(), 1);
+        assert_eq!(phrase_hits[0].sender_id, user1);
+
+        let from_hits = dm_manager
+            .search_history("lunch from:bob")
+            .await
+            .unwrap();
+        assert_eq!(from_hits.len(), 1);
+        assert_eq!(from_hits[0].sender_id, user2);
+    }
+
     #[tokio::test]
     async fn test_rate_limiting() {
         let mut rate_limiter = RateLimiter::new(2, Duration::from_secs(1));
@@ -11,20 +11,22 @@ pub mod dm_conversations;
 pub mod dm_manager;
 pub mod messages;
 pub mod rooms;
+pub mod search_index;
 pub mod user_manager;
 pub mod users;
 
 pub use direct_messages::{
     ConversationId, ConversationSummary, DirectConversation, DirectMessage, MessageDeliveryStatus,
-    MessageTarget,
+    MessageTarget, TagId,
 };
 pub use dm_conversations::{DMConversation, DMConversationManager, DMMessage};
 pub use dm_manager::{
-    DirectMessageError, DirectMessageEvent, DirectMessageManager, DirectMessageObserver,
-    DirectMessageResult, DirectMessageStats,
+    ConversationSearchResult, DirectMessageError, DirectMessageEvent, DirectMessageManager,
+    DirectMessageObserver, DirectMessageResult, DirectMessageStats,
 };
 pub use messages::{ChatMessage, MessageStatus, MessageType};
 pub use rooms::{Room, RoomManager, RoomSettings, RoomType};
+pub use search_index::{MessageSearchIndex, SearchHit, SearchQuery};
 pub use user_manager::{UserFilter, UserManager, UserPresence, UserProfile, UserStats};
 pub use users::{RoomUser, UserRole, UserStatus};
 
@@ -0,0 +1,334 @@
+//! In-memory full-text search index over direct message history for
+//! Lair-Chat. Unlike a per-call scan over loaded conversation summaries,
+//! this builds a token -> message inverted index once and is refreshed
+//! incrementally as messages arrive, so `/`-style search covers every
+//! message a conversation has ever held.
+
+use std::collections::HashMap;
+
+use super::direct_messages::DirectMessage;
+use super::{ConversationId, MessageId, UserId};
+
+/// Maximum length of a returned snippet before it's truncated with "...".
+const SNIPPET_MAX_CHARS: usize = 80;
+
+/// A single indexed message's searchable metadata
+#[derive(Debug, Clone)]
+struct IndexedMessage {
+    conversation_id: ConversationId,
+    sender_id: UserId,
+    created_at: u64,
+    content: String,
+}
+
+/// Inverted index over message content: lowercased token -> the messages
+/// containing it, with the char offset of each occurrence so a future
+/// highlighter could mark the match the way `dm_navigation`'s fuzzy search
+/// marks username matches.
+#[derive(Debug, Default)]
+pub struct MessageSearchIndex {
+    messages: HashMap<MessageId, IndexedMessage>,
+    postings: HashMap<String, Vec<(MessageId, usize)>>,
+}
+
+/// A parsed `/`-search query: bare terms are ANDed together, quoted
+/// phrases must appear verbatim, and a `from:username` token restricts
+/// results to messages sent by that user.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SearchQuery {
+    pub terms: Vec<String>,
+    pub phrases: Vec<String>,
+    pub from_username: Option<String>,
+}
+
+impl SearchQuery {
+    /// Parse `raw` into terms, quoted phrases, and an optional `from:`
+    /// filter. Unterminated quotes are treated as running to the end of
+    /// the input rather than being dropped.
+    pub fn parse(raw: &str) -> Self {
+        let mut query = SearchQuery::default();
+        let mut chars = raw.chars().peekable();
+        let mut current = String::new();
+
+        while let Some(c) = chars.next() {
+            if c == '"' {
+                let mut phrase = String::new();
+                for pc in chars.by_ref() {
+                    if pc == '"' {
+                        break;
+                    }
+                    phrase.push(pc);
+                }
+                let trimmed = phrase.trim();
+                if !trimmed.is_empty() {
+                    query.phrases.push(trimmed.to_lowercase());
+                }
+            } else if c.is_whitespace() {
+                if !current.is_empty() {
+                    query.push_token(std::mem::take(&mut current));
+                }
+            } else {
+                current.push(c);
+            }
+        }
+        if !current.is_empty() {
+            query.push_token(current);
+        }
+
+        query
+    }
+
+    fn push_token(&mut self, token: String) {
+        match token.strip_prefix("from:") {
+            Some(username) if !username.is_empty() => {
+                self.from_username = Some(username.to_lowercase());
+            }
+            _ => self.terms.push(token.to_lowercase()),
+        }
+    }
+
+    /// Whether the query carries no filters at all
+    pub fn is_empty(&self) -> bool {
+        self.terms.is_empty() && self.phrases.is_empty() && self.from_username.is_none()
+    }
+}
+
+/// Best match for one conversation, returned by [`MessageSearchIndex::search`]
+#[derive(Debug, Clone)]
+pub struct SearchHit {
+    pub conversation_id: ConversationId,
+    pub message_id: MessageId,
+    pub sender_id: UserId,
+    pub created_at: u64,
+    /// Truncated preview of the matching message, suitable for a
+    /// "...matched in message from 3d ago" style label
+    pub snippet: String,
+}
+
+impl MessageSearchIndex {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add or refresh a message's postings. Re-indexing a message id first
+    /// drops its earlier postings, so an edited message matches only its
+    /// current content; a deleted message is removed from the index.
+    pub fn index_message(&mut self, conversation_id: ConversationId, message: &DirectMessage) {
+        for hits in self.postings.values_mut() {
+            hits.retain(|(id, _)| *id != message.id);
+        }
+        if message.is_deleted() {
+            self.messages.remove(&message.id);
+            return;
+        }
+
+        let lowercased = message.content.to_lowercase();
+        let chars: Vec<char> = lowercased.chars().collect();
+        let mut token_start: Option<usize> = None;
+
+        for (i, c) in chars.iter().enumerate() {
+            if c.is_alphanumeric() {
+                token_start.get_or_insert(i);
+            } else if let Some(start) = token_start.take() {
+                self.record_token(&chars, start, i, message.id);
+            }
+        }
+        if let Some(start) = token_start {
+            self.record_token(&chars, start, chars.len(), message.id);
+        }
+
+        self.messages.insert(
+            message.id,
+            IndexedMessage {
+                conversation_id,
+                sender_id: message.sender_id,
+                created_at: message.created_at,
+                content: message.content.clone(),
+            },
+        );
+    }
+
+    fn record_token(&mut self, chars: &[char], start: usize, end: usize, message_id: MessageId) {
+        let token: String = chars[start..end].iter().collect();
+        self.postings.entry(token).or_default().push((message_id, start));
+    }
+
+    /// Rebuild the entire index from scratch, e.g. after conversation
+    /// state was mutated without going through `index_message`
+    pub fn rebuild<'a>(
+        &mut self,
+        conversations: impl IntoIterator<Item = (&'a ConversationId, &'a [DirectMessage])>,
+    ) {
+        self.messages.clear();
+        self.postings.clear();
+        for (conversation_id, messages) in conversations {
+            for message in messages {
+                self.index_message(conversation_id.clone(), message);
+            }
+        }
+    }
+
+    /// Search the index, returning the single best (most recent) matching
+    /// message per conversation, newest-matched conversation first.
+    /// `from_user_id`, if given, resolves `query.from_username` to a
+    /// concrete user and additionally restricts results to their messages.
+    pub fn search(&self, query: &SearchQuery, from_user_id: Option<UserId>) -> Vec<SearchHit> {
+        if query.is_empty() {
+            return Vec::new();
+        }
+        if query.from_username.is_some() && from_user_id.is_none() {
+            // The `from:` filter didn't resolve to a known user, so it
+            // can't match anything.
+            return Vec::new();
+        }
+
+        let mut best_per_conversation: HashMap<ConversationId, SearchHit> = HashMap::new();
+
+        'messages: for (message_id, indexed) in &self.messages {
+            if let Some(sender) = from_user_id {
+                if indexed.sender_id != sender {
+                    continue;
+                }
+            }
+
+            for term in &query.terms {
+                let matches = self
+                    .postings
+                    .get(term)
+                    .map(|hits| hits.iter().any(|(id, _)| id == message_id))
+                    .unwrap_or(false);
+                if !matches {
+                    continue 'messages;
+                }
+            }
+
+            let content_lower = indexed.content.to_lowercase();
+            for phrase in &query.phrases {
+                if !content_lower.contains(phrase.as_str()) {
+                    continue 'messages;
+                }
+            }
+
+            let hit = SearchHit {
+                conversation_id: indexed.conversation_id.clone(),
+                message_id: *message_id,
+                sender_id: indexed.sender_id,
+                created_at: indexed.created_at,
+                snippet: Self::snippet(&indexed.content),
+            };
+
+            best_per_conversation
+                .entry(indexed.conversation_id.clone())
+                .and_modify(|existing| {
+                    if hit.created_at > existing.created_at {
+                        *existing = hit.clone();
+                    }
+                })
+                .or_insert(hit);
+        }
+
+        let mut hits: Vec<SearchHit> = best_per_conversation.into_values().collect();
+        hits.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+        hits
+    }
+
+    fn snippet(content: &str) -> String {
+        if content.chars().count() <= SNIPPET_MAX_CHARS {
+            content.to_string()
+        } else {
+            let truncated: String = content.chars().take(SNIPPET_MAX_CHARS).collect();
+            format!("{}...", truncated)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message(sender_id: UserId, content: &str, created_at: u64) -> DirectMessage {
+        let mut message = DirectMessage::new_text(sender_id, UserId::new_v4(), content.to_string());
+        message.created_at = created_at;
+        message
+    }
+
+    #[test]
+    fn test_parse_query_terms_phrases_and_from_filter() {
+        let query = SearchQuery::parse(r#"coffee "grab lunch" from:alice"#);
+        assert_eq!(query.terms, vec!["coffee"]);
+        assert_eq!(query.phrases, vec!["grab lunch"]);
+        assert_eq!(query.from_username, Some("alice".to_string()));
+    }
+
+    #[test]
+    fn test_index_and_search_matches_across_history() {
+        let mut index = MessageSearchIndex::new();
+        let alice = UserId::new_v4();
+        let bob = UserId::new_v4();
+        let conv_a = ConversationId::from_participants(alice, bob);
+        let conv_b = ConversationId::from_participants(alice, UserId::new_v4());
+
+        index.index_message(conv_a.clone(), &message(alice, "let's grab coffee tomorrow", 100));
+        index.index_message(conv_a.clone(), &message(bob, "sounds good", 200));
+        index.index_message(conv_b.clone(), &message(alice, "did you finish the coffee order?", 50));
+
+        let query = SearchQuery::parse("coffee");
+        let hits = index.search(&query, None);
+
+        assert_eq!(hits.len(), 2);
+        // conv_a's matching message (created_at 100) is more recent than
+        // conv_b's (created_at 50), so it's ranked first.
+        assert_eq!(hits[0].conversation_id, conv_a);
+        assert_eq!(hits[1].conversation_id, conv_b);
+    }
+
+    #[test]
+    fn test_phrase_query_requires_exact_substring() {
+        let mut index = MessageSearchIndex::new();
+        let alice = UserId::new_v4();
+        let conv = ConversationId::from_participants(alice, UserId::new_v4());
+
+        index.index_message(conv.clone(), &message(alice, "grab lunch at noon", 100));
+        index.index_message(conv.clone(), &message(alice, "lunch plans: grab food", 200));
+
+        let query = SearchQuery::parse(r#""grab lunch""#);
+        let hits = index.search(&query, None);
+
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].created_at, 100);
+    }
+
+    #[test]
+    fn test_from_filter_restricts_to_resolved_sender() {
+        let mut index = MessageSearchIndex::new();
+        let alice = UserId::new_v4();
+        let bob = UserId::new_v4();
+        let conv = ConversationId::from_participants(alice, bob);
+
+        index.index_message(conv.clone(), &message(alice, "coffee at 3pm", 100));
+        index.index_message(conv.clone(), &message(bob, "coffee sounds great", 200));
+
+        let query = SearchQuery::parse("coffee from:alice");
+
+        // Unresolved `from:` username can't match anything.
+        assert!(index.search(&query, None).is_empty());
+
+        let hits = index.search(&query, Some(alice));
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].sender_id, alice);
+    }
+
+    #[test]
+    fn test_deleted_message_is_not_indexed() {
+        let mut index = MessageSearchIndex::new();
+        let alice = UserId::new_v4();
+        let conv = ConversationId::from_participants(alice, UserId::new_v4());
+
+        let mut deleted = message(alice, "secret plan", 100);
+        deleted.delete();
+        index.index_message(conv, &deleted);
+
+        let query = SearchQuery::parse("secret");
+        assert!(index.search(&query, None).is_empty());
+    }
+}
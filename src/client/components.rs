@@ -14,6 +14,8 @@ pub mod app;
 pub mod auth;
 #[path = "components/chat/mod.rs"]
 pub mod chat;
+#[path = "components/compose.rs"]
+pub mod compose;
 #[path = "components/dm_conversation.rs"]
 pub mod dm_conversation;
 #[path = "components/dm_navigation.rs"]
@@ -22,6 +24,8 @@ pub mod dm_navigation;
 pub mod fps;
 #[path = "components/home.rs"]
 pub mod home;
+#[path = "components/notifications.rs"]
+pub mod notifications;
 #[path = "components/status/mod.rs"]
 pub mod status;
 #[path = "components/user_list.rs"]
@@ -29,8 +33,12 @@ pub mod user_list;
 
 pub use self::auth::{AuthStatusBar, LoginScreen};
 pub use self::chat::ChatView;
+pub use self::compose::ComposeScreen;
 pub use self::dm_conversation::{ConversationEvent, ConversationPanel, ConversationState};
 pub use self::dm_navigation::{NavigationEvent, NavigationPanel, NavigationState};
+pub use self::notifications::{
+    NotificationEntry, NotificationKind, NotificationPanel, NotificationState,
+};
 pub use self::status::StatusBar;
 pub use self::user_list::{UserListEvent, UserListPanel, UserListState};
 
@@ -6,6 +6,7 @@ use tui_input::Input;
 
 use color_eyre::Result;
 
+use crate::accounts::SavedAccount;
 use crate::action::Action;
 use crate::auth::{AuthError, AuthState, Credentials};
 use crate::components::Component;
@@ -24,6 +25,7 @@ pub struct LoginScreen {
     password_confirm: Input,
     server: Input,
     port: Input,
+    device: Input,
     error_message: Option<String>,
     pub mode: LoginMode,
     focused_field: usize,
@@ -32,6 +34,9 @@ pub struct LoginScreen {
     help_scroll: usize,
     show_error: bool,
     error_scroll: usize,
+    accounts: Vec<SavedAccount>,
+    show_account_picker: bool,
+    account_picker_index: usize,
 }
 
 impl LoginScreen {
@@ -42,6 +47,7 @@ impl LoginScreen {
             password_confirm: Input::default(),
             server: Input::new("127.0.0.1".into()),
             port: Input::new("8080".into()),
+            device: Input::default(),
             error_message: None,
             mode: LoginMode::Login,
             focused_field: 0,
@@ -50,9 +56,59 @@ impl LoginScreen {
             help_scroll: 0,
             show_error: false,
             error_scroll: 0,
+            accounts: Vec::new(),
+            show_account_picker: false,
+            account_picker_index: 0,
         }
     }
 
+    /// Provide the saved accounts available for the account picker. Call
+    /// this after loading accounts from the `AccountRepository` at startup.
+    pub fn set_accounts(&mut self, accounts: Vec<SavedAccount>) {
+        self.accounts = accounts;
+        self.account_picker_index = 0;
+    }
+
+    fn toggle_account_picker(&mut self) {
+        if self.accounts.is_empty() {
+            self.error_message = Some("No saved accounts yet".to_string());
+            return;
+        }
+        self.show_account_picker = !self.show_account_picker;
+        self.account_picker_index = 0;
+    }
+
+    /// Pre-fill the username, server and port fields from a saved account
+    /// and mark it as just used. The password is not filled in and no login
+    /// is started.
+    fn select_account(&mut self, index: usize) -> Option<Action> {
+        let account = self.accounts.get(index)?;
+        self.username = Input::new(account.username.clone());
+        if let Some((host, port)) = account.server.rsplit_once(':') {
+            self.server = Input::new(host.to_string());
+            self.port = Input::new(port.to_string());
+        }
+        let name = account.name.clone();
+        self.show_account_picker = false;
+        self.error_message = None;
+
+        Some(Action::SelectAccount(name))
+    }
+
+    fn forget_selected_account(&mut self) -> Option<Action> {
+        let account = self.accounts.get(self.account_picker_index)?;
+        let name = account.name.clone();
+        self.accounts.remove(self.account_picker_index);
+        if self.account_picker_index >= self.accounts.len() {
+            self.account_picker_index = self.accounts.len().saturating_sub(1);
+        }
+        if self.accounts.is_empty() {
+            self.show_account_picker = false;
+        }
+
+        Some(Action::ForgetAccount(name))
+    }
+
     fn get_field_indexes(&self) -> (usize, usize, usize, usize, Option<usize>) {
         // Returns (username, password, password_confirm/server, server/port, port/none)
         match self.mode {
@@ -61,10 +117,16 @@ impl LoginScreen {
         }
     }
 
+    /// Index of the optional device-name field, which is always the last
+    /// field regardless of mode (see [`Self::get_max_field`]).
+    fn device_field_index(&self) -> usize {
+        self.get_max_field()
+    }
+
     fn get_max_field(&self) -> usize {
         match self.mode {
-            LoginMode::Login => 3,
-            LoginMode::Register => 4,
+            LoginMode::Login => 4,
+            LoginMode::Register => 5,
         }
     }
 
@@ -143,9 +205,15 @@ impl LoginScreen {
             }
         }
 
+        let device_name = self.device.value().trim();
         let credentials = Credentials {
             username: self.username.value().trim().to_string(),
             password: self.password.value().trim().to_string(),
+            device_name: if device_name.is_empty() {
+                None
+            } else {
+                Some(device_name.to_string())
+            },
         };
 
         let server_address = format!(
@@ -210,15 +278,25 @@ impl Component for LoginScreen {
         }
 
         match key.code {
+            crossterm::event::KeyCode::Char('a')
+                if key
+                    .modifiers
+                    .contains(crossterm::event::KeyModifiers::CONTROL)
+                    && !self.show_help
+                    && !self.show_error =>
+            {
+                self.toggle_account_picker();
+                None
+            }
             crossterm::event::KeyCode::Tab => {
-                if !self.show_help && !self.show_error {
+                if !self.show_help && !self.show_error && !self.show_account_picker {
                     let max_field = self.get_max_field();
                     self.focused_field = (self.focused_field + 1) % (max_field + 1);
                 }
                 None
             }
             crossterm::event::KeyCode::BackTab => {
-                if !self.show_help && !self.show_error {
+                if !self.show_help && !self.show_error && !self.show_account_picker {
                     let max_field = self.get_max_field();
                     self.focused_field = if self.focused_field == 0 {
                         max_field
@@ -238,10 +316,20 @@ impl Component for LoginScreen {
                     self.show_help = false;
                     self.help_scroll = 0;
                     None
+                } else if self.show_account_picker {
+                    self.select_account(self.account_picker_index)
                 } else {
                     self.submit()
                 }
             }
+            crossterm::event::KeyCode::Char('d')
+                if self.show_account_picker
+                    && key
+                        .modifiers
+                        .contains(crossterm::event::KeyModifiers::CONTROL) =>
+            {
+                self.forget_selected_account()
+            }
             crossterm::event::KeyCode::Char('t')
                 if key
                     .modifiers
@@ -251,7 +339,7 @@ impl Component for LoginScreen {
                 None
             }
             crossterm::event::KeyCode::Char('?') => {
-                if !self.show_error {
+                if !self.show_error && !self.show_account_picker {
                     self.show_help = !self.show_help;
                     self.help_scroll = 0; // Reset scroll when opening help
                 }
@@ -266,10 +354,25 @@ impl Component for LoginScreen {
                     self.show_help = false;
                     self.help_scroll = 0;
                     None
+                } else if self.show_account_picker {
+                    self.show_account_picker = false;
+                    None
                 } else {
                     None
                 }
             }
+            crossterm::event::KeyCode::Up if self.show_account_picker => {
+                if self.account_picker_index > 0 {
+                    self.account_picker_index -= 1;
+                }
+                None
+            }
+            crossterm::event::KeyCode::Down if self.show_account_picker => {
+                if self.account_picker_index + 1 < self.accounts.len() {
+                    self.account_picker_index += 1;
+                }
+                None
+            }
             crossterm::event::KeyCode::Up if self.show_help || self.show_error => {
                 if self.show_help && self.help_scroll > 0 {
                     self.help_scroll -= 1;
@@ -303,7 +406,7 @@ impl Component for LoginScreen {
                 None
             }
             crossterm::event::KeyCode::Char(c) => {
-                if !self.show_help && !self.show_error {
+                if !self.show_help && !self.show_error && !self.show_account_picker {
                     match self.focused_field {
                         0 => {
                             self.username = self.username.clone().with_value(format!(
@@ -355,6 +458,21 @@ impl Component for LoginScreen {
                                     self.port.value(),
                                     c
                                 ));
+                            } else {
+                                self.device = self.device.clone().with_value(format!(
+                                    "{}{}",
+                                    self.device.value(),
+                                    c
+                                ));
+                            }
+                        }
+                        5 => {
+                            if matches!(self.mode, LoginMode::Register) {
+                                self.device = self.device.clone().with_value(format!(
+                                    "{}{}",
+                                    self.device.value(),
+                                    c
+                                ));
                             }
                         }
                         _ => {}
@@ -363,7 +481,7 @@ impl Component for LoginScreen {
                 None
             }
             crossterm::event::KeyCode::Backspace => {
-                if !self.show_help && !self.show_error {
+                if !self.show_help && !self.show_error && !self.show_account_picker {
                     match self.focused_field {
                         0 => {
                             let value = self.username.value();
@@ -430,6 +548,25 @@ impl Component for LoginScreen {
                                         .clone()
                                         .with_value(value[..value.len() - 1].to_string());
                                 }
+                            } else {
+                                let value = self.device.value();
+                                if !value.is_empty() {
+                                    self.device = self
+                                        .device
+                                        .clone()
+                                        .with_value(value[..value.len() - 1].to_string());
+                                }
+                            }
+                        }
+                        5 => {
+                            if matches!(self.mode, LoginMode::Register) {
+                                let value = self.device.value();
+                                if !value.is_empty() {
+                                    self.device = self
+                                        .device
+                                        .clone()
+                                        .with_value(value[..value.len() - 1].to_string());
+                                }
                             }
                         }
 
@@ -493,6 +630,7 @@ impl Component for LoginScreen {
                     Constraint::Length(3), // Password confirm
                     Constraint::Length(3), // Server input
                     Constraint::Length(3), // Port input
+                    Constraint::Length(3), // Device name input
                     Constraint::Length(1), // Spacer
                     Constraint::Length(2), // Help label (taller for errors)
                 ]
@@ -504,6 +642,7 @@ impl Component for LoginScreen {
                     Constraint::Length(3), // Password input
                     Constraint::Length(3), // Server input
                     Constraint::Length(3), // Port input
+                    Constraint::Length(3), // Device name input
                     Constraint::Length(1), // Spacer
                     Constraint::Length(2), // Help label (taller for errors)
                 ]
@@ -730,6 +869,44 @@ impl Component for LoginScreen {
             .wrap(ratatui::widgets::Wrap { trim: false });
         f.render_widget(port_input, form_chunks[current_chunk + 1]);
 
+        // Draw device name field with better styling
+        let device_idx = self.device_field_index();
+        let device_focused = self.focused_field == device_idx;
+        let device_title = if device_focused {
+            "Device Name (optional, FOCUSED - Type here)"
+        } else {
+            "Device Name (optional)"
+        };
+
+        let device_style = if device_focused {
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(Color::White)
+        };
+
+        let device_block = Block::default()
+            .borders(Borders::ALL)
+            .title(device_title)
+            .border_style(if device_focused {
+                Style::default().fg(Color::Yellow)
+            } else {
+                Style::default().fg(Color::Gray)
+            });
+
+        let device_display = if device_focused {
+            format!("{}|", self.device.value())
+        } else {
+            self.device.value().to_string()
+        };
+
+        let device_input = Paragraph::new(device_display)
+            .style(device_style)
+            .block(device_block)
+            .wrap(ratatui::widgets::Wrap { trim: false });
+        f.render_widget(device_input, form_chunks[current_chunk + 2]);
+
         // Draw simple help label
         let help_text = if self.processing {
             Paragraph::new("Processing... | Press ? for help")
@@ -737,11 +914,14 @@ impl Component for LoginScreen {
         } else if self.error_message.is_some() {
             Paragraph::new("Error occurred | Press Esc to view details | Press ? for help")
                 .style(Style::default().fg(Color::Red))
-        } else {
+        } else if self.accounts.is_empty() {
             Paragraph::new("Press ? for help").style(Style::default().fg(Color::Blue))
+        } else {
+            Paragraph::new("Ctrl+A for saved accounts | Press ? for help")
+                .style(Style::default().fg(Color::Blue))
         };
 
-        f.render_widget(help_text, form_chunks[6]);
+        f.render_widget(help_text, form_chunks[form_chunks.len() - 1]);
 
         // Draw help popup if visible
         if self.show_help {
@@ -753,6 +933,11 @@ impl Component for LoginScreen {
             self.draw_error_popup(f, area)?;
         }
 
+        // Draw account picker popup if visible
+        if self.show_account_picker {
+            self.draw_account_picker_popup(f, area)?;
+        }
+
         Ok(())
     }
 }
@@ -1037,6 +1222,59 @@ impl LoginScreen {
 
         Ok(())
     }
+
+    fn draw_account_picker_popup(&mut self, f: &mut Frame<'_>, area: Rect) -> Result<()> {
+        // Create centered popup for the saved account list
+        let popup_area = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Percentage(25),
+                Constraint::Percentage(50),
+                Constraint::Percentage(25),
+            ])
+            .split(area)[1];
+
+        let popup_area = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([
+                Constraint::Percentage(20),
+                Constraint::Percentage(60),
+                Constraint::Percentage(20),
+            ])
+            .split(popup_area)[1];
+
+        f.render_widget(Clear, popup_area);
+
+        let lines: Vec<Line> = self
+            .accounts
+            .iter()
+            .enumerate()
+            .map(|(i, account)| {
+                let style = if i == self.account_picker_index {
+                    Style::default()
+                        .fg(Color::Yellow)
+                        .add_modifier(Modifier::BOLD)
+                } else {
+                    Style::default().fg(Color::White)
+                };
+                Line::from(Span::styled(
+                    format!("{} ({}@{})", account.name, account.username, account.server),
+                    style,
+                ))
+            })
+            .collect();
+
+        let popup = Paragraph::new(lines).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title("Saved Accounts - Enter: use | Ctrl+D: forget | Esc: close")
+                .border_style(Style::default().fg(Color::Yellow)),
+        );
+
+        f.render_widget(popup, popup_area);
+
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -1100,4 +1338,42 @@ mod tests {
             _ => panic!("Expected Login action"),
         }
     }
+
+    #[test]
+    fn test_submit_with_device_name() {
+        let mut screen = LoginScreen::new();
+        screen.username = Input::new("testuser".into());
+        screen.password = Input::new("password123".into());
+        screen.device = Input::new("alice-laptop".into());
+
+        match screen.submit().unwrap() {
+            Action::Login(creds) => {
+                assert_eq!(creds.device_name, Some("alice-laptop".to_string()));
+            }
+            _ => panic!("Expected Login action"),
+        }
+    }
+
+    #[test]
+    fn test_submit_without_device_name_is_none() {
+        let mut screen = LoginScreen::new();
+        screen.username = Input::new("testuser".into());
+        screen.password = Input::new("password123".into());
+
+        match screen.submit().unwrap() {
+            Action::Login(creds) => {
+                assert_eq!(creds.device_name, None);
+            }
+            _ => panic!("Expected Login action"),
+        }
+    }
+
+    #[test]
+    fn test_device_field_is_last_field() {
+        let mut screen = LoginScreen::new();
+        assert_eq!(screen.device_field_index(), screen.get_max_field());
+
+        screen.mode = LoginMode::Register;
+        assert_eq!(screen.device_field_index(), screen.get_max_field());
+    }
 }
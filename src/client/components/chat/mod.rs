@@ -26,6 +26,10 @@ pub struct ChatView {
     show_help: bool,
     /// Reference to status bar
     status_bar: Option<Arc<Mutex<StatusBar>>>,
+    /// Whether the search bar is focused
+    search_mode: bool,
+    /// Input buffer for the search bar
+    search_input: Input,
 }
 
 impl ChatView {
@@ -36,6 +40,8 @@ impl ChatView {
             username: None,
             show_help: false,
             status_bar: None,
+            search_mode: false,
+            search_input: Input::default(),
         }
     }
 
@@ -80,10 +86,55 @@ impl ChatView {
     fn toggle_help(&mut self) {
         self.show_help = !self.show_help;
     }
+
+    fn toggle_search(&mut self) {
+        self.search_mode = !self.search_mode;
+        if !self.search_mode {
+            self.search_input.reset();
+        }
+    }
+
+    fn submit_search(&mut self) -> Option<Action> {
+        let query = self.search_input.value().trim();
+        if query.is_empty() {
+            return None;
+        }
+
+        let query = query.to_string();
+        self.search_input.reset();
+        self.search_mode = false;
+        Some(Action::SearchMessages(query))
+    }
 }
 
 impl Component for ChatView {
     fn handle_key(&mut self, key: crossterm::event::KeyEvent) -> Option<Action> {
+        if self.search_mode {
+            return match key.code {
+                crossterm::event::KeyCode::Enter => self.submit_search(),
+                crossterm::event::KeyCode::Esc => {
+                    self.toggle_search();
+                    None
+                }
+                crossterm::event::KeyCode::Char(c) => {
+                    let current = self.search_input.value();
+                    self.search_input = self.search_input.clone().with_value(format!("{}{}", current, c));
+                    None
+                }
+                crossterm::event::KeyCode::Backspace => {
+                    let current = self.search_input.value();
+                    if !current.is_empty() {
+                        self.search_input = self
+                            .search_input
+                            .clone()
+                            .with_value(current[..current.len() - 1].to_string());
+                    }
+                    None
+                }
+                _ => None,
+            };
+        }
+
         match key.code {
             crossterm::event::KeyCode::Enter => self.submit_message(),
             crossterm::event::KeyCode::Char('h')
@@ -92,6 +143,12 @@ impl Component for ChatView {
                 self.toggle_help();
                 None
             }
+            crossterm::event::KeyCode::Char('f')
+                if key.modifiers.contains(crossterm::event::KeyModifiers::CONTROL) =>
+            {
+                self.toggle_search();
+                None
+            }
             crossterm::event::KeyCode::Up => {
                 // TODO: Implement history navigation
                 None
@@ -149,6 +206,15 @@ impl Component for ChatView {
             .block(Block::default().borders(Borders::ALL).title("Message"));
         f.render_widget(input, chunks[1]);
 
+        // Draw search bar if active
+        if self.search_mode {
+            let search_area = centered_rect(60, 15, area);
+            let search = Paragraph::new(self.search_input.value())
+                .style(Style::default().fg(Color::Cyan))
+                .block(Block::default().borders(Borders::ALL).title("Search"));
+            f.render_widget(search, search_area);
+        }
+
         // Draw help popup if enabled
         if self.show_help {
             let help_text = vec![
@@ -156,6 +222,7 @@ impl Component for ChatView {
                 "",
                 "Enter    - Send message",
                 "Ctrl+h   - Toggle help",
+                "Ctrl+f   - Search messages",
                 "Ctrl+c   - Quit",
                 "",
                 "Press any key to close",
@@ -258,4 +325,38 @@ mod tests {
         assert_eq!(view.messages[1].message_type, MessageType::UserMessage);
         assert_eq!(view.messages[2].message_type, MessageType::ErrorMessage);
     }
+
+    #[test]
+    fn test_search_toggle() {
+        let mut view = ChatView::new();
+        assert!(!view.search_mode);
+
+        view.toggle_search();
+        assert!(view.search_mode);
+
+        view.toggle_search();
+        assert!(!view.search_mode);
+    }
+
+    #[test]
+    fn test_search_submission() {
+        let mut view = ChatView::new();
+        view.toggle_search();
+
+        // Empty query should not create an action
+        assert!(view.submit_search().is_none());
+
+        view.search_input = Input::new("hello".into());
+
+        match view.submit_search() {
+            Some(Action::SearchMessages(query)) => {
+                assert_eq!(query, "hello");
+            }
+            _ => panic!("Expected SearchMessages action"),
+        }
+
+        // Search input should be cleared and search mode exited
+        assert!(view.search_input.value().is_empty());
+        assert!(!view.search_mode);
+    }
 }
\ No newline at end of file
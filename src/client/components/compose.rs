@@ -0,0 +1,258 @@
+//! Message composition screen with attachment support and an external-editor hook.
+
+use std::process::Command;
+
+use color_eyre::Result;
+use crossterm::event::KeyEvent;
+use ratatui::layout::{Constraint, Direction, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, List, ListItem, Paragraph};
+use ratatui::Frame;
+use uuid::Uuid;
+
+use crate::action::Action;
+use crate::components::Component;
+
+/// Which part of the compose screen currently has keyboard focus.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ComposeField {
+    Body,
+    Attachments,
+}
+
+/// A screen for drafting a message with optional attachments, analogous to
+/// [`LoginScreen`](crate::components::auth::LoginScreen) but for composing a
+/// message rather than authenticating.
+#[derive(Debug)]
+pub struct ComposeScreen {
+    body: String,
+    attachments: Vec<String>,
+    focused: ComposeField,
+    error_message: Option<String>,
+}
+
+impl ComposeScreen {
+    pub fn new() -> Self {
+        Self {
+            body: String::new(),
+            attachments: Vec::new(),
+            focused: ComposeField::Body,
+            error_message: None,
+        }
+    }
+
+    /// Attach a file by path.
+    pub fn add_attachment(&mut self, path: String) {
+        self.attachments.push(path);
+        self.error_message = None;
+    }
+
+    fn remove_last_attachment(&mut self) {
+        self.attachments.pop();
+    }
+
+    fn toggle_focus(&mut self) {
+        self.focused = match self.focused {
+            ComposeField::Body => ComposeField::Attachments,
+            ComposeField::Attachments => ComposeField::Body,
+        };
+    }
+
+    /// Spawn `$EDITOR` on a scratch file seeded with the current draft, and
+    /// replace the draft with whatever the user saved on exit. Falls back
+    /// to `vi` if `$EDITOR` isn't set.
+    pub fn edit_in_external_editor(&mut self) -> Result<()> {
+        let editor = std::env::var("EDITOR").unwrap_or_else(|_| "vi".to_string());
+        let scratch_path =
+            std::env::temp_dir().join(format!("lair-chat-draft-{}.txt", Uuid::new_v4()));
+
+        std::fs::write(&scratch_path, &self.body)?;
+
+        let status = Command::new(&editor).arg(&scratch_path).status();
+        let draft = std::fs::read_to_string(&scratch_path);
+        let _ = std::fs::remove_file(&scratch_path);
+
+        match status {
+            Ok(status) if status.success() => {
+                self.body = draft?;
+                self.error_message = None;
+            }
+            Ok(status) => {
+                self.error_message = Some(format!("Editor '{editor}' exited with {status}"));
+            }
+            Err(e) => {
+                self.error_message = Some(format!("Failed to launch editor '{editor}': {e}"));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Submit the draft, aborting if there's nothing to send.
+    fn submit(&mut self) -> Option<Action> {
+        let body = self.body.trim();
+        if body.is_empty() && self.attachments.is_empty() {
+            self.error_message = Some("Cannot send an empty message".to_string());
+            return None;
+        }
+
+        let body = body.to_string();
+        let attachments = std::mem::take(&mut self.attachments);
+        self.body.clear();
+        self.error_message = None;
+
+        Some(Action::SendMessageWithAttachments(body, attachments))
+    }
+}
+
+impl Default for ComposeScreen {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Component for ComposeScreen {
+    fn handle_key(&mut self, key: KeyEvent) -> Option<Action> {
+        match key.code {
+            crossterm::event::KeyCode::Tab => {
+                self.toggle_focus();
+                None
+            }
+            crossterm::event::KeyCode::Char('e')
+                if key
+                    .modifiers
+                    .contains(crossterm::event::KeyModifiers::CONTROL) =>
+            {
+                let _ = self.edit_in_external_editor();
+                None
+            }
+            crossterm::event::KeyCode::Enter => self.submit(),
+            crossterm::event::KeyCode::Char(c) if self.focused == ComposeField::Body => {
+                self.body.push(c);
+                None
+            }
+            crossterm::event::KeyCode::Backspace if self.focused == ComposeField::Body => {
+                self.body.pop();
+                None
+            }
+            crossterm::event::KeyCode::Backspace if self.focused == ComposeField::Attachments => {
+                self.remove_last_attachment();
+                None
+            }
+            _ => None,
+        }
+    }
+
+    fn draw(&mut self, f: &mut Frame<'_>, area: Rect) -> Result<()> {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Min(3),    // Body draft
+                Constraint::Length(5), // Attachments
+                Constraint::Length(1), // Help line
+            ])
+            .split(area);
+
+        let body_style = if self.focused == ComposeField::Body {
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(Color::White)
+        };
+        let body = Paragraph::new(self.body.as_str())
+            .style(body_style)
+            .block(Block::default().borders(Borders::ALL).title("Compose"));
+        f.render_widget(body, chunks[0]);
+
+        let attachment_items: Vec<ListItem> = self
+            .attachments
+            .iter()
+            .map(|path| ListItem::new(Line::from(Span::raw(path.clone()))))
+            .collect();
+        let attachments_style = if self.focused == ComposeField::Attachments {
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(Color::White)
+        };
+        let attachments = List::new(attachment_items).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title("Attachments")
+                .border_style(attachments_style),
+        );
+        f.render_widget(attachments, chunks[1]);
+
+        let help = if let Some(error) = &self.error_message {
+            Paragraph::new(error.as_str()).style(Style::default().fg(Color::Red))
+        } else {
+            Paragraph::new("Tab: switch focus | Ctrl+E: edit in $EDITOR | Enter: send")
+                .style(Style::default().fg(Color::Blue))
+        };
+        f.render_widget(help, chunks[2]);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compose_screen_creation() {
+        let screen = ComposeScreen::new();
+        assert!(screen.body.is_empty());
+        assert!(screen.attachments.is_empty());
+        assert_eq!(screen.focused, ComposeField::Body);
+    }
+
+    #[test]
+    fn test_empty_submit_is_aborted() {
+        let mut screen = ComposeScreen::new();
+        assert!(screen.submit().is_none());
+        assert!(screen.error_message.is_some());
+    }
+
+    #[test]
+    fn test_submit_with_body() {
+        let mut screen = ComposeScreen::new();
+        screen.body = "hello".to_string();
+
+        match screen.submit() {
+            Some(Action::SendMessageWithAttachments(body, attachments)) => {
+                assert_eq!(body, "hello");
+                assert!(attachments.is_empty());
+            }
+            _ => panic!("Expected SendMessageWithAttachments action"),
+        }
+        assert!(screen.body.is_empty());
+    }
+
+    #[test]
+    fn test_submit_with_attachment_only() {
+        let mut screen = ComposeScreen::new();
+        screen.add_attachment("/tmp/photo.png".to_string());
+
+        match screen.submit() {
+            Some(Action::SendMessageWithAttachments(body, attachments)) => {
+                assert!(body.is_empty());
+                assert_eq!(attachments, vec!["/tmp/photo.png".to_string()]);
+            }
+            _ => panic!("Expected SendMessageWithAttachments action"),
+        }
+    }
+
+    #[test]
+    fn test_toggle_focus() {
+        let mut screen = ComposeScreen::new();
+        assert_eq!(screen.focused, ComposeField::Body);
+        screen.toggle_focus();
+        assert_eq!(screen.focused, ComposeField::Attachments);
+        screen.toggle_focus();
+        assert_eq!(screen.focused, ComposeField::Body);
+    }
+}
@@ -1,18 +1,20 @@
 //! DM navigation and conversation management UI component for Lair-Chat
 //! Provides conversation list, navigation between DMs, and overall DM management interface.
 
-use crossterm::event::{KeyCode, KeyEvent};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use ratatui::{
     layout::{Alignment, Constraint, Direction, Layout, Margin, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, List, ListItem, ListState, Paragraph, Wrap},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph, Wrap},
     Frame,
 };
 use std::collections::HashMap;
 use tokio::sync::mpsc;
 
-use crate::chat::{ConversationId, ConversationSummary, UserId, UserPresence};
+use crate::chat::{ConversationId, ConversationSummary, SearchHit, TagId, UserId, UserPresence};
+
+use super::notifications::{NotificationEntry, NotificationState};
 
 /// Events that can be emitted by the DM navigation component
 #[derive(Debug, Clone)]
@@ -33,10 +35,18 @@ pub enum NavigationEvent {
     UnmuteConversation(ConversationId),
     /// Delete conversation
     DeleteConversation(ConversationId),
+    /// Attach a tag to a conversation
+    AddTag(ConversationId, TagId),
+    /// Remove a tag from a conversation
+    RemoveTag(ConversationId, TagId),
     /// Mark conversation as read
     MarkConversationRead(ConversationId),
     /// Mark all conversations as read
     MarkAllRead,
+    /// Jump to the conversation a notification refers to
+    OpenNotification(ConversationId),
+    /// Mark every notification in the notification center as read
+    MarkAllNotificationsRead,
     /// Search conversations
     SearchConversations(String),
     /// Refresh conversation list
@@ -56,6 +66,181 @@ pub enum NavigationViewMode {
     Archived,
     /// Show all conversations
     All,
+    /// Show the notification feed (new messages, mentions, conversations
+    /// archived by the peer) instead of the conversation list. Mute, read
+    /// and connection changes have no notification kind.
+    Notifications,
+}
+
+/// Field the conversation list is sorted on
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortField {
+    /// Most recently active conversation
+    Activity,
+    /// Other participant's username, alphabetically
+    Name,
+    /// Unread message count
+    UnreadCount,
+}
+
+impl SortField {
+    /// Cycle to the next field in the rotation
+    fn next(self) -> Self {
+        match self {
+            SortField::Activity => SortField::Name,
+            SortField::Name => SortField::UnreadCount,
+            SortField::UnreadCount => SortField::Activity,
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            SortField::Activity => "Activity",
+            SortField::Name => "Name",
+            SortField::UnreadCount => "Unread",
+        }
+    }
+}
+
+/// Direction a [`SortField`] is applied in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortOrder {
+    Ascending,
+    Descending,
+}
+
+impl SortOrder {
+    fn flip(self) -> Self {
+        match self {
+            SortOrder::Ascending => SortOrder::Descending,
+            SortOrder::Descending => SortOrder::Ascending,
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            SortOrder::Ascending => "asc",
+            SortOrder::Descending => "desc",
+        }
+    }
+}
+
+/// How conversation timestamps are rendered
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimestampFormat {
+    /// Always relative, e.g. "5m", "2h", "3d"
+    Relative,
+    /// Always an absolute date via `NavigationState::date_format`
+    Absolute,
+    /// Relative for recent activity, falling back to an absolute date once
+    /// the age exceeds a day so old conversations don't all collapse into
+    /// an indistinguishable "52w"
+    Smart,
+}
+
+impl TimestampFormat {
+    fn next(self) -> Self {
+        match self {
+            TimestampFormat::Relative => TimestampFormat::Absolute,
+            TimestampFormat::Absolute => TimestampFormat::Smart,
+            TimestampFormat::Smart => TimestampFormat::Relative,
+        }
+    }
+}
+
+/// State for the modal quick-switcher overlay: a view-independent jump
+/// list, complementing the in-panel `/`-search by fuzzy-matching every
+/// known conversation regardless of the active `NavigationViewMode`.
+#[derive(Debug, Clone, Default)]
+pub struct QuickSwitcherState {
+    /// Whether the overlay is currently shown
+    pub active: bool,
+    /// Current input text
+    pub query: String,
+    /// Snapshot of every conversation taken when the overlay opened
+    all: Vec<ConversationSummary>,
+    /// `all` ranked against `query`, most relevant first
+    pub results: Vec<ConversationSummary>,
+    /// Selected index into `results`
+    pub selected_index: Option<usize>,
+}
+
+impl QuickSwitcherState {
+    /// Create a new, closed quick-switcher state
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Open the overlay over a snapshot of `conversations`, independent of
+    /// whatever view mode the underlying panel is currently showing
+    pub fn open(&mut self, conversations: Vec<ConversationSummary>) {
+        self.active = true;
+        self.query.clear();
+        self.all = conversations;
+        self.refilter();
+    }
+
+    /// Dismiss the overlay. The underlying panel's own focus/selection
+    /// state was never touched, so there's nothing to restore beyond
+    /// clearing the overlay's own state.
+    pub fn close(&mut self) {
+        self.active = false;
+        self.query.clear();
+        self.all.clear();
+        self.results.clear();
+        self.selected_index = None;
+    }
+
+    /// Update the query and re-rank `results`
+    pub fn set_query(&mut self, query: String) {
+        self.query = query;
+        self.refilter();
+    }
+
+    fn refilter(&mut self) {
+        self.results = if self.query.is_empty() {
+            self.all.clone()
+        } else {
+            fuzzy_rank_conversations(&self.query, &self.all)
+                .into_iter()
+                .map(|(conv, _)| conv)
+                .collect()
+        };
+        self.selected_index = if self.results.is_empty() {
+            None
+        } else {
+            Some(0)
+        };
+    }
+
+    /// Select the next result, wrapping around
+    pub fn select_next(&mut self) {
+        if self.results.is_empty() {
+            return;
+        }
+        let next = self
+            .selected_index
+            .map(|i| (i + 1) % self.results.len())
+            .unwrap_or(0);
+        self.selected_index = Some(next);
+    }
+
+    /// Select the previous result, wrapping around
+    pub fn select_previous(&mut self) {
+        if self.results.is_empty() {
+            return;
+        }
+        let previous = self
+            .selected_index
+            .map(|i| if i == 0 { self.results.len() - 1 } else { i - 1 })
+            .unwrap_or(0);
+        self.selected_index = Some(previous);
+    }
+
+    /// The currently selected conversation, if any
+    pub fn selected_conversation(&self) -> Option<&ConversationSummary> {
+        self.selected_index.and_then(|i| self.results.get(i))
+    }
 }
 
 /// DM navigation state
@@ -63,6 +248,10 @@ pub enum NavigationViewMode {
 pub struct NavigationState {
     /// List of conversations
     pub conversations: Vec<ConversationSummary>,
+    /// Unfiltered snapshot of every known conversation, independent of
+    /// `view_mode`, so the quick-switcher overlay can jump to an archived
+    /// conversation without first switching views
+    pub all_conversations: Vec<ConversationSummary>,
     /// Currently selected conversation index
     pub selected_index: Option<usize>,
     /// List state for conversation selection
@@ -87,14 +276,45 @@ pub struct NavigationState {
     pub show_message_preview: bool,
     /// Show timestamps
     pub show_timestamps: bool,
-    /// Sort order (newest first or alphabetical)
-    pub sort_by_activity: bool,
+    /// Field the conversation list is currently sorted on
+    pub sort_field: SortField,
+    /// Direction `sort_field` is applied in
+    pub sort_order: SortOrder,
+    /// Matched character indices into `other_username` for conversations
+    /// currently surviving a fuzzy search, keyed by conversation id, so
+    /// `create_conversation_item` can highlight them. Empty when search is
+    /// inactive or a conversation matched only on `last_message`.
+    pub search_matches: HashMap<ConversationId, Vec<usize>>,
+    /// Display color for each known tag, used to render chips and to drive
+    /// `cycle_tag_filter`'s iteration order alongside `known_tags`
+    pub tag_colors: HashMap<TagId, Color>,
+    /// Tags in registration order, so `cycle_tag_filter` and the digit-key
+    /// quick-tag bindings have a stable ordering `tag_colors` can't provide
+    pub known_tags: Vec<TagId>,
+    /// When set, restrict the conversation list to conversations carrying
+    /// this tag
+    pub tag_filter: Option<TagId>,
+    /// How `format_timestamp` renders `last_activity`
+    pub timestamp_format: TimestampFormat,
+    /// `chrono` strftime string used to render absolute dates in
+    /// `TimestampFormat::Absolute` and `TimestampFormat::Smart`
+    pub date_format: String,
+    /// Activity feed backing `NavigationViewMode::Notifications`
+    pub notifications: NotificationState,
+    /// Best-matching history snippet (preview text, message timestamp) per
+    /// conversation, populated from a `MessageSearchIndex` hit when the
+    /// active search matches a message outside `last_message`. Drawn in
+    /// place of the normal preview line until search is cleared.
+    pub history_matches: HashMap<ConversationId, (String, u64)>,
+    /// State for the modal quick-switcher overlay
+    pub quick_switcher: QuickSwitcherState,
 }
 
 impl Default for NavigationState {
     fn default() -> Self {
         Self {
             conversations: Vec::new(),
+            all_conversations: Vec::new(),
             selected_index: None,
             list_state: ListState::default(),
             search_query: String::new(),
@@ -107,7 +327,17 @@ impl Default for NavigationState {
             show_unread_counts: true,
             show_message_preview: true,
             show_timestamps: true,
-            sort_by_activity: true,
+            sort_field: SortField::Activity,
+            sort_order: SortOrder::Descending,
+            search_matches: HashMap::new(),
+            tag_colors: HashMap::new(),
+            known_tags: Vec::new(),
+            tag_filter: None,
+            timestamp_format: TimestampFormat::Relative,
+            date_format: "%Y-%m-%d".to_string(),
+            notifications: NotificationState::new(),
+            history_matches: HashMap::new(),
+            quick_switcher: QuickSwitcherState::new(),
         }
     }
 }
@@ -125,6 +355,10 @@ impl NavigationState {
 
     /// Update conversation list
     pub fn update_conversations(&mut self, conversations: Vec<ConversationSummary>) {
+        // Keep an unfiltered snapshot so the quick-switcher overlay can jump
+        // to any conversation regardless of the active view mode.
+        self.all_conversations = conversations.clone();
+
         // Apply filters based on view mode
         self.conversations = match self.view_mode {
             NavigationViewMode::Active => conversations
@@ -135,21 +369,27 @@ impl NavigationState {
                 .into_iter()
                 .filter(|conv| conv.is_archived)
                 .collect(),
-            NavigationViewMode::All => conversations,
+            NavigationViewMode::All | NavigationViewMode::Notifications => conversations,
         };
 
-        // Apply search filter if active
-        if self.search_active && !self.search_query.is_empty() {
-            self.conversations = self.filter_by_search();
+        // Restrict to a single tag if a filter is active
+        if let Some(tag) = &self.tag_filter {
+            self.conversations.retain(|conv| conv.tags.contains(tag));
         }
 
-        // Sort conversations
-        if self.sort_by_activity {
-            self.conversations
-                .sort_by(|a, b| b.last_activity.cmp(&a.last_activity));
+        // Apply search filter if active. A fuzzy search ranks its own
+        // results by relevance, so it replaces rather than precedes the
+        // activity/name sort below.
+        let searching = self.search_active && !self.search_query.is_empty();
+        if searching {
+            self.conversations = self.filter_by_search();
         } else {
-            self.conversations
-                .sort_by(|a, b| a.other_username.cmp(&b.other_username));
+            self.search_matches.clear();
+            self.history_matches.clear();
+        }
+
+        if !searching {
+            self.sort_conversations();
         }
 
         // Reset selection if it's out of bounds
@@ -168,21 +408,25 @@ impl NavigationState {
         self.list_state.select(self.selected_index);
     }
 
-    /// Filter conversations by search query
-    fn filter_by_search(&self) -> Vec<ConversationSummary> {
-        let query = self.search_query.to_lowercase();
-        self.conversations
-            .iter()
-            .filter(|conv| {
-                conv.other_username.to_lowercase().contains(&query)
-                    || conv
-                        .last_message
-                        .as_ref()
-                        .map(|msg| msg.to_lowercase().contains(&query))
-                        .unwrap_or(false)
-            })
-            .cloned()
-            .collect()
+    /// Fuzzy-filter conversations by search query, ranking survivors by
+    /// descending match score (ties broken by most recent activity) and
+    /// recording the matched `other_username` indices in `search_matches`
+    /// for highlighting.
+    fn filter_by_search(&mut self) -> Vec<ConversationSummary> {
+        self.search_matches.clear();
+        let ranked = fuzzy_rank_conversations(&self.search_query, &self.conversations);
+
+        let mut results = Vec::with_capacity(ranked.len());
+        for (conv, username_indices) in ranked {
+            if let Some(username_indices) = username_indices {
+                if !username_indices.is_empty() {
+                    self.search_matches
+                        .insert(conv.id.clone(), username_indices);
+                }
+            }
+            results.push(conv);
+        }
+        results
     }
 
     /// Set search query
@@ -200,7 +444,104 @@ impl NavigationState {
         self.search_active = !self.search_active;
         if !self.search_active {
             self.search_query.clear();
+            self.history_matches.clear();
+        }
+    }
+
+    /// Record the best-matching history snippet for each conversation a
+    /// `MessageSearchIndex` query surfaced (see
+    /// `DirectMessageManager::search_history`), so `create_conversation_item`
+    /// can show "...matched in message from X ago" instead of the normal
+    /// last-message preview. Stale entries from a previous query are
+    /// cleared first, since a hit list never includes a conversation that
+    /// no longer matches.
+    pub fn apply_history_search_results(&mut self, hits: &[SearchHit]) {
+        self.history_matches.clear();
+        for hit in hits {
+            self.history_matches.insert(
+                hit.conversation_id.clone(),
+                (hit.snippet.clone(), hit.created_at),
+            );
+        }
+    }
+
+    /// Sort `self.conversations` in place by the current `(sort_field,
+    /// sort_order)` pair.
+    fn sort_conversations(&mut self) {
+        match self.sort_field {
+            SortField::Activity => self
+                .conversations
+                .sort_by(|a, b| a.last_activity.cmp(&b.last_activity)),
+            SortField::Name => self
+                .conversations
+                .sort_by(|a, b| a.other_username.cmp(&b.other_username)),
+            SortField::UnreadCount => self
+                .conversations
+                .sort_by(|a, b| a.unread_count.cmp(&b.unread_count)),
+        }
+
+        if self.sort_order == SortOrder::Descending {
+            self.conversations.reverse();
+        }
+    }
+
+    /// Cycle to the next sort field, keeping the current order
+    pub fn cycle_sort_field(&mut self) {
+        self.sort_field = self.sort_field.next();
+        let all_conversations = self.all_conversations.clone();
+        self.update_conversations(all_conversations);
+    }
+
+    /// Flip ascending/descending for the current sort field
+    pub fn flip_sort_order(&mut self) {
+        self.sort_order = self.sort_order.flip();
+        let all_conversations = self.all_conversations.clone();
+        self.update_conversations(all_conversations);
+    }
+
+    /// Cycle to the next `TimestampFormat`
+    pub fn cycle_timestamp_format(&mut self) {
+        self.timestamp_format = self.timestamp_format.next();
+    }
+
+    /// Set the `chrono` strftime string used for absolute timestamps
+    pub fn set_date_format(&mut self, format: String) {
+        self.date_format = format;
+    }
+
+    /// Number of unseen entries in the notification feed, surfaced as a
+    /// status-bar badge
+    pub fn unseen_notification_count(&self) -> usize {
+        self.notifications.unread_count()
+    }
+
+    /// Register a tag's display color, adding it to `known_tags` the first
+    /// time it's seen so `cycle_tag_filter` and the digit-key quick-tag
+    /// bindings can iterate it in a stable order.
+    pub fn register_tag(&mut self, tag: TagId, color: Color) {
+        if !self.known_tags.contains(&tag) {
+            self.known_tags.push(tag.clone());
         }
+        self.tag_colors.insert(tag, color);
+    }
+
+    /// Get the display color for a tag, if registered
+    pub fn tag_color(&self, tag: &TagId) -> Option<Color> {
+        self.tag_colors.get(tag).copied()
+    }
+
+    /// Cycle `tag_filter` through `None` and each known tag in turn
+    pub fn cycle_tag_filter(&mut self) {
+        self.tag_filter = match &self.tag_filter {
+            None => self.known_tags.first().cloned(),
+            Some(current) => match self.known_tags.iter().position(|t| t == current) {
+                Some(i) if i + 1 < self.known_tags.len() => Some(self.known_tags[i + 1].clone()),
+                _ => None,
+            },
+        };
+        // Reapply filters
+        let all_conversations = self.all_conversations.clone();
+        self.update_conversations(all_conversations);
     }
 
     /// Set view mode
@@ -295,6 +636,13 @@ impl NavigationState {
             .map(|user| user.display_name().to_string())
             .unwrap_or_else(|| format!("User {}", user_id))
     }
+
+    /// The local user's `@`-mentionable username, if known, so preview
+    /// rendering can highlight mentions aimed at them
+    pub fn current_username(&self) -> Option<&str> {
+        let user_id = self.current_user_id?;
+        self.user_cache.get(&user_id).map(|user| user.username.as_str())
+    }
 }
 
 /// DM navigation panel widget
@@ -357,6 +705,17 @@ impl NavigationPanel {
             return false;
         }
 
+        if self.state.quick_switcher.active {
+            return self.handle_quick_switcher_input(event);
+        }
+
+        if event.code == KeyCode::Char('p') && event.modifiers.contains(KeyModifiers::CONTROL) {
+            self.state
+                .quick_switcher
+                .open(self.state.all_conversations.clone());
+            return true;
+        }
+
         match event.code {
             KeyCode::Esc => {
                 if self.state.search_active {
@@ -368,17 +727,31 @@ impl NavigationPanel {
                 true
             }
             KeyCode::Enter => {
-                if let Some(conversation) = self.state.selected_conversation() {
+                if self.state.view_mode == NavigationViewMode::Notifications {
+                    if let Some(entry) = self.state.notifications.selected_entry() {
+                        self.send_event(NavigationEvent::OpenNotification(
+                            entry.conversation_id.clone(),
+                        ));
+                    }
+                } else if let Some(conversation) = self.state.selected_conversation() {
                     self.send_event(NavigationEvent::OpenConversation(conversation.id.clone()));
                 }
                 true
             }
             KeyCode::Up | KeyCode::Char('k') if !self.state.search_active => {
-                self.state.select_previous();
+                if self.state.view_mode == NavigationViewMode::Notifications {
+                    self.state.notifications.select_previous();
+                } else {
+                    self.state.select_previous();
+                }
                 true
             }
             KeyCode::Down | KeyCode::Char('j') if !self.state.search_active => {
-                self.state.select_next();
+                if self.state.view_mode == NavigationViewMode::Notifications {
+                    self.state.notifications.select_next();
+                } else {
+                    self.state.select_next();
+                }
                 true
             }
             KeyCode::Char('n') if !self.state.search_active => {
@@ -416,7 +789,9 @@ impl NavigationPanel {
                 true
             }
             KeyCode::Char('r') if !self.state.search_active => {
-                if let Some(conversation) = self.state.selected_conversation() {
+                if self.state.view_mode == NavigationViewMode::Notifications {
+                    self.state.notifications.mark_selected_read();
+                } else if let Some(conversation) = self.state.selected_conversation() {
                     self.send_event(NavigationEvent::MarkConversationRead(
                         conversation.id.clone(),
                     ));
@@ -424,18 +799,61 @@ impl NavigationPanel {
                 true
             }
             KeyCode::Char('R') if !self.state.search_active => {
-                self.send_event(NavigationEvent::MarkAllRead);
+                if self.state.view_mode == NavigationViewMode::Notifications {
+                    self.state.notifications.mark_all_read();
+                    self.send_event(NavigationEvent::MarkAllNotificationsRead);
+                } else {
+                    self.send_event(NavigationEvent::MarkAllRead);
+                }
+                true
+            }
+            KeyCode::Char('C')
+                if !self.state.search_active
+                    && self.state.view_mode == NavigationViewMode::Notifications =>
+            {
+                self.state.notifications.clear();
                 true
             }
             KeyCode::F(5) if !self.state.search_active => {
                 self.send_event(NavigationEvent::RefreshConversations);
                 true
             }
+            KeyCode::Char('s') if !self.state.search_active => {
+                self.state.cycle_sort_field();
+                true
+            }
+            KeyCode::Char('S') if !self.state.search_active => {
+                self.state.flip_sort_order();
+                true
+            }
+            KeyCode::Char('t') if !self.state.search_active => {
+                self.state.cycle_tag_filter();
+                true
+            }
+            KeyCode::Char('T') if !self.state.search_active => {
+                self.state.cycle_timestamp_format();
+                true
+            }
+            KeyCode::Char(c @ '1'..='9') if !self.state.search_active => {
+                let tag_index = c.to_digit(10).unwrap() as usize - 1;
+                if let Some(tag) = self.state.known_tags.get(tag_index).cloned() {
+                    if let Some(conversation) = self.state.selected_conversation() {
+                        let conversation_id = conversation.id.clone();
+                        if conversation.tags.contains(&tag) {
+                            self.send_event(NavigationEvent::RemoveTag(conversation_id, tag));
+                        } else {
+                            self.send_event(NavigationEvent::AddTag(conversation_id, tag));
+                        }
+                    }
+                }
+                true
+            }
             KeyCode::Tab if !self.state.search_active => {
                 let next_mode = match self.state.view_mode {
                     NavigationViewMode::Active => NavigationViewMode::Archived,
                     NavigationViewMode::Archived => NavigationViewMode::All,
-                    NavigationViewMode::All => NavigationViewMode::Active,
+                    NavigationViewMode::All => NavigationViewMode::Notifications,
+                    NavigationViewMode::Notifications => NavigationViewMode::Active,
                 };
                 self.state.set_view_mode(next_mode);
                 true
@@ -456,6 +874,46 @@ impl NavigationPanel {
         }
     }
 
+    /// Handle keyboard input while the quick-switcher overlay is open,
+    /// taking over entirely until it's dismissed with Esc
+    fn handle_quick_switcher_input(&mut self, event: KeyEvent) -> bool {
+        match event.code {
+            KeyCode::Esc => {
+                self.state.quick_switcher.close();
+                true
+            }
+            KeyCode::Enter => {
+                if let Some(conversation) = self.state.quick_switcher.selected_conversation() {
+                    let conversation_id = conversation.id.clone();
+                    self.state.quick_switcher.close();
+                    self.send_event(NavigationEvent::OpenConversation(conversation_id));
+                }
+                true
+            }
+            KeyCode::Up => {
+                self.state.quick_switcher.select_previous();
+                true
+            }
+            KeyCode::Down => {
+                self.state.quick_switcher.select_next();
+                true
+            }
+            KeyCode::Backspace => {
+                let mut query = self.state.quick_switcher.query.clone();
+                query.pop();
+                self.state.quick_switcher.set_query(query);
+                true
+            }
+            KeyCode::Char(c) => {
+                let mut query = self.state.quick_switcher.query.clone();
+                query.push(c);
+                self.state.quick_switcher.set_query(query);
+                true
+            }
+            _ => false,
+        }
+    }
+
     /// Render the navigation panel
     pub fn render(&mut self, f: &mut Frame, area: Rect) {
         if !self.state.visible {
@@ -487,7 +945,11 @@ impl NavigationPanel {
         } else {
             chunks[1]
         };
-        self.render_conversation_list(f, list_area);
+        if self.state.view_mode == NavigationViewMode::Notifications {
+            self.render_notification_feed(f, list_area);
+        } else {
+            self.render_conversation_list(f, list_area);
+        }
 
         // Render status bar
         let status_area = if self.state.search_active {
@@ -496,6 +958,103 @@ impl NavigationPanel {
             chunks[2]
         };
         self.render_status_bar(f, status_area);
+
+        // Quick-switcher is a modal overlay, drawn last so it sits on top
+        // of the rest of the panel.
+        if self.state.quick_switcher.active {
+            self.render_quick_switcher(f, area);
+        }
+    }
+
+    /// Render the quick-switcher overlay: a centered popup with its own
+    /// input box and ranked result list, independent of `view_mode`
+    fn render_quick_switcher(&self, f: &mut Frame, area: Rect) {
+        let popup_area = Self::centered_rect(60, 60, area);
+        f.render_widget(Clear, popup_area);
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(3), // Input box
+                Constraint::Min(3),    // Result list
+            ])
+            .split(popup_area);
+
+        let block = Block::default()
+            .title_top(Line::from("Jump to conversation").alignment(Alignment::Center))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Cyan));
+        f.render_widget(block, popup_area);
+
+        let input_text = if self.state.quick_switcher.query.is_empty() {
+            "Type to jump to a conversation..."
+        } else {
+            &self.state.quick_switcher.query
+        };
+        let input = Paragraph::new(input_text).block(
+            Block::default()
+                .borders(Borders::BOTTOM)
+                .border_style(Style::default().fg(Color::Yellow)),
+        );
+        f.render_widget(
+            input,
+            chunks[0].inner(Margin {
+                vertical: 0,
+                horizontal: 1,
+            }),
+        );
+
+        let items: Vec<ListItem> = self
+            .state
+            .quick_switcher
+            .results
+            .iter()
+            .map(|conv| {
+                let unread = if conv.unread_count > 0 { "\u{25cf} " } else { "  " };
+                ListItem::new(format!("{}{}", unread, conv.other_username))
+            })
+            .collect();
+
+        let list = List::new(items)
+            .highlight_style(
+                Style::default()
+                    .bg(Color::DarkGray)
+                    .add_modifier(Modifier::BOLD),
+            )
+            .highlight_symbol("> ");
+
+        let mut list_state = ListState::default();
+        list_state.select(self.state.quick_switcher.selected_index);
+        f.render_stateful_widget(
+            list,
+            chunks[1].inner(Margin {
+                vertical: 0,
+                horizontal: 1,
+            }),
+            &mut list_state,
+        );
+    }
+
+    /// Compute a popup rect centered within `area`, `percent_x`/`percent_y`
+    /// of its width/height
+    fn centered_rect(percent_x: u16, percent_y: u16, area: Rect) -> Rect {
+        let popup_layout = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Percentage((100 - percent_y) / 2),
+                Constraint::Percentage(percent_y),
+                Constraint::Percentage((100 - percent_y) / 2),
+            ])
+            .split(area);
+
+        Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([
+                Constraint::Percentage((100 - percent_x) / 2),
+                Constraint::Percentage(percent_x),
+                Constraint::Percentage((100 - percent_x) / 2),
+            ])
+            .split(popup_layout[1])[1]
     }
 
     /// Render navigation header
@@ -521,12 +1080,42 @@ impl NavigationPanel {
             NavigationViewMode::Active => "",
             NavigationViewMode::Archived => " [Archived]",
             NavigationViewMode::All => " [All]",
+            NavigationViewMode::Notifications => " [Notifications]",
         };
 
         if !mode_text.is_empty() {
             title_spans.push(Span::styled(mode_text, Style::default().fg(Color::Yellow)));
         }
 
+        // Add unseen-notification badge, visible from every view so it
+        // isn't missed while browsing conversations
+        let unseen_notifications = self.state.unseen_notification_count();
+        if unseen_notifications > 0 {
+            title_spans.push(Span::styled(
+                format!(" ðŸ”” {}", unseen_notifications),
+                Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+            ));
+        }
+
+        // Add sort indicator
+        title_spans.push(Span::styled(
+            format!(
+                " [{} {}]",
+                self.state.sort_field.label(),
+                self.state.sort_order.label()
+            ),
+            Style::default().fg(Color::Gray),
+        ));
+
+        // Add tag filter indicator
+        if let Some(tag) = &self.state.tag_filter {
+            let chip_color = self.state.tag_color(tag).unwrap_or(Color::Gray);
+            title_spans.push(Span::styled(
+                format!(" #{}", tag),
+                Style::default().fg(chip_color),
+            ));
+        }
+
         let header_block = Block::default()
             .title_top(Line::from(title_spans).alignment(Alignment::Center))
             .borders(Borders::ALL)
@@ -589,6 +1178,9 @@ impl NavigationPanel {
                 }
                 NavigationViewMode::Archived => "No archived conversations.",
                 NavigationViewMode::All => "No conversations yet. Press 'n' to start a new DM.",
+                // `render_notification_feed` handles this mode; `render_conversation_list`
+                // is never called while `view_mode` is `Notifications`.
+                NavigationViewMode::Notifications => "",
             };
 
             let empty_widget = Paragraph::new(empty_text)
@@ -641,16 +1233,18 @@ impl NavigationPanel {
             first_line.push(Span::styled("  ", Style::default()));
         }
 
-        // Username
-        first_line.push(Span::styled(
-            conversation.other_username.clone(),
-            if conversation.unread_count > 0 {
-                Style::default()
-                    .fg(Color::White)
-                    .add_modifier(Modifier::BOLD)
-            } else {
-                Style::default().fg(Color::White)
-            },
+        // Username, with fuzzy-search matches highlighted if present
+        let username_style = if conversation.unread_count > 0 {
+            Style::default()
+                .fg(Color::White)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(Color::White)
+        };
+        first_line.extend(self.render_username_spans(
+            &conversation.other_username,
+            username_style,
+            self.state.search_matches.get(&conversation.id),
         ));
 
         // Unread count
@@ -666,6 +1260,16 @@ impl NavigationPanel {
             first_line.push(Span::styled(" ðŸ”‡", Style::default().fg(Color::Yellow)));
         }
 
+        // Tag chips, colored per `tag_colors` (falling back to gray for an
+        // unregistered tag)
+        for tag in &conversation.tags {
+            let chip_color = self.state.tag_color(tag).unwrap_or(Color::Gray);
+            first_line.push(Span::styled(
+                format!(" [{}]", tag),
+                Style::default().fg(chip_color),
+            ));
+        }
+
         // Timestamp
         if self.state.show_timestamps {
             let time_text = self.format_timestamp(conversation.last_activity);
@@ -677,19 +1281,34 @@ impl NavigationPanel {
 
         lines.push(Line::from(first_line));
 
-        // Second line: Last message preview
+        // Second line: Last message preview, rendered through a small
+        // inline-markup parser so *bold*, _italic_, `code`, URLs, and
+        // @mentions stand out instead of showing their raw markup.
         if self.state.show_message_preview {
-            if let Some(preview) = &conversation.last_message {
-                let preview_text = if preview.len() > 60 {
-                    format!("  {}...", &preview[..57])
+            if let Some((snippet, matched_at)) = self.state.history_matches.get(&conversation.id) {
+                let age_text = self.format_timestamp(*matched_at);
+                let mut preview_spans = vec![Span::styled(
+                    format!("  \u{2026}matched in message from {} ago: ", age_text),
+                    Style::default().fg(Color::DarkGray),
+                )];
+                preview_spans.extend(parse_message_spans(snippet, self.state.current_username()));
+
+                lines.push(Line::from(preview_spans));
+            } else if let Some(preview) = &conversation.last_message {
+                let truncated = if preview.chars().count() > 60 {
+                    let head: String = preview.chars().take(57).collect();
+                    format!("{}...", head)
                 } else {
-                    format!("  {}", preview)
+                    preview.clone()
                 };
 
-                lines.push(Line::from(vec![Span::styled(
-                    preview_text,
-                    Style::default().fg(Color::DarkGray),
-                )]));
+                let mut preview_spans = vec![Span::raw("  ")];
+                preview_spans.extend(parse_message_spans(
+                    &truncated,
+                    self.state.current_username(),
+                ));
+
+                lines.push(Line::from(preview_spans));
             } else {
                 lines.push(Line::from(vec![Span::styled(
                     "  No messages yet",
@@ -706,41 +1325,188 @@ impl NavigationPanel {
         ListItem::new(lines)
     }
 
-    /// Format timestamp for display
-    fn format_timestamp(&self, timestamp: u64) -> String {
-        let now = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap_or_default()
-            .as_secs();
+    /// Render the notification feed backing `NavigationViewMode::Notifications`
+    fn render_notification_feed(&self, f: &mut Frame, area: Rect) {
+        let list_area = area.inner(Margin {
+            vertical: 0,
+            horizontal: 1,
+        });
 
-        let age = now.saturating_sub(timestamp);
+        if self.state.notifications.entries.is_empty() {
+            let empty_widget = Paragraph::new("No notifications.")
+                .style(Style::default().fg(Color::DarkGray))
+                .alignment(Alignment::Center)
+                .wrap(Wrap { trim: true });
 
-        if age < 60 {
-            "now".to_string()
-        } else if age < 3600 {
-            format!("{}m", age / 60)
-        } else if age < 86400 {
-            format!("{}h", age / 3600)
-        } else if age < 604800 {
-            format!("{}d", age / 86400)
-        } else {
-            format!("{}w", age / 604800)
+            f.render_widget(empty_widget, list_area);
+            return;
         }
-    }
-
-    /// Render status bar
-    fn render_status_bar(&self, f: &mut Frame, area: Rect) {
-        let mut status_spans = Vec::new();
 
-        // Conversation count
-        let count_text = format!("{} conversations", self.state.conversations.len());
-        status_spans.push(Span::styled(count_text, Style::default().fg(Color::Cyan)));
+        // Newest first, so users triage the most recent activity first.
+        let items: Vec<ListItem> = self
+            .state
+            .notifications
+            .entries
+            .iter()
+            .rev()
+            .map(|entry| self.create_notification_feed_item(entry))
+            .collect();
 
-        // View mode
+        let list = List::new(items)
+            .highlight_style(
+                Style::default()
+                    .bg(Color::Blue)
+                    .fg(Color::White)
+                    .add_modifier(Modifier::BOLD),
+            )
+            .highlight_symbol("â–º ");
+
+        // `selected_index` is ordinal into `entries` (oldest-first); the
+        // rendered list is reversed, so flip it to match.
+        let mut list_state = self.state.notifications.list_state.clone();
+        if let Some(index) = self.state.notifications.selected_index {
+            list_state.select(Some(self.state.notifications.entries.len() - 1 - index));
+        }
+        f.render_stateful_widget(list, list_area, &mut list_state);
+    }
+
+    /// Create a list item for a notification feed entry
+    fn create_notification_feed_item(&self, entry: &NotificationEntry) -> ListItem {
+        let mut line = Vec::new();
+
+        if !entry.read {
+            line.push(Span::styled(
+                "â— ",
+                Style::default()
+                    .fg(Color::Green)
+                    .add_modifier(Modifier::BOLD),
+            ));
+        } else {
+            line.push(Span::styled("  ", Style::default()));
+        }
+
+        line.push(Span::styled(
+            format!("[{}] ", entry.kind.label()),
+            Style::default()
+                .fg(entry.kind.color())
+                .add_modifier(Modifier::BOLD),
+        ));
+
+        line.push(Span::styled(
+            entry.preview.clone(),
+            Style::default().fg(Color::White),
+        ));
+
+        line.push(Span::styled(
+            format!(" - {}", self.format_timestamp(entry.created_at)),
+            Style::default().fg(Color::DarkGray),
+        ));
+
+        ListItem::new(Line::from(line))
+    }
+
+    /// Render `username` as one span per character, highlighting those at
+    /// `highlight_indices` (the char offsets a fuzzy search matched) with
+    /// an underline on top of `base_style`. Falls back to a single span
+    /// when there's nothing to highlight.
+    fn render_username_spans<'a>(
+        &self,
+        username: &'a str,
+        base_style: Style,
+        highlight_indices: Option<&Vec<usize>>,
+    ) -> Vec<Span<'a>> {
+        match highlight_indices {
+            Some(indices) if !indices.is_empty() => {
+                let highlighted: std::collections::HashSet<usize> =
+                    indices.iter().copied().collect();
+                let highlight_style = base_style
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::UNDERLINED);
+
+                username
+                    .chars()
+                    .enumerate()
+                    .map(|(i, c)| {
+                        let style = if highlighted.contains(&i) {
+                            highlight_style
+                        } else {
+                            base_style
+                        };
+                        Span::styled(c.to_string(), style)
+                    })
+                    .collect()
+            }
+            _ => vec![Span::styled(username, base_style)],
+        }
+    }
+
+    /// Format `timestamp` (epoch seconds) for display per
+    /// `NavigationState::timestamp_format`
+    fn format_timestamp(&self, timestamp: u64) -> String {
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+
+        let age = now.saturating_sub(timestamp);
+
+        match self.state.timestamp_format {
+            TimestampFormat::Relative => Self::format_relative(age),
+            TimestampFormat::Absolute => self.format_absolute(timestamp),
+            TimestampFormat::Smart => {
+                if age < 86400 {
+                    Self::format_relative(age)
+                } else {
+                    self.format_absolute(timestamp)
+                }
+            }
+        }
+    }
+
+    /// Coarse relative age, e.g. "now", "5m", "2h", "3d", "4w"
+    fn format_relative(age: u64) -> String {
+        if age < 60 {
+            "now".to_string()
+        } else if age < 3600 {
+            format!("{}m", age / 60)
+        } else if age < 86400 {
+            format!("{}h", age / 3600)
+        } else if age < 604800 {
+            format!("{}d", age / 86400)
+        } else {
+            format!("{}w", age / 604800)
+        }
+    }
+
+    /// Render `timestamp` as a localized date via `NavigationState::date_format`
+    fn format_absolute(&self, timestamp: u64) -> String {
+        chrono::DateTime::from_timestamp(timestamp as i64, 0)
+            .map(|utc| {
+                chrono::DateTime::<chrono::Local>::from(utc)
+                    .format(&self.state.date_format)
+                    .to_string()
+            })
+            .unwrap_or_else(|| "unknown".to_string())
+    }
+
+    /// Render status bar
+    fn render_status_bar(&self, f: &mut Frame, area: Rect) {
+        let mut status_spans = Vec::new();
+
+        // Conversation count, or notification count while in that feed
+        let count_text = if self.state.view_mode == NavigationViewMode::Notifications {
+            format!("{} notifications", self.state.notifications.entries.len())
+        } else {
+            format!("{} conversations", self.state.conversations.len())
+        };
+        status_spans.push(Span::styled(count_text, Style::default().fg(Color::Cyan)));
+
+        // View mode
         let mode_text = match self.state.view_mode {
             NavigationViewMode::Active => "Active",
             NavigationViewMode::Archived => "Archived",
             NavigationViewMode::All => "All",
+            NavigationViewMode::Notifications => "Notifications",
         };
         status_spans.push(Span::styled(
             format!(" | {}", mode_text),
@@ -748,14 +1514,19 @@ impl NavigationPanel {
         ));
 
         // Help text
-        if !self.state.search_active {
+        if self.state.search_active {
+            status_spans.push(Span::styled(
+                " | Type to search, Esc:cancel",
+                Style::default().fg(Color::DarkGray),
+            ));
+        } else if self.state.view_mode == NavigationViewMode::Notifications {
             status_spans.push(Span::styled(
-                " | â†‘â†“:navigate n:new /:search a:archive m:mute r:read Tab:mode",
+                " | â†‘â†“:navigate Enter:open r:read R:read all C:clear Tab:mode Ctrl+p:jump",
                 Style::default().fg(Color::DarkGray),
             ));
         } else {
             status_spans.push(Span::styled(
-                " | Type to search, Esc:cancel",
+                " | â†‘â†“:navigate n:new /:search a:archive m:mute r:read Tab:mode s:sort S:order t:tag T:time 1-9:label Ctrl+p:jump",
                 Style::default().fg(Color::DarkGray),
             ));
         }
@@ -774,6 +1545,231 @@ impl Default for NavigationPanel {
     }
 }
 
+/// Parse a small, safe inline-markup subset of `text` — `*bold*`,
+/// `_italic_`, `` `code` ``, bare URLs, and `@mentions` — into styled
+/// `Span`s for the conversation preview line. Control characters are
+/// stripped first so a malicious peer can't smuggle ANSI escapes into the
+/// terminal through a message preview. `local_username`, if given, gets a
+/// highlight color on its own mentions so pinged conversations stand out.
+fn parse_message_spans(text: &str, local_username: Option<&str>) -> Vec<Span<'static>> {
+    let chars: Vec<char> = text.chars().filter(|c| !c.is_control()).collect();
+    let mut spans = Vec::new();
+    let mut plain_start = 0;
+    let mut i = 0;
+
+    let plain_style = Style::default().fg(Color::DarkGray);
+
+    while i < chars.len() {
+        let c = chars[i];
+
+        if (c == '*' || c == '_' || c == '`') && i + 1 < chars.len() {
+            if let Some(close) = chars[i + 1..].iter().position(|&ch| ch == c) {
+                let close = i + 1 + close;
+                if close > i + 1 {
+                    if plain_start < i {
+                        spans.push(Span::styled(
+                            chars[plain_start..i].iter().collect::<String>(),
+                            plain_style,
+                        ));
+                    }
+                    let inner: String = chars[i + 1..close].iter().collect();
+                    let style = match c {
+                        '*' => plain_style.add_modifier(Modifier::BOLD),
+                        '_' => plain_style.add_modifier(Modifier::ITALIC),
+                        _ => Style::default().fg(Color::Green),
+                    };
+                    spans.push(Span::styled(inner, style));
+                    i = close + 1;
+                    plain_start = i;
+                    continue;
+                }
+            }
+        } else if c == '@' && i + 1 < chars.len() {
+            let start = i + 1;
+            let mut end = start;
+            while end < chars.len() && (chars[end].is_alphanumeric() || chars[end] == '_') {
+                end += 1;
+            }
+            if end > start {
+                if plain_start < i {
+                    spans.push(Span::styled(
+                        chars[plain_start..i].iter().collect::<String>(),
+                        plain_style,
+                    ));
+                }
+                let name: String = chars[start..end].iter().collect();
+                let is_self_mention = local_username
+                    .map(|username| username.eq_ignore_ascii_case(&name))
+                    .unwrap_or(false);
+                let style = if is_self_mention {
+                    Style::default()
+                        .fg(Color::Black)
+                        .bg(Color::Yellow)
+                        .add_modifier(Modifier::BOLD)
+                } else {
+                    Style::default().fg(Color::Cyan)
+                };
+                spans.push(Span::styled(format!("@{}", name), style));
+                i = end;
+                plain_start = i;
+                continue;
+            }
+        } else if matches_url_prefix(&chars, i) {
+            let start = i;
+            let mut end = start;
+            while end < chars.len() && !chars[end].is_whitespace() {
+                end += 1;
+            }
+            if plain_start < i {
+                spans.push(Span::styled(
+                    chars[plain_start..i].iter().collect::<String>(),
+                    plain_style,
+                ));
+            }
+            let url: String = chars[start..end].iter().collect();
+            spans.push(Span::styled(
+                url,
+                Style::default()
+                    .fg(Color::Blue)
+                    .add_modifier(Modifier::UNDERLINED),
+            ));
+            i = end;
+            plain_start = i;
+            continue;
+        }
+
+        i += 1;
+    }
+
+    if plain_start < chars.len() {
+        spans.push(Span::styled(
+            chars[plain_start..].iter().collect::<String>(),
+            plain_style,
+        ));
+    }
+
+    spans
+}
+
+/// Whether `chars[i..]` begins with `http://` or `https://`
+fn matches_url_prefix(chars: &[char], i: usize) -> bool {
+    const PREFIXES: [&str; 2] = ["http://", "https://"];
+    PREFIXES.iter().any(|prefix| {
+        let prefix_chars: Vec<char> = prefix.chars().collect();
+        chars[i..].len() >= prefix_chars.len() && chars[i..i + prefix_chars.len()] == prefix_chars[..]
+    })
+}
+
+/// Consecutive-match bonus: rewards runs of matched characters that sit
+/// right next to each other in `candidate`, the way a real fuzzy finder
+/// prefers a tight match over a scattered one.
+const FUZZY_CONSECUTIVE_BONUS: i64 = 15;
+/// Bonus for a match at the very start of `candidate`.
+const FUZZY_START_BONUS: i64 = 10;
+/// Bonus for a match right after a word boundary (space, `_`, or `-`).
+const FUZZY_BOUNDARY_BONUS: i64 = 8;
+
+/// Rank `conversations` against `query` the same way the in-panel
+/// `/`-search does: the best of a `other_username`, `last_message`, or tag
+/// fuzzy match, descending by score and tied by most recent activity.
+/// Shared by `NavigationState::filter_by_search` and `QuickSwitcherState`
+/// so both searches agree on what counts as a match. Returns the matched
+/// `other_username` char indices alongside each survivor, for highlighting.
+fn fuzzy_rank_conversations(
+    query: &str,
+    conversations: &[ConversationSummary],
+) -> Vec<(ConversationSummary, Option<Vec<usize>>)> {
+    let mut scored: Vec<(i64, ConversationSummary, Option<Vec<usize>>)> = Vec::new();
+    for conv in conversations {
+        let username_match = fuzzy_match(query, &conv.other_username).map(|(s, i)| (s, Some(i)));
+        let message_match = conv
+            .last_message
+            .as_deref()
+            .and_then(|msg| fuzzy_match(query, msg))
+            .map(|(s, _)| (s, None));
+        let tag_match = conv
+            .tags
+            .iter()
+            .filter_map(|tag| fuzzy_match(query, tag.as_str()))
+            .max_by_key(|(s, _)| *s)
+            .map(|(s, _)| (s, None));
+
+        // `max_by_key` keeps the last of equally-scored entries, so list
+        // username last to preserve its priority on ties.
+        let Some((score, username_indices)) = [tag_match, message_match, username_match]
+            .into_iter()
+            .flatten()
+            .max_by_key(|(s, _)| *s)
+        else {
+            continue;
+        };
+
+        scored.push((score, conv.clone(), username_indices));
+    }
+
+    // Ties fall back to most recent activity first, so equally-scored
+    // matches don't shuffle unpredictably as the query changes.
+    scored.sort_by(|a, b| {
+        b.0.cmp(&a.0)
+            .then_with(|| b.1.last_activity.cmp(&a.1.last_activity))
+    });
+    scored
+        .into_iter()
+        .map(|(_, conv, indices)| (conv, indices))
+        .collect()
+}
+
+/// Fuzzy subsequence matcher in the spirit of `SkimMatcherV2`: confirms
+/// `query`'s characters appear in order as a subsequence of `candidate`
+/// (case-insensitively), then scores the match, rewarding consecutive runs
+/// and hits at word boundaries or the start of the string, and penalizing
+/// leading skipped characters and gaps between matches. Returns `None` if
+/// `query` is not a subsequence of `candidate`; returns the matched char
+/// indices alongside the score so callers can highlight them.
+fn fuzzy_match(query: &str, candidate: &str) -> Option<(i64, Vec<usize>)> {
+    if query.is_empty() {
+        return Some((0, Vec::new()));
+    }
+
+    let query_chars: Vec<char> = query.to_lowercase().chars().collect();
+    let candidate_chars: Vec<char> = candidate.chars().collect();
+    let candidate_lower: Vec<char> = candidate.to_lowercase().chars().collect();
+
+    let mut indices = Vec::with_capacity(query_chars.len());
+    let mut score: i64 = 0;
+    let mut query_pos = 0;
+    let mut prev_match: Option<usize> = None;
+
+    for (i, &c) in candidate_lower.iter().enumerate() {
+        if query_pos >= query_chars.len() {
+            break;
+        }
+        if c != query_chars[query_pos] {
+            continue;
+        }
+
+        match prev_match {
+            Some(prev) if i == prev + 1 => score += FUZZY_CONSECUTIVE_BONUS,
+            Some(prev) => score -= (i - prev - 1) as i64,
+            None if i == 0 => score += FUZZY_START_BONUS,
+            None if matches!(candidate_chars[i - 1], ' ' | '_' | '-') => {
+                score += FUZZY_BOUNDARY_BONUS
+            }
+            None => score -= i as i64,
+        }
+
+        indices.push(i);
+        prev_match = Some(i);
+        query_pos += 1;
+    }
+
+    if query_pos < query_chars.len() {
+        None
+    } else {
+        Some((score + 1, indices))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -801,6 +1797,7 @@ mod tests {
                 unread_count: 1,
                 is_archived: false,
                 is_muted: false,
+                tags: Vec::new(),
             },
             ConversationSummary {
                 id: ConversationId::from("conv2"),
@@ -811,6 +1808,7 @@ mod tests {
                 unread_count: 0,
                 is_archived: true,
                 is_muted: false,
+                tags: Vec::new(),
             },
         ];
 
@@ -832,6 +1830,31 @@ mod tests {
         assert_eq!(state.conversations.len(), 2);
     }
 
+    #[test]
+    fn test_notifications_view_mode() {
+        use super::super::notifications::NotificationKind;
+
+        let mut state = NavigationState::new();
+        state.notifications.push(NotificationEntry::new(
+            ConversationId::from("conv1"),
+            NotificationKind::Mention,
+            "@you: are you around?".to_string(),
+            100,
+        ));
+
+        assert_eq!(state.unseen_notification_count(), 1);
+
+        // Tab cycles Active -> Archived -> All -> Notifications -> Active
+        state.set_view_mode(NavigationViewMode::Notifications);
+        assert_eq!(state.view_mode, NavigationViewMode::Notifications);
+
+        state.notifications.mark_selected_read();
+        assert_eq!(state.unseen_notification_count(), 0);
+
+        state.notifications.clear();
+        assert!(state.notifications.entries.is_empty());
+    }
+
     #[test]
     fn test_search_functionality() {
         let mut state = NavigationState::new();
@@ -846,6 +1869,7 @@ mod tests {
                 unread_count: 0,
                 is_archived: false,
                 is_muted: false,
+                tags: Vec::new(),
             },
             ConversationSummary {
                 id: ConversationId::from("conv2"),
@@ -856,6 +1880,7 @@ mod tests {
                 unread_count: 0,
                 is_archived: false,
                 is_muted: false,
+                tags: Vec::new(),
             },
         ];
 
@@ -874,6 +1899,333 @@ mod tests {
         assert_eq!(state.conversations[0].other_username, "alice");
     }
 
+    #[test]
+    fn test_apply_history_search_results_tracked_and_cleared() {
+        let mut state = NavigationState::new();
+        let conversation_id = ConversationId::from("conv1");
+
+        state.apply_history_search_results(&[SearchHit {
+            conversation_id: conversation_id.clone(),
+            message_id: uuid::Uuid::new_v4(),
+            sender_id: uuid::Uuid::new_v4(),
+            created_at: 100,
+            snippet: "let's grab coffee sometime".to_string(),
+        }]);
+
+        let (snippet, matched_at) = state.history_matches.get(&conversation_id).unwrap();
+        assert_eq!(snippet, "let's grab coffee sometime");
+        assert_eq!(*matched_at, 100);
+
+        // Toggling search off clears any history match, same as the
+        // fuzzy-search highlight state.
+        state.search_active = true;
+        state.toggle_search();
+        assert!(state.history_matches.is_empty());
+    }
+
+    #[test]
+    fn test_quick_switcher_matches_regardless_of_view_mode() {
+        let mut switcher = QuickSwitcherState::new();
+
+        let conversations = vec![
+            ConversationSummary {
+                id: ConversationId::from("conv1"),
+                other_user_id: uuid::Uuid::new_v4(),
+                other_username: "alice".to_string(),
+                last_message: Some("Hello world".to_string()),
+                last_activity: 100,
+                unread_count: 0,
+                is_archived: false,
+                is_muted: false,
+                tags: Vec::new(),
+            },
+            ConversationSummary {
+                id: ConversationId::from("conv2"),
+                other_user_id: uuid::Uuid::new_v4(),
+                other_username: "archie".to_string(),
+                last_message: None,
+                last_activity: 50,
+                unread_count: 0,
+                is_archived: true,
+                is_muted: false,
+                tags: Vec::new(),
+            },
+        ];
+
+        // The overlay opens over the full snapshot, so an archived
+        // conversation is reachable without first switching views.
+        switcher.open(conversations);
+        assert_eq!(switcher.results.len(), 2);
+        assert_eq!(switcher.selected_index, Some(0));
+
+        switcher.set_query("archi".to_string());
+        assert_eq!(switcher.results.len(), 1);
+        assert_eq!(switcher.selected_conversation().unwrap().other_username, "archie");
+
+        switcher.close();
+        assert!(!switcher.active);
+        assert!(switcher.results.is_empty());
+        assert!(switcher.selected_conversation().is_none());
+    }
+
+    #[test]
+    fn test_sort_field_and_order_cycling() {
+        let mut state = NavigationState::new();
+
+        let conversations = vec![
+            ConversationSummary {
+                id: ConversationId::from("conv1"),
+                other_user_id: uuid::Uuid::new_v4(),
+                other_username: "bob".to_string(),
+                last_message: None,
+                last_activity: 200,
+                unread_count: 5,
+                is_archived: false,
+                is_muted: false,
+                tags: Vec::new(),
+            },
+            ConversationSummary {
+                id: ConversationId::from("conv2"),
+                other_user_id: uuid::Uuid::new_v4(),
+                other_username: "alice".to_string(),
+                last_message: None,
+                last_activity: 100,
+                unread_count: 1,
+                is_archived: false,
+                is_muted: false,
+                tags: Vec::new(),
+            },
+        ];
+
+        state.update_conversations(conversations);
+
+        // Default: activity descending (newest first).
+        assert_eq!(state.conversations[0].other_username, "bob");
+
+        // Cycle to Name and sort ascending by default order (descending),
+        // so it comes back reversed until we flip it.
+        state.cycle_sort_field();
+        assert_eq!(state.sort_field, SortField::Name);
+        assert_eq!(state.conversations[0].other_username, "bob");
+
+        state.flip_sort_order();
+        assert_eq!(state.sort_order, SortOrder::Ascending);
+        assert_eq!(state.conversations[0].other_username, "alice");
+
+        // Cycling once more lands on UnreadCount, still ascending.
+        state.cycle_sort_field();
+        assert_eq!(state.sort_field, SortField::UnreadCount);
+        assert_eq!(state.conversations[0].other_username, "alice");
+    }
+
+    #[test]
+    fn test_timestamp_format_cycling() {
+        let mut state = NavigationState::new();
+        assert_eq!(state.timestamp_format, TimestampFormat::Relative);
+        assert_eq!(state.date_format, "%Y-%m-%d");
+
+        state.cycle_timestamp_format();
+        assert_eq!(state.timestamp_format, TimestampFormat::Absolute);
+
+        state.cycle_timestamp_format();
+        assert_eq!(state.timestamp_format, TimestampFormat::Smart);
+
+        state.cycle_timestamp_format();
+        assert_eq!(state.timestamp_format, TimestampFormat::Relative);
+
+        state.set_date_format("%b %d".to_string());
+        assert_eq!(state.date_format, "%b %d");
+    }
+
+    #[test]
+    fn test_tag_filter_and_tag_search() {
+        let mut state = NavigationState::new();
+        let work = TagId::from("work");
+        let family = TagId::from("family");
+        state.register_tag(work.clone(), Color::Blue);
+        state.register_tag(family.clone(), Color::Magenta);
+
+        let conversations = vec![
+            ConversationSummary {
+                id: ConversationId::from("conv1"),
+                other_user_id: uuid::Uuid::new_v4(),
+                other_username: "alice".to_string(),
+                last_message: None,
+                last_activity: 100,
+                unread_count: 0,
+                is_archived: false,
+                is_muted: false,
+                tags: vec![work.clone()],
+            },
+            ConversationSummary {
+                id: ConversationId::from("conv2"),
+                other_user_id: uuid::Uuid::new_v4(),
+                other_username: "bob".to_string(),
+                last_message: None,
+                last_activity: 200,
+                unread_count: 0,
+                is_archived: false,
+                is_muted: false,
+                tags: vec![family.clone()],
+            },
+        ];
+
+        state.update_conversations(conversations);
+        assert_eq!(state.conversations.len(), 2);
+
+        // Cycling the tag filter narrows to conversations carrying that tag.
+        state.cycle_tag_filter();
+        assert_eq!(state.tag_filter, Some(work.clone()));
+        assert_eq!(state.conversations.len(), 1);
+        assert_eq!(state.conversations[0].other_username, "alice");
+
+        state.cycle_tag_filter();
+        assert_eq!(state.tag_filter, Some(family));
+        assert_eq!(state.conversations.len(), 1);
+        assert_eq!(state.conversations[0].other_username, "bob");
+
+        state.cycle_tag_filter();
+        assert_eq!(state.tag_filter, None);
+        assert_eq!(state.conversations.len(), 2);
+
+        // Searching by tag name narrows instantly, same as a username or
+        // message match would.
+        let conversations = vec![
+            ConversationSummary {
+                id: ConversationId::from("conv1"),
+                other_user_id: uuid::Uuid::new_v4(),
+                other_username: "alice".to_string(),
+                last_message: None,
+                last_activity: 100,
+                unread_count: 0,
+                is_archived: false,
+                is_muted: false,
+                tags: vec![work],
+            },
+            ConversationSummary {
+                id: ConversationId::from("conv2"),
+                other_user_id: uuid::Uuid::new_v4(),
+                other_username: "bob".to_string(),
+                last_message: None,
+                last_activity: 200,
+                unread_count: 0,
+                is_archived: false,
+                is_muted: false,
+                tags: Vec::new(),
+            },
+        ];
+        state.update_conversations(conversations);
+        state.toggle_search();
+        state.set_search_query("work".to_string());
+        assert_eq!(state.conversations.len(), 1);
+        assert_eq!(state.conversations[0].other_username, "alice");
+    }
+
+    #[test]
+    fn test_fuzzy_match_scores_consecutive_and_boundary_hits_higher() {
+        // A tight, start-anchored match should outscore the same letters
+        // scattered across the candidate.
+        let (tight_score, tight_indices) = fuzzy_match("ali", "alice").unwrap();
+        assert_eq!(tight_indices, vec![0, 1, 2]);
+
+        let (scattered_score, _) = fuzzy_match("ale", "alice").unwrap();
+        assert!(tight_score > scattered_score);
+
+        // No subsequence match at all.
+        assert!(fuzzy_match("xyz", "alice").is_none());
+
+        // A word-boundary match beats a mid-word match of the same length.
+        let (boundary_score, _) = fuzzy_match("bob", "alice_bob").unwrap();
+        let (midword_score, _) = fuzzy_match("ice", "alice_bob").unwrap();
+        assert!(boundary_score > midword_score);
+    }
+
+    #[test]
+    fn test_fuzzy_search_ranks_best_match_first_and_records_highlight_indices() {
+        let mut state = NavigationState::new();
+
+        let conversations = vec![
+            ConversationSummary {
+                id: ConversationId::from("conv1"),
+                other_user_id: uuid::Uuid::new_v4(),
+                other_username: "bob_alister".to_string(),
+                last_message: None,
+                last_activity: 100,
+                unread_count: 0,
+                is_archived: false,
+                is_muted: false,
+                tags: Vec::new(),
+            },
+            ConversationSummary {
+                id: ConversationId::from("conv2"),
+                other_user_id: uuid::Uuid::new_v4(),
+                other_username: "alice".to_string(),
+                last_message: None,
+                last_activity: 50,
+                unread_count: 0,
+                is_archived: false,
+                is_muted: false,
+                tags: Vec::new(),
+            },
+        ];
+
+        state.update_conversations(conversations);
+        state.toggle_search();
+        state.set_search_query("ali".to_string());
+
+        // Both usernames contain "ali" as a subsequence, but "alice" is a
+        // tighter, start-anchored match and should rank first despite
+        // having less recent activity.
+        assert_eq!(state.conversations.len(), 2);
+        assert_eq!(state.conversations[0].other_username, "alice");
+
+        let alice_id = state.conversations[0].id.clone();
+        assert_eq!(
+            state.search_matches.get(&alice_id),
+            Some(&vec![0usize, 1, 2])
+        );
+    }
+
+    #[test]
+    fn test_fuzzy_search_ties_break_by_most_recent_activity() {
+        let mut state = NavigationState::new();
+
+        let conversations = vec![
+            ConversationSummary {
+                id: ConversationId::from("conv1"),
+                other_user_id: uuid::Uuid::new_v4(),
+                other_username: "alice".to_string(),
+                last_message: None,
+                last_activity: 50,
+                unread_count: 0,
+                is_archived: false,
+                is_muted: false,
+                tags: Vec::new(),
+            },
+            ConversationSummary {
+                id: ConversationId::from("conv2"),
+                other_user_id: uuid::Uuid::new_v4(),
+                other_username: "alica".to_string(),
+                last_message: None,
+                last_activity: 150,
+                unread_count: 0,
+                is_archived: false,
+                is_muted: false,
+                tags: Vec::new(),
+            },
+        ];
+
+        state.update_conversations(conversations);
+        state.toggle_search();
+        state.set_search_query("ali".to_string());
+
+        // Both usernames score identically on a start-anchored 3-char
+        // subsequence, so the tie falls to whichever was active more
+        // recently.
+        assert_eq!(state.conversations.len(), 2);
+        assert_eq!(state.conversations[0].other_username, "alica");
+    }
+
     #[test]
     fn test_navigation() {
         let mut state = NavigationState::new();
@@ -888,6 +2240,7 @@ mod tests {
                 unread_count: 0,
                 is_archived: false,
                 is_muted: false,
+                tags: Vec::new(),
             },
             ConversationSummary {
                 id: ConversationId::from("conv2"),
@@ -898,6 +2251,7 @@ mod tests {
                 unread_count: 0,
                 is_archived: false,
                 is_muted: false,
+                tags: Vec::new(),
             },
         ];
 
@@ -917,4 +2271,42 @@ mod tests {
         state.select_previous();
         assert_eq!(state.selected_index, Some(1));
     }
+
+    #[test]
+    fn test_parse_message_spans_markup_and_mention_highlight() {
+        let spans = parse_message_spans("*hey* check _this_ out: `cargo test`", None);
+        let rendered: Vec<String> = spans.iter().map(|s| s.content.to_string()).collect();
+        assert_eq!(
+            rendered,
+            vec!["hey", " check ", "this", " out: ", "cargo test"]
+        );
+        assert!(spans[0].style.add_modifier.contains(Modifier::BOLD));
+        assert!(spans[2].style.add_modifier.contains(Modifier::ITALIC));
+
+        let url_spans = parse_message_spans("see https://example.com/x for details", None);
+        let url_span = url_spans
+            .iter()
+            .find(|s| s.content.contains("example.com"))
+            .unwrap();
+        assert_eq!(url_span.content, "https://example.com/x");
+
+        // A mention of the local user gets a distinct highlight from other mentions.
+        let mention_spans = parse_message_spans("hey @alice and @bob", Some("alice"));
+        let alice_span = mention_spans
+            .iter()
+            .find(|s| s.content == "@alice")
+            .unwrap();
+        let bob_span = mention_spans
+            .iter()
+            .find(|s| s.content == "@bob")
+            .unwrap();
+        assert_ne!(alice_span.style, bob_span.style);
+    }
+
+    #[test]
+    fn test_parse_message_spans_strips_control_characters() {
+        let spans = parse_message_spans("hello\u{1b}[31mworld", None);
+        let rendered: String = spans.iter().map(|s| s.content.to_string()).collect();
+        assert_eq!(rendered, "hello[31mworld");
+    }
 }
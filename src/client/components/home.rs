@@ -163,6 +163,7 @@ impl Default for Home {
                         unread_count: 2,
                         is_archived: false,
                         is_muted: false,
+                        tags: Vec::new(),
                     },
                     crate::chat::ConversationSummary {
                         id: crate::chat::ConversationId::from_participants(user1, user3),
@@ -177,6 +178,7 @@ impl Default for Home {
                         unread_count: 0,
                         is_archived: false,
                         is_muted: false,
+                        tags: Vec::new(),
                     },
                 ];
                 panel.state_mut().update_conversations(sample_conversations);
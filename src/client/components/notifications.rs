@@ -0,0 +1,577 @@
+//! Notification center for Lair-Chat
+//! Accumulates unread-DM, mention, and peer-activity events into a
+//! scrollable, timestamped list, giving users one place to triage what
+//! happened while they were away instead of scanning every conversation row
+//! for the unread dot.
+
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Margin, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, ListState, Paragraph, Wrap},
+    Frame,
+};
+use std::collections::VecDeque;
+use tokio::sync::mpsc;
+
+use crate::chat::ConversationId;
+
+use super::dm_navigation::NavigationEvent;
+
+/// Maximum number of notifications retained; the oldest entry is dropped
+/// once the ring buffer is full.
+const NOTIFICATION_CAPACITY: usize = 200;
+
+/// Kind of event a [`NotificationEntry`] reports
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NotificationKind {
+    /// A new message arrived in a conversation
+    NewMessage,
+    /// The current user was mentioned
+    Mention,
+    /// The peer archived the conversation
+    ConversationArchivedByPeer,
+}
+
+impl NotificationKind {
+    /// Short label shown on the notification row
+    pub fn label(self) -> &'static str {
+        match self {
+            NotificationKind::NewMessage => "Message",
+            NotificationKind::Mention => "Mention",
+            NotificationKind::ConversationArchivedByPeer => "Archived",
+        }
+    }
+
+    /// Display color for the kind's label chip
+    pub fn color(self) -> Color {
+        match self {
+            NotificationKind::NewMessage => Color::Cyan,
+            NotificationKind::Mention => Color::Red,
+            NotificationKind::ConversationArchivedByPeer => Color::Yellow,
+        }
+    }
+}
+
+/// Single entry in the notification center
+#[derive(Debug, Clone)]
+pub struct NotificationEntry {
+    /// Conversation the notification refers to
+    pub conversation_id: ConversationId,
+    /// What kind of event this is
+    pub kind: NotificationKind,
+    /// Short preview text (message snippet, mention context, etc.)
+    pub preview: String,
+    /// When the event occurred, unix seconds
+    pub created_at: u64,
+    /// Whether the notification has been acknowledged
+    pub read: bool,
+}
+
+impl NotificationEntry {
+    /// Create a new, unread notification
+    pub fn new(
+        conversation_id: ConversationId,
+        kind: NotificationKind,
+        preview: String,
+        created_at: u64,
+    ) -> Self {
+        Self {
+            conversation_id,
+            kind,
+            preview,
+            created_at,
+            read: false,
+        }
+    }
+}
+
+/// Notification center state
+#[derive(Debug, Clone)]
+pub struct NotificationState {
+    /// Ring buffer of entries, newest at the back
+    pub entries: VecDeque<NotificationEntry>,
+    /// Currently selected entry index
+    pub selected_index: Option<usize>,
+    /// List state for selection
+    pub list_state: ListState,
+    /// Whether the overlay panel is visible
+    pub visible: bool,
+    /// Whether the overlay panel has focus
+    pub focused: bool,
+}
+
+impl Default for NotificationState {
+    fn default() -> Self {
+        Self {
+            entries: VecDeque::new(),
+            selected_index: None,
+            list_state: ListState::default(),
+            visible: false,
+            focused: false,
+        }
+    }
+}
+
+impl NotificationState {
+    /// Create new notification state
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Push a new notification, evicting the oldest entry once the ring
+    /// buffer is at `NOTIFICATION_CAPACITY`.
+    pub fn push(&mut self, entry: NotificationEntry) {
+        if self.entries.len() >= NOTIFICATION_CAPACITY {
+            self.entries.pop_front();
+        }
+        self.entries.push_back(entry);
+
+        if self.selected_index.is_none() {
+            self.selected_index = Some(0);
+        }
+        self.list_state.select(self.selected_index);
+    }
+
+    /// Count of unread notifications
+    pub fn unread_count(&self) -> usize {
+        self.entries.iter().filter(|entry| !entry.read).count()
+    }
+
+    /// Mark every notification as read
+    pub fn mark_all_read(&mut self) {
+        for entry in &mut self.entries {
+            entry.read = true;
+        }
+    }
+
+    /// Mark the currently selected notification as read
+    pub fn mark_selected_read(&mut self) {
+        if let Some(entry) = self
+            .selected_index
+            .and_then(|index| self.entries.get_mut(index))
+        {
+            entry.read = true;
+        }
+    }
+
+    /// Discard every notification
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.selected_index = None;
+        self.list_state.select(None);
+    }
+
+    /// Select next notification
+    pub fn select_next(&mut self) {
+        if self.entries.is_empty() {
+            return;
+        }
+
+        let next_index = match self.selected_index {
+            Some(index) if index + 1 < self.entries.len() => index + 1,
+            _ => 0,
+        };
+
+        self.selected_index = Some(next_index);
+        self.list_state.select(self.selected_index);
+    }
+
+    /// Select previous notification
+    pub fn select_previous(&mut self) {
+        if self.entries.is_empty() {
+            return;
+        }
+
+        let prev_index = match self.selected_index {
+            Some(0) | None => self.entries.len() - 1,
+            Some(index) => index - 1,
+        };
+
+        self.selected_index = Some(prev_index);
+        self.list_state.select(self.selected_index);
+    }
+
+    /// Get currently selected notification
+    pub fn selected_entry(&self) -> Option<&NotificationEntry> {
+        self.selected_index.and_then(|index| self.entries.get(index))
+    }
+
+    /// Show the panel
+    pub fn show(&mut self) {
+        self.visible = true;
+        self.focused = true;
+    }
+
+    /// Hide the panel
+    pub fn hide(&mut self) {
+        self.visible = false;
+        self.focused = false;
+    }
+}
+
+/// Notification center overlay panel
+pub struct NotificationPanel {
+    /// Panel state
+    state: NotificationState,
+    /// Event sender, reusing `NavigationEvent` so the rest of the app only
+    /// has to listen on one channel for DM-related events
+    event_sender: Option<mpsc::UnboundedSender<NavigationEvent>>,
+    /// Panel title
+    title: String,
+}
+
+impl NotificationPanel {
+    /// Create new notification panel
+    pub fn new() -> Self {
+        Self {
+            state: NotificationState::new(),
+            event_sender: None,
+            title: "Notifications".to_string(),
+        }
+    }
+
+    /// Create notification panel with event sender
+    pub fn with_event_sender(event_sender: mpsc::UnboundedSender<NavigationEvent>) -> Self {
+        Self {
+            state: NotificationState::new(),
+            event_sender: Some(event_sender),
+            title: "Notifications".to_string(),
+        }
+    }
+
+    /// Get mutable reference to state
+    pub fn state_mut(&mut self) -> &mut NotificationState {
+        &mut self.state
+    }
+
+    /// Get reference to state
+    pub fn state(&self) -> &NotificationState {
+        &self.state
+    }
+
+    /// Send event if sender is available
+    fn send_event(&self, event: NavigationEvent) {
+        if let Some(sender) = &self.event_sender {
+            let _ = sender.send(event);
+        }
+    }
+
+    /// Handle keyboard input
+    pub fn handle_input(&mut self, event: KeyEvent) -> bool {
+        if !self.state.visible || !self.state.focused {
+            return false;
+        }
+
+        match event.code {
+            KeyCode::Esc => {
+                self.state.hide();
+                true
+            }
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.state.select_previous();
+                true
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                self.state.select_next();
+                true
+            }
+            KeyCode::Enter => {
+                if let Some(entry) = self.state.selected_entry() {
+                    let conversation_id = entry.conversation_id.clone();
+                    self.send_event(NavigationEvent::OpenNotification(conversation_id));
+                    self.state.hide();
+                }
+                true
+            }
+            KeyCode::Char('R') => {
+                self.state.mark_all_read();
+                self.send_event(NavigationEvent::MarkAllNotificationsRead);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Render the notification panel
+    pub fn render(&mut self, f: &mut Frame, area: Rect) {
+        if !self.state.visible {
+            return;
+        }
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(3), // Header
+                Constraint::Min(5),    // Notification list
+                Constraint::Length(2), // Status bar
+            ])
+            .split(area);
+
+        self.render_header(f, chunks[0]);
+        self.render_notification_list(f, chunks[1]);
+        self.render_status_bar(f, chunks[2]);
+    }
+
+    /// Render notification header
+    fn render_header(&self, f: &mut Frame, area: Rect) {
+        let mut title_spans = vec![Span::styled(
+            self.title.clone(),
+            Style::default()
+                .fg(Color::White)
+                .add_modifier(Modifier::BOLD),
+        )];
+
+        let unread_count = self.state.unread_count();
+        if unread_count > 0 {
+            title_spans.push(Span::styled(
+                format!(" ({})", unread_count),
+                Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+            ));
+        }
+
+        let header_block = Block::default()
+            .title_top(Line::from(title_spans).alignment(Alignment::Center))
+            .borders(Borders::ALL)
+            .border_style(if self.state.focused {
+                Style::default().fg(Color::Cyan)
+            } else {
+                Style::default().fg(Color::Gray)
+            });
+
+        f.render_widget(header_block, area);
+    }
+
+    /// Render notification list
+    fn render_notification_list(&self, f: &mut Frame, area: Rect) {
+        let list_area = area.inner(Margin {
+            vertical: 0,
+            horizontal: 1,
+        });
+
+        if self.state.entries.is_empty() {
+            let empty_widget = Paragraph::new("No notifications.")
+                .style(Style::default().fg(Color::DarkGray))
+                .alignment(Alignment::Center)
+                .wrap(Wrap { trim: true });
+
+            f.render_widget(empty_widget, list_area);
+            return;
+        }
+
+        // Newest first, so users triage the most recent activity first.
+        let items: Vec<ListItem> = self
+            .state
+            .entries
+            .iter()
+            .rev()
+            .map(|entry| self.create_notification_item(entry))
+            .collect();
+
+        let list = List::new(items)
+            .highlight_style(
+                Style::default()
+                    .bg(Color::Blue)
+                    .fg(Color::White)
+                    .add_modifier(Modifier::BOLD),
+            )
+            .highlight_symbol("\u{25ba} ");
+
+        // `selected_index` is ordinal into `entries` (oldest-first); the
+        // rendered list is reversed, so flip it to match.
+        let mut list_state = self.state.list_state.clone();
+        if let Some(index) = self.state.selected_index {
+            list_state.select(Some(self.state.entries.len() - 1 - index));
+        }
+        f.render_stateful_widget(list, list_area, &mut list_state);
+    }
+
+    /// Create a list item for a notification
+    fn create_notification_item(&self, entry: &NotificationEntry) -> ListItem {
+        let mut first_line = Vec::new();
+
+        if !entry.read {
+            first_line.push(Span::styled(
+                "\u{25cf} ",
+                Style::default()
+                    .fg(Color::Green)
+                    .add_modifier(Modifier::BOLD),
+            ));
+        } else {
+            first_line.push(Span::styled("  ", Style::default()));
+        }
+
+        first_line.push(Span::styled(
+            format!("[{}] ", entry.kind.label()),
+            Style::default()
+                .fg(entry.kind.color())
+                .add_modifier(Modifier::BOLD),
+        ));
+
+        first_line.push(Span::styled(
+            entry.preview.clone(),
+            Style::default().fg(Color::White),
+        ));
+
+        first_line.push(Span::styled(
+            format!(" - {}", self.format_timestamp(entry.created_at)),
+            Style::default().fg(Color::DarkGray),
+        ));
+
+        ListItem::new(Line::from(first_line))
+    }
+
+    /// Format a unix timestamp as an age string like "5m", "2h", "3d"
+    fn format_timestamp(&self, timestamp: u64) -> String {
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+
+        let age = now.saturating_sub(timestamp);
+
+        if age < 60 {
+            "now".to_string()
+        } else if age < 3600 {
+            format!("{}m", age / 60)
+        } else if age < 86400 {
+            format!("{}h", age / 3600)
+        } else {
+            format!("{}d", age / 86400)
+        }
+    }
+
+    /// Render status bar
+    fn render_status_bar(&self, f: &mut Frame, area: Rect) {
+        let count_text = format!("{} notifications", self.state.entries.len());
+        let mut status_spans = vec![Span::styled(count_text, Style::default().fg(Color::Cyan))];
+
+        status_spans.push(Span::styled(
+            " | \u{2191}\u{2193}:navigate Enter:open R:mark all read Esc:close",
+            Style::default().fg(Color::DarkGray),
+        ));
+
+        let status_widget = Paragraph::new(Line::from(status_spans))
+            .alignment(Alignment::Left)
+            .wrap(Wrap { trim: true });
+
+        f.render_widget(status_widget, area);
+    }
+}
+
+impl Default for NotificationPanel {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_notification_push_and_unread_count() {
+        let mut state = NotificationState::new();
+        assert_eq!(state.unread_count(), 0);
+
+        state.push(NotificationEntry::new(
+            ConversationId::from("conv1"),
+            NotificationKind::NewMessage,
+            "Hello".to_string(),
+            100,
+        ));
+        state.push(NotificationEntry::new(
+            ConversationId::from("conv2"),
+            NotificationKind::Mention,
+            "@you check this out".to_string(),
+            200,
+        ));
+
+        assert_eq!(state.entries.len(), 2);
+        assert_eq!(state.unread_count(), 2);
+
+        state.mark_all_read();
+        assert_eq!(state.unread_count(), 0);
+    }
+
+    #[test]
+    fn test_notification_ring_buffer_evicts_oldest() {
+        let mut state = NotificationState::new();
+        for i in 0..NOTIFICATION_CAPACITY + 5 {
+            state.push(NotificationEntry::new(
+                ConversationId::from(format!("conv{}", i)),
+                NotificationKind::NewMessage,
+                format!("message {}", i),
+                i as u64,
+            ));
+        }
+
+        assert_eq!(state.entries.len(), NOTIFICATION_CAPACITY);
+        // The oldest 5 entries should have been evicted.
+        assert_eq!(state.entries.front().unwrap().created_at, 5);
+        assert_eq!(
+            state.entries.back().unwrap().created_at,
+            (NOTIFICATION_CAPACITY + 4) as u64
+        );
+    }
+
+    #[test]
+    fn test_notification_navigation() {
+        let mut state = NotificationState::new();
+        state.push(NotificationEntry::new(
+            ConversationId::from("conv1"),
+            NotificationKind::NewMessage,
+            "first".to_string(),
+            100,
+        ));
+        state.push(NotificationEntry::new(
+            ConversationId::from("conv2"),
+            NotificationKind::NewMessage,
+            "second".to_string(),
+            200,
+        ));
+
+        assert_eq!(state.selected_index, Some(0));
+
+        state.select_next();
+        assert_eq!(state.selected_index, Some(1));
+
+        // Wrap around
+        state.select_next();
+        assert_eq!(state.selected_index, Some(0));
+
+        state.select_previous();
+        assert_eq!(state.selected_index, Some(1));
+        assert_eq!(
+            state.selected_entry().unwrap().conversation_id,
+            ConversationId::from("conv2")
+        );
+    }
+
+    #[test]
+    fn test_mark_selected_read_and_clear() {
+        let mut state = NotificationState::new();
+        state.push(NotificationEntry::new(
+            ConversationId::from("conv1"),
+            NotificationKind::NewMessage,
+            "first".to_string(),
+            100,
+        ));
+        state.push(NotificationEntry::new(
+            ConversationId::from("conv2"),
+            NotificationKind::Mention,
+            "second".to_string(),
+            200,
+        ));
+
+        state.mark_selected_read();
+        assert_eq!(state.unread_count(), 1);
+        assert!(state.entries[0].read);
+        assert!(!state.entries[1].read);
+
+        state.clear();
+        assert!(state.entries.is_empty());
+        assert_eq!(state.selected_index, None);
+    }
+}
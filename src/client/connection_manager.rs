@@ -844,6 +844,7 @@ mod tests {
         let credentials = Credentials {
             username: "testuser".to_string(),
             password: "password123".to_string(),
+            device_name: None,
         };
 
         if let Some(auth_manager) = manager.auth_manager.clone() {
@@ -0,0 +1,70 @@
+//! Central event type and dispatcher for the TUI loop.
+//!
+//! `App::run` used to call `tui.next_event()` directly and race shutdown
+//! against an abrupt `Action::Quit`. [`EventDispatcher`] pulls the
+//! `CancellationToken` that background tasks (login, register, reconnect)
+//! already need out to one place: the main loop asks it for the next
+//! [`AppEvent`] instead of the raw `tui::Event`, so a cancelled token wins
+//! the race immediately instead of waiting on another terminal event, and
+//! spawned network tasks can `select!` on [`EventDispatcher::token`] to
+//! stop as soon as the app starts tearing down.
+
+use tokio_util::sync::CancellationToken;
+
+use crate::tui::{Event, Tui};
+
+/// Event fed into the app's dispatch loop. `Input` forwards whatever the
+/// terminal produced (key/mouse/tick/resize/...); `Shutdown` is delivered
+/// once the dispatcher's token is cancelled, ahead of any further input.
+#[derive(Debug, Clone)]
+pub enum AppEvent {
+    Input(Event),
+    Shutdown,
+}
+
+/// Owns the `CancellationToken` shared between the main loop and the
+/// background tasks it spawns for login, registration and reconnection.
+pub struct EventDispatcher {
+    shutdown: CancellationToken,
+}
+
+impl EventDispatcher {
+    pub fn new() -> Self {
+        Self {
+            shutdown: CancellationToken::new(),
+        }
+    }
+
+    /// Token background tasks should race against so they stop as soon as
+    /// the app starts shutting down instead of running to completion
+    /// underneath a torn-down terminal.
+    pub fn token(&self) -> CancellationToken {
+        self.shutdown.clone()
+    }
+
+    /// Begin graceful shutdown: cancels the token returned by [`Self::token`]
+    /// for every task holding a clone of it.
+    pub fn cancel(&self) {
+        self.shutdown.cancel();
+    }
+
+    pub fn is_shutting_down(&self) -> bool {
+        self.shutdown.is_cancelled()
+    }
+
+    /// Wait for the next terminal event, or `AppEvent::Shutdown` if the
+    /// token is cancelled first.
+    pub async fn next_event(&self, tui: &mut Tui) -> Option<AppEvent> {
+        tokio::select! {
+            biased;
+            _ = self.shutdown.cancelled() => Some(AppEvent::Shutdown),
+            event = tui.next_event() => event.map(AppEvent::Input),
+        }
+    }
+}
+
+impl Default for EventDispatcher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -12,9 +12,11 @@ pub mod config;
 pub mod connection_manager;
 pub mod encryption;
 pub mod errors;
+pub mod events;
 pub mod history;
 pub mod logging;
 
 pub mod tcp_transport;
 pub mod transport;
 pub mod tui;
+pub mod unix_transport;
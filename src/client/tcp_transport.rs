@@ -5,12 +5,18 @@ use tokio::sync::Mutex;
 use std::sync::Arc;
 use async_trait::async_trait;
 
-use super::transport::{Transport, TransportError, ConnectionConfig};
+use super::transport::{Transport, TransportError, ConnectionConfig, Framing, Reconnectable};
 
 /// TCP-based implementation of the Transport trait
 pub struct TcpTransport {
     stream: Option<Arc<Mutex<TcpStream>>>,
     config: ConnectionConfig,
+    /// Address actually dialed by the last successful `connect`, cached so
+    /// callers can tell which of several DNS-resolved addresses won out
+    /// without awaiting the stream's mutex.
+    peer_addr: Option<SocketAddr>,
+    /// Local address the last successful `connect` bound to.
+    local_addr: Option<SocketAddr>,
 }
 
 impl TcpTransport {
@@ -19,40 +25,114 @@ impl TcpTransport {
         Self {
             stream: None,
             config,
+            peer_addr: None,
+            local_addr: None,
         }
     }
 
+    /// The address of the remote peer, populated once `connect` succeeds
+    /// (e.g. after DNS failover has picked one of several resolved
+    /// addresses), and cleared again on `close`.
+    pub fn peer_addr(&self) -> Option<SocketAddr> {
+        self.peer_addr
+    }
+
+    /// The local address `connect` bound to, cleared again on `close`.
+    pub fn local_addr(&self) -> Option<SocketAddr> {
+        self.local_addr
+    }
+
+    /// Whether this transport currently holds a live stream.
+    pub fn is_connected(&self) -> bool {
+        self.stream.is_some()
+    }
+}
+
+impl std::fmt::Debug for TcpTransport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TcpTransport")
+            .field("peer_addr", &self.peer_addr)
+            .field("is_connected", &self.is_connected())
+            .finish()
+    }
 }
 
 #[async_trait]
 impl Transport for TcpTransport {
-    /// Establish a connection to the remote endpoint
+    /// Establish a connection to the remote endpoint, resolving
+    /// `self.config.address` via Tokio's async DNS resolution (offloaded to
+    /// the blocking thread pool, so the runtime isn't stalled) and trying
+    /// every resolved address in turn until one connects, returning the
+    /// last error if none do.
     async fn connect(&mut self) -> Result<(), TransportError> {
-        // Connect to the remote address
-        let stream = TcpStream::connect(self.config.address)
+        let stream = TcpStream::connect(&self.config.address)
             .await
             .map_err(TransportError::ConnectionError)?;
-        
+
+        self.peer_addr = stream.peer_addr().ok();
+        self.local_addr = stream.local_addr().ok();
+
         // Store the stream
         self.stream = Some(Arc::new(Mutex::new(stream)));
-        
+
         Ok(())
     }
 
-    /// Send data over the transport
+    /// Send `data` as UTF-8 text. Thin wrapper over [`Transport::send_bytes`]
+    /// so text-only callers don't need to think about framing.
     async fn send(&mut self, data: &str) -> Result<(), TransportError> {
+        self.send_bytes(data.as_bytes()).await
+    }
+
+    /// Receive the next frame and decode it as UTF-8 text. Thin wrapper
+    /// over [`Transport::receive_bytes`].
+    async fn receive(&mut self) -> Result<Option<String>, TransportError> {
+        match self.receive_bytes().await? {
+            Some(bytes) => String::from_utf8(bytes).map(Some).map_err(|e| {
+                TransportError::ConnectionError(std::io::Error::new(
+                    std::io::ErrorKind::InvalidData,
+                    e,
+                ))
+            }),
+            None => Ok(None),
+        }
+    }
+
+    /// Send `data` as a single frame, using whichever [`Framing`] strategy
+    /// `self.config.framing` selects.
+    async fn send_bytes(&mut self, data: &[u8]) -> Result<(), TransportError> {
         if let Some(stream) = &self.stream {
             let mut stream_guard = stream.lock().await;
-            
-            // Send the data with a newline terminator
-            stream_guard.write_all(format!("{}\n", data).as_bytes())
-                .await
-                .map_err(TransportError::ConnectionError)?;
-            
+
+            match &self.config.framing {
+                Framing::LineDelimited => {
+                    stream_guard.write_all(data)
+                        .await
+                        .map_err(TransportError::ConnectionError)?;
+                    stream_guard.write_all(b"\n")
+                        .await
+                        .map_err(TransportError::ConnectionError)?;
+                }
+                Framing::LengthDelimited { .. } => {
+                    let len = u32::try_from(data.len()).map_err(|_| {
+                        TransportError::ConnectionError(std::io::Error::new(
+                            std::io::ErrorKind::InvalidInput,
+                            "frame too large to encode in a u32 length header",
+                        ))
+                    })?;
+                    stream_guard.write_all(&len.to_be_bytes())
+                        .await
+                        .map_err(TransportError::ConnectionError)?;
+                    stream_guard.write_all(data)
+                        .await
+                        .map_err(TransportError::ConnectionError)?;
+                }
+            }
+
             // Ensure data is sent immediately
             stream_guard.flush().await
                 .map_err(TransportError::ConnectionError)?;
-            
+
             Ok(())
         } else {
             Err(TransportError::ConnectionError(
@@ -60,38 +140,68 @@ impl Transport for TcpTransport {
             ))
         }
     }
-    
-    /// Receive data from the transport
-    async fn receive(&mut self) -> Result<Option<String>, TransportError> {
+
+    /// Receive the next frame, using whichever [`Framing`] strategy
+    /// `self.config.framing` selects.
+    async fn receive_bytes(&mut self) -> Result<Option<Vec<u8>>, TransportError> {
         if let Some(stream) = &self.stream {
             let mut stream_guard = stream.lock().await;
-            
-            // Create a temporary buffer reader for this operation
-            let mut reader = BufReader::new(&mut *stream_guard);
-            
-            // Read a line from the stream
-            let mut line = String::new();
-            let bytes_read = reader.read_line(&mut line)
-                .await
-                .map_err(TransportError::ConnectionError)?;
-            
-            // If we read 0 bytes, the connection was closed
-            if bytes_read == 0 {
-                return Ok(None);
+
+            match &self.config.framing {
+                Framing::LineDelimited => {
+                    // Create a temporary buffer reader for this operation
+                    let mut reader = BufReader::new(&mut *stream_guard);
+
+                    // Read a line from the stream
+                    let mut line = String::new();
+                    let bytes_read = reader.read_line(&mut line)
+                        .await
+                        .map_err(TransportError::ConnectionError)?;
+
+                    // If we read 0 bytes, the connection was closed
+                    if bytes_read == 0 {
+                        return Ok(None);
+                    }
+
+                    // Trim the newline character
+                    Ok(Some(line.trim_end().as_bytes().to_vec()))
+                }
+                Framing::LengthDelimited { max_frame_len } => {
+                    let mut header = [0u8; 4];
+                    match stream_guard.read_exact(&mut header).await {
+                        Ok(_) => {}
+                        Err(e) if e.kind() == std::io::ErrorKind::UnexpectedEof => {
+                            return Ok(None);
+                        }
+                        Err(e) => return Err(TransportError::ConnectionError(e)),
+                    }
+
+                    let len = u32::from_be_bytes(header) as usize;
+                    if len > *max_frame_len {
+                        return Err(TransportError::ConnectionError(std::io::Error::new(
+                            std::io::ErrorKind::InvalidData,
+                            format!(
+                                "peer advertised frame length {} exceeding max_frame_len {}",
+                                len, max_frame_len
+                            ),
+                        )));
+                    }
+
+                    let mut body = vec![0u8; len];
+                    stream_guard.read_exact(&mut body)
+                        .await
+                        .map_err(TransportError::ConnectionError)?;
+
+                    Ok(Some(body))
+                }
             }
-            
-            // Trim the newline character
-            let line = line.trim_end().to_string();
-            
-            // Return the received line
-            Ok(Some(line))
         } else {
             Err(TransportError::ConnectionError(
                 std::io::Error::new(std::io::ErrorKind::NotConnected, "Not connected")
             ))
         }
     }
-    
+
     /// Close the transport connection
     async fn close(&mut self) -> Result<(), TransportError> {
         if let Some(stream) = &self.stream {
@@ -103,24 +213,133 @@ impl Transport for TcpTransport {
                 .map_err(TransportError::ConnectionError)?;
         }
             
-        // Clear the stream
+        // Clear the stream and cached connection metadata
         self.stream = None;
-        
+        self.peer_addr = None;
+        self.local_addr = None;
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl Reconnectable for TcpTransport {
+    /// Re-resolve and re-dial `self.config.address` (picking up any DNS
+    /// change since the last connect) and replace the stored stream. Leaves
+    /// the existing stream in place if the dial fails, so a caller can keep
+    /// retrying without losing track of configuration.
+    async fn reconnect(&mut self) -> Result<(), TransportError> {
+        let stream = TcpStream::connect(&self.config.address)
+            .await
+            .map_err(TransportError::ConnectionError)?;
+
+        self.peer_addr = stream.peer_addr().ok();
+        self.local_addr = stream.local_addr().ok();
+        self.stream = Some(Arc::new(Mutex::new(stream)));
+
         Ok(())
     }
 }
 
-/// Create a new TCP transport with the given socket address
-pub fn create_tcp_transport(addr: SocketAddr) -> TcpTransport {
+/// Starting delay for [`ReconnectingTransport`]'s exponential backoff.
+const RECONNECT_BASE_BACKOFF_MS: u64 = 100;
+/// Upper bound the backoff delay doubles towards before it stops growing.
+const RECONNECT_MAX_BACKOFF_MS: u64 = 30_000;
+
+/// Wraps a transport that also implements [`Reconnectable`], retrying a
+/// failed `send`/`receive` by reconnecting and trying again, with
+/// exponential backoff between attempts (base
+/// [`RECONNECT_BASE_BACKOFF_MS`], doubling, capped at
+/// [`RECONNECT_MAX_BACKOFF_MS`]) up to
+/// `config.max_reconnect_attempts`. A brief network blip no longer has to
+/// tear down the whole connection from the caller's perspective.
+pub struct ReconnectingTransport<T: Transport + Reconnectable> {
+    inner: T,
+    config: ConnectionConfig,
+}
+
+impl<T: Transport + Reconnectable> ReconnectingTransport<T> {
+    /// Wrap `inner`, retrying per `config.max_reconnect_attempts` on a
+    /// connection error.
+    pub fn new(inner: T, config: ConnectionConfig) -> Self {
+        Self { inner, config }
+    }
+}
+
+#[async_trait]
+impl<T: Transport + Reconnectable> Transport for ReconnectingTransport<T> {
+    async fn connect(&mut self) -> Result<(), TransportError> {
+        self.inner.connect().await
+    }
+
+    async fn send(&mut self, data: &str) -> Result<(), TransportError> {
+        let mut attempt = 0;
+        let mut backoff_ms = RECONNECT_BASE_BACKOFF_MS;
+
+        loop {
+            match self.inner.send(data).await {
+                Ok(()) => return Ok(()),
+                Err(TransportError::ConnectionError(_))
+                    if attempt < self.config.max_reconnect_attempts =>
+                {
+                    attempt += 1;
+                    tokio::time::sleep(std::time::Duration::from_millis(backoff_ms)).await;
+                    backoff_ms = (backoff_ms * 2).min(RECONNECT_MAX_BACKOFF_MS);
+                    let _ = self.inner.reconnect().await;
+                }
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    async fn receive(&mut self) -> Result<Option<String>, TransportError> {
+        let mut attempt = 0;
+        let mut backoff_ms = RECONNECT_BASE_BACKOFF_MS;
+
+        loop {
+            match self.inner.receive().await {
+                Ok(data) => return Ok(data),
+                Err(TransportError::ConnectionError(_))
+                    if attempt < self.config.max_reconnect_attempts =>
+                {
+                    attempt += 1;
+                    tokio::time::sleep(std::time::Duration::from_millis(backoff_ms)).await;
+                    backoff_ms = (backoff_ms * 2).min(RECONNECT_MAX_BACKOFF_MS);
+                    let _ = self.inner.reconnect().await;
+                }
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    async fn close(&mut self) -> Result<(), TransportError> {
+        self.inner.close().await
+    }
+}
+
+/// Create a new TCP transport targeting `addr`, which may be a literal
+/// socket address or an unresolved hostname (e.g. `"chat.example.com:9000"`)
+/// — resolution happens lazily on [`TcpTransport::connect`].
+pub fn create_tcp_transport<S: std::fmt::Display>(addr: S) -> TcpTransport {
     let config = ConnectionConfig::new(addr);
     TcpTransport::new(config)
 }
 
 /// Create a boxed TCP transport for use with ConnectionManager
-pub fn create_boxed_tcp_transport(addr: SocketAddr) -> Box<dyn Transport> {
+pub fn create_boxed_tcp_transport<S: std::fmt::Display>(addr: S) -> Box<dyn Transport> {
     Box::new(create_tcp_transport(addr))
 }
 
+/// Create a boxed TCP transport wrapped in auto-reconnect-with-backoff, for
+/// use with ConnectionManager
+pub fn create_boxed_reconnecting_tcp_transport<S: std::fmt::Display>(addr: S) -> Box<dyn Transport> {
+    let config = ConnectionConfig::new(addr);
+    Box::new(ReconnectingTransport::new(
+        TcpTransport::new(config.clone()),
+        config,
+    ))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -172,10 +391,30 @@ mod tests {
         // Create and connect a transport
         let mut transport = create_tcp_transport(addr);
         let result = transport.connect().await;
-        
+
         assert!(result.is_ok());
     }
 
+    #[tokio::test]
+    async fn test_tcp_transport_connection_metadata() {
+        let addr = "127.0.0.1:50010".parse::<SocketAddr>().unwrap();
+        let _server = start_echo_server(addr).await;
+        tokio::time::sleep(Duration::from_millis(100)).await;
+
+        let mut transport = create_tcp_transport(addr);
+        assert!(!transport.is_connected());
+        assert_eq!(transport.peer_addr(), None);
+
+        transport.connect().await.unwrap();
+        assert!(transport.is_connected());
+        assert_eq!(transport.peer_addr(), Some(addr));
+        assert!(transport.local_addr().is_some());
+
+        transport.close().await.unwrap();
+        assert!(!transport.is_connected());
+        assert_eq!(transport.peer_addr(), None);
+    }
+
     #[tokio::test]
     async fn test_tcp_transport_send_receive() {
         // Use a different port for each test
@@ -252,4 +491,109 @@ mod tests {
         let send_result = transport.send("test").await;
         assert!(send_result.is_err());
     }
+
+    #[tokio::test]
+    async fn test_tcp_transport_reconnect() {
+        let addr = "127.0.0.1:50005".parse::<SocketAddr>().unwrap();
+        let _server = start_echo_server(addr).await;
+        tokio::time::sleep(Duration::from_millis(100)).await;
+
+        let mut transport = create_tcp_transport(addr);
+        transport.connect().await.unwrap();
+        transport.close().await.unwrap();
+
+        // No listener is accepting a second connection on this address, so
+        // reconnect should surface a connection error rather than panic.
+        assert!(transport.reconnect().await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_reconnecting_transport_recovers_from_a_dropped_connection() {
+        let addr = "127.0.0.1:50006".parse::<SocketAddr>().unwrap();
+        let server = start_echo_server(addr).await;
+        tokio::time::sleep(Duration::from_millis(100)).await;
+
+        let config = ConnectionConfig::new(addr).with_max_reconnect_attempts(3);
+        let mut transport =
+            ReconnectingTransport::new(TcpTransport::new(config.clone()), config);
+        transport.connect().await.unwrap();
+
+        // Sever the connection from underneath the wrapper without telling
+        // it, and wait for the original server task to notice the shutdown
+        // and free the port, so a fresh listener can take over.
+        transport.inner.close().await.unwrap();
+        server.await.unwrap();
+        let _server = start_echo_server(addr).await;
+        tokio::time::sleep(Duration::from_millis(100)).await;
+
+        // The first send over the now-absent stream fails; the wrapper
+        // should reconnect and retry rather than propagating the error.
+        transport.send("hello").await.unwrap();
+        let response = transport.receive().await.unwrap();
+        assert_eq!(response, Some("hello".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_reconnecting_transport_gives_up_after_max_attempts() {
+        let addr = "127.0.0.1:50007".parse::<SocketAddr>().unwrap();
+        let server = start_echo_server(addr).await;
+        tokio::time::sleep(Duration::from_millis(100)).await;
+
+        let config = ConnectionConfig::new(addr).with_max_reconnect_attempts(2);
+        let mut transport =
+            ReconnectingTransport::new(TcpTransport::new(config.clone()), config);
+        transport.connect().await.unwrap();
+
+        // No one ever re-binds this address, so every reconnect attempt
+        // fails and the wrapper should give up and surface the error.
+        transport.inner.close().await.unwrap();
+        server.await.unwrap();
+
+        let result = transport.send("hello").await;
+        assert!(matches!(result, Err(TransportError::ConnectionError(_))));
+    }
+
+    #[tokio::test]
+    async fn test_length_delimited_framing_round_trips_embedded_newlines() {
+        let addr = "127.0.0.1:50008".parse::<SocketAddr>().unwrap();
+        let _server = start_echo_server(addr).await;
+        tokio::time::sleep(Duration::from_millis(100)).await;
+
+        let config = ConnectionConfig::new(addr)
+            .with_framing(Framing::LengthDelimited { max_frame_len: 1024 });
+        let mut transport = TcpTransport::new(config);
+        transport.connect().await.unwrap();
+
+        // A payload with an embedded newline would be silently split in two
+        // under line-delimited framing; length-delimited framing must
+        // return it whole.
+        let message = b"line one\nline two";
+        transport.send_bytes(message).await.unwrap();
+
+        let response = transport.receive_bytes().await.unwrap();
+        assert_eq!(response, Some(message.to_vec()));
+    }
+
+    #[tokio::test]
+    async fn test_length_delimited_framing_rejects_oversized_frame() {
+        let addr = "127.0.0.1:50009".parse::<SocketAddr>().unwrap();
+        let listener = TcpListener::bind(addr).await.unwrap();
+
+        let server = tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            // Advertise a frame far larger than the client's max_frame_len.
+            socket.write_all(&1_000_000u32.to_be_bytes()).await.unwrap();
+            socket.flush().await.unwrap();
+        });
+
+        let config = ConnectionConfig::new(addr)
+            .with_framing(Framing::LengthDelimited { max_frame_len: 64 });
+        let mut transport = TcpTransport::new(config);
+        transport.connect().await.unwrap();
+
+        let result = transport.receive_bytes().await;
+        assert!(matches!(result, Err(TransportError::ConnectionError(_))));
+
+        server.await.unwrap();
+    }
 }
\ No newline at end of file
@@ -42,17 +42,48 @@ pub trait EncryptionService: Send + Sync {
 pub trait Transport: Send + Sync {
     /// Establish a connection to the remote endpoint
     async fn connect(&mut self) -> Result<(), TransportError>;
-    
+
     /// Send data over the transport
     async fn send(&mut self, data: &str) -> Result<(), TransportError>;
-    
+
     /// Receive data from the transport
     async fn receive(&mut self) -> Result<Option<String>, TransportError>;
-    
+
+    /// Send raw bytes as a single frame. Default implementation requires
+    /// `data` to be valid UTF-8 and delegates to [`Transport::send`], which
+    /// is all any transport needed before binary framing existed; override
+    /// this directly (as `TcpTransport` does) to support arbitrary bytes.
+    async fn send_bytes(&mut self, data: &[u8]) -> Result<(), TransportError> {
+        let text = std::str::from_utf8(data).map_err(|e| {
+            TransportError::ConnectionError(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                e,
+            ))
+        })?;
+        self.send(text).await
+    }
+
+    /// Receive the next frame as raw bytes, or `None` if the peer closed
+    /// the connection. Default implementation delegates to
+    /// [`Transport::receive`].
+    async fn receive_bytes(&mut self) -> Result<Option<Vec<u8>>, TransportError> {
+        Ok(self.receive().await?.map(String::into_bytes))
+    }
+
     /// Close the transport connection
     async fn close(&mut self) -> Result<(), TransportError>;
 }
 
+/// Capability for a transport that can re-establish a dropped connection.
+/// Separate from [`Transport::connect`] so it can assume prior connection
+/// state (e.g. `self.config.address`) is already known and just needs to be
+/// re-dialed, rather than set up from scratch.
+#[async_trait::async_trait]
+pub trait Reconnectable: Send + Sync {
+    /// Re-dial the remote endpoint, replacing any existing connection state.
+    async fn reconnect(&mut self) -> Result<(), TransportError>;
+}
+
 /// Trait abstraction for UI notifications and message handling
 pub trait ConnectionObserver: Send + Sync {
     /// Called when a message should be displayed to the user
@@ -126,25 +157,69 @@ impl ConnectionObserver for TuiObserver {
     }
 }
 
+/// Wire framing strategy used by [`Transport::send_bytes`]/`receive_bytes`
+/// implementations to split a byte stream into discrete messages.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Framing {
+    /// Each message is UTF-8 text terminated by `\n`. Simple and
+    /// human-inspectable, but a message containing an embedded newline (or
+    /// arbitrary binary data) corrupts the stream. Matches the historical
+    /// wire format and any existing line-based server.
+    LineDelimited,
+    /// Each frame is prefixed with a 4-byte big-endian length header
+    /// followed by exactly that many bytes. Safe for binary payloads and
+    /// text with embedded newlines. `max_frame_len` bounds the header so a
+    /// misbehaving or malicious peer can't advertise an unbounded length
+    /// and force an unbounded allocation.
+    LengthDelimited { max_frame_len: usize },
+}
+
 /// Configuration for establishing a connection
 #[derive(Debug, Clone)]
 pub struct ConnectionConfig {
-    pub address: std::net::SocketAddr,
+    /// An unresolved connection target, e.g. `"127.0.0.1:8080"` or
+    /// `"chat.example.com:9000"`. Resolved at connect time (see
+    /// `TcpTransport::connect`) via Tokio's async `ToSocketAddrs`, so a
+    /// hostname is looked up fresh on every reconnect instead of being
+    /// pinned to whatever address it first resolved to.
+    pub address: String,
     pub timeout_ms: u64,
+    /// Maximum number of reconnect attempts a [`ReconnectingTransport`] will
+    /// make for a single failed `send`/`receive` before giving up and
+    /// returning the error to the caller.
+    pub max_reconnect_attempts: u32,
+    /// Wire framing strategy. Defaults to [`Framing::LineDelimited`] to
+    /// match every existing server this client talks to.
+    pub framing: Framing,
 }
 
 impl ConnectionConfig {
-    pub fn new(address: std::net::SocketAddr) -> Self {
+    /// `address` is anything accepted by [`tokio::net::ToSocketAddrs`] once
+    /// stringified: a literal `"ip:port"`, or a hostname like
+    /// `"chat.example.com:9000"` to be resolved at connect time.
+    pub fn new<S: std::fmt::Display>(address: S) -> Self {
         Self {
-            address,
+            address: address.to_string(),
             timeout_ms: 5000, // 5 second default timeout
+            max_reconnect_attempts: 5,
+            framing: Framing::LineDelimited,
         }
     }
-    
+
+    pub fn with_framing(mut self, framing: Framing) -> Self {
+        self.framing = framing;
+        self
+    }
+
     pub fn with_timeout(mut self, timeout_ms: u64) -> Self {
         self.timeout_ms = timeout_ms;
         self
     }
+
+    pub fn with_max_reconnect_attempts(mut self, max_reconnect_attempts: u32) -> Self {
+        self.max_reconnect_attempts = max_reconnect_attempts;
+        self
+    }
 }
 
 /// Represents a chat message with metadata
@@ -695,7 +770,7 @@ mod tests {
 
     #[test]
     fn test_connection_config() {
-        let addr = "127.0.0.1:8080".parse().unwrap();
+        let addr = "127.0.0.1:8080";
         let config = ConnectionConfig::new(addr);
         
         assert_eq!(config.address, addr);
@@ -705,6 +780,21 @@ mod tests {
         assert_eq!(config_with_timeout.timeout_ms, 10000);
     }
 
+    #[test]
+    fn test_connection_config_accepts_hostname() {
+        let config = ConnectionConfig::new("chat.example.com:9000");
+        assert_eq!(config.address, "chat.example.com:9000");
+    }
+
+    #[test]
+    fn test_connection_config_defaults_to_line_delimited_framing() {
+        let config = ConnectionConfig::new("127.0.0.1:8080");
+        assert_eq!(config.framing, Framing::LineDelimited);
+
+        let config = config.with_framing(Framing::LengthDelimited { max_frame_len: 4096 });
+        assert_eq!(config.framing, Framing::LengthDelimited { max_frame_len: 4096 });
+    }
+
     #[test]
     fn test_message_creation() {
         let user_msg = Message::user_message("Hello".to_string());
@@ -746,7 +836,7 @@ mod tests {
 
     #[test]
     fn test_helper_functions() {
-        let addr = "127.0.0.1:8080".parse().unwrap();
+        let addr = "127.0.0.1:8080";
         let config = create_connection_config(addr);
         assert_eq!(config.address, addr);
         
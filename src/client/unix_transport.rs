@@ -0,0 +1,282 @@
+use std::path::PathBuf;
+use std::sync::Arc;
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::net::UnixStream;
+use tokio::sync::Mutex;
+use async_trait::async_trait;
+
+use super::transport::{Framing, Transport, TransportError};
+
+/// Unix-domain-socket implementation of the [`Transport`] trait, for talking
+/// to a relay/daemon on the same host without the overhead and exposed port
+/// of TCP over loopback. Shares [`Framing`] and wire semantics with
+/// [`crate::tcp_transport::TcpTransport`] so a `ConnectionManager` can hold
+/// either behind `Box<dyn Transport>` interchangeably.
+pub struct UnixTransport {
+    stream: Option<Arc<Mutex<UnixStream>>>,
+    path: PathBuf,
+    framing: Framing,
+}
+
+impl UnixTransport {
+    /// Create a new Unix transport targeting the socket at `path`.
+    pub fn new(path: PathBuf) -> Self {
+        Self {
+            stream: None,
+            path,
+            framing: Framing::LineDelimited,
+        }
+    }
+
+    pub fn with_framing(mut self, framing: Framing) -> Self {
+        self.framing = framing;
+        self
+    }
+}
+
+#[async_trait]
+impl Transport for UnixTransport {
+    /// Connect to the Unix domain socket at `self.path`.
+    async fn connect(&mut self) -> Result<(), TransportError> {
+        let stream = UnixStream::connect(&self.path)
+            .await
+            .map_err(TransportError::ConnectionError)?;
+
+        self.stream = Some(Arc::new(Mutex::new(stream)));
+
+        Ok(())
+    }
+
+    /// Send `data` as UTF-8 text. Thin wrapper over [`Transport::send_bytes`]
+    /// so text-only callers don't need to think about framing.
+    async fn send(&mut self, data: &str) -> Result<(), TransportError> {
+        self.send_bytes(data.as_bytes()).await
+    }
+
+    /// Receive the next frame and decode it as UTF-8 text. Thin wrapper
+    /// over [`Transport::receive_bytes`].
+    async fn receive(&mut self) -> Result<Option<String>, TransportError> {
+        match self.receive_bytes().await? {
+            Some(bytes) => String::from_utf8(bytes).map(Some).map_err(|e| {
+                TransportError::ConnectionError(std::io::Error::new(
+                    std::io::ErrorKind::InvalidData,
+                    e,
+                ))
+            }),
+            None => Ok(None),
+        }
+    }
+
+    /// Send `data` as a single frame, using whichever [`Framing`] strategy
+    /// `self.framing` selects.
+    async fn send_bytes(&mut self, data: &[u8]) -> Result<(), TransportError> {
+        if let Some(stream) = &self.stream {
+            let mut stream_guard = stream.lock().await;
+
+            match &self.framing {
+                Framing::LineDelimited => {
+                    stream_guard.write_all(data)
+                        .await
+                        .map_err(TransportError::ConnectionError)?;
+                    stream_guard.write_all(b"\n")
+                        .await
+                        .map_err(TransportError::ConnectionError)?;
+                }
+                Framing::LengthDelimited { .. } => {
+                    let len = u32::try_from(data.len()).map_err(|_| {
+                        TransportError::ConnectionError(std::io::Error::new(
+                            std::io::ErrorKind::InvalidInput,
+                            "frame too large to encode in a u32 length header",
+                        ))
+                    })?;
+                    stream_guard.write_all(&len.to_be_bytes())
+                        .await
+                        .map_err(TransportError::ConnectionError)?;
+                    stream_guard.write_all(data)
+                        .await
+                        .map_err(TransportError::ConnectionError)?;
+                }
+            }
+
+            stream_guard.flush().await
+                .map_err(TransportError::ConnectionError)?;
+
+            Ok(())
+        } else {
+            Err(TransportError::ConnectionError(
+                std::io::Error::new(std::io::ErrorKind::NotConnected, "Not connected")
+            ))
+        }
+    }
+
+    /// Receive the next frame, using whichever [`Framing`] strategy
+    /// `self.framing` selects.
+    async fn receive_bytes(&mut self) -> Result<Option<Vec<u8>>, TransportError> {
+        if let Some(stream) = &self.stream {
+            let mut stream_guard = stream.lock().await;
+
+            match &self.framing {
+                Framing::LineDelimited => {
+                    let mut reader = BufReader::new(&mut *stream_guard);
+
+                    let mut line = String::new();
+                    let bytes_read = reader.read_line(&mut line)
+                        .await
+                        .map_err(TransportError::ConnectionError)?;
+
+                    if bytes_read == 0 {
+                        return Ok(None);
+                    }
+
+                    Ok(Some(line.trim_end().as_bytes().to_vec()))
+                }
+                Framing::LengthDelimited { max_frame_len } => {
+                    let mut header = [0u8; 4];
+                    match stream_guard.read_exact(&mut header).await {
+                        Ok(_) => {}
+                        Err(e) if e.kind() == std::io::ErrorKind::UnexpectedEof => {
+                            return Ok(None);
+                        }
+                        Err(e) => return Err(TransportError::ConnectionError(e)),
+                    }
+
+                    let len = u32::from_be_bytes(header) as usize;
+                    if len > *max_frame_len {
+                        return Err(TransportError::ConnectionError(std::io::Error::new(
+                            std::io::ErrorKind::InvalidData,
+                            format!(
+                                "peer advertised frame length {} exceeding max_frame_len {}",
+                                len, max_frame_len
+                            ),
+                        )));
+                    }
+
+                    let mut body = vec![0u8; len];
+                    stream_guard.read_exact(&mut body)
+                        .await
+                        .map_err(TransportError::ConnectionError)?;
+
+                    Ok(Some(body))
+                }
+            }
+        } else {
+            Err(TransportError::ConnectionError(
+                std::io::Error::new(std::io::ErrorKind::NotConnected, "Not connected")
+            ))
+        }
+    }
+
+    /// Close the transport connection. The socket file is left in place;
+    /// it belongs to the listener, not to this client.
+    async fn close(&mut self) -> Result<(), TransportError> {
+        if let Some(stream) = &self.stream {
+            let mut stream_guard = stream.lock().await;
+
+            stream_guard.shutdown()
+                .await
+                .map_err(TransportError::ConnectionError)?;
+        }
+
+        self.stream = None;
+
+        Ok(())
+    }
+}
+
+/// Create a new Unix transport targeting the socket at `path`.
+pub fn create_unix_transport(path: PathBuf) -> UnixTransport {
+    UnixTransport::new(path)
+}
+
+/// Create a boxed Unix transport for use with ConnectionManager
+pub fn create_boxed_unix_transport(path: PathBuf) -> Box<dyn Transport> {
+    Box::new(create_unix_transport(path))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::net::UnixListener;
+
+    // Helper function to create an echo server for testing, mirroring
+    // `tcp_transport`'s `start_echo_server`.
+    async fn start_echo_server(path: PathBuf) -> tokio::task::JoinHandle<()> {
+        let listener = UnixListener::bind(&path).unwrap();
+
+        tokio::spawn(async move {
+            if let Ok((mut socket, _)) = listener.accept().await {
+                let (mut reader, mut writer) = socket.split();
+                tokio::io::copy(&mut reader, &mut writer).await.unwrap();
+            }
+        })
+    }
+
+    #[tokio::test]
+    async fn test_unix_transport_connection() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("test.sock");
+
+        let _server = start_echo_server(path.clone()).await;
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+
+        let mut transport = create_unix_transport(path);
+        let result = transport.connect().await;
+
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_unix_transport_send_receive() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("test.sock");
+
+        let _server = start_echo_server(path.clone()).await;
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+
+        let mut transport = create_unix_transport(path);
+        transport.connect().await.unwrap();
+
+        let message = "Hello, local daemon!";
+        transport.send(message).await.unwrap();
+
+        let response = transport.receive().await.unwrap();
+        assert_eq!(response, Some(message.to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_unix_transport_length_delimited_framing() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("test.sock");
+
+        let _server = start_echo_server(path.clone()).await;
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+
+        let mut transport = create_unix_transport(path)
+            .with_framing(Framing::LengthDelimited { max_frame_len: 1024 });
+        transport.connect().await.unwrap();
+
+        let message = b"line one\nline two";
+        transport.send_bytes(message).await.unwrap();
+
+        let response = transport.receive_bytes().await.unwrap();
+        assert_eq!(response, Some(message.to_vec()));
+    }
+
+    #[tokio::test]
+    async fn test_unix_transport_close() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("test.sock");
+
+        let _server = start_echo_server(path.clone()).await;
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+
+        let mut transport = create_unix_transport(path);
+        transport.connect().await.unwrap();
+
+        let result = transport.close().await;
+        assert!(result.is_ok());
+
+        let send_result = transport.send("test").await;
+        assert!(send_result.is_err());
+    }
+}
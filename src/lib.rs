@@ -10,6 +10,9 @@ pub mod common;
 #[path = "client/action.rs"]
 pub mod action;
 
+#[path = "client/accounts.rs"]
+pub mod accounts;
+
 #[path = "client/app.rs"]
 pub mod app;
 
@@ -66,6 +69,9 @@ pub mod server {
     pub mod storage {
         pub use crate::server_storage::*;
     }
+    pub mod error {
+        pub use crate::server_error::*;
+    }
 }
 
 #[path = "server/api/mod.rs"]
@@ -89,6 +95,9 @@ pub mod server_config;
 #[path = "server/storage/mod.rs"]
 pub mod server_storage;
 
+#[path = "server/error/mod.rs"]
+pub mod server_error;
+
 // Re-export common modules for backward compatibility
 pub use common::crypto as aes_gcm_encryption;
 pub use common::crypto as encryption;
@@ -100,7 +109,7 @@ pub use common::transport;
 // Group client modules under a client namespace for cleaner imports
 pub mod client {
     pub use super::{
-        action::*, app::*, auth::*, chat::*, cli::*, components::*, config::*,
+        accounts::*, action::*, app::*, auth::*, chat::*, cli::*, components::*, config::*,
         connection_manager::*, errors::*, history::*, logging::*, tui::*,
     };
 
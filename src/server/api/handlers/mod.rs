@@ -18,7 +18,12 @@
 //! Errors are automatically converted to appropriate HTTP status codes
 //! and JSON error responses.
 
-use axum::{extract::State, http::StatusCode, response::Json, Extension};
+use axum::{
+    extract::State,
+    http::{header, StatusCode},
+    response::{IntoResponse, Json},
+    Extension,
+};
 use serde_json::Value;
 use tracing::{debug, error, info, warn};
 
@@ -77,6 +82,22 @@ pub async fn health_check(State(state): State<ApiState>) -> ApiResult<Json<Value
     Ok(Json(response))
 }
 
+/// Metrics scrape handler, exposing [`ErrorStats`](crate::server::error::ErrorStats)
+/// and circuit breaker state in OpenMetrics text format for Prometheus (or
+/// compatible scrapers) to poll.
+pub async fn metrics_handler() -> impl IntoResponse {
+    let body = crate::server::error::get_error_handler().metrics_snapshot().await;
+
+    (
+        StatusCode::OK,
+        [(
+            header::CONTENT_TYPE,
+            "application/openmetrics-text; version=1.0.0; charset=utf-8",
+        )],
+        body,
+    )
+}
+
 /// Extract user context from request (helper function)
 pub fn get_current_user(Extension(user_context): Extension<UserContext>) -> UserContext {
     user_context
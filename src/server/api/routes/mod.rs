@@ -28,6 +28,8 @@ pub fn create_api_routes() -> Router<ApiState> {
     Router::new()
         // Health check endpoint (no auth required)
         .route("/health", get(crate::server::api::handlers::health_check))
+        // Metrics scrape endpoint (no auth required, matches /health)
+        .route("/metrics", get(crate::server::api::handlers::metrics_handler))
         // Authentication routes (no auth required)
         .nest("/auth", create_auth_routes())
         // User management routes (auth required)
@@ -73,6 +73,8 @@ pub struct ChatMessage {
     pub edited: bool,
     /// Timestamp of last edit (if any)
     pub edited_at: Option<u64>,
+    /// IDs of attachments stored via `AttachmentStorage`, in upload order
+    pub attachments: Vec<String>,
 }
 
 impl ChatMessage {
@@ -99,6 +101,7 @@ impl ChatMessage {
             metadata: HashMap::new(),
             edited: false,
             edited_at: None,
+            attachments: Vec::new(),
         }
     }
 
@@ -120,6 +123,7 @@ impl ChatMessage {
             metadata: HashMap::new(),
             edited: false,
             edited_at: None,
+            attachments: Vec::new(),
         }
     }
 
@@ -146,6 +150,7 @@ impl ChatMessage {
             metadata: HashMap::new(),
             edited: false,
             edited_at: None,
+            attachments: Vec::new(),
         }
     }
 
@@ -167,6 +172,7 @@ impl ChatMessage {
             metadata: HashMap::new(),
             edited: false,
             edited_at: None,
+            attachments: Vec::new(),
         }
     }
 
@@ -212,6 +218,16 @@ impl ChatMessage {
         self.metadata.get(key)
     }
 
+    /// Attach a previously stored attachment (by `AttachmentStorage` ID)
+    pub fn add_attachment(&mut self, attachment_id: String) {
+        self.attachments.push(attachment_id);
+    }
+
+    /// Check if this message carries any attachments
+    pub fn has_attachments(&self) -> bool {
+        !self.attachments.is_empty()
+    }
+
     /// Check if this is a direct message
     pub fn is_direct_message(&self) -> bool {
         self.message_type == MessageType::DirectMessage && self.recipient_id.is_some()
@@ -224,7 +240,7 @@ impl ChatMessage {
 
     /// Validate message content
     pub fn validate(&self) -> Result<(), String> {
-        if self.content.is_empty() {
+        if self.content.is_empty() && !self.has_attachments() {
             return Err("Message content cannot be empty".to_string());
         }
 
@@ -553,6 +569,24 @@ mod tests {
         assert!(store.get_message(&message_id).is_none());
     }
 
+    #[test]
+    fn test_message_attachments() {
+        let mut message = ChatMessage::new_text_message(
+            "alice".to_string(),
+            Some(Uuid::new_v4()),
+            "".to_string(),
+            "general".to_string(),
+        );
+
+        // Empty content with no attachments is invalid...
+        assert!(message.validate().is_err());
+
+        // ...but an attachment-only message is fine.
+        message.add_attachment("attachment-1".to_string());
+        assert!(message.has_attachments());
+        assert!(message.validate().is_ok());
+    }
+
     #[test]
     fn test_direct_message_store() {
         let mut store = MessageStore::new();
@@ -140,6 +140,7 @@ impl Default for LimitsConfig {
             rate_limit_window: 60, // 1 minute
             memory_limit: 512,     // 512 MB
             cpu_limit: 80.0,       // 80% CPU usage
+            custom: HashMap::new(),
         }
     }
 }
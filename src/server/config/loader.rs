@@ -10,6 +10,9 @@ use std::collections::HashMap;
 use std::env;
 use std::fs;
 use std::path::Path;
+
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
+use tokio::sync::{mpsc, watch};
 use tracing::{debug, info, warn};
 
 /// Configuration file formats supported by the loader
@@ -38,11 +41,45 @@ impl ConfigFormat {
     }
 }
 
+/// Default cap on the size of a single configuration file, enforced before
+/// it is read into memory. Override per-loader with
+/// [`ConfigLoader::allow_large_config`], or set `LAIR_CHAT_ALLOW_LARGE_CONFIG`
+/// in the environment.
+const DEFAULT_MAX_CONFIG_SIZE_BYTES: u64 = 100 * 1024 * 1024;
+
 /// Configuration loader with support for multiple sources and formats
+#[derive(Clone)]
 pub struct ConfigLoader {
     search_paths: Vec<PathBuf>,
     environment_prefix: String,
     format_preference: Vec<ConfigFormat>,
+    /// Active environment profile (e.g. `"development"`, `"production"`),
+    /// used to select which section of a layered config file overrides
+    /// `[default]`. `None` falls back to the `LAIR_CHAT_ENV` environment
+    /// variable at load time, and then to `[default]` alone.
+    profile: Option<String>,
+    /// Bypass the [`DEFAULT_MAX_CONFIG_SIZE_BYTES`] guard, set by
+    /// [`allow_large_config`](Self::allow_large_config) or falling back to
+    /// the `LAIR_CHAT_ALLOW_LARGE_CONFIG` environment variable at load time.
+    allow_large_config: bool,
+    /// Maximum config file size enforced unless [`allow_large_config`] is
+    /// set. Defaults to [`DEFAULT_MAX_CONFIG_SIZE_BYTES`]; overridable via
+    /// [`with_max_config_size_bytes`](Self::with_max_config_size_bytes),
+    /// mainly so tests don't need a 100 MB fixture.
+    max_config_size_bytes: u64,
+}
+
+/// Handle to a background task started by [`ConfigLoader::watch`]. Dropping
+/// it stops the file watcher and the reload task.
+pub struct ConfigWatchHandle {
+    _watcher: RecommendedWatcher,
+    task: tokio::task::JoinHandle<()>,
+}
+
+impl Drop for ConfigWatchHandle {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
 }
 
 impl ConfigLoader {
@@ -52,7 +89,83 @@ impl ConfigLoader {
             search_paths: Self::default_search_paths(),
             environment_prefix: "LAIR_CHAT".to_string(),
             format_preference: vec![ConfigFormat::Toml, ConfigFormat::Json, ConfigFormat::Yaml],
+            profile: None,
+            allow_large_config: false,
+            max_config_size_bytes: DEFAULT_MAX_CONFIG_SIZE_BYTES,
+        }
+    }
+
+    /// Bypass the configuration file size cap (see
+    /// [`with_max_config_size_bytes`](Self::with_max_config_size_bytes)). Off
+    /// by default; [`load_from_file`](Self::load_from_file) and
+    /// [`load_with_provenance`](Self::load_with_provenance) reject a config
+    /// file larger than the cap with [`ConfigError::TooLarge`] unless this is
+    /// set, or `LAIR_CHAT_ALLOW_LARGE_CONFIG` is set in the environment.
+    /// [`load`](Self::load) reads its file through [`ConfigBuilder`] and does
+    /// not check the size.
+    pub fn allow_large_config(mut self, allow: bool) -> Self {
+        self.allow_large_config = allow;
+        self
+    }
+
+    /// Override the configuration file size cap (default
+    /// [`DEFAULT_MAX_CONFIG_SIZE_BYTES`]).
+    pub fn with_max_config_size_bytes(mut self, max_bytes: u64) -> Self {
+        self.max_config_size_bytes = max_bytes;
+        self
+    }
+
+    /// Whether the size guard should be skipped for the next load: set via
+    /// [`allow_large_config`](Self::allow_large_config), or else the
+    /// `LAIR_CHAT_ALLOW_LARGE_CONFIG` environment variable.
+    fn large_config_allowed(&self) -> bool {
+        self.allow_large_config
+            || env::var("LAIR_CHAT_ALLOW_LARGE_CONFIG")
+                .ok()
+                .and_then(|v| parse_bool(&v).ok())
+                .unwrap_or(false)
+    }
+
+    /// Reject `path` with [`ConfigError::TooLarge`] if it exceeds
+    /// `max_config_size_bytes` and the size guard hasn't been bypassed (see
+    /// [`large_config_allowed`](Self::large_config_allowed)).
+    fn check_config_size(&self, path: &Path) -> Result<(), ConfigError> {
+        if self.large_config_allowed() {
+            return Ok(());
         }
+
+        let size = fs::metadata(path)
+            .map_err(|e| ConfigError::ReadError { source: e })?
+            .len();
+
+        if size > self.max_config_size_bytes {
+            return Err(ConfigError::TooLarge {
+                path: path.display().to_string(),
+                size,
+                limit: self.max_config_size_bytes,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Select which profile section (e.g. `"development"`, `"production"`)
+    /// overrides `[default]` in a layered config file, instead of reading it
+    /// from the `LAIR_CHAT_ENV` environment variable at load time.
+    pub fn with_profile<S: Into<String>>(mut self, profile: S) -> Self {
+        self.profile = Some(profile.into());
+        self
+    }
+
+    /// The profile that [`load_from_file`](Self::load_from_file) and
+    /// [`load_with_provenance`](Self::load_with_provenance) apply: the one set
+    /// by [`with_profile`](Self::with_profile), or else `LAIR_CHAT_ENV`.
+    /// [`load`](Self::load) reads its file through [`ConfigBuilder`] and
+    /// applies no profile.
+    fn active_profile(&self) -> Option<String> {
+        self.profile
+            .clone()
+            .or_else(|| env::var("LAIR_CHAT_ENV").ok())
     }
 
     /// Set custom search paths for configuration files
@@ -95,6 +208,55 @@ impl ConfigLoader {
         builder.build()
     }
 
+    /// Load configuration from the same sources as [`load`](Self::load)
+    /// (defaults, the first config file found, then the environment),
+    /// additionally returning a `Definition` per leaf key recording which
+    /// source last set it, so callers can answer "where did this value come
+    /// from?". Unlike `load`, the file may be TOML, JSON or YAML, is subject
+    /// to the size guard and has the active profile applied, and environment
+    /// variables here are layered directly onto the
+    /// file-derived value (rather than through [`ConfigBuilder`]'s
+    /// whole-config overlay), so a file can set some fields and the
+    /// environment override just one without losing the rest of the file.
+    pub fn load_with_provenance(
+        &self,
+    ) -> Result<(ServerConfig, HashMap<String, Definition>), ConfigError> {
+        let shape = serde_json::to_value(ServerConfig::default())
+            .expect("ServerConfig::default() is always representable as JSON");
+
+        let mut value = shape.clone();
+        let mut provenance = HashMap::new();
+        record_leaf_provenance(
+            &value,
+            &ConfigSource::Default,
+            "<default>",
+            "",
+            &mut provenance,
+        );
+
+        if let Ok(path) = self.load_from_files() {
+            self.check_config_size(&path)?;
+            let format = ConfigFormat::from_path(&path).unwrap_or(ConfigFormat::Toml);
+            let contents =
+                fs::read_to_string(&path).map_err(|e| ConfigError::ReadError { source: e })?;
+            let file_value = self.parse_to_value(&contents, format)?;
+            deep_merge_json(&mut value, file_value.clone());
+            record_leaf_provenance(
+                &file_value,
+                &ConfigSource::File(path.clone()),
+                &path.display().to_string(),
+                "",
+                &mut provenance,
+            );
+        }
+
+        let (config, env_provenance) = self.load_environment_with_provenance(value)?;
+        provenance.extend(env_provenance);
+
+        validate_config(&config)?;
+        Ok((config, provenance))
+    }
+
     /// Load configuration from a specific file
     pub fn load_from_file<P: AsRef<Path>>(&self, path: P) -> Result<ServerConfig, ConfigError> {
         let path = path.as_ref();
@@ -105,6 +267,8 @@ impl ConfigLoader {
             });
         }
 
+        self.check_config_size(path)?;
+
         let format = ConfigFormat::from_path(path).unwrap_or(ConfigFormat::Toml);
         let contents =
             fs::read_to_string(path).map_err(|e| ConfigError::ReadError { source: e })?;
@@ -114,369 +278,247 @@ impl ConfigLoader {
 
     /// Load configuration from the first available file in search paths
     pub fn load_from_files(&self) -> Result<PathBuf, ConfigError> {
+        for candidate in self.candidate_paths() {
+            if candidate.exists() {
+                info!("Found configuration file: {}", candidate.display());
+                return Ok(candidate);
+            }
+        }
+
+        Err(ConfigError::FileNotFound {
+            path: "No configuration file found in search paths".to_string(),
+        })
+    }
+
+    /// Every `search_path` joined with every filename `format_preference`
+    /// would accept, regardless of whether the file actually exists.
+    fn candidate_paths(&self) -> Vec<PathBuf> {
+        let mut candidates = Vec::new();
         for search_path in &self.search_paths {
             for format in &self.format_preference {
-                let filename = match format {
-                    ConfigFormat::Toml => "server.toml",
-                    ConfigFormat::Json => "server.json",
-                    ConfigFormat::Yaml => "server.yaml",
+                let filenames: &[&str] = match format {
+                    ConfigFormat::Toml => &["server.toml"],
+                    ConfigFormat::Json => &["server.json"],
+                    ConfigFormat::Yaml => &["server.yaml", "server.yml"],
                 };
-
-                let config_path = search_path.join(filename);
-                if config_path.exists() {
-                    info!("Found configuration file: {}", config_path.display());
-                    return Ok(config_path);
+                for filename in filenames {
+                    candidates.push(search_path.join(filename));
                 }
             }
         }
+        candidates
+    }
 
-        Err(ConfigError::FileNotFound {
-            path: "No configuration file found in search paths".to_string(),
+    /// The subset of [`candidate_paths`](Self::candidate_paths) that
+    /// currently exist on disk. `load()` reads only the first of them.
+    fn existing_config_paths(&self) -> Vec<PathBuf> {
+        self.candidate_paths()
+            .into_iter()
+            .filter(|path| path.exists())
+            .collect()
+    }
+
+    /// Watch every resolved configuration file for modification, reloading
+    /// and re-validating on change. A reload is atomic: the new file is
+    /// fully parsed and validated before being published over the returned
+    /// `watch` channel, and a parse/validation failure keeps the previously
+    /// published configuration in place (logging a `warn!` naming the
+    /// offending path) instead of ever publishing a broken config.
+    ///
+    /// Subscribers can diff `*rx.borrow()` against the previous value (see
+    /// [`diff_config`](super::diff_config)) to decide whether a change can
+    /// be applied live or requires a restart.
+    pub fn watch(&self) -> Result<(watch::Receiver<ServerConfig>, ConfigWatchHandle), ConfigError> {
+        let initial = self.load()?;
+        let (tx, rx) = watch::channel(initial.clone());
+
+        let (notify_tx, mut notify_rx) = mpsc::unbounded_channel();
+        let mut watcher = notify::recommended_watcher(move |event: notify::Result<notify::Event>| {
+            if let Ok(event) = event {
+                let _ = notify_tx.send(event);
+            }
         })
+        .map_err(|e| ConfigError::ValidationError {
+            message: format!("failed to start configuration file watcher: {}", e),
+        })?;
+
+        for path in self.existing_config_paths() {
+            if let Err(e) = watcher.watch(&path, RecursiveMode::NonRecursive) {
+                warn!(
+                    path = %path.display(),
+                    error = %e,
+                    "failed to watch configuration file for changes"
+                );
+            }
+        }
+
+        let loader = self.clone();
+        let mut current = initial;
+        let task = tokio::spawn(async move {
+            while let Some(event) = notify_rx.recv().await {
+                if !matches!(
+                    event.kind,
+                    notify::EventKind::Modify(_) | notify::EventKind::Create(_)
+                ) {
+                    continue;
+                }
+
+                match loader.load() {
+                    Ok(new_config) => {
+                        let diff = diff_config(&current, &new_config);
+                        if diff.is_empty() {
+                            continue;
+                        }
+                        info!(
+                            changed_fields = ?diff.changed_fields,
+                            requires_restart = diff.requires_restart,
+                            "configuration reloaded"
+                        );
+                        current = new_config.clone();
+                        if tx.send(new_config).is_err() {
+                            break; // No subscribers left.
+                        }
+                    }
+                    Err(e) => {
+                        warn!(
+                            paths = ?event.paths,
+                            error = %e,
+                            "configuration reload failed; keeping previous configuration"
+                        );
+                    }
+                }
+            }
+        });
+
+        Ok((
+            rx,
+            ConfigWatchHandle {
+                _watcher: watcher,
+                task,
+            },
+        ))
     }
 
     /// Load configuration from environment variables
     pub fn load_from_environment(&self) -> Result<ServerConfig, ConfigError> {
-        let mut config = ServerConfig::default();
+        let (config, _) = self.load_environment_with_provenance(serde_json::to_value(
+            ServerConfig::default(),
+        )
+        .expect("ServerConfig::default() is always representable as JSON"))?;
+        Ok(config)
+    }
+
+    /// Overlay every `LAIR_CHAT_*` environment variable onto `base` (a JSON
+    /// value already shaped like [`ServerConfig`]), returning the resulting
+    /// config alongside a per-leaf [`Definition`] recording that it came from
+    /// the environment. The shape of `ServerConfig::default()` (not `base`'s
+    /// own) is consulted when resolving multi-word field names and coercing
+    /// scalars, so a field `base` omits still resolves; the values are written
+    /// into `base`, which may be a file-derived value.
+    fn load_environment_with_provenance(
+        &self,
+        base: serde_json::Value,
+    ) -> Result<(ServerConfig, HashMap<String, Definition>), ConfigError> {
+        let shape = serde_json::to_value(ServerConfig::default())
+            .expect("ServerConfig::default() is always representable as JSON");
         let prefix = format!("{}_", self.environment_prefix);
 
-        // Collect all environment variables with our prefix
         let env_vars: HashMap<String, String> = env::vars()
             .filter(|(key, _)| key.starts_with(&prefix))
             .collect();
 
+        let mut value = base;
+        let mut provenance = HashMap::new();
+
         if env_vars.is_empty() {
             debug!("No environment variables found with prefix {}", prefix);
-            return Ok(config);
+        } else {
+            info!(
+                "Loading configuration from {} environment variables",
+                env_vars.len()
+            );
+
+            for (key, raw_value) in &env_vars {
+                let config_key = key.strip_prefix(&prefix).unwrap().to_lowercase();
+                let tokens: Vec<String> = config_key.split('_').map(str::to_string).collect();
+                let path = insert_path(&mut value, &shape, &tokens, raw_value)?;
+                provenance.insert(
+                    path,
+                    Definition {
+                        source: ConfigSource::Environment,
+                        origin: key.clone(),
+                    },
+                );
+            }
         }
 
-        info!(
-            "Loading configuration from {} environment variables",
-            env_vars.len()
-        );
-
-        // Parse environment variables into configuration
-        self.apply_environment_variables(&mut config, &env_vars)?;
-
-        Ok(config)
+        let config = serde_json::from_value(value).map_err(|e| ConfigError::ValidationError {
+            message: format!("invalid configuration: {}", e),
+        })?;
+        Ok((config, provenance))
     }
 
-    /// Parse configuration string with the specified format
-    fn parse_config(
+    /// Parse configuration string into its JSON representation, with the
+    /// active profile (if any) already layered over `[default]`.
+    fn parse_to_value(
         &self,
         contents: &str,
         format: ConfigFormat,
-    ) -> Result<ServerConfig, ConfigError> {
-        match format {
+    ) -> Result<serde_json::Value, ConfigError> {
+        let root: serde_json::Value = match format {
             ConfigFormat::Toml => {
-                toml::from_str(contents).map_err(|e| ConfigError::ParseError { source: e })
+                toml::from_str(contents).map_err(|e| ConfigError::ParseError { source: e })?
             }
             ConfigFormat::Json => {
                 serde_json::from_str(contents).map_err(|e| ConfigError::ValidationError {
                     message: format!("JSON parse error: {}", e),
-                })
+                })?
             }
             ConfigFormat::Yaml => {
-                // Note: In a real implementation, you'd use serde_yaml
-                // For now, we'll return an error as YAML support isn't implemented
-                Err(ConfigError::ValidationError {
-                    message: "YAML format not yet implemented".to_string(),
-                })
-            }
-        }
-    }
-
-    /// Apply environment variables to configuration
-    fn apply_environment_variables(
-        &self,
-        config: &mut ServerConfig,
-        env_vars: &HashMap<String, String>,
-    ) -> Result<(), ConfigError> {
-        let prefix = format!("{}_", self.environment_prefix);
-
-        for (key, value) in env_vars {
-            let config_key = key.strip_prefix(&prefix).unwrap().to_lowercase();
-            self.apply_environment_variable(config, &config_key, value)?;
-        }
-
-        Ok(())
-    }
-
-    /// Apply a single environment variable to configuration
-    fn apply_environment_variable(
-        &self,
-        config: &mut ServerConfig,
-        key: &str,
-        value: &str,
-    ) -> Result<(), ConfigError> {
-        let parts: Vec<&str> = key.split('_').collect();
-
-        if parts.len() < 2 {
-            warn!("Invalid environment variable format: {}", key);
-            return Ok(());
-        }
-
-        let section = parts[0];
-        let field = parts[1..].join("_");
-
-        match section {
-            "server" => self.apply_server_env(config, &field, value)?,
-            "database" => self.apply_database_env(config, &field, value)?,
-            "security" => self.apply_security_env(config, &field, value)?,
-            "logging" => self.apply_logging_env(config, &field, value)?,
-            "features" => self.apply_features_env(config, &field, value)?,
-            "limits" => self.apply_limits_env(config, &field, value)?,
-            "admin" => self.apply_admin_env(config, &field, value)?,
-            _ => {
-                warn!("Unknown configuration section: {}", section);
-            }
-        }
-
-        Ok(())
-    }
-
-    /// Apply server environment variables
-    fn apply_server_env(
-        &self,
-        config: &mut ServerConfig,
-        field: &str,
-        value: &str,
-    ) -> Result<(), ConfigError> {
-        match field {
-            "host" => config.server.host = value.to_string(),
-            "port" => {
-                config.server.port = value.parse().map_err(|_| ConfigError::EnvironmentError {
-                    name: "SERVER_PORT".to_string(),
-                    message: "Invalid port number".to_string(),
-                })?;
-            }
-            "max_connections" => {
-                config.server.max_connections =
-                    value.parse().map_err(|_| ConfigError::EnvironmentError {
-                        name: "SERVER_MAX_CONNECTIONS".to_string(),
-                        message: "Invalid connection count".to_string(),
-                    })?;
-            }
-            "connection_timeout" => {
-                config.server.connection_timeout =
-                    value.parse().map_err(|_| ConfigError::EnvironmentError {
-                        name: "SERVER_CONNECTION_TIMEOUT".to_string(),
-                        message: "Invalid timeout value".to_string(),
-                    })?;
-            }
-            "enable_tls" => {
-                config.server.enable_tls = self.parse_bool(value)?;
-            }
-            "tls_cert_path" => {
-                config.server.tls_cert_path = Some(PathBuf::from(value));
-            }
-            "tls_key_path" => {
-                config.server.tls_key_path = Some(PathBuf::from(value));
-            }
-            _ => warn!("Unknown server configuration field: {}", field),
-        }
-        Ok(())
-    }
-
-    /// Apply database environment variables
-    fn apply_database_env(
-        &self,
-        config: &mut ServerConfig,
-        field: &str,
-        value: &str,
-    ) -> Result<(), ConfigError> {
-        match field {
-            "type" => config.database.database_type = value.to_string(),
-            "url" => config.database.url = value.to_string(),
-            "max_connections" => {
-                config.database.max_connections =
-                    value.parse().map_err(|_| ConfigError::EnvironmentError {
-                        name: "DATABASE_MAX_CONNECTIONS".to_string(),
-                        message: "Invalid connection count".to_string(),
-                    })?;
-            }
-            "connection_timeout" => {
-                config.database.connection_timeout =
-                    value.parse().map_err(|_| ConfigError::EnvironmentError {
-                        name: "DATABASE_CONNECTION_TIMEOUT".to_string(),
-                        message: "Invalid timeout value".to_string(),
-                    })?;
-            }
-            "auto_migrate" => {
-                config.database.auto_migrate = self.parse_bool(value)?;
+                serde_yaml::from_str(contents).map_err(|e| ConfigError::ValidationError {
+                    message: format!("YAML parse error: {}", e),
+                })?
             }
-            _ => warn!("Unknown database configuration field: {}", field),
-        }
-        Ok(())
-    }
+        };
 
-    /// Apply security environment variables
-    fn apply_security_env(
-        &self,
-        config: &mut ServerConfig,
-        field: &str,
-        value: &str,
-    ) -> Result<(), ConfigError> {
-        match field {
-            "enable_encryption" => {
-                config.security.enable_encryption = self.parse_bool(value)?;
-            }
-            "session_timeout" => {
-                config.security.session_timeout =
-                    value.parse().map_err(|_| ConfigError::EnvironmentError {
-                        name: "SECURITY_SESSION_TIMEOUT".to_string(),
-                        message: "Invalid timeout value".to_string(),
-                    })?;
-            }
-            "max_login_attempts" => {
-                config.security.max_login_attempts =
-                    value.parse().map_err(|_| ConfigError::EnvironmentError {
-                        name: "SECURITY_MAX_LOGIN_ATTEMPTS".to_string(),
-                        message: "Invalid attempt count".to_string(),
-                    })?;
-            }
-            "password_min_length" => {
-                config.security.password_min_length =
-                    value.parse().map_err(|_| ConfigError::EnvironmentError {
-                        name: "SECURITY_PASSWORD_MIN_LENGTH".to_string(),
-                        message: "Invalid length value".to_string(),
-                    })?;
-            }
-            "jwt_secret" => {
-                config.security.jwt_secret = Some(value.to_string());
-            }
-            _ => warn!("Unknown security configuration field: {}", field),
-        }
-        Ok(())
+        Ok(self.apply_profile(root))
     }
 
-    /// Apply logging environment variables
-    fn apply_logging_env(
+    /// Parse configuration string with the specified format, layered over
+    /// `ServerConfig::default()` so a file may set only some fields.
+    fn parse_config(
         &self,
-        config: &mut ServerConfig,
-        field: &str,
-        value: &str,
-    ) -> Result<(), ConfigError> {
-        match field {
-            "level" => config.logging.level = value.to_string(),
-            "format" => config.logging.format = value.to_string(),
-            "enable_file_logging" => {
-                config.logging.enable_file_logging = self.parse_bool(value)?;
-            }
-            "file_path" => {
-                config.logging.file_path = Some(PathBuf::from(value));
-            }
-            "enable_stdout" => {
-                config.logging.enable_stdout = self.parse_bool(value)?;
-            }
-            _ => warn!("Unknown logging configuration field: {}", field),
-        }
-        Ok(())
+        contents: &str,
+        format: ConfigFormat,
+    ) -> Result<ServerConfig, ConfigError> {
+        let mut effective = serde_json::to_value(ServerConfig::default())
+            .expect("ServerConfig::default() is always representable as JSON");
+        deep_merge_json(&mut effective, self.parse_to_value(contents, format)?);
+        serde_json::from_value(effective).map_err(|e| ConfigError::ValidationError {
+            message: format!("invalid configuration: {}", e),
+        })
     }
 
-    /// Apply features environment variables
-    fn apply_features_env(
-        &self,
-        config: &mut ServerConfig,
-        field: &str,
-        value: &str,
-    ) -> Result<(), ConfigError> {
-        match field {
-            "enable_direct_messages" => {
-                config.features.enable_direct_messages = self.parse_bool(value)?;
-            }
-            "enable_file_uploads" => {
-                config.features.enable_file_uploads = self.parse_bool(value)?;
-            }
-            "enable_message_history" => {
-                config.features.enable_message_history = self.parse_bool(value)?;
-            }
-            "max_file_size" => {
-                config.features.max_file_size =
-                    value.parse().map_err(|_| ConfigError::EnvironmentError {
-                        name: "FEATURES_MAX_FILE_SIZE".to_string(),
-                        message: "Invalid file size".to_string(),
-                    })?;
-            }
-            _ => warn!("Unknown features configuration field: {}", field),
-        }
-        Ok(())
-    }
+    /// Layer the active profile's section over `[default]`, if `root` has a
+    /// `default` key at all. A file with no `default` section is assumed to
+    /// already be a flat, profile-less `ServerConfig` and is passed through
+    /// unchanged, so existing single-profile config files keep working.
+    fn apply_profile(&self, root: serde_json::Value) -> serde_json::Value {
+        let serde_json::Value::Object(mut root_map) = root else {
+            return root;
+        };
 
-    /// Apply limits environment variables
-    fn apply_limits_env(
-        &self,
-        config: &mut ServerConfig,
-        field: &str,
-        value: &str,
-    ) -> Result<(), ConfigError> {
-        match field {
-            "messages_per_minute" => {
-                config.limits.messages_per_minute =
-                    value.parse().map_err(|_| ConfigError::EnvironmentError {
-                        name: "LIMITS_MESSAGES_PER_MINUTE".to_string(),
-                        message: "Invalid rate limit".to_string(),
-                    })?;
-            }
-            "max_message_length" => {
-                config.limits.max_message_length =
-                    value.parse().map_err(|_| ConfigError::EnvironmentError {
-                        name: "LIMITS_MAX_MESSAGE_LENGTH".to_string(),
-                        message: "Invalid message length".to_string(),
-                    })?;
-            }
-            "max_connections_per_ip" => {
-                config.limits.max_connections_per_ip =
-                    value.parse().map_err(|_| ConfigError::EnvironmentError {
-                        name: "LIMITS_MAX_CONNECTIONS_PER_IP".to_string(),
-                        message: "Invalid connection limit".to_string(),
-                    })?;
-            }
-            _ => warn!("Unknown limits configuration field: {}", field),
-        }
-        Ok(())
-    }
+        let Some(mut effective) = root_map.remove("default") else {
+            return serde_json::Value::Object(root_map);
+        };
 
-    /// Apply admin environment variables
-    fn apply_admin_env(
-        &self,
-        config: &mut ServerConfig,
-        field: &str,
-        value: &str,
-    ) -> Result<(), ConfigError> {
-        match field {
-            "enable_admin_api" => {
-                config.admin.enable_admin_api = self.parse_bool(value)?;
-            }
-            "admin_host" => config.admin.admin_host = value.to_string(),
-            "admin_port" => {
-                config.admin.admin_port =
-                    value.parse().map_err(|_| ConfigError::EnvironmentError {
-                        name: "ADMIN_ADMIN_PORT".to_string(),
-                        message: "Invalid port number".to_string(),
-                    })?;
-            }
-            "admin_token" => {
-                config.admin.admin_token = Some(value.to_string());
+        if let Some(profile) = self.active_profile() {
+            if let Some(section) = root_map.remove(&profile) {
+                deep_merge_json(&mut effective, section);
             }
-            "enable_metrics" => {
-                config.admin.enable_metrics = self.parse_bool(value)?;
-            }
-            _ => warn!("Unknown admin configuration field: {}", field),
         }
-        Ok(())
-    }
 
-    /// Parse boolean value from string
-    fn parse_bool(&self, value: &str) -> Result<bool, ConfigError> {
-        match value.to_lowercase().as_str() {
-            "true" | "1" | "yes" | "on" | "enabled" => Ok(true),
-            "false" | "0" | "no" | "off" | "disabled" => Ok(false),
-            _ => Err(ConfigError::EnvironmentError {
-                name: "BOOLEAN_VALUE".to_string(),
-                message: format!("Invalid boolean value: {}", value),
-            }),
-        }
+        effective
     }
 
     /// Get default search paths for configuration files
@@ -520,9 +562,9 @@ impl ConfigLoader {
                 })?
             }
             ConfigFormat::Yaml => {
-                return Err(ConfigError::ValidationError {
-                    message: "YAML format not yet implemented".to_string(),
-                });
+                serde_yaml::to_string(&config).map_err(|e| ConfigError::ValidationError {
+                    message: format!("YAML serialization error: {}", e),
+                })?
             }
         };
 
@@ -539,6 +581,277 @@ impl Default for ConfigLoader {
     }
 }
 
+/// Recursively merge `overlay` onto `base`, with `overlay` taking
+/// precedence. Objects are merged key-by-key; any other pairing (including
+/// an object meeting a scalar) has `overlay` win outright.
+fn deep_merge_json(base: &mut serde_json::Value, overlay: serde_json::Value) {
+    match (base, overlay) {
+        (serde_json::Value::Object(base_map), serde_json::Value::Object(overlay_map)) => {
+            for (key, value) in overlay_map {
+                deep_merge_json(base_map.entry(key).or_insert(serde_json::Value::Null), value);
+            }
+        }
+        (base_slot, overlay_value) => {
+            *base_slot = overlay_value;
+        }
+    }
+}
+
+/// Where a configuration leaf's final value came from, as recorded by
+/// [`ConfigLoader::load_with_provenance`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Definition {
+    pub source: ConfigSource,
+    /// The file path or environment variable name that supplied the value,
+    /// or `"<default>"` if nothing overrode the built-in default.
+    pub origin: String,
+}
+
+/// Record a `Definition` for every leaf (non-object) value reachable from
+/// `value`, keyed by its dotted path (array elements are suffixed with
+/// `[index]`, e.g. `"admin.allowed_origins[0]"`). Existing entries for the
+/// same path are overwritten, so calling this with sources in priority order
+/// (defaults, then file, then environment) leaves the highest-priority
+/// source recorded for each leaf.
+fn record_leaf_provenance(
+    value: &serde_json::Value,
+    source: &ConfigSource,
+    origin: &str,
+    path_so_far: &str,
+    provenance: &mut HashMap<String, Definition>,
+) {
+    match value {
+        serde_json::Value::Object(map) => {
+            for (key, child) in map {
+                let child_path = if path_so_far.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{}.{}", path_so_far, key)
+                };
+                record_leaf_provenance(child, source, origin, &child_path, provenance);
+            }
+        }
+        serde_json::Value::Array(items) => {
+            for (index, item) in items.iter().enumerate() {
+                let child_path = format!("{}[{}]", path_so_far, index);
+                record_leaf_provenance(item, source, origin, &child_path, provenance);
+            }
+        }
+        _ => {
+            provenance.insert(
+                path_so_far.to_string(),
+                Definition {
+                    source: source.clone(),
+                    origin: origin.to_string(),
+                },
+            );
+        }
+    }
+}
+
+/// Find the longest run of leading `tokens`, joined with `_`, that names a
+/// key of `shape` (an object) — e.g. `["max", "connections", "per", "ip"]`
+/// against a shape with key `"max_connections"` resolves to `"max_connections"`
+/// plus the remaining `["per", "ip"]`. Falls back to joining every token if
+/// none of the prefixes match, so a genuinely new field (absent from `shape`)
+/// is still named sensibly instead of being rejected.
+fn resolve_segment<'a>(tokens: &'a [String], shape: &serde_json::Value) -> (String, &'a [String]) {
+    let serde_json::Value::Object(shape_map) = shape else {
+        return (tokens.join("_"), &[]);
+    };
+
+    for split in (1..=tokens.len()).rev() {
+        let candidate = tokens[..split].join("_");
+        if shape_map.contains_key(&candidate) {
+            return (candidate, &tokens[split..]);
+        }
+    }
+
+    (tokens.join("_"), &[])
+}
+
+/// Coerce a raw environment variable string to the JSON type of `shape`
+/// (the default value at this path), preserving the historical boolean
+/// vocabulary (`true/1/yes/on/enabled`, `false/0/no/off/disabled`) only when
+/// the shape actually expects a bool, so numeric literals in non-boolean
+/// fields are never misread as booleans. When `shape` is `Value::Null` (a
+/// field with no default to consult, i.e. genuinely new), falls back to
+/// sniffing bool, then integer, then float, then string.
+fn coerce_scalar(raw: &str, shape: &serde_json::Value) -> Result<serde_json::Value, ConfigError> {
+    match shape {
+        serde_json::Value::Bool(_) => parse_bool(raw).map(serde_json::Value::Bool),
+        serde_json::Value::Number(_) => {
+            if let Ok(i) = raw.parse::<i64>() {
+                Ok(serde_json::Value::Number(i.into()))
+            } else if let Ok(f) = raw.parse::<f64>() {
+                Ok(serde_json::Number::from_f64(f)
+                    .map(serde_json::Value::Number)
+                    .unwrap_or_else(|| serde_json::Value::String(raw.to_string())))
+            } else {
+                Err(ConfigError::EnvironmentError {
+                    name: raw.to_string(),
+                    message: "Invalid numeric value".to_string(),
+                })
+            }
+        }
+        serde_json::Value::Null => {
+            if let Ok(b) = parse_bool(raw) {
+                Ok(serde_json::Value::Bool(b))
+            } else if let Ok(i) = raw.parse::<i64>() {
+                Ok(serde_json::Value::Number(i.into()))
+            } else if let Ok(f) = raw.parse::<f64>() {
+                Ok(serde_json::Number::from_f64(f)
+                    .map(serde_json::Value::Number)
+                    .unwrap_or_else(|| serde_json::Value::String(raw.to_string())))
+            } else {
+                Ok(serde_json::Value::String(raw.to_string()))
+            }
+        }
+        _ => Ok(serde_json::Value::String(raw.to_string())),
+    }
+}
+
+/// Parse a boolean the way this crate's configuration always has:
+/// `true/1/yes/on/enabled` and `false/0/no/off/disabled` (case-insensitive).
+fn parse_bool(value: &str) -> Result<bool, ConfigError> {
+    match value.to_lowercase().as_str() {
+        "true" | "1" | "yes" | "on" | "enabled" => Ok(true),
+        "false" | "0" | "no" | "off" | "disabled" => Ok(false),
+        _ => Err(ConfigError::EnvironmentError {
+            name: "BOOLEAN_VALUE".to_string(),
+            message: format!("Invalid boolean value: {}", value),
+        }),
+    }
+}
+
+/// Navigate/create the nested `Value::Object`/`Value::Array` path named by
+/// `tokens` under `node` (consulting `shape` at each level to resolve
+/// multi-word field names via [`resolve_segment`]), coerce `raw_value` to
+/// the type the shape expects, and insert it. Returns the dotted path that
+/// was written, for provenance tracking. A purely-numeric token is treated
+/// as an array index; a collision between a scalar and a table at the same
+/// path is rejected with `ConfigError::Conflict` naming the offending path.
+fn insert_path(
+    node: &mut serde_json::Value,
+    shape: &serde_json::Value,
+    tokens: &[String],
+    raw_value: &str,
+) -> Result<String, ConfigError> {
+    insert_path_inner(node, shape, tokens, raw_value, String::new())
+}
+
+fn insert_path_inner(
+    node: &mut serde_json::Value,
+    shape: &serde_json::Value,
+    tokens: &[String],
+    raw_value: &str,
+    path_so_far: String,
+) -> Result<String, ConfigError> {
+    if tokens.is_empty() {
+        return Err(ConfigError::Conflict {
+            message: format!("empty configuration key at '{}'", path_so_far),
+        });
+    }
+
+    if let Ok(index) = tokens[0].parse::<usize>() {
+        if !node.is_array() && !node.is_null() {
+            return Err(ConfigError::Conflict {
+                message: format!(
+                    "configuration key '{}' mixes an array index with a scalar or table value",
+                    path_so_far
+                ),
+            });
+        }
+        if node.is_null() {
+            *node = serde_json::Value::Array(Vec::new());
+        }
+        let serde_json::Value::Array(items) = node else {
+            unreachable!("checked above");
+        };
+        while items.len() <= index {
+            items.push(serde_json::Value::Null);
+        }
+        let item_shape = shape
+            .as_array()
+            .and_then(|items| items.first())
+            .unwrap_or(&serde_json::Value::Null);
+        let child_path = format!("{}[{}]", path_so_far, index);
+        return insert_path_inner(
+            &mut items[index],
+            item_shape,
+            &tokens[1..],
+            raw_value,
+            child_path,
+        );
+    }
+
+    let (segment, rest) = resolve_segment(tokens, shape);
+    let child_shape = shape.get(&segment).unwrap_or(&serde_json::Value::Null);
+    let child_path = if path_so_far.is_empty() {
+        segment.clone()
+    } else {
+        format!("{}.{}", path_so_far, segment)
+    };
+
+    if rest.is_empty() {
+        if !node.is_object() && !node.is_null() {
+            return Err(ConfigError::Conflict {
+                message: format!(
+                    "configuration key '{}' mixes a scalar with a table value",
+                    child_path
+                ),
+            });
+        }
+        if node.is_null() {
+            *node = serde_json::Value::Object(serde_json::Map::new());
+        }
+        let serde_json::Value::Object(map) = node else {
+            unreachable!("checked above");
+        };
+        if matches!(map.get(&segment), Some(serde_json::Value::Object(_)) | Some(serde_json::Value::Array(_)))
+        {
+            return Err(ConfigError::Conflict {
+                message: format!(
+                    "configuration key '{}' would overwrite a table with a scalar value",
+                    child_path
+                ),
+            });
+        }
+        map.insert(segment, coerce_scalar(raw_value, child_shape)?);
+        return Ok(child_path);
+    }
+
+    if !node.is_object() && !node.is_null() {
+        return Err(ConfigError::Conflict {
+            message: format!(
+                "configuration key '{}' mixes a table with a scalar value",
+                child_path
+            ),
+        });
+    }
+    if node.is_null() {
+        *node = serde_json::Value::Object(serde_json::Map::new());
+    }
+    let serde_json::Value::Object(map) = node else {
+        unreachable!("checked above");
+    };
+    if matches!(map.get(&segment), Some(v) if !v.is_object() && !v.is_null()) {
+        return Err(ConfigError::Conflict {
+            message: format!(
+                "configuration key '{}' would overwrite a scalar value with a table",
+                child_path
+            ),
+        });
+    }
+    insert_path_inner(
+        map.entry(segment).or_insert(serde_json::Value::Null),
+        child_shape,
+        rest,
+        raw_value,
+        child_path,
+    )
+}
+
 /// Utility function to load configuration with default settings
 pub fn load_config() -> Result<ServerConfig, ConfigError> {
     ConfigLoader::new().load()
@@ -590,16 +903,14 @@ mod tests {
 
     #[test]
     fn test_boolean_parsing() {
-        let loader = ConfigLoader::new();
-
-        assert_eq!(loader.parse_bool("true").unwrap(), true);
-        assert_eq!(loader.parse_bool("1").unwrap(), true);
-        assert_eq!(loader.parse_bool("yes").unwrap(), true);
-        assert_eq!(loader.parse_bool("false").unwrap(), false);
-        assert_eq!(loader.parse_bool("0").unwrap(), false);
-        assert_eq!(loader.parse_bool("no").unwrap(), false);
-
-        assert!(loader.parse_bool("invalid").is_err());
+        assert_eq!(parse_bool("true").unwrap(), true);
+        assert_eq!(parse_bool("1").unwrap(), true);
+        assert_eq!(parse_bool("yes").unwrap(), true);
+        assert_eq!(parse_bool("false").unwrap(), false);
+        assert_eq!(parse_bool("0").unwrap(), false);
+        assert_eq!(parse_bool("no").unwrap(), false);
+
+        assert!(parse_bool("invalid").is_err());
     }
 
     #[test]
@@ -619,6 +930,27 @@ mod tests {
         assert_eq!(loaded_config.server.host, "127.0.0.1");
     }
 
+    #[test]
+    fn test_sample_config_round_trips_every_format() {
+        let dir = tempdir().unwrap();
+        let loader = ConfigLoader::new();
+        let expected = ServerConfig::default();
+
+        for (format, filename) in [
+            (ConfigFormat::Toml, "sample.toml"),
+            (ConfigFormat::Json, "sample.json"),
+            (ConfigFormat::Yaml, "sample.yaml"),
+        ] {
+            let config_path = dir.path().join(filename);
+            loader
+                .create_sample_config(&config_path, format.clone())
+                .unwrap();
+
+            let loaded = loader.load_from_file(&config_path).unwrap();
+            assert_eq!(loaded, expected, "round-trip mismatch for {:?}", format);
+        }
+    }
+
     #[test]
     fn test_environment_variable_loading() {
         // Set test environment variables
@@ -664,6 +996,90 @@ url = "postgresql://localhost/test"
         assert_eq!(config.database.database_type, "postgresql");
     }
 
+    #[tokio::test]
+    async fn test_watch_reloads_on_file_change() {
+        let dir = tempdir().unwrap();
+        let config_path = dir.path().join("server.toml");
+        fs::write(&config_path, "[server]\nhost = \"127.0.0.1\"\nport = 8080\n").unwrap();
+
+        let loader = ConfigLoader::new().with_search_paths(vec![dir.path().to_path_buf()]);
+        let (mut rx, _handle) = loader.watch().unwrap();
+        assert_eq!(rx.borrow().server.port, 8080);
+
+        fs::write(&config_path, "[server]\nhost = \"127.0.0.1\"\nport = 9090\n").unwrap();
+
+        tokio::time::timeout(std::time::Duration::from_secs(5), rx.changed())
+            .await
+            .expect("timed out waiting for reload")
+            .unwrap();
+        assert_eq!(rx.borrow().server.port, 9090);
+    }
+
+    #[test]
+    fn test_profile_section_overrides_default() {
+        let dir = tempdir().unwrap();
+        let config_path = dir.path().join("test.toml");
+
+        let test_config = r#"
+[default.server]
+host = "127.0.0.1"
+port = 8080
+
+[development.server]
+host = "0.0.0.0"
+"#;
+        fs::write(&config_path, test_config).unwrap();
+
+        let loader = ConfigLoader::new().with_profile("development");
+        let config = loader.load_from_file(&config_path).unwrap();
+
+        // Overridden by the profile section.
+        assert_eq!(config.server.host, "0.0.0.0");
+        // Left untouched, inherited from [default].
+        assert_eq!(config.server.port, 8080);
+    }
+
+    #[test]
+    fn test_inactive_profile_section_is_ignored() {
+        let dir = tempdir().unwrap();
+        let config_path = dir.path().join("test.toml");
+
+        let test_config = r#"
+[default.server]
+host = "127.0.0.1"
+
+[production.server]
+host = "10.0.0.1"
+"#;
+        fs::write(&config_path, test_config).unwrap();
+
+        let loader = ConfigLoader::new().with_profile("development");
+        let config = loader.load_from_file(&config_path).unwrap();
+
+        assert_eq!(config.server.host, "127.0.0.1");
+    }
+
+    #[test]
+    fn test_profile_without_default_section_is_a_no_op() {
+        let dir = tempdir().unwrap();
+        let config_path = dir.path().join("test.toml");
+
+        let test_config = r#"
+[server]
+host = "0.0.0.0"
+port = 9000
+"#;
+        fs::write(&config_path, test_config).unwrap();
+
+        // No [default] section, so this is treated as a flat, profile-less
+        // file exactly as before profiles were introduced.
+        let loader = ConfigLoader::new().with_profile("production");
+        let config = loader.load_from_file(&config_path).unwrap();
+
+        assert_eq!(config.server.host, "0.0.0.0");
+        assert_eq!(config.server.port, 9000);
+    }
+
     #[test]
     fn test_nonexistent_file() {
         let loader = ConfigLoader::new();
@@ -676,4 +1092,29 @@ url = "postgresql://localhost/test"
             panic!("Expected FileNotFound error");
         }
     }
+
+    #[test]
+    fn test_oversized_config_file_is_rejected() {
+        let dir = tempdir().unwrap();
+        let config_path = dir.path().join("huge.toml");
+        fs::write(&config_path, "[server]\nhost = \"127.0.0.1\"\n").unwrap();
+
+        let loader = ConfigLoader::new().with_max_config_size_bytes(4);
+        let result = loader.load_from_file(&config_path);
+
+        assert!(matches!(result, Err(ConfigError::TooLarge { limit: 4, .. })));
+    }
+
+    #[test]
+    fn test_allow_large_config_bypasses_the_guard() {
+        let dir = tempdir().unwrap();
+        let config_path = dir.path().join("huge.toml");
+        fs::write(&config_path, "[server]\nhost = \"127.0.0.1\"\n").unwrap();
+
+        let loader = ConfigLoader::new()
+            .with_max_config_size_bytes(4)
+            .allow_large_config(true);
+
+        assert!(loader.load_from_file(&config_path).is_ok());
+    }
 }
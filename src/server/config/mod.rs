@@ -44,6 +44,33 @@ pub struct ServerConfig {
     pub admin: AdminConfig,
 }
 
+impl ServerConfig {
+    /// Look up a named byte/rate limit: first `self.limits.custom`, then a
+    /// built-in default for well-known names (e.g. `"file_upload"`), so
+    /// handlers can call this for a cap that has no dedicated
+    /// [`LimitsConfig`] field yet without operators needing to configure
+    /// anything. Returns `None` for a name that is neither configured nor
+    /// built in.
+    pub fn limit(&self, name: &str) -> Option<u64> {
+        self.limits
+            .custom
+            .get(name)
+            .copied()
+            .or_else(|| builtin_limit_default(name))
+    }
+}
+
+/// Built-in defaults for named limits that aren't set in
+/// [`LimitsConfig::custom`], consulted by [`ServerConfig::limit`].
+fn builtin_limit_default(name: &str) -> Option<u64> {
+    match name {
+        "file_upload" => Some(25 * 1024 * 1024), // 25 MB
+        "avatar" => Some(2 * 1024 * 1024),        // 2 MB
+        "json_body" => Some(1024 * 1024),         // 1 MB
+        _ => None,
+    }
+}
+
 /// Network and connection configuration
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct NetworkConfig {
@@ -268,6 +295,15 @@ pub struct LimitsConfig {
 
     /// CPU usage limit percentage
     pub cpu_limit: f32,
+
+    /// Arbitrary named byte/rate limits beyond the fixed fields above, e.g.
+    /// `file_upload`, `avatar`, `json_body`. Adding a new limit for a
+    /// feature-specific cap needs only an entry here (or a
+    /// `LAIR_CHAT_LIMITS_CUSTOM_<NAME>` environment variable) rather than a
+    /// new field and matching env-var plumbing; look it up with
+    /// [`ServerConfig::limit`].
+    #[serde(default)]
+    pub custom: HashMap<String, u64>,
 }
 
 /// Administrative configuration
@@ -355,6 +391,13 @@ pub enum ConfigError {
 
     #[error("Configuration conflict: {message}")]
     Conflict { message: String },
+
+    #[error("Configuration file {path} is {size} bytes, exceeding the {limit} byte limit; use ConfigLoader::allow_large_config(true) or set LAIR_CHAT_ALLOW_LARGE_CONFIG=1 if this is expected")]
+    TooLarge {
+        path: String,
+        size: u64,
+        limit: u64,
+    },
 }
 
 /// Configuration source priority
@@ -454,6 +497,66 @@ fn merge_configs(_base: ServerConfig, overlay: ServerConfig) -> Result<ServerCon
     Ok(overlay)
 }
 
+/// Which top-level sections changed between two configurations, and whether
+/// any of those changes can only take effect after a restart.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ConfigDiff {
+    /// Top-level `ServerConfig` field names that differ between the two
+    /// configurations (e.g. `"server"`, `"limits"`).
+    pub changed_fields: Vec<String>,
+    /// Whether any changed field binds a socket or loads TLS material, so a
+    /// running server can't apply it without restarting.
+    pub requires_restart: bool,
+}
+
+impl ConfigDiff {
+    /// Whether anything changed at all.
+    pub fn is_empty(&self) -> bool {
+        self.changed_fields.is_empty()
+    }
+}
+
+/// Diff two configurations by section, flagging sections whose changes
+/// require a restart (bind address/port, TLS material, admin API binding).
+pub fn diff_config(old: &ServerConfig, new: &ServerConfig) -> ConfigDiff {
+    let mut diff = ConfigDiff::default();
+
+    if old.server != new.server {
+        diff.changed_fields.push("server".to_string());
+        if old.server.host != new.server.host
+            || old.server.port != new.server.port
+            || old.server.enable_tls != new.server.enable_tls
+            || old.server.tls_cert_path != new.server.tls_cert_path
+            || old.server.tls_key_path != new.server.tls_key_path
+        {
+            diff.requires_restart = true;
+        }
+    }
+    if old.database != new.database {
+        diff.changed_fields.push("database".to_string());
+    }
+    if old.security != new.security {
+        diff.changed_fields.push("security".to_string());
+    }
+    if old.logging != new.logging {
+        diff.changed_fields.push("logging".to_string());
+    }
+    if old.features != new.features {
+        diff.changed_fields.push("features".to_string());
+    }
+    if old.limits != new.limits {
+        diff.changed_fields.push("limits".to_string());
+    }
+    if old.admin != new.admin {
+        diff.changed_fields.push("admin".to_string());
+        if old.admin.admin_host != new.admin.admin_host || old.admin.admin_port != new.admin.admin_port {
+            diff.requires_restart = true;
+        }
+    }
+
+    diff
+}
+
 /// Load configuration from a TOML file
 fn load_from_file(path: &Path) -> Result<ServerConfig, ConfigError> {
     let contents =
@@ -607,6 +710,33 @@ max_connections = 20
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_diff_config_reports_changed_sections() {
+        let old = ServerConfig::default();
+        let mut new = ServerConfig::default();
+        new.limits.messages_per_minute += 10;
+        new.server.port += 1;
+
+        let diff = diff_config(&old, &new);
+
+        assert!(diff.changed_fields.contains(&"limits".to_string()));
+        assert!(diff.changed_fields.contains(&"server".to_string()));
+        assert!(!diff.changed_fields.contains(&"database".to_string()));
+        assert!(diff.requires_restart);
+    }
+
+    #[test]
+    fn test_diff_config_no_restart_for_live_applicable_changes() {
+        let old = ServerConfig::default();
+        let mut new = ServerConfig::default();
+        new.logging.level = "debug".to_string();
+
+        let diff = diff_config(&old, &new);
+
+        assert_eq!(diff.changed_fields, vec!["logging".to_string()]);
+        assert!(!diff.requires_restart);
+    }
+
     #[test]
     fn test_config_serialization() {
         let config = ServerConfig::default();
@@ -614,4 +744,19 @@ max_connections = 20
         let parsed_config: ServerConfig = toml::from_str(&toml_str).unwrap();
         assert_eq!(config, parsed_config);
     }
+
+    #[test]
+    fn test_named_limit_prefers_configured_over_builtin_default() {
+        let mut config = ServerConfig::default();
+        assert_eq!(config.limit("file_upload"), Some(25 * 1024 * 1024));
+
+        config.limits.custom.insert("file_upload".to_string(), 1024);
+        assert_eq!(config.limit("file_upload"), Some(1024));
+    }
+
+    #[test]
+    fn test_named_limit_unknown_name_is_none() {
+        let config = ServerConfig::default();
+        assert_eq!(config.limit("does_not_exist"), None);
+    }
 }
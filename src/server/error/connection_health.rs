@@ -0,0 +1,249 @@
+//! Heartbeat tracking and reconnect-strategy recovery for individual client
+//! connections.
+//!
+//! [`ErrorHandler`](super::ErrorHandler) keeps one [`ConnectionHealth`] per
+//! logical client id (the same id preserved across a reconnect). Callers
+//! periodically ask whether a heartbeat is due via
+//! [`ConnectionHealth::should_send_heartbeat`] and send the frame returned by
+//! [`ConnectionHealth::heartbeat_frame`]; when too many heartbeats go
+//! unanswered, or a [`RecoveryAction::Disconnect`](super::RecoveryAction::Disconnect)
+//! or [`RecoveryAction::Authenticate`](super::RecoveryAction::Authenticate)
+//! fires, [`ErrorHandler::begin_reconnect`](super::ErrorHandler::begin_reconnect)
+//! drives the connection through its configured [`ReconnectStrategy`]
+//! instead of the caller silently dropping the socket.
+
+use std::time::{Duration, Instant};
+
+use crate::common::protocol::ProtocolMessage;
+use crate::server::storage::current_timestamp;
+
+/// Strategy governing how long to wait between reconnect attempts after a
+/// connection is judged dead.
+#[derive(Debug, Clone)]
+pub enum ReconnectStrategy {
+    /// Always wait the same interval between attempts, retrying forever.
+    FixedInterval(Duration),
+    /// Double the delay after every failed attempt (capped at `max`), giving
+    /// up once `max_retries` attempts have been made.
+    ExponentialBackoff {
+        base: Duration,
+        max: Duration,
+        max_retries: u32,
+    },
+    /// Retry at a fixed `interval` until `timeout` has elapsed since the
+    /// connection started reconnecting, then give up.
+    FailAfter { interval: Duration, timeout: Duration },
+}
+
+/// Lifecycle state of a tracked connection.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConnectionState {
+    /// Heartbeats are arriving on schedule.
+    Connected,
+    /// The connection was judged dead and is being re-established.
+    Reconnecting,
+    /// The configured [`ReconnectStrategy`] gave up.
+    Failed,
+}
+
+/// Heartbeat and reconnect bookkeeping for a single client connection,
+/// keyed by the client's logical id so identity survives a reconnect.
+#[derive(Debug, Clone)]
+pub struct ConnectionHealth {
+    logical_id: String,
+    heartbeat_interval: Duration,
+    missed_heartbeat_limit: u32,
+    reconnect_strategy: ReconnectStrategy,
+    last_heartbeat: Instant,
+    missed_heartbeats: u32,
+    state: ConnectionState,
+    reconnect_attempts: u32,
+    reconnecting_since: Option<Instant>,
+}
+
+impl ConnectionHealth {
+    /// Track a connection that should be considered dead after
+    /// `missed_heartbeat_limit` consecutive missed heartbeats spaced
+    /// `heartbeat_interval` apart, reconnecting per `reconnect_strategy`.
+    pub fn new(
+        logical_id: impl Into<String>,
+        heartbeat_interval: Duration,
+        missed_heartbeat_limit: u32,
+        reconnect_strategy: ReconnectStrategy,
+    ) -> Self {
+        Self {
+            logical_id: logical_id.into(),
+            heartbeat_interval,
+            missed_heartbeat_limit: missed_heartbeat_limit.max(1),
+            reconnect_strategy,
+            last_heartbeat: Instant::now(),
+            missed_heartbeats: 0,
+            state: ConnectionState::Connected,
+            reconnect_attempts: 0,
+            reconnecting_since: None,
+        }
+    }
+
+    /// The logical client id this connection is tracked under.
+    pub fn logical_id(&self) -> &str {
+        &self.logical_id
+    }
+
+    /// Current lifecycle state.
+    pub fn state(&self) -> ConnectionState {
+        self.state
+    }
+
+    /// Number of reconnect attempts made during the current reconnect cycle.
+    pub fn reconnect_attempts(&self) -> u32 {
+        self.reconnect_attempts
+    }
+
+    /// Whether a heartbeat frame is due to be sent, given the current time.
+    pub fn should_send_heartbeat(&self, now: Instant) -> bool {
+        now.duration_since(self.last_heartbeat) >= self.heartbeat_interval
+    }
+
+    /// The frame to send when [`should_send_heartbeat`](Self::should_send_heartbeat)
+    /// is true.
+    pub fn heartbeat_frame(&self) -> ProtocolMessage {
+        ProtocolMessage::Ping {
+            timestamp: current_timestamp(),
+        }
+    }
+
+    /// Record that a heartbeat (pong) arrived. Clears the missed-heartbeat
+    /// count and, if the connection was reconnecting, marks it recovered.
+    pub fn record_heartbeat(&mut self) {
+        self.last_heartbeat = Instant::now();
+        self.missed_heartbeats = 0;
+        self.reconnect_attempts = 0;
+        self.reconnecting_since = None;
+        if self.state != ConnectionState::Failed {
+            self.state = ConnectionState::Connected;
+        }
+    }
+
+    /// Record that an expected heartbeat did not arrive in time. Returns
+    /// `true` once the number of consecutive misses reaches the configured
+    /// limit, meaning the connection should be torn down and reconnected.
+    pub fn record_missed_heartbeat(&mut self) -> bool {
+        self.missed_heartbeats += 1;
+        self.missed_heartbeats >= self.missed_heartbeat_limit
+    }
+
+    /// Begin (or continue) a reconnect cycle, returning the delay to wait
+    /// before the next attempt, or `None` once the configured
+    /// [`ReconnectStrategy`] has given up (the connection is then
+    /// [`ConnectionState::Failed`]).
+    pub fn next_reconnect_delay(&mut self) -> Option<Duration> {
+        if self.state != ConnectionState::Reconnecting {
+            self.state = ConnectionState::Reconnecting;
+            self.reconnecting_since = Some(Instant::now());
+            self.reconnect_attempts = 0;
+        }
+        self.reconnect_attempts += 1;
+
+        match &self.reconnect_strategy {
+            ReconnectStrategy::FixedInterval(interval) => Some(*interval),
+            ReconnectStrategy::ExponentialBackoff {
+                base,
+                max,
+                max_retries,
+            } => {
+                if self.reconnect_attempts > *max_retries {
+                    self.state = ConnectionState::Failed;
+                    return None;
+                }
+                let multiplier = 2_u32.saturating_pow(self.reconnect_attempts.saturating_sub(1));
+                let delay = base.checked_mul(multiplier).unwrap_or(*max);
+                Some(delay.min(*max))
+            }
+            ReconnectStrategy::FailAfter { interval, timeout } => {
+                let elapsed = self
+                    .reconnecting_since
+                    .map(|since| since.elapsed())
+                    .unwrap_or_default();
+                if elapsed >= *timeout {
+                    self.state = ConnectionState::Failed;
+                    return None;
+                }
+                Some(*interval)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_missed_heartbeats_trip_at_limit() {
+        let mut health = ConnectionHealth::new(
+            "user-1",
+            Duration::from_millis(10),
+            3,
+            ReconnectStrategy::FixedInterval(Duration::from_millis(50)),
+        );
+
+        assert!(!health.record_missed_heartbeat());
+        assert!(!health.record_missed_heartbeat());
+        assert!(health.record_missed_heartbeat());
+    }
+
+    #[test]
+    fn test_record_heartbeat_clears_missed_count_and_reconnect_state() {
+        let mut health = ConnectionHealth::new(
+            "user-1",
+            Duration::from_millis(10),
+            2,
+            ReconnectStrategy::FixedInterval(Duration::from_millis(50)),
+        );
+
+        health.record_missed_heartbeat();
+        health.next_reconnect_delay();
+        assert_eq!(health.state(), ConnectionState::Reconnecting);
+
+        health.record_heartbeat();
+        assert_eq!(health.state(), ConnectionState::Connected);
+        assert_eq!(health.reconnect_attempts(), 0);
+    }
+
+    #[test]
+    fn test_exponential_backoff_gives_up_after_max_retries() {
+        let mut health = ConnectionHealth::new(
+            "user-1",
+            Duration::from_millis(10),
+            1,
+            ReconnectStrategy::ExponentialBackoff {
+                base: Duration::from_millis(10),
+                max: Duration::from_millis(100),
+                max_retries: 2,
+            },
+        );
+
+        assert_eq!(health.next_reconnect_delay(), Some(Duration::from_millis(10)));
+        assert_eq!(health.next_reconnect_delay(), Some(Duration::from_millis(20)));
+        assert_eq!(health.next_reconnect_delay(), None);
+        assert_eq!(health.state(), ConnectionState::Failed);
+    }
+
+    #[test]
+    fn test_fail_after_gives_up_once_timeout_elapses() {
+        let mut health = ConnectionHealth::new(
+            "user-1",
+            Duration::from_millis(10),
+            1,
+            ReconnectStrategy::FailAfter {
+                interval: Duration::from_millis(5),
+                timeout: Duration::from_millis(15),
+            },
+        );
+
+        assert_eq!(health.next_reconnect_delay(), Some(Duration::from_millis(5)));
+        std::thread::sleep(Duration::from_millis(20));
+        assert_eq!(health.next_reconnect_delay(), None);
+        assert_eq!(health.state(), ConnectionState::Failed);
+    }
+}
@@ -0,0 +1,261 @@
+//! Deterministic fault injection for exercising the error/retry/circuit-breaker
+//! paths in tests and staging.
+//!
+//! [`ErrorHandler::execute_with_retry`](super::ErrorHandler::execute_with_retry) and
+//! [`ErrorHandler::execute_with_circuit_breaker`](super::ErrorHandler::execute_with_circuit_breaker)
+//! consult a [`FaultInjector`] before running the real operation. With no
+//! rules installed this is a complete no-op, so production code paths are
+//! unaffected unless a test or staging harness explicitly installs one.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::RwLock;
+use tokio::time::sleep;
+
+use super::types::TcpError;
+
+/// What a matching fault rule does instead of letting the operation run.
+#[derive(Debug, Clone)]
+pub enum FaultAction {
+    /// Let the operation run normally.
+    None,
+    /// Sleep for the given duration, then fail with a simulated timeout.
+    Timeout(Duration),
+    /// Fail immediately with the given error.
+    Error(TcpError),
+    /// Pretend the operation succeeded, discarding its real output.
+    Blackhole,
+}
+
+/// The call an installed [`FaultRule`] is being asked to judge.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FaultContext<'a> {
+    pub operation: &'a str,
+    pub user_id: Option<&'a str>,
+    pub room_id: Option<&'a str>,
+}
+
+/// Optional predicate narrowing which calls a fault rule applies to.
+///
+/// `None` fields match anything; a rule with every field `None` matches
+/// every call.
+#[derive(Debug, Clone, Default)]
+pub struct FaultMatch {
+    pub operation_prefix: Option<String>,
+    pub user_id: Option<String>,
+    pub room_id: Option<String>,
+}
+
+impl FaultMatch {
+    /// Match every call, regardless of operation, user, or room.
+    pub fn any() -> Self {
+        Self::default()
+    }
+
+    /// Match calls whose operation name starts with `prefix`.
+    pub fn operation_prefix(prefix: impl Into<String>) -> Self {
+        Self {
+            operation_prefix: Some(prefix.into()),
+            ..Self::default()
+        }
+    }
+
+    fn matches(&self, ctx: &FaultContext<'_>) -> bool {
+        if let Some(prefix) = &self.operation_prefix {
+            if !ctx.operation.starts_with(prefix.as_str()) {
+                return false;
+            }
+        }
+        if let Some(user_id) = &self.user_id {
+            if ctx.user_id != Some(user_id.as_str()) {
+                return false;
+            }
+        }
+        if let Some(room_id) = &self.room_id {
+            if ctx.room_id != Some(room_id.as_str()) {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+/// A configured fault, which only activates `delay` after it was installed.
+#[derive(Debug, Clone)]
+struct FaultRule {
+    matcher: FaultMatch,
+    action: FaultAction,
+    installed_at: Instant,
+    delay: Duration,
+}
+
+impl FaultRule {
+    fn is_active(&self) -> bool {
+        self.installed_at.elapsed() >= self.delay
+    }
+}
+
+/// Deterministic fault injection harness.
+///
+/// Installed rules are evaluated in order; the first active, matching rule
+/// wins. Cloning an injector shares the same rule set and counters (it wraps
+/// `Arc`s), so a single instance can be handed to an `ErrorHandler` and
+/// configured separately by a test.
+#[derive(Debug, Clone, Default)]
+pub struct FaultInjector {
+    rules: Arc<RwLock<Vec<FaultRule>>>,
+    injected_counts: Arc<RwLock<HashMap<String, u64>>>,
+}
+
+impl FaultInjector {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Install a fault rule that activates immediately.
+    pub async fn install(&self, matcher: FaultMatch, action: FaultAction) {
+        self.install_after(matcher, action, Duration::ZERO).await;
+    }
+
+    /// Install a fault rule that only starts injecting `delay` after this call.
+    pub async fn install_after(&self, matcher: FaultMatch, action: FaultAction, delay: Duration) {
+        let mut rules = self.rules.write().await;
+        rules.push(FaultRule {
+            matcher,
+            action,
+            installed_at: Instant::now(),
+            delay,
+        });
+    }
+
+    /// Remove every installed rule and reset the injected-fault counters.
+    pub async fn clear(&self) {
+        self.rules.write().await.clear();
+        self.injected_counts.write().await.clear();
+    }
+
+    /// How many faults have been injected so far, keyed by operation name.
+    pub async fn injected_counts(&self) -> HashMap<String, u64> {
+        self.injected_counts.read().await.clone()
+    }
+
+    async fn resolve(&self, ctx: FaultContext<'_>) -> FaultAction {
+        let rules = self.rules.read().await;
+        for rule in rules.iter() {
+            if rule.is_active() && rule.matcher.matches(&ctx) {
+                if !matches!(rule.action, FaultAction::None) {
+                    let mut counts = self.injected_counts.write().await;
+                    *counts.entry(ctx.operation.to_string()).or_insert(0) += 1;
+                }
+                return rule.action.clone();
+            }
+        }
+        FaultAction::None
+    }
+
+    /// Consult the injector for `ctx`. Returns `Some(result)` if a fault
+    /// fired in place of the real operation, `None` if it should run
+    /// normally. `blackhole_value` is returned as the faked success value
+    /// when the matched action is [`FaultAction::Blackhole`].
+    pub async fn maybe_inject<T>(
+        &self,
+        ctx: FaultContext<'_>,
+        blackhole_value: T,
+    ) -> Option<Result<T, TcpError>> {
+        match self.resolve(ctx).await {
+            FaultAction::None => None,
+            FaultAction::Timeout(duration) => {
+                sleep(duration).await;
+                Some(Err(TcpError::TimeoutError(
+                    "fault injector: simulated timeout".to_string(),
+                )))
+            }
+            FaultAction::Error(error) => Some(Err(error)),
+            FaultAction::Blackhole => Some(Ok(blackhole_value)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_no_rules_is_a_no_op() {
+        let injector = FaultInjector::new();
+        let ctx = FaultContext {
+            operation: "send_message",
+            user_id: None,
+            room_id: None,
+        };
+        assert!(injector.maybe_inject(ctx, "value").await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_error_rule_matches_operation_prefix() {
+        let injector = FaultInjector::new();
+        injector
+            .install(
+                FaultMatch::operation_prefix("send_"),
+                FaultAction::Error(TcpError::NetworkError("boom".to_string())),
+            )
+            .await;
+
+        let matching = FaultContext {
+            operation: "send_message",
+            user_id: None,
+            room_id: None,
+        };
+        let result = injector.maybe_inject(matching, "value").await;
+        assert!(matches!(result, Some(Err(TcpError::NetworkError(_)))));
+
+        let other = FaultContext {
+            operation: "delete_message",
+            user_id: None,
+            room_id: None,
+        };
+        assert!(injector.maybe_inject(other, "value").await.is_none());
+
+        let counts = injector.injected_counts().await;
+        assert_eq!(counts.get("send_message"), Some(&1));
+    }
+
+    #[tokio::test]
+    async fn test_blackhole_discards_real_output() {
+        let injector = FaultInjector::new();
+        injector
+            .install(FaultMatch::any(), FaultAction::Blackhole)
+            .await;
+
+        let ctx = FaultContext {
+            operation: "anything",
+            user_id: None,
+            room_id: None,
+        };
+        let result = injector.maybe_inject(ctx, "blackholed".to_string()).await;
+        assert_eq!(result.unwrap().unwrap(), "blackholed");
+    }
+
+    #[tokio::test]
+    async fn test_delayed_activation() {
+        let injector = FaultInjector::new();
+        injector
+            .install_after(
+                FaultMatch::any(),
+                FaultAction::Error(TcpError::SystemError("late".to_string())),
+                Duration::from_millis(30),
+            )
+            .await;
+
+        let ctx = FaultContext {
+            operation: "op",
+            user_id: None,
+            room_id: None,
+        };
+        assert!(injector.maybe_inject(ctx, "value").await.is_none());
+
+        tokio::time::sleep(Duration::from_millis(40)).await;
+        assert!(injector.maybe_inject(ctx, "value").await.is_some());
+    }
+}
@@ -0,0 +1,152 @@
+//! Renders [`ErrorStats`](super::ErrorStats) and per-operation circuit
+//! breaker state as OpenMetrics text exposition format.
+//!
+//! [`ErrorHandler::metrics_snapshot`](super::ErrorHandler::metrics_snapshot)
+//! is the entry point callers should use; [`render`] is split out as a pure
+//! function so the exposition format itself can be tested without spinning
+//! up an `ErrorHandler`.
+
+use super::{CircuitBreakerState, ErrorStats};
+
+/// Circuit breaker state and failure rate for a single tracked operation, as
+/// exported by [`render`].
+#[derive(Debug, Clone)]
+pub struct CircuitBreakerSnapshot {
+    pub operation: String,
+    pub state: CircuitBreakerState,
+    pub failure_rate: f64,
+}
+
+/// Escape a label value per the OpenMetrics text format (backslash, double
+/// quote, and newline must be escaped).
+fn escape_label_value(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+fn state_value(state: &CircuitBreakerState) -> u8 {
+    match state {
+        CircuitBreakerState::Closed => 0,
+        CircuitBreakerState::Open => 1,
+        CircuitBreakerState::HalfOpen => 2,
+    }
+}
+
+/// Render `stats` and `circuit_breakers` as OpenMetrics text exposition
+/// format. Output is sorted by label so it's stable across calls despite
+/// the underlying data living in hash maps.
+pub fn render(stats: &ErrorStats, circuit_breakers: &[CircuitBreakerSnapshot]) -> String {
+    let mut out = String::new();
+
+    out.push_str("# TYPE tcp_errors_total counter\n");
+    out.push_str("# HELP tcp_errors_total Total TCP errors handled, by error type and by severity.\n");
+    let mut by_type: Vec<_> = stats.errors_by_type.iter().collect();
+    by_type.sort_by_key(|(k, _)| k.clone());
+    for (error_type, count) in by_type {
+        out.push_str(&format!(
+            "tcp_errors_total{{type=\"{}\"}} {}\n",
+            escape_label_value(error_type),
+            count
+        ));
+    }
+    let mut by_severity: Vec<_> = stats.errors_by_severity.iter().collect();
+    by_severity.sort_by_key(|(k, _)| k.clone());
+    for (severity, count) in by_severity {
+        out.push_str(&format!(
+            "tcp_errors_total{{severity=\"{}\"}} {}\n",
+            escape_label_value(severity),
+            count
+        ));
+    }
+
+    out.push_str("# TYPE tcp_recovery_attempts_total counter\n");
+    out.push_str("# HELP tcp_recovery_attempts_total Total recovery attempts made after a handled error.\n");
+    out.push_str(&format!("tcp_recovery_attempts_total {}\n", stats.recovery_attempts));
+
+    out.push_str("# TYPE tcp_recovery_success_total counter\n");
+    out.push_str("# HELP tcp_recovery_success_total Recovery attempts that succeeded.\n");
+    out.push_str(&format!(
+        "tcp_recovery_success_total {}\n",
+        stats.successful_recoveries
+    ));
+
+    out.push_str("# TYPE tcp_circuit_breaker_state gauge\n");
+    out.push_str(
+        "# HELP tcp_circuit_breaker_state Circuit breaker state per operation (0=closed, 1=open, 2=half-open).\n",
+    );
+    let mut breakers: Vec<_> = circuit_breakers.iter().collect();
+    breakers.sort_by_key(|snapshot| snapshot.operation.clone());
+    for snapshot in &breakers {
+        out.push_str(&format!(
+            "tcp_circuit_breaker_state{{operation=\"{}\"}} {}\n",
+            escape_label_value(&snapshot.operation),
+            state_value(&snapshot.state)
+        ));
+    }
+
+    out.push_str("# TYPE tcp_circuit_breaker_failure_rate gauge\n");
+    out.push_str(
+        "# HELP tcp_circuit_breaker_failure_rate Fraction of requests in the current window that failed, per operation.\n",
+    );
+    for snapshot in &breakers {
+        out.push_str(&format!(
+            "tcp_circuit_breaker_failure_rate{{operation=\"{}\"}} {}\n",
+            escape_label_value(&snapshot.operation),
+            snapshot.failure_rate
+        ));
+    }
+
+    out.push_str("# EOF\n");
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_includes_error_and_recovery_counters() {
+        let mut stats = ErrorStats::default();
+        stats.errors_by_type.insert("NetworkError".to_string(), 3);
+        stats.errors_by_severity.insert("High".to_string(), 3);
+        stats.recovery_attempts = 2;
+        stats.successful_recoveries = 1;
+
+        let rendered = render(&stats, &[]);
+
+        assert!(rendered.contains("tcp_errors_total{type=\"NetworkError\"} 3\n"));
+        assert!(rendered.contains("tcp_errors_total{severity=\"High\"} 3\n"));
+        assert!(rendered.contains("tcp_recovery_attempts_total 2\n"));
+        assert!(rendered.contains("tcp_recovery_success_total 1\n"));
+        assert!(rendered.trim_end().ends_with("# EOF"));
+    }
+
+    #[test]
+    fn test_render_includes_circuit_breaker_gauges() {
+        let stats = ErrorStats::default();
+        let breakers = vec![CircuitBreakerSnapshot {
+            operation: "send_message".to_string(),
+            state: CircuitBreakerState::HalfOpen,
+            failure_rate: 0.5,
+        }];
+
+        let rendered = render(&stats, &breakers);
+
+        assert!(rendered.contains("tcp_circuit_breaker_state{operation=\"send_message\"} 2\n"));
+        assert!(rendered.contains("tcp_circuit_breaker_failure_rate{operation=\"send_message\"} 0.5\n"));
+    }
+
+    #[test]
+    fn test_render_escapes_label_values() {
+        let mut stats = ErrorStats::default();
+        stats
+            .errors_by_type
+            .insert("ValidationError(\"bad\\path\")".to_string(), 1);
+
+        let rendered = render(&stats, &[]);
+
+        assert!(rendered.contains("type=\"ValidationError(\\\"bad\\\\path\\\")\""));
+    }
+}
@@ -0,0 +1,1192 @@
+//! Error handling framework for the TCP server
+//!
+//! This module provides comprehensive error handling with structured error types,
+//! logging, metrics collection, and recovery mechanisms.
+
+use std::collections::{HashMap, VecDeque};
+use std::future::Future;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::RwLock;
+use tracing::{debug, error, info, warn, Level};
+
+pub mod connection_health;
+pub mod fault_injection;
+pub mod metrics;
+pub mod retry;
+pub mod types;
+pub use connection_health::*;
+pub use fault_injection::*;
+pub use metrics::*;
+pub use retry::*;
+pub use types::*;
+
+use crate::server::storage::current_timestamp;
+
+/// Result type for TCP operations
+pub type TcpResult<T> = Result<T, TcpError>;
+
+/// Default sliding window used to evaluate a circuit breaker's failure rate.
+const DEFAULT_CIRCUIT_BREAKER_WINDOW: Duration = Duration::from_secs(60);
+
+/// Error handler for managing errors across the TCP server
+pub struct ErrorHandler {
+    /// Error statistics
+    stats: Arc<RwLock<ErrorStats>>,
+    /// Recovery policies
+    recovery_policies: HashMap<String, RetryPolicy>,
+    /// Circuit breakers for error-prone operations
+    circuit_breakers: Arc<RwLock<HashMap<String, CircuitBreaker>>>,
+    retry_executor: RetryExecutor,
+    /// Shared budget bounding the ratio of retries to original requests
+    /// across every call to [`execute_with_retry`](Self::execute_with_retry).
+    retry_budget: Arc<RetryBudget>,
+    /// Sliding window newly created circuit breakers are given.
+    circuit_breaker_window: Duration,
+    /// Fault injection harness consulted before retried/circuit-broken
+    /// operations actually run. A no-op until a caller installs rules on it.
+    fault_injector: FaultInjector,
+    /// Heartbeat/reconnect state for tracked connections, keyed by logical
+    /// client id so identity survives a reconnect. Empty until a caller
+    /// registers a connection with [`register_connection`](Self::register_connection).
+    connections: Arc<RwLock<HashMap<String, ConnectionHealth>>>,
+}
+
+/// Error statistics for monitoring
+#[derive(Debug, Clone, Default)]
+pub struct ErrorStats {
+    pub total_errors: u64,
+    pub errors_by_type: HashMap<String, u64>,
+    pub errors_by_severity: HashMap<String, u64>,
+    pub recovery_attempts: u64,
+    pub successful_recoveries: u64,
+    pub failed_recoveries: u64,
+    pub last_error_time: Option<u64>,
+    /// Retries denied because the handler's [`RetryBudget`] was exhausted,
+    /// causing `execute_with_retry` to fail fast instead of retrying.
+    pub budget_denied_retries: u64,
+    /// Connections currently tracked by the heartbeat subsystem.
+    pub live_connections: u64,
+    /// Reconnect attempts made across all tracked connections.
+    pub reconnect_attempts: u64,
+    /// Reconnect attempts that ended with a heartbeat from the client.
+    pub successful_reconnects: u64,
+}
+
+/// A single success/failure observation inside a circuit breaker's window.
+#[derive(Debug, Clone, Copy)]
+struct WindowEvent {
+    at: Instant,
+    failed: bool,
+}
+
+/// Circuit breaker for preventing cascading failures.
+///
+/// Failures and successes are tracked as timestamped events inside a rolling
+/// `window` rather than a single monotonic counter, so a handful of failures
+/// spread across hours no longer wedges the breaker open forever. Expired
+/// events are evicted lazily whenever [`can_execute`](Self::can_execute),
+/// [`record_failure`](Self::record_failure), or
+/// [`record_success`](Self::record_success) is called.
+///
+/// While `HalfOpen`, at most `half_open_max_probes` callers are admitted at
+/// once; everyone else is rejected until a probe resolves. A probe failure
+/// reopens the breaker with an exponentially increasing timeout
+/// (`base_timeout * 2^consecutive_opens`, capped at `max_timeout`) instead of
+/// reusing the fixed timeout, which avoids a thundering herd re-opening a
+/// still-broken dependency every time the timeout expires.
+#[derive(Debug, Clone)]
+pub struct CircuitBreaker {
+    /// Current state of the circuit breaker
+    state: CircuitBreakerState,
+    /// Success/failure events observed within the current window
+    events: VecDeque<WindowEvent>,
+    /// How far back events are kept before being evicted
+    window: Duration,
+    /// Failure count within the window that trips the breaker
+    failure_threshold: u32,
+    /// Minimum number of requests in the window before the threshold applies
+    minimum_requests: u32,
+    /// Timeout applied on the very first open; later opens back off from this
+    base_timeout: Duration,
+    /// Upper bound the exponential backoff is capped at
+    max_timeout: Duration,
+    /// Timeout currently in effect (grows on repeated half-open failures)
+    timeout_duration: Duration,
+    /// Next attempt time (for half-open state)
+    next_attempt_time: Option<Instant>,
+    /// Opens since the breaker last fully closed, driving the backoff exponent
+    consecutive_opens: u32,
+    /// Maximum number of probe requests admitted concurrently while half-open
+    half_open_max_probes: u32,
+    /// Probes currently admitted and awaiting a result
+    half_open_probes_in_flight: u32,
+    /// Consecutive probe successes required to close the breaker
+    half_open_success_threshold: u32,
+    /// Consecutive probe successes observed so far while half-open
+    half_open_successes: u32,
+}
+
+/// Circuit breaker states
+#[derive(Debug, Clone, PartialEq)]
+pub enum CircuitBreakerState {
+    Closed,
+    Open,
+    HalfOpen,
+}
+
+/// Error response for TCP clients
+#[derive(Debug, Clone)]
+pub struct ErrorResponse {
+    pub error_code: String,
+    pub message: String,
+    pub details: Option<String>,
+    pub timestamp: u64,
+    pub request_id: Option<String>,
+    /// What the client should do about the connection this error occurred
+    /// on, beyond reading `message`.
+    pub action: ClientAction,
+}
+
+/// Instruction to the client about what to do with its connection,
+/// attached to an [`ErrorResponse`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum ClientAction {
+    /// No connection-level action is needed.
+    None,
+    /// The connection is being (or should be) torn down and
+    /// re-established; wait `retry_after` before attempting it.
+    Reconnect { retry_after: Duration },
+}
+
+impl ErrorHandler {
+    /// Create a new error handler
+    pub fn new() -> Self {
+        Self {
+            stats: Arc::new(RwLock::new(ErrorStats::default())),
+            recovery_policies: HashMap::new(),
+            circuit_breakers: Arc::new(RwLock::new(HashMap::new())),
+            retry_executor: RetryExecutor::new(),
+            retry_budget: Arc::new(RetryBudget::default()),
+            circuit_breaker_window: DEFAULT_CIRCUIT_BREAKER_WINDOW,
+            fault_injector: FaultInjector::new(),
+            connections: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+
+    /// Create an error handler whose circuit breakers default to `window`
+    /// instead of [`DEFAULT_CIRCUIT_BREAKER_WINDOW`].
+    pub fn with_circuit_breaker_window(window: Duration) -> Self {
+        Self {
+            circuit_breaker_window: window,
+            ..Self::new()
+        }
+    }
+
+    /// Create an error handler whose retries are bounded by a
+    /// [`RetryBudget`] allowing retries only while `retries / requests <
+    /// ratio`, instead of the default 10%.
+    pub fn with_retry_budget_ratio(ratio: f64) -> Self {
+        Self {
+            retry_budget: Arc::new(RetryBudget::new(ratio)),
+            ..Self::new()
+        }
+    }
+
+    /// The fault injection harness this handler consults before running
+    /// retried or circuit-broken operations. Install rules on it to drive
+    /// resilience behavior deterministically in tests or staging.
+    pub fn fault_injector(&self) -> &FaultInjector {
+        &self.fault_injector
+    }
+
+    /// The retry budget shared across every call to
+    /// [`execute_with_retry`](Self::execute_with_retry) on this handler.
+    pub fn retry_budget(&self) -> &RetryBudget {
+        &self.retry_budget
+    }
+
+    /// Start tracking heartbeats for a connection under `logical_id`,
+    /// reconnecting per `reconnect_strategy` once `missed_heartbeat_limit`
+    /// consecutive heartbeats spaced `heartbeat_interval` apart go unanswered.
+    pub async fn register_connection(
+        &self,
+        logical_id: impl Into<String>,
+        heartbeat_interval: Duration,
+        missed_heartbeat_limit: u32,
+        reconnect_strategy: ReconnectStrategy,
+    ) {
+        let logical_id = logical_id.into();
+        let health = ConnectionHealth::new(
+            logical_id.clone(),
+            heartbeat_interval,
+            missed_heartbeat_limit,
+            reconnect_strategy,
+        );
+        self.connections.write().await.insert(logical_id, health);
+
+        let mut stats = self.stats.write().await;
+        stats.live_connections += 1;
+    }
+
+    /// Stop tracking a connection (e.g. on a clean client-initiated logout).
+    pub async fn deregister_connection(&self, logical_id: &str) {
+        if self.connections.write().await.remove(logical_id).is_some() {
+            let mut stats = self.stats.write().await;
+            stats.live_connections = stats.live_connections.saturating_sub(1);
+        }
+    }
+
+    /// Current lifecycle state of a tracked connection, if any.
+    pub async fn connection_state(&self, logical_id: &str) -> Option<ConnectionState> {
+        self.connections
+            .read()
+            .await
+            .get(logical_id)
+            .map(|health| health.state())
+    }
+
+    /// Record that a heartbeat (pong) arrived from `logical_id`, clearing
+    /// its missed-heartbeat count and, if it was mid-reconnect, marking the
+    /// reconnect successful.
+    pub async fn record_connection_heartbeat(&self, logical_id: &str) {
+        let was_reconnecting = {
+            let mut connections = self.connections.write().await;
+            match connections.get_mut(logical_id) {
+                Some(health) => {
+                    let was = health.state() == ConnectionState::Reconnecting;
+                    health.record_heartbeat();
+                    was
+                }
+                None => return,
+            }
+        };
+
+        if was_reconnecting {
+            let mut stats = self.stats.write().await;
+            stats.successful_reconnects += 1;
+        }
+    }
+
+    /// Check whether `logical_id` is overdue for a heartbeat and, if a
+    /// heartbeat is due but has been missed past the configured limit,
+    /// begin a reconnect. Returns the delay the caller should wait before
+    /// its next reconnect attempt, if one was started.
+    pub async fn check_connection_liveness(&self, logical_id: &str) -> Option<Duration> {
+        let missed_past_limit = {
+            let mut connections = self.connections.write().await;
+            let health = connections.get_mut(logical_id)?;
+            if !health.should_send_heartbeat(Instant::now()) {
+                return None;
+            }
+            health.record_missed_heartbeat()
+        };
+
+        if missed_past_limit {
+            self.begin_reconnect(logical_id).await
+        } else {
+            None
+        }
+    }
+
+    /// Begin (or continue) a reconnect cycle for `logical_id`, driven by a
+    /// missed-heartbeat timeout or a `Disconnect`/`Authenticate` recovery
+    /// action. Returns the delay before the next attempt, or `None` if
+    /// `logical_id` isn't tracked or its `ReconnectStrategy` has given up.
+    pub async fn begin_reconnect(&self, logical_id: &str) -> Option<Duration> {
+        let delay = {
+            let mut connections = self.connections.write().await;
+            let health = connections.get_mut(logical_id)?;
+            health.next_reconnect_delay()
+        };
+
+        let mut stats = self.stats.write().await;
+        stats.reconnect_attempts += 1;
+        delay
+    }
+
+    /// Handle an error with logging, metrics, and recovery
+    pub async fn handle_error(
+        &self,
+        error: TcpError,
+        context: Option<ErrorContext>,
+    ) -> ErrorResponse {
+        // Log the error
+        self.log_error(&error, context.as_ref()).await;
+
+        // Update statistics
+        self.update_stats(&error).await;
+
+        // Attempt recovery if applicable
+        let mut reconnect_after = None;
+        if let Some(recovery_action) = error.recovery_action() {
+            reconnect_after = self
+                .attempt_recovery(&error, &recovery_action, context.as_ref())
+                .await;
+        }
+
+        // Create response
+        self.create_error_response(&error, context, reconnect_after)
+    }
+
+    /// Log error with appropriate level and context
+    async fn log_error(&self, error: &TcpError, context: Option<&ErrorContext>) {
+        let level = error.log_level();
+        let error_code = error.error_code();
+        let message = error.user_message();
+
+        match level {
+            Level::ERROR => {
+                error!(
+                    error_code = error_code,
+                    error_type = format!("{:?}", error),
+                    severity = format!("{:?}", error.severity()),
+                    context = format!("{:?}", context),
+                    "TCP Error: {}",
+                    message
+                );
+            }
+            Level::WARN => {
+                warn!(
+                    error_code = error_code,
+                    error_type = format!("{:?}", error),
+                    severity = format!("{:?}", error.severity()),
+                    context = format!("{:?}", context),
+                    "TCP Warning: {}",
+                    message
+                );
+            }
+            Level::INFO => {
+                info!(
+                    error_code = error_code,
+                    error_type = format!("{:?}", error),
+                    severity = format!("{:?}", error.severity()),
+                    context = format!("{:?}", context),
+                    "TCP Info: {}",
+                    message
+                );
+            }
+            Level::DEBUG => {
+                debug!(
+                    error_code = error_code,
+                    error_type = format!("{:?}", error),
+                    severity = format!("{:?}", error.severity()),
+                    context = format!("{:?}", context),
+                    "TCP Debug: {}",
+                    message
+                );
+            }
+            _ => {
+                info!(
+                    error_code = error_code,
+                    error_type = format!("{:?}", error),
+                    severity = format!("{:?}", error.severity()),
+                    context = format!("{:?}", context),
+                    "TCP: {}",
+                    message
+                );
+            }
+        }
+    }
+
+    /// Update error statistics
+    async fn update_stats(&self, error: &TcpError) {
+        let mut stats = self.stats.write().await;
+        stats.total_errors += 1;
+
+        // Keyed by variant name only (e.g. "AuthenticationFailed"), not payload.
+        let debug = format!("{:?}", error);
+        let error_type = debug
+            .split(|c: char| !c.is_alphanumeric() && c != '_')
+            .next()
+            .unwrap_or_default()
+            .to_string();
+        *stats.errors_by_type.entry(error_type).or_insert(0) += 1;
+
+        let severity = format!("{:?}", error.severity());
+        *stats.errors_by_severity.entry(severity).or_insert(0) += 1;
+
+        stats.last_error_time = Some(current_timestamp());
+    }
+
+    /// Attempt error recovery.
+    ///
+    /// Returns the delay the client should wait before reconnecting if this
+    /// error's recovery action requires tearing down the connection and
+    /// `context.user_id` names a connection registered with
+    /// [`register_connection`](Self::register_connection); `None` if no
+    /// reconnect is needed (or the connection isn't tracked).
+    async fn attempt_recovery(
+        &self,
+        error: &TcpError,
+        recovery_action: &RecoveryAction,
+        context: Option<&ErrorContext>,
+    ) -> Option<Duration> {
+        let mut stats = self.stats.write().await;
+        stats.recovery_attempts += 1;
+        drop(stats);
+
+        match recovery_action {
+            RecoveryAction::Retry(policy) => {
+                debug!(
+                    error_code = error.error_code(),
+                    max_retries = policy.max_retries,
+                    backoff_strategy = format!("{:?}", policy.backoff_strategy),
+                    "Attempting error recovery with retry policy"
+                );
+                None
+            }
+            RecoveryAction::Fallback(fallback_msg) => {
+                info!(
+                    error_code = error.error_code(),
+                    fallback = fallback_msg,
+                    "Using fallback recovery strategy"
+                );
+                None
+            }
+            RecoveryAction::Disconnect => {
+                warn!(
+                    error_code = error.error_code(),
+                    "Recovery action requires client disconnection"
+                );
+                self.begin_reconnect_for_context(context).await
+            }
+            RecoveryAction::RateLimitDelay(duration) => {
+                info!(
+                    error_code = error.error_code(),
+                    delay_ms = duration.as_millis(),
+                    "Rate limit recovery delay applied"
+                );
+                None
+            }
+            RecoveryAction::Authenticate => {
+                info!(
+                    error_code = error.error_code(),
+                    "Recovery requires re-authentication"
+                );
+                self.begin_reconnect_for_context(context).await
+            }
+            _ => {
+                debug!(
+                    error_code = error.error_code(),
+                    recovery_action = format!("{:?}", recovery_action),
+                    "Recovery action logged"
+                );
+                None
+            }
+        }
+    }
+
+    /// Start a reconnect cycle for the connection named by `context.user_id`,
+    /// if any and if it's tracked.
+    async fn begin_reconnect_for_context(&self, context: Option<&ErrorContext>) -> Option<Duration> {
+        let logical_id = context?.user_id.as_deref()?;
+        self.begin_reconnect(logical_id).await
+    }
+
+    /// Create error response for client
+    fn create_error_response(
+        &self,
+        error: &TcpError,
+        context: Option<ErrorContext>,
+        reconnect_after: Option<Duration>,
+    ) -> ErrorResponse {
+        ErrorResponse {
+            error_code: error.error_code().to_string(),
+            message: error.user_message(),
+            details: None,
+            timestamp: current_timestamp(),
+            request_id: context.and_then(|ctx| ctx.additional_info.get("request_id").cloned()),
+            action: match reconnect_after {
+                Some(retry_after) => ClientAction::Reconnect { retry_after },
+                None => ClientAction::None,
+            },
+        }
+    }
+
+    /// Get error statistics
+    pub async fn get_stats(&self) -> ErrorStats {
+        self.stats.read().await.clone()
+    }
+
+    /// Render current error statistics and per-operation circuit breaker
+    /// state as OpenMetrics text exposition format, suitable for a scrape
+    /// endpoint.
+    pub async fn metrics_snapshot(&self) -> String {
+        let stats = self.get_stats().await;
+
+        let mut circuit_breakers = Vec::new();
+        let mut breakers = self.circuit_breakers.write().await;
+        for (operation, breaker) in breakers.iter_mut() {
+            circuit_breakers.push(CircuitBreakerSnapshot {
+                operation: operation.clone(),
+                state: breaker.state().clone(),
+                failure_rate: breaker.failure_rate(),
+            });
+        }
+        drop(breakers);
+
+        metrics::render(&stats, &circuit_breakers)
+    }
+
+    /// Reset error statistics
+    pub async fn reset_stats(&self) {
+        let mut stats = self.stats.write().await;
+        *stats = ErrorStats::default();
+    }
+
+    /// Get or create the circuit breaker for an operation.
+    ///
+    /// Newly created breakers default to `self.circuit_breaker_window` so a
+    /// handler constructed with [`with_circuit_breaker_window`](Self::with_circuit_breaker_window)
+    /// applies that window to every operation it tracks.
+    pub async fn get_circuit_breaker(&self, operation: &str) -> CircuitBreaker {
+        let circuit_breakers = self.circuit_breakers.read().await;
+        circuit_breakers.get(operation).cloned().unwrap_or_else(|| {
+            // Default: 5 failures, 30s open timeout, handler's default window
+            CircuitBreaker::with_window(5, Duration::from_secs(30), self.circuit_breaker_window)
+        })
+    }
+
+    /// Update circuit breaker state
+    pub async fn update_circuit_breaker(&self, operation: &str, circuit_breaker: CircuitBreaker) {
+        let mut circuit_breakers = self.circuit_breakers.write().await;
+        circuit_breakers.insert(operation.to_string(), circuit_breaker);
+    }
+
+    /// Check if operation should be executed based on circuit breaker state
+    pub async fn should_execute_operation(&self, operation: &str) -> bool {
+        let mut circuit_breaker = self.get_circuit_breaker(operation).await;
+        let allowed = circuit_breaker.can_execute();
+        self.update_circuit_breaker(operation, circuit_breaker).await;
+        allowed
+    }
+
+    /// Record operation success for circuit breaker
+    pub async fn record_success(&self, operation: &str) {
+        let mut circuit_breaker = self.get_circuit_breaker(operation).await;
+        circuit_breaker.record_success();
+        self.update_circuit_breaker(operation, circuit_breaker)
+            .await;
+    }
+
+    /// Record operation failure for circuit breaker
+    pub async fn record_failure(&self, operation: &str) {
+        let mut circuit_breaker = self.get_circuit_breaker(operation).await;
+        circuit_breaker.record_failure();
+        self.update_circuit_breaker(operation, circuit_breaker)
+            .await;
+    }
+
+    /// Execute operation with retry logic
+    ///
+    /// `operation_name` identifies the call to the fault injector, which is
+    /// consulted before `operation` runs; if a fault fires, `operation`
+    /// itself is never invoked.
+    pub async fn execute_with_retry<F, Fut, T>(
+        &self,
+        operation_name: &str,
+        operation: F,
+    ) -> RetryResult<T>
+    where
+        F: Fn() -> Fut,
+        Fut: Future<Output = Result<T, TcpError>>,
+        T: Default,
+    {
+        let ctx = FaultContext {
+            operation: operation_name,
+            user_id: None,
+            room_id: None,
+        };
+        if let Some(result) = self.fault_injector.maybe_inject(ctx, T::default()).await {
+            return RetryResult {
+                result,
+                attempts: 0,
+                total_duration: Duration::from_secs(0),
+                max_retries_reached: false,
+                budget_exhausted: false,
+            };
+        }
+
+        let result = self
+            .retry_executor
+            .execute_with_budget(operation, &self.retry_budget)
+            .await;
+
+        if result.budget_exhausted {
+            let mut stats = self.stats.write().await;
+            stats.budget_denied_retries += 1;
+        }
+
+        result
+    }
+
+    /// Execute operation with custom retry executor
+    pub async fn execute_with_custom_retry<F, Fut, T>(
+        &self,
+        executor: &RetryExecutor,
+        operation: F,
+    ) -> RetryResult<T>
+    where
+        F: Fn() -> Fut,
+        Fut: Future<Output = Result<T, TcpError>>,
+    {
+        executor.execute(operation).await
+    }
+
+    /// Execute operation with circuit breaker and retry
+    pub async fn execute_with_circuit_breaker<F, Fut, T>(
+        &self,
+        operation_name: &str,
+        operation: F,
+    ) -> Result<T, TcpError>
+    where
+        F: Fn() -> Fut,
+        Fut: Future<Output = Result<T, TcpError>>,
+        T: Default,
+    {
+        // Check circuit breaker
+        if !self.should_execute_operation(operation_name).await {
+            return Err(TcpError::SystemError("Circuit breaker is open".to_string()));
+        }
+
+        let ctx = FaultContext {
+            operation: operation_name,
+            user_id: None,
+            room_id: None,
+        };
+        if let Some(result) = self.fault_injector.maybe_inject(ctx, T::default()).await {
+            match &result {
+                Ok(_) => self.record_success(operation_name).await,
+                Err(_) => self.record_failure(operation_name).await,
+            }
+            return result;
+        }
+
+        // Execute with retry
+        let result = self.retry_executor.execute(operation).await;
+
+        // Update circuit breaker based on result
+        match &result.result {
+            Ok(_) => {
+                self.record_success(operation_name).await;
+            }
+            Err(_) => {
+                self.record_failure(operation_name).await;
+            }
+        }
+
+        result.result
+    }
+}
+
+impl Default for ErrorHandler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CircuitBreaker {
+    /// Create a new circuit breaker using the default sliding window.
+    pub fn new(failure_threshold: u32, timeout_duration: Duration) -> Self {
+        Self::with_window(
+            failure_threshold,
+            timeout_duration,
+            DEFAULT_CIRCUIT_BREAKER_WINDOW,
+        )
+    }
+
+    /// Create a circuit breaker that counts failures within a rolling
+    /// `window` instead of since the breaker was last reset.
+    pub fn with_window(failure_threshold: u32, timeout_duration: Duration, window: Duration) -> Self {
+        let max_timeout = timeout_duration.checked_mul(16).unwrap_or(Duration::from_secs(3600));
+        Self {
+            state: CircuitBreakerState::Closed,
+            events: VecDeque::new(),
+            window,
+            failure_threshold,
+            minimum_requests: failure_threshold,
+            base_timeout: timeout_duration,
+            max_timeout,
+            timeout_duration,
+            next_attempt_time: None,
+            consecutive_opens: 0,
+            half_open_max_probes: 1,
+            half_open_probes_in_flight: 0,
+            half_open_success_threshold: 1,
+            half_open_successes: 0,
+        }
+    }
+
+    /// Require at least `minimum_requests` observations in the window before
+    /// the failure threshold is evaluated, so a single failure out of one
+    /// request doesn't trip a breaker configured with `failure_threshold: 1`.
+    pub fn with_minimum_requests(mut self, minimum_requests: u32) -> Self {
+        self.minimum_requests = minimum_requests;
+        self
+    }
+
+    /// Cap the exponential backoff applied to repeated half-open failures.
+    pub fn with_max_timeout(mut self, max_timeout: Duration) -> Self {
+        self.max_timeout = max_timeout;
+        self
+    }
+
+    /// Admit at most `max_probes` concurrent trial requests while half-open.
+    pub fn with_half_open_max_probes(mut self, max_probes: u32) -> Self {
+        self.half_open_max_probes = max_probes.max(1);
+        self
+    }
+
+    /// Require `threshold` consecutive probe successes before closing.
+    pub fn with_half_open_success_threshold(mut self, threshold: u32) -> Self {
+        self.half_open_success_threshold = threshold.max(1);
+        self
+    }
+
+    /// Drop events that have aged out of the window.
+    fn evict_expired(&mut self) {
+        let window = self.window;
+        let now = Instant::now();
+        while let Some(front) = self.events.front() {
+            if now.duration_since(front.at) > window {
+                self.events.pop_front();
+            } else {
+                break;
+            }
+        }
+    }
+
+    fn failures_in_window(&self) -> u32 {
+        self.events.iter().filter(|event| event.failed).count() as u32
+    }
+
+    /// Check if the circuit breaker allows execution.
+    ///
+    /// While `HalfOpen`, only `half_open_max_probes` callers are admitted at
+    /// once; once that many probes are in flight, further callers are
+    /// rejected until one resolves via [`record_success`](Self::record_success)
+    /// or [`record_failure`](Self::record_failure).
+    pub fn can_execute(&mut self) -> bool {
+        self.evict_expired();
+        match self.state {
+            CircuitBreakerState::Closed => true,
+            CircuitBreakerState::Open => {
+                if let Some(next_attempt) = self.next_attempt_time {
+                    if Instant::now() >= next_attempt {
+                        self.state = CircuitBreakerState::HalfOpen;
+                        self.half_open_probes_in_flight = 0;
+                        self.half_open_successes = 0;
+                        self.admit_half_open_probe()
+                    } else {
+                        false
+                    }
+                } else {
+                    false
+                }
+            }
+            CircuitBreakerState::HalfOpen => self.admit_half_open_probe(),
+        }
+    }
+
+    fn admit_half_open_probe(&mut self) -> bool {
+        if self.half_open_probes_in_flight < self.half_open_max_probes {
+            self.half_open_probes_in_flight += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Record a successful operation
+    pub fn record_success(&mut self) {
+        self.evict_expired();
+        self.events.push_back(WindowEvent {
+            at: Instant::now(),
+            failed: false,
+        });
+
+        if self.state == CircuitBreakerState::HalfOpen {
+            self.half_open_probes_in_flight = self.half_open_probes_in_flight.saturating_sub(1);
+            self.half_open_successes += 1;
+            if self.half_open_successes >= self.half_open_success_threshold {
+                self.close();
+            }
+        } else {
+            self.close();
+        }
+    }
+
+    /// Record a failed operation
+    pub fn record_failure(&mut self) {
+        self.evict_expired();
+        self.events.push_back(WindowEvent {
+            at: Instant::now(),
+            failed: true,
+        });
+
+        if self.state == CircuitBreakerState::HalfOpen {
+            self.half_open_probes_in_flight = self.half_open_probes_in_flight.saturating_sub(1);
+            self.open_with_backoff();
+            return;
+        }
+
+        let has_enough_volume = self.events.len() as u32 >= self.minimum_requests;
+        let over_threshold = self.failures_in_window() >= self.failure_threshold;
+
+        if has_enough_volume && over_threshold {
+            self.open_with_backoff();
+        }
+    }
+
+    /// Close the breaker and reset its backoff/half-open bookkeeping.
+    fn close(&mut self) {
+        self.state = CircuitBreakerState::Closed;
+        self.next_attempt_time = None;
+        self.consecutive_opens = 0;
+        self.timeout_duration = self.base_timeout;
+        self.half_open_successes = 0;
+        self.half_open_probes_in_flight = 0;
+    }
+
+    /// Open the breaker, doubling the timeout for every open since the last
+    /// time it fully closed (capped at `max_timeout`).
+    fn open_with_backoff(&mut self) {
+        let multiplier = 2_u32.saturating_pow(self.consecutive_opens.min(16));
+        self.consecutive_opens = self.consecutive_opens.saturating_add(1);
+
+        let backed_off = self
+            .base_timeout
+            .checked_mul(multiplier)
+            .unwrap_or(self.max_timeout);
+        self.timeout_duration = backed_off.min(self.max_timeout);
+
+        self.state = CircuitBreakerState::Open;
+        self.next_attempt_time = Some(Instant::now() + self.timeout_duration);
+        self.half_open_successes = 0;
+        self.half_open_probes_in_flight = 0;
+    }
+
+    /// Get current state
+    pub fn state(&self) -> &CircuitBreakerState {
+        &self.state
+    }
+
+    /// Number of failures within the current window
+    pub fn failure_count(&self) -> u32 {
+        self.failures_in_window()
+    }
+
+    /// Fraction of requests within the window that failed (`0.0` if empty).
+    pub fn failure_rate(&mut self) -> f64 {
+        self.evict_expired();
+        if self.events.is_empty() {
+            return 0.0;
+        }
+        self.failures_in_window() as f64 / self.events.len() as f64
+    }
+
+    /// Number of requests (success or failure) currently inside the window.
+    pub fn requests_in_window(&mut self) -> u32 {
+        self.evict_expired();
+        self.events.len() as u32
+    }
+}
+
+impl ErrorResponse {
+    /// Convert to TCP protocol message
+    pub fn to_tcp_message(&self) -> String {
+        format!(
+            "ERROR:{}:{}:{}",
+            self.error_code, self.message, self.timestamp
+        )
+    }
+
+    /// Convert to system message format
+    pub fn to_system_message(&self) -> String {
+        format!("SYSTEM_MESSAGE:ERROR: {}", self.message)
+    }
+}
+
+/// Convenience macro for creating error context
+#[macro_export]
+macro_rules! error_context {
+    ($operation:expr) => {
+        ErrorContext {
+            operation: $operation.to_string(),
+            user_id: None,
+            room_id: None,
+            timestamp: current_timestamp(),
+            additional_info: std::collections::HashMap::new(),
+        }
+    };
+    ($operation:expr, user_id = $user_id:expr) => {
+        ErrorContext {
+            operation: $operation.to_string(),
+            user_id: Some($user_id.to_string()),
+            room_id: None,
+            timestamp: current_timestamp(),
+            additional_info: std::collections::HashMap::new(),
+        }
+    };
+    ($operation:expr, user_id = $user_id:expr, room_id = $room_id:expr) => {
+        ErrorContext {
+            operation: $operation.to_string(),
+            user_id: Some($user_id.to_string()),
+            room_id: Some($room_id.to_string()),
+            timestamp: current_timestamp(),
+            additional_info: std::collections::HashMap::new(),
+        }
+    };
+}
+
+/// Convenience macro for handling errors with context
+#[macro_export]
+macro_rules! handle_error {
+    ($error_handler:expr, $error:expr, $context:expr) => {
+        $error_handler.handle_error($error, Some($context)).await
+    };
+    ($error_handler:expr, $error:expr) => {
+        $error_handler.handle_error($error, None).await
+    };
+}
+
+/// Global error handler instance
+static ERROR_HANDLER: std::sync::OnceLock<ErrorHandler> = std::sync::OnceLock::new();
+
+/// Get the global error handler
+pub fn get_error_handler() -> &'static ErrorHandler {
+    ERROR_HANDLER.get_or_init(ErrorHandler::new)
+}
+
+/// Initialize the global error handler
+pub fn init_error_handler() -> &'static ErrorHandler {
+    get_error_handler()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_error_handler_creation() {
+        let handler = ErrorHandler::new();
+        let stats = handler.get_stats().await;
+        assert_eq!(stats.total_errors, 0);
+    }
+
+    #[tokio::test]
+    async fn test_error_handling() {
+        let handler = ErrorHandler::new();
+        let error = TcpError::ValidationError(ValidationError::InvalidFormat("test".to_string()));
+        let context = error_context!("test_operation", user_id = "user123");
+
+        let response = handler.handle_error(error, Some(context)).await;
+        assert_eq!(response.error_code, "1101");
+        assert!(response.message.contains("Invalid format"));
+    }
+
+    #[tokio::test]
+    async fn test_circuit_breaker_trips_on_threshold() {
+        let mut breaker = CircuitBreaker::new(2, Duration::from_secs(1));
+
+        assert!(breaker.can_execute());
+
+        breaker.record_failure();
+        assert!(breaker.can_execute());
+
+        breaker.record_failure();
+        assert!(!breaker.can_execute());
+
+        breaker.record_success();
+        assert!(breaker.can_execute());
+    }
+
+    #[tokio::test]
+    async fn test_circuit_breaker_evicts_old_failures() {
+        let mut breaker =
+            CircuitBreaker::with_window(2, Duration::from_secs(30), Duration::from_millis(20));
+
+        breaker.record_failure();
+        tokio::time::sleep(Duration::from_millis(30)).await;
+
+        // The first failure should have aged out of the window, so a second,
+        // isolated failure shouldn't trip the breaker on its own.
+        breaker.record_failure();
+        assert_eq!(breaker.failure_count(), 1);
+        assert!(breaker.can_execute());
+    }
+
+    #[tokio::test]
+    async fn test_circuit_breaker_failure_rate_and_volume() {
+        let mut breaker = CircuitBreaker::new(10, Duration::from_secs(30)).with_minimum_requests(4);
+
+        breaker.record_failure();
+        breaker.record_failure();
+        breaker.record_failure();
+        // Below minimum_requests, so the breaker stays closed even though
+        // every request so far has failed.
+        assert!(breaker.can_execute());
+        assert_eq!(breaker.requests_in_window(), 3);
+        assert!((breaker.failure_rate() - 1.0).abs() < f64::EPSILON);
+    }
+
+    #[tokio::test]
+    async fn test_error_stats() {
+        let handler = ErrorHandler::new();
+        let error = TcpError::AuthenticationFailed("test".to_string());
+
+        handler.handle_error(error, None).await;
+
+        let stats = handler.get_stats().await;
+        assert_eq!(stats.total_errors, 1);
+        assert!(stats.errors_by_type.contains_key("AuthenticationFailed"));
+    }
+
+    #[tokio::test]
+    async fn test_get_circuit_breaker_uses_handler_window() {
+        let handler = ErrorHandler::with_circuit_breaker_window(Duration::from_millis(5));
+        let mut breaker = handler.get_circuit_breaker("op").await;
+        assert_eq!(breaker.requests_in_window(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_execute_with_retry_records_budget_denied_retries() {
+        let handler = ErrorHandler::with_retry_budget_ratio(0.1);
+
+        // A single request on the books means the very first retry attempt
+        // already exceeds the 10% budget, so it should fail fast.
+        let result = handler
+            .execute_with_retry("op", || async {
+                Err::<(), _>(TcpError::NetworkError("down".to_string()))
+            })
+            .await;
+
+        assert!(result.budget_exhausted);
+        assert_eq!(handler.get_stats().await.budget_denied_retries, 1);
+    }
+
+    #[tokio::test]
+    async fn test_check_connection_liveness_triggers_reconnect_after_missed_heartbeats() {
+        let handler = ErrorHandler::new();
+        handler
+            .register_connection(
+                "user-1",
+                Duration::from_millis(5),
+                2,
+                ReconnectStrategy::FixedInterval(Duration::from_millis(50)),
+            )
+            .await;
+
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        assert!(handler.check_connection_liveness("user-1").await.is_none());
+        assert_eq!(
+            handler.connection_state("user-1").await,
+            Some(ConnectionState::Connected)
+        );
+
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        let delay = handler.check_connection_liveness("user-1").await;
+        assert_eq!(delay, Some(Duration::from_millis(50)));
+        assert_eq!(
+            handler.connection_state("user-1").await,
+            Some(ConnectionState::Reconnecting)
+        );
+        assert_eq!(handler.get_stats().await.reconnect_attempts, 1);
+
+        handler.record_connection_heartbeat("user-1").await;
+        assert_eq!(
+            handler.connection_state("user-1").await,
+            Some(ConnectionState::Connected)
+        );
+        assert_eq!(handler.get_stats().await.successful_reconnects, 1);
+    }
+
+    #[tokio::test]
+    async fn test_handle_error_instructs_reconnect_for_session_expired() {
+        let handler = ErrorHandler::new();
+        handler
+            .register_connection(
+                "user-2",
+                Duration::from_secs(30),
+                3,
+                ReconnectStrategy::FixedInterval(Duration::from_millis(25)),
+            )
+            .await;
+
+        let context = error_context!("resume_session", user_id = "user-2");
+        let response = handler
+            .handle_error(TcpError::SessionExpired("expired".to_string()), Some(context))
+            .await;
+
+        assert_eq!(
+            response.action,
+            ClientAction::Reconnect {
+                retry_after: Duration::from_millis(25)
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_half_open_admits_only_configured_probe_count() {
+        let mut breaker = CircuitBreaker::new(1, Duration::from_millis(10))
+            .with_half_open_max_probes(2)
+            .with_half_open_success_threshold(2);
+
+        breaker.record_failure();
+        assert!(!breaker.can_execute());
+
+        tokio::time::sleep(Duration::from_millis(15)).await;
+
+        // First two callers are admitted as probes...
+        assert!(breaker.can_execute());
+        assert!(breaker.can_execute());
+        // ...a third is rejected until a probe resolves.
+        assert!(!breaker.can_execute());
+
+        // One probe succeeds: not enough yet to satisfy the success threshold.
+        breaker.record_success();
+        assert_eq!(breaker.state(), &CircuitBreakerState::HalfOpen);
+
+        // A slot freed up, so a new probe can be admitted.
+        assert!(breaker.can_execute());
+        breaker.record_success();
+        assert_eq!(breaker.state(), &CircuitBreakerState::Closed);
+    }
+
+    #[tokio::test]
+    async fn test_metrics_snapshot_reports_errors_and_circuit_breaker_state() {
+        let handler = ErrorHandler::new();
+        handler
+            .handle_error(TcpError::AuthenticationFailed("bad creds".to_string()), None)
+            .await;
+        handler.record_failure("login").await;
+
+        let snapshot = handler.metrics_snapshot().await;
+
+        assert!(snapshot.contains("tcp_errors_total{type=\"AuthenticationFailed"));
+        assert!(snapshot.contains("tcp_circuit_breaker_state{operation=\"login\"} 0"));
+        assert!(snapshot.trim_end().ends_with("# EOF"));
+    }
+
+    #[tokio::test]
+    async fn test_half_open_failure_backs_off_exponentially() {
+        let mut breaker = CircuitBreaker::new(1, Duration::from_millis(10))
+            .with_max_timeout(Duration::from_millis(1000));
+
+        breaker.record_failure();
+        let first_timeout = breaker.timeout_duration;
+        assert_eq!(first_timeout, Duration::from_millis(10));
+
+        tokio::time::sleep(first_timeout + Duration::from_millis(5)).await;
+        assert!(breaker.can_execute());
+        breaker.record_failure();
+        let second_timeout = breaker.timeout_duration;
+        assert_eq!(second_timeout, Duration::from_millis(20));
+
+        tokio::time::sleep(second_timeout + Duration::from_millis(5)).await;
+        assert!(breaker.can_execute());
+        breaker.record_failure();
+        assert_eq!(breaker.timeout_duration, Duration::from_millis(40));
+    }
+}
@@ -1,11 +1,16 @@
 //! Retry mechanism with exponential backoff and circuit breaker patterns
 //!
 //! This module provides robust retry logic for handling transient failures
-//! in TCP operations with configurable backoff strategies.
+//! in TCP operations with configurable backoff strategies, plus a
+//! [`RetryBudget`] bounding how much of that retrying a fleet of clients is
+//! allowed to do at once.
 
 use std::future::Future;
 use std::pin::Pin;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::time::{Duration, Instant};
+
+use rand::Rng;
 use tokio::time::sleep;
 use tracing::{debug, info, warn};
 
@@ -32,6 +37,9 @@ pub struct RetryResult<T> {
     pub total_duration: Duration,
     /// Whether the maximum retry limit was reached
     pub max_retries_reached: bool,
+    /// Whether a [`RetryBudget`] denied a further retry, causing this result
+    /// to fail fast with the underlying error instead of continuing to retry.
+    pub budget_exhausted: bool,
 }
 
 /// Retry statistics for monitoring
@@ -49,6 +57,65 @@ pub struct RetryStats {
     pub total_retry_time: Duration,
 }
 
+/// Token-bucket style budget bounding the ratio of retries to original
+/// requests across an [`ErrorHandler`](super::ErrorHandler), preventing the
+/// "retry storm" failure mode where a fleet of clients collectively
+/// overwhelm an already-degraded node with retries.
+///
+/// Requests and retries are tracked as plain counters rather than draining
+/// over time, so the ratio reflects the budget's entire lifetime; callers
+/// that want a fresh budget per time window should construct a new one
+/// periodically.
+#[derive(Debug)]
+pub struct RetryBudget {
+    requests: AtomicU64,
+    retries: AtomicU64,
+    ratio: f64,
+}
+
+impl RetryBudget {
+    /// Create a budget that allows retries only while
+    /// `retries / requests < ratio`.
+    pub fn new(ratio: f64) -> Self {
+        Self {
+            requests: AtomicU64::new(0),
+            retries: AtomicU64::new(0),
+            ratio,
+        }
+    }
+
+    /// Record that a new top-level operation started.
+    pub fn record_request(&self) {
+        self.requests.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Try to withdraw a single retry token. Returns `false` without
+    /// recording anything if granting it would push `retries / requests` to
+    /// or past the configured ratio.
+    pub fn try_withdraw(&self) -> bool {
+        let requests = self.requests.load(Ordering::Relaxed).max(1);
+        let retries = self.retries.load(Ordering::Relaxed);
+        if (retries as f64 + 1.0) / requests as f64 >= self.ratio {
+            return false;
+        }
+        self.retries.fetch_add(1, Ordering::Relaxed);
+        true
+    }
+
+    /// Current ratio of retries to requests (`0.0` if no requests yet).
+    pub fn retry_ratio(&self) -> f64 {
+        let requests = self.requests.load(Ordering::Relaxed).max(1);
+        self.retries.load(Ordering::Relaxed) as f64 / requests as f64
+    }
+}
+
+impl Default for RetryBudget {
+    /// Allows retries only while they stay under 10% of original requests.
+    fn default() -> Self {
+        Self::new(0.1)
+    }
+}
+
 impl RetryExecutor {
     /// Create a new retry executor with default settings
     pub fn new() -> Self {
@@ -85,6 +152,7 @@ impl RetryExecutor {
         let start_time = Instant::now();
         let mut attempts = 0;
         let mut last_error = None;
+        let mut prev_delay = self.initial_prev_delay();
 
         loop {
             attempts += 1;
@@ -109,6 +177,7 @@ impl RetryExecutor {
                         attempts,
                         total_duration: start_time.elapsed(),
                         max_retries_reached: false,
+                        budget_exhausted: false,
                     };
                 }
                 Err(error) => {
@@ -122,6 +191,7 @@ impl RetryExecutor {
                             attempts,
                             total_duration: start_time.elapsed(),
                             max_retries_reached: false,
+                            budget_exhausted: false,
                         };
                     }
 
@@ -137,11 +207,13 @@ impl RetryExecutor {
                             attempts,
                             total_duration: start_time.elapsed(),
                             max_retries_reached: true,
+                            budget_exhausted: false,
                         };
                     }
 
                     // Calculate delay before next retry
-                    let delay = self.calculate_delay(attempts);
+                    let delay = self.calculate_delay(attempts, prev_delay);
+                    prev_delay = delay;
                     debug!(
                         attempt = attempts,
                         delay_ms = delay.as_millis(),
@@ -168,6 +240,7 @@ impl RetryExecutor {
     {
         let start_time = Instant::now();
         let mut attempts = 0;
+        let mut prev_delay = self.initial_prev_delay();
 
         loop {
             attempts += 1;
@@ -192,6 +265,7 @@ impl RetryExecutor {
                         attempts,
                         total_duration: start_time.elapsed(),
                         max_retries_reached: false,
+                        budget_exhausted: false,
                     };
                 }
                 Err(error) => {
@@ -206,6 +280,7 @@ impl RetryExecutor {
                             attempts,
                             total_duration: start_time.elapsed(),
                             max_retries_reached: false,
+                            budget_exhausted: false,
                         };
                     }
 
@@ -221,11 +296,13 @@ impl RetryExecutor {
                             attempts,
                             total_duration: start_time.elapsed(),
                             max_retries_reached: true,
+                            budget_exhausted: false,
                         };
                     }
 
                     // Calculate delay before next retry
-                    let delay = self.calculate_delay(attempts);
+                    let delay = self.calculate_delay(attempts, prev_delay);
+                    prev_delay = delay;
                     debug!(
                         attempt = attempts,
                         delay_ms = delay.as_millis(),
@@ -239,6 +316,114 @@ impl RetryExecutor {
         }
     }
 
+    /// Execute an operation with retry logic, fast-failing instead of
+    /// retrying once `budget` is exhausted.
+    ///
+    /// `budget` records one request up front and then a withdrawal for every
+    /// retry attempt; once the configured retry ratio would be exceeded,
+    /// this returns immediately with the last error and
+    /// [`RetryResult::budget_exhausted`] set, instead of sleeping and trying
+    /// again. This bounds how much retrying a fleet of callers sharing one
+    /// `budget` can pile onto an already-struggling dependency.
+    pub async fn execute_with_budget<F, Fut, T>(
+        &self,
+        operation: F,
+        budget: &RetryBudget,
+    ) -> RetryResult<T>
+    where
+        F: Fn() -> Fut,
+        Fut: Future<Output = Result<T, TcpError>>,
+    {
+        budget.record_request();
+
+        let start_time = Instant::now();
+        let mut attempts = 0;
+        let mut prev_delay = self.initial_prev_delay();
+
+        loop {
+            attempts += 1;
+
+            debug!(
+                attempt = attempts,
+                max_retries = self.max_retries,
+                "Executing operation attempt with retry budget"
+            );
+
+            match operation().await {
+                Ok(result) => {
+                    if attempts > 1 {
+                        info!(
+                            attempts = attempts,
+                            duration_ms = start_time.elapsed().as_millis(),
+                            "Operation succeeded after retry with retry budget"
+                        );
+                    }
+                    return RetryResult {
+                        result: Ok(result),
+                        attempts,
+                        total_duration: start_time.elapsed(),
+                        max_retries_reached: false,
+                        budget_exhausted: false,
+                    };
+                }
+                Err(error) => {
+                    if !self.should_retry(&error) {
+                        debug!(error = format!("{:?}", error), "Error is not retryable");
+                        return RetryResult {
+                            result: Err(error),
+                            attempts,
+                            total_duration: start_time.elapsed(),
+                            max_retries_reached: false,
+                            budget_exhausted: false,
+                        };
+                    }
+
+                    if attempts >= self.max_retries {
+                        warn!(
+                            attempts = attempts,
+                            error = format!("{:?}", error),
+                            "Maximum retry attempts reached"
+                        );
+                        return RetryResult {
+                            result: Err(error),
+                            attempts,
+                            total_duration: start_time.elapsed(),
+                            max_retries_reached: true,
+                            budget_exhausted: false,
+                        };
+                    }
+
+                    if !budget.try_withdraw() {
+                        warn!(
+                            attempts = attempts,
+                            error = format!("{:?}", error),
+                            retry_ratio = budget.retry_ratio(),
+                            "Retry budget exhausted, failing fast"
+                        );
+                        return RetryResult {
+                            result: Err(error),
+                            attempts,
+                            total_duration: start_time.elapsed(),
+                            max_retries_reached: false,
+                            budget_exhausted: true,
+                        };
+                    }
+
+                    let delay = self.calculate_delay(attempts, prev_delay);
+                    prev_delay = delay;
+                    debug!(
+                        attempt = attempts,
+                        delay_ms = delay.as_millis(),
+                        error = format!("{:?}", error),
+                        "Retrying after delay with retry budget"
+                    );
+
+                    sleep(delay).await;
+                }
+            }
+        }
+    }
+
     /// Check if an error should trigger a retry
     fn should_retry(&self, error: &TcpError) -> bool {
         for condition in &self.retry_conditions {
@@ -283,8 +468,13 @@ impl RetryExecutor {
         }
     }
 
-    /// Calculate the delay before the next retry attempt
-    fn calculate_delay(&self, attempt: u32) -> Duration {
+    /// Calculate the delay before the next retry attempt.
+    ///
+    /// `prev_delay` is only consulted by [`BackoffStrategy::DecorrelatedJitter`];
+    /// callers thread the previously returned delay back in on each
+    /// subsequent attempt (seeded with the strategy's `base` before the
+    /// first retry).
+    fn calculate_delay(&self, attempt: u32, prev_delay: Duration) -> Duration {
         match &self.backoff_strategy {
             BackoffStrategy::Fixed(duration) => *duration,
             BackoffStrategy::Linear(base_duration) => {
@@ -294,6 +484,28 @@ impl RetryExecutor {
                 let multiplier = 2_u64.pow(attempt.saturating_sub(1));
                 Duration::from_millis(base_duration.as_millis() as u64 * multiplier)
             }
+            BackoffStrategy::DecorrelatedJitter { base, cap } => {
+                let lower = base.as_millis() as u64;
+                let upper = (prev_delay.as_millis() as u64)
+                    .saturating_mul(3)
+                    .max(lower)
+                    .min(cap.as_millis() as u64);
+                let millis = if upper > lower {
+                    rand::thread_rng().gen_range(lower..=upper)
+                } else {
+                    lower
+                };
+                Duration::from_millis(millis)
+            }
+        }
+    }
+
+    /// The delay a fresh retry loop should seed `prev_delay` with before its
+    /// first call to [`calculate_delay`](Self::calculate_delay).
+    fn initial_prev_delay(&self) -> Duration {
+        match &self.backoff_strategy {
+            BackoffStrategy::DecorrelatedJitter { base, .. } => *base,
+            _ => Duration::from_millis(0),
         }
     }
 
@@ -445,9 +657,10 @@ mod tests {
         );
 
         // Test delay calculation
-        assert_eq!(executor.calculate_delay(1), Duration::from_millis(10));
-        assert_eq!(executor.calculate_delay(2), Duration::from_millis(20));
-        assert_eq!(executor.calculate_delay(3), Duration::from_millis(40));
+        let zero = Duration::from_millis(0);
+        assert_eq!(executor.calculate_delay(1, zero), Duration::from_millis(10));
+        assert_eq!(executor.calculate_delay(2, zero), Duration::from_millis(20));
+        assert_eq!(executor.calculate_delay(3, zero), Duration::from_millis(40));
 
         let executor = RetryExecutor::with_config(
             3,
@@ -455,9 +668,72 @@ mod tests {
             vec![RetryCondition::NetworkError],
         );
 
-        assert_eq!(executor.calculate_delay(1), Duration::from_millis(10));
-        assert_eq!(executor.calculate_delay(2), Duration::from_millis(20));
-        assert_eq!(executor.calculate_delay(3), Duration::from_millis(30));
+        assert_eq!(executor.calculate_delay(1, zero), Duration::from_millis(10));
+        assert_eq!(executor.calculate_delay(2, zero), Duration::from_millis(20));
+        assert_eq!(executor.calculate_delay(3, zero), Duration::from_millis(30));
+    }
+
+    #[tokio::test]
+    async fn test_decorrelated_jitter_stays_within_bounds() {
+        let executor = RetryExecutor::with_config(
+            10,
+            BackoffStrategy::DecorrelatedJitter {
+                base: Duration::from_millis(10),
+                cap: Duration::from_millis(100),
+            },
+            vec![RetryCondition::NetworkError],
+        );
+
+        let mut prev_delay = executor.initial_prev_delay();
+        assert_eq!(prev_delay, Duration::from_millis(10));
+
+        for attempt in 1_u32..=20 {
+            let delay = executor.calculate_delay(attempt, prev_delay);
+            assert!(delay >= Duration::from_millis(10));
+            assert!(delay <= Duration::from_millis(100));
+            prev_delay = delay;
+        }
+    }
+
+    #[tokio::test]
+    async fn test_retry_budget_denies_once_ratio_exceeded() {
+        let budget = RetryBudget::new(0.1);
+        budget.record_request();
+
+        // retries / requests must stay under 0.1, so with a single request
+        // on the books the very first retry attempt is already denied.
+        assert!(!budget.try_withdraw());
+
+        for _ in 0..19 {
+            budget.record_request();
+        }
+        // 20 requests on the books: one retry keeps the ratio at 0.05, still
+        // under the 0.1 budget.
+        assert!(budget.try_withdraw());
+        assert!((budget.retry_ratio() - 0.05).abs() < f64::EPSILON);
+    }
+
+    #[tokio::test]
+    async fn test_execute_with_budget_fails_fast_when_exhausted() {
+        let executor = RetryExecutor::with_config(
+            5,
+            BackoffStrategy::Fixed(Duration::from_millis(1)),
+            vec![RetryCondition::NetworkError],
+        );
+        let budget = RetryBudget::new(0.1);
+
+        let result = executor
+            .execute_with_budget(
+                || async { Err(TcpError::NetworkError("persistent error".to_string())) },
+                &budget,
+            )
+            .await;
+
+        assert!(result.result.is_err());
+        assert!(result.budget_exhausted);
+        assert!(!result.max_retries_reached);
+        // Denied on the very first retry attempt, so no sleep/backoff happened.
+        assert_eq!(result.attempts, 1);
     }
 
     #[tokio::test]
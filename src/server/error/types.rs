@@ -118,6 +118,12 @@ pub enum BackoffStrategy {
     Linear(Duration),
     Exponential(Duration),
     Fixed(Duration),
+    /// Decorrelated jitter: each delay is drawn uniformly from
+    /// `[base, min(cap, prev_delay * 3)]`, seeded with `prev_delay = base`.
+    /// Spreads out retries far better than pure exponential backoff, which
+    /// keeps every client's delays in lockstep and can make a degraded node
+    /// worse (the "retry storm" failure mode).
+    DecorrelatedJitter { base: Duration, cap: Duration },
 }
 
 /// Conditions that determine if a retry should be attempted
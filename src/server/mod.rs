@@ -13,6 +13,7 @@ pub mod app;
 pub mod auth;
 pub mod chat;
 pub mod config;
+pub mod error;
 pub mod network;
 pub mod storage;
 
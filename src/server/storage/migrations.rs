@@ -347,6 +347,23 @@ CREATE INDEX idx_typing_indicators_user_id ON typing_indicators(user_id);
 CREATE INDEX idx_typing_indicators_expires_at ON typing_indicators(expires_at);
 "#;
 
+/// Migration 016: Add resumable sync cursors for sessions
+pub const MIGRATION_016_SYNC_CURSORS: &str = r#"
+CREATE TABLE sync_cursors (
+    session_id TEXT PRIMARY KEY,
+    cursor TEXT NOT NULL,
+    updated_at INTEGER NOT NULL,
+    FOREIGN KEY (session_id) REFERENCES sessions(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_sync_cursors_updated_at ON sync_cursors(updated_at);
+"#;
+
+/// Migration 017: Add inline blob storage for small attachments
+pub const MIGRATION_017_ATTACHMENT_BLOBS: &str = r#"
+ALTER TABLE file_attachments ADD COLUMN inline_data BLOB;
+"#;
+
 /// Get all migrations in order
 pub fn get_all_migrations() -> Vec<(&'static str, &'static str)> {
     vec![
@@ -389,6 +406,11 @@ pub fn get_all_migrations() -> Vec<(&'static str, &'static str)> {
             "015_create_typing_indicators_table",
             MIGRATION_015_TYPING_INDICATORS,
         ),
+        ("016_create_sync_cursors_table", MIGRATION_016_SYNC_CURSORS),
+        (
+            "017_add_attachment_inline_data",
+            MIGRATION_017_ATTACHMENT_BLOBS,
+        ),
     ]
 }
 
@@ -64,6 +64,17 @@ pub enum StorageError {
 
     #[error("Unsupported operation: {operation}")]
     UnsupportedOperation { operation: String },
+
+    #[error("Attachment blob I/O failed: {message}")]
+    IoError { message: String },
+}
+
+impl From<std::io::Error> for StorageError {
+    fn from(err: std::io::Error) -> Self {
+        StorageError::IoError {
+            message: err.to_string(),
+        }
+    }
 }
 
 impl From<sqlx::Error> for StorageError {
@@ -133,6 +144,7 @@ pub struct StorageManager {
     session_storage: Box<dyn SessionStorage>,
     audit_log_storage: Box<dyn AuditLogStorage>,
     invitation_storage: Box<dyn InvitationStorage>,
+    attachment_storage: Box<dyn AttachmentStorage>,
     transaction_manager: Arc<dyn TransactionManager>,
     transaction_operations: Arc<dyn TransactionOperations>,
 }
@@ -154,7 +166,8 @@ impl StorageManager {
             room_storage: Box::new(backend.clone()),
             session_storage: Box::new(backend.clone()),
             audit_log_storage: Box::new(backend.clone()),
-            invitation_storage: Box::new(backend),
+            invitation_storage: Box::new(backend.clone()),
+            attachment_storage: Box::new(backend),
             transaction_manager,
             transaction_operations,
         })
@@ -190,6 +203,11 @@ impl StorageManager {
         self.invitation_storage.as_ref()
     }
 
+    /// Get attachment storage interface
+    pub fn attachments(&self) -> &dyn AttachmentStorage {
+        self.attachment_storage.as_ref()
+    }
+
     /// Get transaction manager interface
     pub fn transactions(&self) -> &dyn TransactionManager {
         self.transaction_manager.as_ref()
@@ -18,15 +18,29 @@ use std::str::FromStr;
 use std::time::Duration;
 use tracing::{debug, info};
 
+/// A sync cursor older than this is treated as stale: the client was
+/// probably offline long enough that the server can no longer guarantee
+/// nothing newer than the cursor was pruned, so it falls back to a full
+/// resync instead of streaming from a potentially-missing position.
+const SYNC_CURSOR_STALE_AFTER_SECS: u64 = 7 * 24 * 60 * 60;
+
+/// Attachments at or below this size are stored inline in the database;
+/// larger ones are written to a file under `attachments_dir` instead, to
+/// keep the SQLite file itself from growing unbounded.
+const ATTACHMENT_INLINE_MAX_BYTES: u64 = 256 * 1024;
+
 /// SQLite storage backend
 #[derive(Debug, Clone)]
 pub struct SqliteStorage {
     pool: SqlitePool,
+    attachments_dir: std::path::PathBuf,
 }
 
 impl SqliteStorage {
     /// Create a new SQLite storage instance
     pub async fn new(config: DatabaseConfig) -> StorageResult<Self> {
+        let attachments_dir = Self::attachments_dir_for(&config.url);
+
         let connect_options = SqliteConnectOptions::from_str(&config.url)
             .map_err(|e| StorageError::ConnectionError {
                 message: format!("Invalid SQLite URL: {}", e),
@@ -48,7 +62,10 @@ impl SqliteStorage {
                 message: format!("Failed to connect to SQLite: {}", e),
             })?;
 
-        let storage = Self { pool };
+        let storage = Self {
+            pool,
+            attachments_dir,
+        };
 
         if config.auto_migrate {
             storage.run_migrations().await?;
@@ -58,6 +75,28 @@ impl SqliteStorage {
         Ok(storage)
     }
 
+    /// Derive the on-disk attachments directory from the database URL: a
+    /// sibling `attachments` directory next to the SQLite file. URLs with
+    /// no filesystem path (e.g. `sqlite::memory:`) fall back to a relative
+    /// `./attachments` directory.
+    fn attachments_dir_for(url: &str) -> std::path::PathBuf {
+        let path = url
+            .trim_start_matches("sqlite://")
+            .trim_start_matches("sqlite:")
+            .split('?')
+            .next()
+            .unwrap_or("");
+
+        if path.is_empty() || path == ":memory:" {
+            return std::path::PathBuf::from("./attachments");
+        }
+
+        match std::path::Path::new(path).parent() {
+            Some(parent) if !parent.as_os_str().is_empty() => parent.join("attachments"),
+            _ => std::path::PathBuf::from("./attachments"),
+        }
+    }
+
     /// Run database migrations
     pub async fn run_migrations(&self) -> StorageResult<()> {
         info!("Running database migrations...");
@@ -737,6 +776,28 @@ impl SqliteStorage {
         })
     }
 
+    /// Convert a database row to a FileAttachment struct (without its blob data)
+    fn row_to_attachment(&self, row: sqlx::sqlite::SqliteRow) -> StorageResult<FileAttachment> {
+        use sqlx::Row;
+
+        let metadata_json: String = row.get("metadata");
+        let metadata: HashMap<String, String> =
+            serde_json::from_str(&metadata_json).map_err(|e| StorageError::SerializationError {
+                message: e.to_string(),
+            })?;
+
+        Ok(FileAttachment {
+            id: row.get("id"),
+            filename: row.get("filename"),
+            size: row.get::<i64, _>("size") as u64,
+            mime_type: row.get("mime_type"),
+            hash: row.get("hash"),
+            storage_path: row.get("storage_path"),
+            uploaded_at: row.get::<i64, _>("uploaded_at") as u64,
+            metadata,
+        })
+    }
+
     /// Convert a database row to a Session struct
     fn row_to_session(&self, row: sqlx::sqlite::SqliteRow) -> StorageResult<Session> {
         use sqlx::Row;
@@ -2359,4 +2420,166 @@ impl SessionStorage for SqliteStorage {
             average_session_duration: avg_duration.unwrap_or(0.0),
         })
     }
+
+    async fn store_sync_cursor(&self, session_id: &str, cursor: &str) -> StorageResult<()> {
+        let now = super::current_timestamp();
+        sqlx::query(
+            r#"
+            INSERT INTO sync_cursors (session_id, cursor, updated_at)
+            VALUES (?, ?, ?)
+            ON CONFLICT(session_id) DO UPDATE SET cursor = excluded.cursor, updated_at = excluded.updated_at
+            "#,
+        )
+        .bind(session_id)
+        .bind(cursor)
+        .bind(now as i64)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn load_sync_cursor(&self, session_id: &str) -> StorageResult<Option<String>> {
+        let row = sqlx::query("SELECT cursor, updated_at FROM sync_cursors WHERE session_id = ?")
+            .bind(session_id)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        let Some(row) = row else {
+            return Ok(None);
+        };
+
+        let updated_at: i64 = row.get("updated_at");
+        let now = super::current_timestamp();
+        if now.saturating_sub(updated_at as u64) > SYNC_CURSOR_STALE_AFTER_SECS {
+            debug!(
+                "Sync cursor for session {} is stale, falling back to full resync",
+                session_id
+            );
+            return Ok(None);
+        }
+
+        Ok(Some(row.get("cursor")))
+    }
+}
+
+#[async_trait]
+impl AttachmentStorage for SqliteStorage {
+    async fn store_attachment(
+        &self,
+        message_id: &str,
+        mut attachment: FileAttachment,
+        data: &[u8],
+    ) -> StorageResult<FileAttachment> {
+        let metadata_json = serde_json::to_string(&attachment.metadata).map_err(|e| {
+            StorageError::SerializationError {
+                message: e.to_string(),
+            }
+        })?;
+
+        let (storage_path, inline_data): (String, Option<&[u8]>) =
+            if attachment.size <= ATTACHMENT_INLINE_MAX_BYTES {
+                ("inline".to_string(), Some(data))
+            } else {
+                tokio::fs::create_dir_all(&self.attachments_dir).await?;
+                let path = self.attachments_dir.join(&attachment.id);
+                tokio::fs::write(&path, data).await?;
+                (path.to_string_lossy().into_owned(), None)
+            };
+        attachment.storage_path = storage_path;
+
+        sqlx::query(
+            "INSERT INTO file_attachments
+             (id, message_id, filename, original_name, size, mime_type, hash, storage_path,
+              uploaded_at, metadata, inline_data)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+        )
+        .bind(&attachment.id)
+        .bind(message_id)
+        .bind(&attachment.filename)
+        .bind(&attachment.filename)
+        .bind(attachment.size as i64)
+        .bind(&attachment.mime_type)
+        .bind(&attachment.hash)
+        .bind(&attachment.storage_path)
+        .bind(attachment.uploaded_at as i64)
+        .bind(&metadata_json)
+        .bind(inline_data)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(attachment)
+    }
+
+    async fn get_attachment(&self, id: &str) -> StorageResult<Option<FileAttachment>> {
+        let row = sqlx::query(
+            "SELECT id, filename, size, mime_type, hash, storage_path, uploaded_at, metadata
+             FROM file_attachments WHERE id = ?",
+        )
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        match row {
+            Some(row) => Ok(Some(self.row_to_attachment(row)?)),
+            None => Ok(None),
+        }
+    }
+
+    async fn load_attachment_data(&self, id: &str) -> StorageResult<Option<Vec<u8>>> {
+        let row =
+            sqlx::query("SELECT storage_path, inline_data FROM file_attachments WHERE id = ?")
+                .bind(id)
+                .fetch_optional(&self.pool)
+                .await?;
+
+        let Some(row) = row else {
+            return Ok(None);
+        };
+
+        let storage_path: String = row.get("storage_path");
+        if storage_path == "inline" {
+            return Ok(row.get("inline_data"));
+        }
+
+        Ok(Some(tokio::fs::read(&storage_path).await?))
+    }
+
+    async fn get_message_attachments(
+        &self,
+        message_id: &str,
+    ) -> StorageResult<Vec<FileAttachment>> {
+        let rows = sqlx::query(
+            "SELECT id, filename, size, mime_type, hash, storage_path, uploaded_at, metadata
+             FROM file_attachments WHERE message_id = ? ORDER BY uploaded_at ASC",
+        )
+        .bind(message_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.into_iter()
+            .map(|row| self.row_to_attachment(row))
+            .collect()
+    }
+
+    async fn delete_attachment(&self, id: &str) -> StorageResult<()> {
+        let row = sqlx::query("SELECT storage_path FROM file_attachments WHERE id = ?")
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        if let Some(row) = row {
+            let storage_path: String = row.get("storage_path");
+            if storage_path != "inline" {
+                let _ = tokio::fs::remove_file(&storage_path).await;
+            }
+        }
+
+        sqlx::query("DELETE FROM file_attachments WHERE id = ?")
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
 }
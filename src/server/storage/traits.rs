@@ -399,6 +399,50 @@ pub trait SessionStorage: Send + Sync {
 
     /// Get session statistics
     async fn get_session_stats(&self) -> StorageResult<SessionStats>;
+
+    /// Store a resumable sync cursor for a session, so a reconnect can
+    /// resume streaming from the point the client last acknowledged
+    /// instead of replaying or refetching everything.
+    async fn store_sync_cursor(&self, session_id: &str, cursor: &str) -> StorageResult<()>;
+
+    /// Load the resumable sync cursor for a session, if one was stored and
+    /// it hasn't gone stale. A stale cursor is treated as absent so the
+    /// caller falls back to a full resync rather than streaming from a
+    /// position the server may no longer have data for.
+    async fn load_sync_cursor(&self, session_id: &str) -> StorageResult<Option<String>>;
+}
+
+/// Attachment blob storage operations
+///
+/// `FileAttachment` (in [`models`](super::models)) only ever carried
+/// descriptive metadata; nothing actually persisted the file bytes. This
+/// trait owns that: small attachments are kept inline in the database,
+/// larger ones are written to disk, and `storage_path` records which.
+#[async_trait]
+pub trait AttachmentStorage: Send + Sync {
+    /// Persist an attachment's metadata and bytes against its owning
+    /// message, choosing inline or on-disk storage based on size. Returns
+    /// the stored record with `storage_path` set to reflect where the
+    /// bytes ended up.
+    async fn store_attachment(
+        &self,
+        message_id: &str,
+        attachment: FileAttachment,
+        data: &[u8],
+    ) -> StorageResult<FileAttachment>;
+
+    /// Get attachment metadata by ID, without loading its bytes.
+    async fn get_attachment(&self, id: &str) -> StorageResult<Option<FileAttachment>>;
+
+    /// Load an attachment's bytes, from wherever `store_attachment` put them.
+    async fn load_attachment_data(&self, id: &str) -> StorageResult<Option<Vec<u8>>>;
+
+    /// List attachment metadata for a message.
+    async fn get_message_attachments(&self, message_id: &str)
+        -> StorageResult<Vec<FileAttachment>>;
+
+    /// Delete an attachment's metadata and bytes.
+    async fn delete_attachment(&self, id: &str) -> StorageResult<()>;
 }
 
 /// User statistics